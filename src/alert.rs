@@ -0,0 +1,39 @@
+use notify_rust::Notification;
+
+use crate::budget;
+use crate::config::BudgetAlerts;
+use crate::expense::ExpenseTracker;
+
+// Warn about the budgets the expenses with `ids`, now in `tracker`, took
+// past one of their alert thresholds.
+pub fn budgets(tracker: &ExpenseTracker, alerts: &BudgetAlerts, ids: &[u64]) {
+    for expense in ids.iter().filter_map(|id| tracker.find_by_id(*id)) {
+        let crossed = budget::crossed(tracker, expense, |category| alerts.thresholds(category));
+        for (status, threshold) in crossed {
+            let message = if threshold >= 100.0 && status.remaining() < 0.0 {
+                format!(
+                    "{} is over budget: {:.2} spent of {:.2}.",
+                    status.category,
+                    status.spent,
+                    status.available()
+                )
+            } else {
+                format!(
+                    "{} has used {threshold:.0}% of its budget: {:.2} of {:.2} left.",
+                    status.category,
+                    status.remaining(),
+                    status.available()
+                )
+            };
+            eprintln!("Warning: {message}");
+            // Without a notification service, as over SSH, the warning is
+            // enough.
+            if alerts.desktop {
+                let _ = Notification::new()
+                    .summary("Budget alert")
+                    .body(&message)
+                    .show();
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use chrono::{Datelike, NaiveDate};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::expense::ExpenseTracker;
+
+// The archive for one year, such as `expenses-2023.json` next to the data
+// file.
+pub fn path(data_file: &str, year: i32) -> PathBuf {
+    let path = Path::new(data_file);
+    let stem = path.file_stem().map_or(String::from("expenses"), |s| {
+        s.to_string_lossy().into_owned()
+    });
+    path.with_file_name(format!("{stem}-{year}.json"))
+}
+
+// Every yearly archive of the data file.
+pub fn list(data_file: &str) -> io::Result<Vec<PathBuf>> {
+    let path = Path::new(data_file);
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let mut archives: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|candidate| {
+            candidate
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(".json"))
+                .and_then(|name| name.rsplit_once('-'))
+                .and_then(|(_, year)| year.parse::<i32>().ok())
+                .is_some_and(|year| {
+                    candidate.file_name() == self::path(data_file, year).file_name()
+                })
+        })
+        .collect();
+    archives.sort();
+    Ok(archives)
+}
+
+// Move the expenses dated before `before` out of `tracker` into one archive
+// per year, adding to archives already there. Returns the number moved per
+// year. The caller saves `tracker` afterwards.
+pub fn archive(
+    data_file: &str,
+    tracker: &mut ExpenseTracker,
+    before: NaiveDate,
+) -> io::Result<Vec<(i32, usize)>> {
+    let mut years: Vec<i32> = tracker
+        .expenses
+        .iter()
+        .filter(|e| e.date < before)
+        .map(|e| e.date.year())
+        .collect();
+    years.sort();
+    years.dedup();
+
+    let mut moved = Vec::new();
+    for year in years {
+        let path = path(data_file, year);
+        let mut archived = ExpenseTracker::load_from_json(&path.to_string_lossy())?;
+        archived.currency = tracker.currency.clone();
+        let mut count = 0;
+        for expense in tracker
+            .expenses
+            .iter()
+            .filter(|e| e.date < before && e.date.year() == year)
+        {
+            if archived.find_by_id(expense.id).is_none() {
+                archived.add_expense(expense.clone());
+            }
+            count += 1;
+        }
+        archived.expenses.sort_by_key(|e| (e.date, e.id));
+        archived.save_to_json(&path.to_string_lossy())?;
+        if !tracker.archived_years.contains(&year) {
+            tracker.archived_years.push(year);
+            tracker.archived_years.sort();
+        }
+        moved.push((year, count));
+    }
+    // Only removed once every archive is safely written
+    tracker.expenses.retain(|e| e.date >= before);
+    Ok(moved)
+}
+
+// Merge the archived years overlapping `from`..`to` back into `tracker`, for
+// reports that reach that far. The result must not be saved.
+pub fn include(
+    data_file: &str,
+    tracker: &mut ExpenseTracker,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> io::Result<()> {
+    let years: Vec<i32> = tracker
+        .archived_years
+        .iter()
+        .copied()
+        .filter(|year| from.is_none_or(|from| *year >= from.year()))
+        .filter(|year| to.is_none_or(|to| *year <= to.year()))
+        .collect();
+    let mut archived = Vec::new();
+    for year in years {
+        let path = path(data_file, year);
+        if path.exists() {
+            archived.extend(ExpenseTracker::load_from_json(&path.to_string_lossy())?.expenses);
+        }
+    }
+    if !archived.is_empty() {
+        archived.append(&mut tracker.expenses);
+        tracker.expenses = archived;
+    }
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::currency;
+use crate::expense::ExpenseTracker;
+use crate::locale;
+
+// A balance as it stood on a day
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Snapshot {
+    pub date: NaiveDate,
+    pub amount: f64,
+}
+
+// A bank account, investment or liability whose balance is noted from time
+// to time
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Account {
+    pub name: String,
+    // Owed rather than owned, such as a credit card or mortgage
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub liability: bool,
+    // Oldest first
+    pub balances: Vec<Snapshot>,
+}
+
+impl Account {
+    // The latest balance noted on or before `date`
+    pub fn balance_on(&self, date: NaiveDate) -> Option<f64> {
+        self.balances
+            .iter()
+            .rfind(|s| s.date <= date)
+            .map(|s| s.amount)
+    }
+
+    // Record the balance on `date`, replacing one already noted that day.
+    pub fn set(&mut self, date: NaiveDate, amount: f64) {
+        self.balances.retain(|s| s.date != date);
+        self.balances.push(Snapshot { date, amount });
+        self.balances.sort_by_key(|s| s.date);
+    }
+}
+
+// Assets minus liabilities as they stood on `date`.
+pub fn net_worth(tracker: &ExpenseTracker, date: NaiveDate) -> f64 {
+    tracker
+        .accounts
+        .iter()
+        .filter_map(|a| {
+            let balance = a.balance_on(date)?;
+            Some(if a.liability { -balance } else { balance })
+        })
+        .sum()
+}
+
+pub fn print_list(tracker: &ExpenseTracker, today: NaiveDate) {
+    if tracker.accounts.is_empty() {
+        println!("No accounts recorded.");
+        return;
+    }
+    for liability in [false, true] {
+        let accounts: Vec<_> = tracker
+            .accounts
+            .iter()
+            .filter(|a| a.liability == liability)
+            .collect();
+        if accounts.is_empty() {
+            continue;
+        }
+        println!("{}", if liability { "Liabilities" } else { "Assets" });
+        let mut total = 0.0;
+        for account in accounts {
+            let Some(latest) = account.balances.last() else {
+                continue;
+            };
+            total += latest.amount;
+            println!(
+                "  {:<24} {:>14}  as of {}",
+                account.name,
+                currency::amount(latest.amount),
+                locale::date(latest.date)
+            );
+        }
+        println!("  {:<24} {:>14}\n", "Total", currency::amount(total));
+    }
+    println!("Net worth: {}", currency::amount(net_worth(tracker, today)));
+}
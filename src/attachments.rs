@@ -0,0 +1,53 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+// Directory holding the managed copies of receipts, next to the data file.
+pub fn root(data_file: &str) -> PathBuf {
+    Path::new(data_file).with_file_name("attachments")
+}
+
+// Directory holding the attachments of one expense.
+pub fn dir(data_file: &str, id: u64) -> PathBuf {
+    root(data_file).join(id.to_string())
+}
+
+// Copy `source` into the expense's attachment directory and return the
+// stored file name.
+pub fn attach(data_file: &str, id: u64, source: &Path) -> io::Result<String> {
+    let name = source
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "not a file"))?
+        .to_string_lossy()
+        .to_string();
+    let dir = dir(data_file, id);
+    fs::create_dir_all(&dir)?;
+    fs::copy(source, dir.join(&name))?;
+    Ok(name)
+}
+
+// Remove every attachment of an expense.
+pub fn remove_all(data_file: &str, id: u64) -> io::Result<()> {
+    let dir = dir(data_file, id);
+    if dir.exists() {
+        fs::remove_dir_all(dir)?;
+    }
+    Ok(())
+}
+
+// Open a file with the platform's default application.
+pub fn open(path: &Path) -> io::Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(target_os = "windows") {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    command.arg(path).spawn()?;
+    Ok(())
+}
@@ -0,0 +1,201 @@
+use chrono::{Local, NaiveDate, NaiveDateTime};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+use tracing::debug;
+
+use crate::expense::ExpenseTracker;
+use crate::storage;
+
+// How many of the latest backups are always kept
+const KEEP_LATEST: usize = 10;
+// Older backups are thinned to the newest of each day for this many days
+const KEEP_DAYS: i64 = 30;
+
+// Milliseconds keep backups from saves in quick succession apart
+const STAMP: &str = "%Y%m%d-%H%M%S%.3f";
+
+// Directory holding the backups, next to the data file.
+pub fn root(data_file: &str) -> PathBuf {
+    Path::new(data_file).with_file_name("backups")
+}
+
+// A backup's timestamp, from its file name.
+fn stamp_of(path: &Path) -> Option<NaiveDateTime> {
+    let name = path.file_stem()?.to_str()?;
+    NaiveDateTime::parse_from_str(name, STAMP).ok()
+}
+
+// Backups of the data file, oldest first.
+pub fn list(data_file: &str) -> io::Result<Vec<(NaiveDateTime, PathBuf)>> {
+    let dir = root(data_file);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut backups: Vec<(NaiveDateTime, PathBuf)> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter_map(|path| stamp_of(&path).map(|stamp| (stamp, path)))
+        .collect();
+    backups.sort();
+    Ok(backups)
+}
+
+// Copy the data file as it is now into the backups directory, before it is
+// overwritten, and prune old backups.
+pub fn create(data_file: &str) -> io::Result<()> {
+    if !Path::new(data_file).exists() {
+        return Ok(());
+    }
+    let dir = root(data_file);
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{}.json", Local::now().format(STAMP)));
+    fs::copy(data_file, &path)?;
+    debug!(backup = %path.display(), "backed up data file");
+    prune(data_file)
+}
+
+// Keep the latest backups, plus the newest of each recent day.
+fn prune(data_file: &str) -> io::Result<()> {
+    let backups = list(data_file)?;
+    let cutoff = Local::now().date_naive() - chrono::Duration::days(KEEP_DAYS);
+    let mut days: Vec<NaiveDate> = Vec::new();
+    // Newest first, so the first backup seen for a day is the one kept
+    for (i, (stamp, path)) in backups.iter().rev().enumerate() {
+        let day = stamp.date();
+        let keep = i < KEEP_LATEST || (day >= cutoff && !days.contains(&day));
+        days.push(day);
+        if !keep {
+            fs::remove_file(path)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn print_list(data_file: &str) -> io::Result<()> {
+    let backups = list(data_file)?;
+    if backups.is_empty() {
+        println!("No backups yet.");
+        return Ok(());
+    }
+    for (stamp, path) in backups.iter().rev() {
+        let count = ExpenseTracker::load_from_json(&path.to_string_lossy())
+            .map(|t| format!("{} expense(s)", t.expenses.len()))
+            .unwrap_or_else(|_| String::from("unreadable"));
+        println!(
+            "{}  {}  {}",
+            stamp.format(STAMP),
+            stamp.format("%Y-%m-%d %H:%M:%S"),
+            count
+        );
+    }
+    Ok(())
+}
+
+// The newest backup whose timestamp starts with `stamp`, or the latest one.
+pub fn find(data_file: &str, stamp: Option<&str>) -> io::Result<(NaiveDateTime, PathBuf)> {
+    let backups = list(data_file)?;
+    let found = match stamp {
+        Some(stamp) => backups
+            .iter()
+            .rev()
+            .find(|(s, _)| s.format(STAMP).to_string().starts_with(stamp)),
+        None => backups.last(),
+    };
+    found
+        .cloned()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no such backup; see `backup list`"))
+}
+
+// Replace the data file with `backup`, atomically. The current file is
+// backed up first so the restore can be undone. A journal still next to it
+// would be replayed on top of the backup, so it is set aside; the path it
+// went to is returned.
+pub fn restore(data_file: &str, backup: &Path) -> io::Result<Option<PathBuf>> {
+    // Don't restore something that wouldn't load.
+    ExpenseTracker::load_from_json(&backup.to_string_lossy())?;
+    // Read it before backing up, which may prune it.
+    let contents = fs::read(backup)?;
+    create(data_file)?;
+    let temporary = Path::new(data_file).with_extension("json.tmp");
+    fs::write(&temporary, contents)?;
+    fs::rename(&temporary, data_file)?;
+    storage::set_journal_aside(data_file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expense::Expense;
+    use std::{env, thread, time::Duration};
+
+    fn ledger(descriptions: &[&str]) -> ExpenseTracker {
+        let mut tracker = ExpenseTracker::new();
+        for description in descriptions {
+            tracker.add_expense(Expense {
+                date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+                category: String::from("Food"),
+                amount: 100.0,
+                description: description.to_string(),
+                ..Default::default()
+            });
+        }
+        tracker
+    }
+
+    #[test]
+    fn restore_replaces_the_data_file_and_sets_the_journal_aside() {
+        let dir = env::temp_dir().join(format!("expense-tracker-backup-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let data_file = dir.join("expenses.json").to_string_lossy().into_owned();
+        ledger(&["rent"]).save_to_json(&data_file).unwrap();
+        create(&data_file).unwrap();
+        let (_, backup) = list(&data_file).unwrap().pop().unwrap();
+        ledger(&["rent", "mistake"])
+            .save_to_json(&data_file)
+            .unwrap();
+        let journal = dir.join("expenses.journal");
+        fs::write(&journal, "{}\n").unwrap();
+        fs::write(dir.join("expenses.index"), "{}").unwrap();
+        // Backups are told apart by the millisecond.
+        thread::sleep(Duration::from_millis(5));
+
+        let aside = restore(&data_file, &backup).unwrap();
+        let restored = ExpenseTracker::load_from_json(&data_file).unwrap();
+        let backups = list(&data_file).unwrap().len();
+        let leftovers = (
+            journal.exists(),
+            dir.join("expenses.index").exists(),
+            dir.join("expenses.json.tmp").exists(),
+        );
+        let aside_exists = aside.as_ref().is_some_and(|path| path.exists());
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(restored.expenses.len(), 1);
+        assert_eq!(restored.expenses[0].description, "rent");
+        // The file replaced is kept, so the restore can be undone.
+        assert_eq!(backups, 2);
+        assert_eq!(leftovers, (false, false, false));
+        assert!(aside_exists);
+    }
+
+    #[test]
+    fn restore_refuses_a_backup_that_does_not_load() {
+        let dir = env::temp_dir().join(format!(
+            "expense-tracker-backup-broken-{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        let data_file = dir.join("expenses.json").to_string_lossy().into_owned();
+        ledger(&["rent"]).save_to_json(&data_file).unwrap();
+        let broken = dir.join("broken.json");
+        fs::write(&broken, "{ not json").unwrap();
+
+        let result = restore(&data_file, &broken);
+        let kept = ExpenseTracker::load_from_json(&data_file).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(result.is_err());
+        assert_eq!(kept.expenses.len(), 1);
+    }
+}
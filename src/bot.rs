@@ -0,0 +1,190 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+use serde_json::json;
+use std::{thread, time::Duration};
+
+use crate::config::Config;
+use crate::currency;
+use crate::dates;
+use crate::entry;
+use crate::expense::Expense;
+use crate::filter::Filter;
+use crate::rates;
+use crate::storage::Storage;
+use crate::webhook;
+
+const API: &str = "https://api.telegram.org";
+// Seconds `getUpdates` waits for a message before returning empty
+const POLL_TIMEOUT: u32 = 50;
+// Wait before retrying after a network error
+const RETRY_AFTER: Duration = Duration::from_secs(5);
+
+const HELP: &str = "Send an expense like `250 coffee #food`, optionally with an @payee and a date such as 2025-07-01.\n/today shows today's total.";
+
+#[derive(Deserialize)]
+struct Updates {
+    ok: bool,
+    #[serde(default)]
+    result: Vec<Update>,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Update {
+    update_id: i64,
+    #[serde(default)]
+    message: Option<Message>,
+}
+
+#[derive(Deserialize)]
+struct Message {
+    chat: Chat,
+    #[serde(default)]
+    text: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Chat {
+    id: i64,
+}
+
+fn url(token: &str, method: &str) -> String {
+    format!("{API}/bot{token}/{method}")
+}
+
+fn updates(token: &str, offset: i64) -> Result<Vec<Update>, String> {
+    let updates: Updates = ureq::get(&url(token, "getUpdates"))
+        .query("offset", offset.to_string())
+        .query("timeout", POLL_TIMEOUT.to_string())
+        .call()
+        .and_then(|mut response| response.body_mut().read_json())
+        // The URL holds the token, so it stays out of the message.
+        .map_err(|err| format!("getUpdates: {err}"))?;
+    if updates.ok {
+        Ok(updates.result)
+    } else {
+        Err(updates
+            .description
+            .unwrap_or_else(|| String::from("getUpdates failed")))
+    }
+}
+
+fn send(token: &str, chat: i64, text: &str) -> Result<(), String> {
+    ureq::post(&url(token, "sendMessage"))
+        .send_json(json!({ "chat_id": chat, "text": text }))
+        .map(|_| ())
+        .map_err(|err| format!("sendMessage: {err}"))
+}
+
+// Total spent on `date`, in the ledger's currency.
+fn day_total(storage: &dyn Storage, ledger: &str, date: NaiveDate) -> Result<f64, String> {
+    let mut tracker = storage.load().map_err(|err| err.to_string())?;
+    tracker.currency = Some(ledger.to_string());
+    let (view, _) = rates::convert(&tracker, ledger)?;
+    Ok(view.summary(&Filter::default().on(date)))
+}
+
+// The reply to one message from an allowed chat.
+fn reply(storage: &dyn Storage, config: &Config, ledger: &str, text: &str) -> String {
+    let now = dates::now(config.timezone);
+    let today = now.date;
+    let text = text.trim();
+    match text.split_whitespace().next() {
+        Some("/start") | Some("/help") => return String::from(HELP),
+        Some("/today") => {
+            return match day_total(storage, ledger, today) {
+                Ok(total) => format!("Today: {}", currency::amount(total)),
+                Err(err) => format!("Couldn't read the ledger: {err}"),
+            };
+        }
+        _ => {}
+    }
+    let entry = match entry::parse_quick(text, today) {
+        Ok(entry) => entry,
+        Err(err) => return format!("Not added: {err}.\n\n{HELP}"),
+    };
+    match storage.load_metadata() {
+        Ok(metadata) if metadata.is_month_closed(entry.date) => {
+            return format!("Not added: {} is in a closed month.", entry.date);
+        }
+        Ok(_) => {}
+        Err(err) => return format!("Couldn't read the ledger: {err}"),
+    }
+    let mut expense = Expense {
+        date: entry.date,
+        // Messages about today are taken to be sent as the money is spent.
+        time: now.time.filter(|_| entry.date == today),
+        offset: now.offset.filter(|_| entry.date == today),
+        category: entry.category,
+        amount: entry.amount,
+        description: entry.description,
+        payee: entry.payee,
+        user: config.user(),
+        ..Default::default()
+    };
+    match storage.append(expense.clone()) {
+        Ok(id) => expense.id = id,
+        Err(err) => return format!("Not added: {err}"),
+    }
+    webhook::added_to(storage, &config.webhooks, &[expense.id]);
+    let day = if entry.date == today {
+        String::from("Today")
+    } else {
+        entry.date.to_string()
+    };
+    match day_total(storage, ledger, entry.date) {
+        Ok(total) => format!("Added {expense}\n{day}: {}", currency::amount(total)),
+        Err(_) => format!("Added {expense}"),
+    }
+}
+
+// Log the expenses sent to the bot until interrupted. Chats not listed in
+// the config are told their id and otherwise ignored.
+pub fn run_telegram(storage: &dyn Storage, config: &Config, ledger: &str) -> Result<(), String> {
+    let telegram = &config.telegram;
+    let token = telegram
+        .token
+        .as_deref()
+        .ok_or("set token under [telegram] in the config file")?;
+    if telegram.chats.is_empty() {
+        println!("No chats are allowed yet; message the bot to learn a chat's id.");
+    }
+    // A wrong token fails here rather than being retried forever.
+    ureq::get(&url(token, "getMe"))
+        .call()
+        .map_err(|err| format!("the bot token was not accepted: {err}"))?;
+    println!("Listening for messages. Press Ctrl-C to stop.");
+    let mut offset = 0;
+    loop {
+        let updates = match updates(token, offset) {
+            Ok(updates) => updates,
+            Err(err) => {
+                eprintln!("{err}");
+                thread::sleep(RETRY_AFTER);
+                continue;
+            }
+        };
+        for update in updates {
+            offset = offset.max(update.update_id + 1);
+            let Some(message) = update.message else {
+                continue;
+            };
+            let Some(text) = message.text else {
+                continue;
+            };
+            let chat = message.chat.id;
+            let answer = if telegram.chats.contains(&chat) {
+                reply(storage, config, ledger, &text)
+            } else {
+                eprintln!("Ignored a message from chat {chat}.");
+                format!(
+                    "This chat can't log expenses. Add {chat} to chats under [telegram] in the config file to allow it."
+                )
+            };
+            if let Err(err) = send(token, chat, &answer) {
+                eprintln!("{err}");
+            }
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use chrono::{Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::dates::period_of;
+use crate::expense::{Expense, ExpenseTracker};
+use crate::i18n;
+use crate::report::in_month;
+
+// A monthly spending limit for one category
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Budget {
+    pub category: String,
+    pub amount: f64,
+    // Carry unspent budget (or overspend) into the next month
+    #[serde(default)]
+    pub rollover: bool,
+    // First month the budget applies to
+    pub since: NaiveDate,
+}
+
+// Where a budget stands in a given month
+pub struct Status {
+    pub category: String,
+    pub carried_in: f64,
+    pub amount: f64,
+    pub spent: f64,
+}
+
+impl Status {
+    pub fn available(&self) -> f64 {
+        self.carried_in + self.amount
+    }
+
+    pub fn remaining(&self) -> f64 {
+        self.available() - self.spent
+    }
+}
+
+// Spend in `category` during the month starting at `month`, counting the
+// matching legs of split expenses.
+pub fn spent(tracker: &ExpenseTracker, category: &str, month: NaiveDate) -> f64 {
+    in_month(tracker, month)
+        .iter()
+        .flat_map(|e| e.category_amounts())
+        .filter(|(c, _)| c.eq_ignore_ascii_case(category))
+        .fold(0.0, |sum, (_, amount)| sum + amount)
+}
+
+// The envelope balance a rollover budget brings into `month`: every earlier
+// month since the budget started adds its unspent amount or subtracts its
+// overspend.
+pub fn carried_in(tracker: &ExpenseTracker, budget: &Budget, month: NaiveDate) -> f64 {
+    if !budget.rollover {
+        return 0.0;
+    }
+    let mut balance = 0.0;
+    let mut current = budget.since;
+    while current < month {
+        balance += budget.amount - spent(tracker, &budget.category, current);
+        current = current + Months::new(1);
+    }
+    balance
+}
+
+pub fn status(tracker: &ExpenseTracker, month: NaiveDate) -> Vec<Status> {
+    tracker
+        .budgets
+        .iter()
+        .filter(|b| b.since <= month)
+        .map(|b| Status {
+            category: b.category.clone(),
+            carried_in: carried_in(tracker, b, month),
+            amount: b.amount,
+            spent: spent(tracker, &b.category, month),
+        })
+        .collect()
+}
+
+// The highest of each budget's `thresholds`, as percentages of what's
+// available, that adding `expense` took the month's spending past.
+pub fn crossed(
+    tracker: &ExpenseTracker,
+    expense: &Expense,
+    thresholds: impl Fn(&str) -> Vec<f64>,
+) -> Vec<(Status, f64)> {
+    status(tracker, period_of(expense.date))
+        .into_iter()
+        .filter_map(|status| {
+            let added: f64 = expense
+                .category_amounts()
+                .iter()
+                .filter(|(c, _)| c.eq_ignore_ascii_case(&status.category))
+                .map(|(_, amount)| amount)
+                .sum();
+            let before = status.spent - added;
+            let threshold = thresholds(&status.category)
+                .into_iter()
+                .filter(|percent| {
+                    let limit = status.available() * percent / 100.0;
+                    added > 0.0 && before <= limit && status.spent > limit
+                })
+                .max_by(f64::total_cmp)?;
+            Some((status, threshold))
+        })
+        .collect()
+}
+
+pub fn print_status(statuses: &[Status]) {
+    if statuses.is_empty() {
+        println!("No budgets set.");
+        return;
+    }
+    println!(
+        "{:<20} {:>12} {:>12} {:>12} {:>7}",
+        i18n::t("label-category"),
+        i18n::t("label-budget"),
+        i18n::t("label-spent"),
+        i18n::t("label-remaining"),
+        i18n::t("label-used")
+    );
+    for status in statuses {
+        let used = if status.available() > 0.0 {
+            format!("{:.0}%", status.spent / status.available() * 100.0)
+        } else {
+            String::from("-")
+        };
+        println!(
+            "{:<20} {:>12.2} {:>12.2} {:>12.2} {:>7}",
+            status.category,
+            status.available(),
+            status.spent,
+            status.remaining(),
+            used
+        );
+    }
+}
+
+pub fn print_envelopes(statuses: &[Status]) {
+    if statuses.is_empty() {
+        println!("No budgets set.");
+        return;
+    }
+    println!(
+        "{:<20} {:>12} {:>12} {:>12} {:>12}",
+        i18n::t("label-envelope"),
+        i18n::t("label-carried-in"),
+        i18n::t("label-budget"),
+        i18n::t("label-spent"),
+        i18n::t("label-balance")
+    );
+    for status in statuses {
+        println!(
+            "{:<20} {:>12.2} {:>12.2} {:>12.2} {:>12.2}",
+            status.category,
+            status.carried_in,
+            status.amount,
+            status.spent,
+            status.remaining()
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+
+use crate::expense::ExpenseTracker;
+
+// What categories without a bucket are rolled up into
+pub const UNASSIGNED: &str = "Unassigned";
+
+// A ready-made set of categories, each optionally in a bucket
+pub struct Preset {
+    pub name: &'static str,
+    pub about: &'static str,
+    pub categories: &'static [(&'static str, Option<&'static str>)],
+}
+
+pub const PRESETS: [Preset; 3] = [
+    Preset {
+        name: "simple",
+        about: "A handful of broad categories.",
+        categories: &[
+            ("Food", None),
+            ("Home", None),
+            ("Transport", None),
+            ("Fun", None),
+            ("Other", None),
+        ],
+    },
+    Preset {
+        name: "50/30/20",
+        about: "Categories in needs, wants and savings buckets, for the 50/30/20 rule.",
+        categories: &[
+            ("Rent", Some("Needs")),
+            ("Groceries", Some("Needs")),
+            ("Utilities", Some("Needs")),
+            ("Transport", Some("Needs")),
+            ("Health", Some("Needs")),
+            ("Insurance", Some("Needs")),
+            ("Dining", Some("Wants")),
+            ("Entertainment", Some("Wants")),
+            ("Shopping", Some("Wants")),
+            ("Travel", Some("Wants")),
+            ("Savings", Some("Savings")),
+            ("Investments", Some("Savings")),
+            ("Debt repayment", Some("Savings")),
+        ],
+    },
+    Preset {
+        name: "detailed-household",
+        about: "Fine-grained categories for a family home, in needs, wants and savings buckets.",
+        categories: &[
+            ("Rent", Some("Needs")),
+            ("Maintenance", Some("Needs")),
+            ("Electricity", Some("Needs")),
+            ("Water", Some("Needs")),
+            ("Gas", Some("Needs")),
+            ("Internet", Some("Needs")),
+            ("Phone", Some("Needs")),
+            ("Groceries", Some("Needs")),
+            ("Household supplies", Some("Needs")),
+            ("Domestic help", Some("Needs")),
+            ("Fuel", Some("Needs")),
+            ("Public transport", Some("Needs")),
+            ("Medical", Some("Needs")),
+            ("Insurance", Some("Needs")),
+            ("School fees", Some("Needs")),
+            ("Childcare", Some("Needs")),
+            ("Dining out", Some("Wants")),
+            ("Takeaway", Some("Wants")),
+            ("Entertainment", Some("Wants")),
+            ("Subscriptions", Some("Wants")),
+            ("Clothing", Some("Wants")),
+            ("Personal care", Some("Wants")),
+            ("Gifts", Some("Wants")),
+            ("Hobbies", Some("Wants")),
+            ("Travel", Some("Wants")),
+            ("Savings", Some("Savings")),
+            ("Investments", Some("Savings")),
+            ("Loan EMI", Some("Savings")),
+        ],
+    },
+];
+
+pub fn find_preset(name: &str) -> Option<&'static Preset> {
+    PRESETS.iter().find(|p| p.name.eq_ignore_ascii_case(name))
+}
+
+// The bucket `category` rolls up into, if any.
+pub fn bucket_of<'a>(tracker: &'a ExpenseTracker, category: &str) -> Option<&'a str> {
+    tracker
+        .buckets
+        .iter()
+        .find(|(c, _)| c.eq_ignore_ascii_case(category))
+        .map(|(_, bucket)| bucket.as_str())
+}
+
+// Put `category` in `bucket`, or take it out of any with None.
+pub fn set_bucket(tracker: &mut ExpenseTracker, category: &str, bucket: Option<&str>) {
+    tracker
+        .buckets
+        .retain(|c, _| !c.eq_ignore_ascii_case(category));
+    if let Some(bucket) = bucket {
+        tracker
+            .buckets
+            .insert(category.to_string(), bucket.to_string());
+    }
+}
+
+// Add the preset's categories and buckets, leaving those already set up
+// alone. Returns how many categories were new.
+pub fn apply(tracker: &mut ExpenseTracker, preset: &Preset) -> usize {
+    let mut added = 0;
+    for (category, bucket) in preset.categories {
+        if !tracker
+            .categories
+            .iter()
+            .any(|c| c.eq_ignore_ascii_case(category))
+        {
+            tracker.categories.push(category.to_string());
+            added += 1;
+        }
+        if let Some(bucket) = bucket
+            && bucket_of(tracker, category).is_none()
+        {
+            set_bucket(tracker, category, Some(bucket));
+        }
+    }
+    added
+}
+
+// Category totals rolled up into their buckets, largest first.
+pub fn bucket_totals(tracker: &ExpenseTracker, categories: &[(String, f64)]) -> Vec<(String, f64)> {
+    let mut totals: BTreeMap<String, f64> = BTreeMap::new();
+    for (category, amount) in categories {
+        let bucket = bucket_of(tracker, category).unwrap_or(UNASSIGNED);
+        *totals.entry(bucket.to_string()).or_default() += amount;
+    }
+    let mut totals: Vec<(String, f64)> = totals.into_iter().collect();
+    totals.sort_by(|a, b| b.1.total_cmp(&a.1));
+    totals
+}
+
+pub fn print_list(tracker: &ExpenseTracker) {
+    if tracker.categories.is_empty() && tracker.buckets.is_empty() {
+        println!("No categories set up.");
+        return;
+    }
+    for category in tracker.categories.iter() {
+        match bucket_of(tracker, category) {
+            Some(bucket) => println!("{:<24} {}", category, bucket),
+            None => println!("{category}"),
+        }
+    }
+    // Buckets can be set for categories only ever used, not set up
+    for (category, bucket) in tracker.buckets.iter() {
+        if !tracker
+            .categories
+            .iter()
+            .any(|c| c.eq_ignore_ascii_case(category))
+        {
+            println!("{:<24} {}", category, bucket);
+        }
+    }
+}
+
+pub fn print_presets() {
+    for preset in PRESETS.iter() {
+        println!("{:<20} {}", preset.name, preset.about);
+        let names: Vec<&str> = preset.categories.iter().map(|(c, _)| *c).collect();
+        println!("  {}", names.join(", "));
+    }
+}
@@ -0,0 +1,2244 @@
+use crate::{
+    category, currency, dates, edit, expense, filter, guide, i18n, report, shared, snapshot,
+};
+use clap::{Arg, Command};
+use std::path::PathBuf;
+
+pub fn build() -> Command {
+    Command::new("Expense Tracker CLI")
+        .version("1.0")
+        .author("Arijit Gogoi <arijit@email.com>")
+        .about(i18n::t("cli-about"))
+        .after_help(i18n::t("cli-exit-codes"))
+        .subcommand_required(true)
+        .arg_required_else_help(true)
+        .arg(
+            Arg::new("mask_private")
+                .action(clap::ArgAction::SetTrue)
+                .long("mask-private")
+                .global(true)
+                .help(i18n::t("help-mask_private")),
+        )
+        .arg(
+            Arg::new("no_keyring")
+                .action(clap::ArgAction::SetTrue)
+                .long("no-keyring")
+                .global(true)
+                .help(i18n::t("help-no_keyring")),
+        )
+        .arg(
+            Arg::new("remote")
+                .long("remote")
+                .global(true)
+                .help(i18n::t("help-remote")),
+        )
+        .arg(
+            Arg::new("token")
+                .long("token")
+                .global(true)
+                .help(i18n::t("help-token")),
+        )
+        .arg(
+            Arg::new("verbose")
+                .action(clap::ArgAction::Count)
+                .short('v')
+                .long("verbose")
+                .global(true)
+                .help(i18n::t("help-verbose")),
+        )
+        .arg(
+            Arg::new("log_file")
+                .long("log-file")
+                .global(true)
+                .help(i18n::t("help-log_file"))
+                .value_parser(clap::value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("dry_run")
+                .action(clap::ArgAction::SetTrue)
+                .long("dry-run")
+                .global(true)
+                .help(i18n::t("help-filter-dry_run")),
+        )
+        .arg(
+            Arg::new("read_only")
+                .action(clap::ArgAction::SetTrue)
+                .long("read-only")
+                .global(true)
+                .help(i18n::t("help-read_only")),
+        )
+        .arg(
+            Arg::new("data_dir")
+                .long("data-dir")
+                .global(true)
+                .help(i18n::t("help-data_dir"))
+                .value_parser(clap::value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("currency")
+                .long("currency")
+                .global(true)
+                .help(i18n::t("help-currency"))
+                .value_parser(currency::parse_code),
+        )
+        .arg(
+            Arg::new("only")
+                .long("only")
+                .global(true)
+                .help(i18n::t("help-only"))
+                .value_parser(filter::parse_class),
+        )
+        .subcommand(
+            Command::new("init")
+                .about(i18n::t("cli-init"))
+                .after_help("Asks where to keep expenses, the currency, categories to start with and monthly budgets. An existing config file keeps its other settings, and an existing ledger its expenses."),
+        )
+        .subcommand(
+            Command::new("generate-demo")
+                .about(i18n::t("cli-generate-demo"))
+                .after_help("The demo ledger goes in its own directory, leaving your own untouched; use it with --data-dir. Spending bunches up after payday on the first, with monthly bills, budgets and an occasional big purchase.")
+                .arg(
+                    Arg::new("months")
+                        .long("months")
+                        .default_value("12")
+                        .help(i18n::t("help-generate-demo-months"))
+                        .value_parser(clap::value_parser!(u32).range(1..=120)),
+                )
+                .arg(
+                    Arg::new("dir")
+                        .long("dir")
+                        .required(false)
+                        .help(i18n::t("help-dir"))
+                        .value_parser(clap::value_parser!(PathBuf)),
+                )
+                .arg(
+                    Arg::new("seed")
+                        .long("seed")
+                        .required(false)
+                        .help(i18n::t("help-seed"))
+                        .value_parser(clap::value_parser!(u64)),
+                ),
+        )
+        .subcommand(
+            Command::new("add")
+                .about(i18n::t("cli-add"))
+                .after_help(
+                    "Examples:\n  expense-tracker-rs add -c Food -a 250 -d \"Lunch\"\n  expense-tracker-rs add -a 500 -d \"Supermarket\" -s Groceries=300 -s Household=200\n\nWithout --amount, --description or a category, add asks for each field in turn.\n\nSee `expense-tracker-rs guide getting-started` for more.",
+                )
+                .visible_alias("a")
+                .arg(
+                    Arg::new("category")
+                        .short('c')
+                        .long("category")
+                        .help(i18n::t("help-add-category"))
+                        .value_parser(clap::value_parser!(String)),
+                )
+                .arg(
+                    Arg::new("amount")
+                        .short('a')
+                        .long("amount")
+                        .help(i18n::t("help-add-amount"))
+                        .value_parser(expense::parse_amount),
+                )
+                .arg(
+                    Arg::new("description")
+                        .short('d')
+                        .long("description")
+                        .help(i18n::t("help-add-description")),
+                )
+                .arg(
+                    Arg::new("when")
+                        .required(false)
+                        .short('w')
+                        .long("when")
+                        .help(i18n::t("help-when"))
+                        .value_parser(dates::parse_when),
+                )
+                .arg(
+                    Arg::new("tz")
+                        .required(false)
+                        .long("tz")
+                        .help(i18n::t("help-tz"))
+                        .value_parser(dates::parse_zone),
+                )
+                .arg(
+                    Arg::new("payee")
+                        .required(false)
+                        .short('p')
+                        .long("payee")
+                        .help(i18n::t("help-add-payee"))
+                        .value_parser(clap::value_parser!(String)),
+                )
+                .arg(
+                    Arg::new("trip")
+                        .required(false)
+                        .long("trip")
+                        .help(i18n::t("help-add-trip"))
+                        .value_parser(clap::value_parser!(String)),
+                )
+                .arg(
+                    Arg::new("project")
+                        .required(false)
+                        .long("project")
+                        .help(i18n::t("help-add-project"))
+                        .value_parser(clap::value_parser!(String)),
+                )
+                .arg(
+                    Arg::new("user")
+                        .required(false)
+                        .long("user")
+                        .help(i18n::t("help-add-user"))
+                        .value_parser(clap::value_parser!(String)),
+                )
+                .arg(
+                    Arg::new("split")
+                        .required(false)
+                        .short('s')
+                        .long("split")
+                        .action(clap::ArgAction::Append)
+                        .help(i18n::t("help-split"))
+                        .value_parser(expense::parse_split),
+                )
+                .arg(
+                    Arg::new("item")
+                        .required(false)
+                        .short('i')
+                        .long("item")
+                        .action(clap::ArgAction::Append)
+                        .help(i18n::t("help-item"))
+                        .value_parser(expense::parse_item),
+                )
+                .arg(
+                    Arg::new("split_with")
+                        .required(false)
+                        .long("split-with")
+                        .value_delimiter(',')
+                        .help(i18n::t("help-split_with"))
+                        .value_parser(clap::value_parser!(String)),
+                )
+                .arg(
+                    Arg::new("paid_by")
+                        .required(false)
+                        .long("paid-by")
+                        .requires("split_with")
+                        .help(i18n::t("help-paid_by"))
+                        .value_parser(clap::value_parser!(String)),
+                )
+                .arg(
+                    Arg::new("tag")
+                        .required(false)
+                        .long("tag")
+                        .action(clap::ArgAction::Append)
+                        .value_delimiter(',')
+                        .help(i18n::t("help-add-tag"))
+                        .value_parser(clap::value_parser!(String)),
+                )
+                .arg(
+                    Arg::new("meta")
+                        .required(false)
+                        .long("meta")
+                        .action(clap::ArgAction::Append)
+                        .help(i18n::t("help-add-meta"))
+                        .value_parser(expense::parse_meta),
+                )
+                .arg(
+                    Arg::new("template")
+                        .short('t')
+                        .long("template")
+                        .required(false)
+                        .help(i18n::t("help-add-template"))
+                        .value_parser(clap::value_parser!(String)),
+                )
+                .arg(
+                    Arg::new("stdin")
+                        .action(clap::ArgAction::SetTrue)
+                        .long("stdin")
+                        .conflicts_with_all(["category", "amount", "description", "when", "tz", "payee", "split", "item", "attach", "template"])
+                        .help(i18n::t("help-stdin")),
+                )
+                .arg(
+                    Arg::new("private")
+                        .action(clap::ArgAction::SetTrue)
+                        .long("private")
+                        .help(i18n::t("help-private")),
+                )
+                .arg(
+                    Arg::new("deductible")
+                        .action(clap::ArgAction::SetTrue)
+                        .long("deductible")
+                        .help(i18n::t("help-deductible")),
+                )
+                .arg(
+                    Arg::new("business")
+                        .action(clap::ArgAction::SetTrue)
+                        .long("business")
+                        .help(i18n::t("help-business")),
+                )
+                .arg(
+                    Arg::new("reimbursable")
+                        .action(clap::ArgAction::SetTrue)
+                        .long("reimbursable")
+                        .help(i18n::t("help-reimbursable")),
+                )
+                .arg(
+                    Arg::new("attach")
+                        .required(false)
+                        .long("attach")
+                        .action(clap::ArgAction::Append)
+                        .help(i18n::t("help-attach"))
+                        .value_parser(clap::value_parser!(PathBuf)),
+                ),
+        )
+        .subcommand(
+            Command::new("delete")
+                .about(i18n::t("cli-delete"))
+                .after_help("Example:\n  expense-tracker-rs delete --category test --before 2024-01-01")
+                .visible_alias("d")
+                .arg_required_else_help(true)
+                .arg(
+                    Arg::new("row_number")
+                        .required_unless_present_any(FILTER_IDS)
+                        .conflicts_with_all(FILTER_IDS)
+                        .help(i18n::t("help-row_number"))
+                        .value_parser(clap::value_parser!(usize)),
+                )
+                .args(filters())
+                .arg(
+                    Arg::new("yes")
+                        .action(clap::ArgAction::SetTrue)
+                        .short('y')
+                        .long("yes")
+                        .help(i18n::t("help-delete-yes")),
+                )
+                .arg(
+                    Arg::new("purge_attachments")
+                        .action(clap::ArgAction::SetTrue)
+                        .long("purge-attachments")
+                        .help(i18n::t("help-purge_attachments")),
+                ),
+        )
+        .subcommand(
+            Command::new("edit")
+                .about(i18n::t("cli-edit"))
+                .after_help("Examples:\n  expense-tracker-rs edit --payee BigBasket --set category=Groceries --add-tag home\n  expense-tracker-rs edit --editor --from 2025-07-01")
+                .arg_required_else_help(true)
+                .args(filters())
+                .arg(
+                    Arg::new("set")
+                        .long("set")
+                        .required(false)
+                        .action(clap::ArgAction::Append)
+                        .help(i18n::t("help-set"))
+                        .value_parser(edit::parse_set),
+                )
+                .arg(
+                    Arg::new("add_tag")
+                        .long("add-tag")
+                        .required(false)
+                        .action(clap::ArgAction::Append)
+                        .help(i18n::t("help-add_tag"))
+                        .value_parser(clap::value_parser!(String)),
+                )
+                .arg(
+                    Arg::new("remove_tag")
+                        .long("remove-tag")
+                        .required(false)
+                        .action(clap::ArgAction::Append)
+                        .help(i18n::t("help-remove_tag"))
+                        .value_parser(clap::value_parser!(String)),
+                )
+                .arg(
+                    Arg::new("yes")
+                        .action(clap::ArgAction::SetTrue)
+                        .short('y')
+                        .long("yes")
+                        .help(i18n::t("help-edit-yes")),
+                )
+                .arg(
+                    Arg::new("editor")
+                        .action(clap::ArgAction::SetTrue)
+                        .long("editor")
+                        .conflicts_with_all(["set", "add_tag", "remove_tag", "yes"])
+                        .help(i18n::t("help-editor")),
+                ),
+        )
+        .subcommand(
+            Command::new("summary")
+                .about(i18n::t("cli-summary"))
+                .after_help(
+                    "Examples:\n  expense-tracker-rs summary --all\n  expense-tracker-rs summary --category Food\n\nSee `expense-tracker-rs guide reports` for more.",
+                )
+                .visible_alias("s")
+                .arg_required_else_help(true)
+                .arg(
+                    Arg::new("date")
+                        .short('d')
+                        .long("date")
+                        .required(false)
+                        .conflicts_with_all(["from", "to"])
+                        .help(i18n::t("help-summary-date"))
+                        .value_parser(dates::parse_date),
+                )
+                .arg(
+                    Arg::new("month")
+                        .short('m')
+                        .long("month")
+                        .required(false)
+                        .help(i18n::t("help-summary-month"))
+                        .value_parser(clap::value_parser!(u8)),
+                )
+                .arg(
+                    Arg::new("fy")
+                        .long("fy")
+                        .required(false)
+                        .conflicts_with_all(["date", "month", "from", "to"])
+                        .help(i18n::t("help-summary-fy"))
+                        .value_parser(dates::parse_fiscal_year),
+                )
+                .arg(
+                    Arg::new("quarter")
+                        .long("quarter")
+                        .required(false)
+                        .conflicts_with_all(["date", "month", "from", "to", "fy"])
+                        .help(i18n::t("help-quarter"))
+                        .value_parser(dates::parse_quarter),
+                )
+                .arg(
+                    Arg::new("year")
+                        .long("year")
+                        .required(false)
+                        .conflicts_with_all(["date", "month", "from", "to", "fy", "quarter"])
+                        .help(i18n::t("help-summary-year"))
+                        .value_parser(clap::value_parser!(i32).range(dates::YEARS)),
+                )
+                .arg(
+                    Arg::new("all")
+                        .action(clap::ArgAction::SetTrue)
+                        .short('a')
+                        .long("all")
+                        .required(false)
+                        .help(i18n::t("help-all")),
+                )
+                .args(filters()),
+        )
+        .subcommand(
+            Command::new("total")
+                .about(i18n::t("cli-total"))
+                .visible_alias("t")
+                .arg(
+                    Arg::new("month")
+                        .short('m')
+                        .long("month")
+                        .required(false)
+                        .help(i18n::t("help-total-month"))
+                        .value_parser(clap::value_parser!(u8)),
+                )
+                .args(filters()),
+        )
+        .subcommand(
+            Command::new("search")
+                .about(i18n::t("cli-search"))
+                .arg_required_else_help(true)
+                .arg(
+                    Arg::new("query")
+                        .required(true)
+                        .help(i18n::t("help-query"))
+                        .value_parser(clap::value_parser!(String)),
+                )
+                .arg(
+                    Arg::new("regex")
+                        .action(clap::ArgAction::SetTrue)
+                        .short('r')
+                        .long("regex")
+                        .help(i18n::t("help-regex")),
+                )
+                .args(filters()),
+        )
+        .subcommand(
+            Command::new("stats")
+                .about(i18n::t("cli-stats"))
+                .arg(
+                    Arg::new("month")
+                        .short('m')
+                        .long("month")
+                        .required(false)
+                        .conflicts_with_all(["from", "to"])
+                        .help(i18n::t("help-stats-month"))
+                        .value_parser(dates::parse_month),
+                )
+                .arg(
+                    Arg::new("from")
+                        .long("from")
+                        .required(false)
+                        .help(i18n::t("help-stats-from"))
+                        .value_parser(dates::parse_date),
+                )
+                .arg(
+                    Arg::new("to")
+                        .long("to")
+                        .required(false)
+                        .help(i18n::t("help-stats-to"))
+                        .value_parser(dates::parse_date),
+                ),
+        )
+        .subcommand(
+            Command::new("forecast")
+                .about(i18n::t("cli-forecast"))
+                .arg(
+                    Arg::new("as_of")
+                        .long("as-of")
+                        .required(false)
+                        .help(i18n::t("help-as_of"))
+                        .value_parser(dates::parse_date),
+                )
+                .arg(
+                    Arg::new("history")
+                        .long("history")
+                        .default_value("3")
+                        .help(i18n::t("help-history"))
+                        .value_parser(clap::value_parser!(u32)),
+                ),
+        )
+        .subcommand(
+            Command::new("list")
+                .about(i18n::t("cli-list"))
+                .visible_alias("l")
+                .args(filters())
+                .arg(
+                    Arg::new("cumulative")
+                        .action(clap::ArgAction::SetTrue)
+                        .long("cumulative")
+                        .help(i18n::t("help-cumulative")),
+                )
+                .arg(
+                    Arg::new("budget")
+                        .long("budget")
+                        .required(false)
+                        .num_args(0..=1)
+                        .requires("cumulative")
+                        .help(i18n::t("help-budget"))
+                        .value_parser(expense::parse_amount),
+                ),
+        )
+        .subcommand(
+            Command::new("archive")
+                .about(i18n::t("cli-archive"))
+                .after_help("Example:\n  expense-tracker-rs archive --before 2024-01-01\n\nExpenses move to files such as expenses-2023.json next to the data file. summary, total, stats, export, chart and report read the archives back in when their range reaches them; list, edit and delete only see the active file.")
+                .arg_required_else_help(true)
+                .arg(
+                    Arg::new("before")
+                        .long("before")
+                        .required(true)
+                        .help(i18n::t("help-archive-before"))
+                        .value_parser(dates::parse_date),
+                ),
+        )
+        .subcommand(
+            Command::new("snapshot")
+                .about(i18n::t("cli-snapshot"))
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("create")
+                        .about(i18n::t("cli-snapshot-create"))
+                        .after_help("Example:\n  expense-tracker-rs snapshot create before-import\n  expense-tracker-rs import statement.csv\n  expense-tracker-rs diff before-import")
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("name")
+                                .required(true)
+                                .help(i18n::t("help-snapshot-create-name"))
+                                .value_parser(snapshot::parse_name),
+                        ),
+                )
+                .subcommand(
+                    Command::new("remove")
+                        .about(i18n::t("cli-snapshot-remove"))
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("name")
+                                .required(true)
+                                .help(i18n::t("help-snapshot-remove-name"))
+                                .value_parser(snapshot::parse_name),
+                        ),
+                )
+                .subcommand(Command::new("list").about(i18n::t("cli-snapshot-list"))),
+        )
+        .subcommand(
+            Command::new("diff")
+                .about(i18n::t("cli-diff"))
+                .arg_required_else_help(true)
+                .arg(
+                    Arg::new("snapshot")
+                        .required(true)
+                        .help(i18n::t("help-snapshot"))
+                        .value_parser(snapshot::parse_name),
+                )
+                .arg(
+                    Arg::new("other")
+                        .required(false)
+                        .help(i18n::t("help-other"))
+                        .value_parser(snapshot::parse_name),
+                ),
+        )
+        .subcommand(
+            Command::new("history")
+                .about(i18n::t("cli-history"))
+                .after_help("History is kept in expenses.history from the first change onwards; expenses present then are recorded as added by (existing). An encrypted ledger's history is encrypted too.")
+                .arg(
+                    Arg::new("id")
+                        .required(false)
+                        .help(i18n::t("help-history-id"))
+                        .value_parser(clap::value_parser!(u64)),
+                )
+                .arg(
+                    Arg::new("limit")
+                        .short('n')
+                        .long("limit")
+                        .default_value("20")
+                        .help(i18n::t("help-history-limit"))
+                        .value_parser(clap::value_parser!(usize)),
+                )
+                .arg(
+                    Arg::new("at")
+                        .long("at")
+                        .required(false)
+                        .conflicts_with_all(["id", "limit"])
+                        .help(i18n::t("help-at"))
+                        .value_parser(dates::parse_date),
+                ),
+        )
+        .subcommand(
+            Command::new("show")
+                .about(i18n::t("cli-show"))
+                .arg_required_else_help(true)
+                .arg(
+                    Arg::new("id")
+                        .required(true)
+                        .help(i18n::t("help-show-id"))
+                        .value_parser(clap::value_parser!(u64)),
+                )
+                .arg(
+                    Arg::new("open")
+                        .action(clap::ArgAction::SetTrue)
+                        .long("open")
+                        .help(i18n::t("help-open")),
+                ),
+        )
+        .subcommand(
+            Command::new("balances")
+                .about(i18n::t("cli-balances"))
+                .visible_alias("b"),
+        )
+        .subcommand(
+            Command::new("settle")
+                .about(i18n::t("cli-settle"))
+                .arg_required_else_help(true)
+                .arg(
+                    Arg::new("amount")
+                        .required(true)
+                        .help(i18n::t("help-settle-amount"))
+                        .value_parser(expense::parse_amount),
+                )
+                .arg(
+                    Arg::new("from")
+                        .long("from")
+                        .default_value(shared::ME)
+                        .help(i18n::t("help-settle-from"))
+                        .value_parser(clap::value_parser!(String)),
+                )
+                .arg(
+                    Arg::new("to")
+                        .long("to")
+                        .default_value(shared::ME)
+                        .help(i18n::t("help-settle-to"))
+                        .value_parser(clap::value_parser!(String)),
+                ),
+        )
+        .subcommand(
+            Command::new("debt")
+                .about(i18n::t("cli-debt"))
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("add")
+                        .about(i18n::t("cli-debt-add"))
+                        .arg_required_else_help(true)
+                        .after_help("Example:\n  expense-tracker-rs debt add alice 500 \"lunch\"")
+                        .arg(
+                            Arg::new("person")
+                                .required(true)
+                                .help(i18n::t("help-debt-add-person"))
+                                .value_parser(clap::value_parser!(String)),
+                        )
+                        .arg(
+                            Arg::new("amount")
+                                .required(true)
+                                .help(i18n::t("help-debt-add-amount"))
+                                .value_parser(expense::parse_amount),
+                        )
+                        .arg(
+                            Arg::new("description")
+                                .required(false)
+                                .help(i18n::t("help-debt-add-description"))
+                                .value_parser(clap::value_parser!(String)),
+                        )
+                        .arg(
+                            Arg::new("borrowed")
+                                .action(clap::ArgAction::SetTrue)
+                                .long("borrowed")
+                                .help(i18n::t("help-borrowed")),
+                        )
+                        .arg(
+                            Arg::new("date")
+                                .short('d')
+                                .long("date")
+                                .required(false)
+                                .help(i18n::t("help-debt-add-date"))
+                                .value_parser(dates::parse_date),
+                        ),
+                )
+                .subcommand(
+                    Command::new("repay")
+                        .about(i18n::t("cli-debt-repay"))
+                        .arg_required_else_help(true)
+                        .after_help("Example:\n  expense-tracker-rs debt repay alice 200")
+                        .arg(
+                            Arg::new("person")
+                                .required(true)
+                                .help(i18n::t("help-debt-repay-person"))
+                                .value_parser(clap::value_parser!(String)),
+                        )
+                        .arg(
+                            Arg::new("amount")
+                                .required(false)
+                                .help(i18n::t("help-debt-repay-amount"))
+                                .value_parser(expense::parse_amount),
+                        )
+                        .arg(
+                            Arg::new("date")
+                                .short('d')
+                                .long("date")
+                                .required(false)
+                                .help(i18n::t("help-debt-repay-date"))
+                                .value_parser(dates::parse_date),
+                        ),
+                )
+                .subcommand(
+                    Command::new("list")
+                        .about(i18n::t("cli-debt-list"))
+                        .arg(
+                            Arg::new("person")
+                                .required(false)
+                                .help(i18n::t("help-debt-list-person"))
+                                .value_parser(clap::value_parser!(String)),
+                        ),
+                )
+                .subcommand(Command::new("balances").about(i18n::t("cli-debt-balances"))),
+        )
+        .subcommand(
+            Command::new("assets")
+                .about(i18n::t("cli-assets"))
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("set")
+                        .about(i18n::t("cli-assets-set"))
+                        .arg_required_else_help(true)
+                        .after_help("Example:\n  expense-tracker-rs assets set Savings 250000\n  expense-tracker-rs assets set \"Credit card\" 18000 --liability")
+                        .arg(
+                            Arg::new("name")
+                                .required(true)
+                                .help(i18n::t("help-assets-set-name"))
+                                .value_parser(clap::value_parser!(String)),
+                        )
+                        .arg(
+                            Arg::new("amount")
+                                .required(true)
+                                .help(i18n::t("help-assets-set-amount"))
+                                .value_parser(expense::parse_non_negative),
+                        )
+                        .arg(
+                            Arg::new("liability")
+                                .action(clap::ArgAction::SetTrue)
+                                .long("liability")
+                                .help(i18n::t("help-liability")),
+                        )
+                        .arg(
+                            Arg::new("date")
+                                .short('d')
+                                .long("date")
+                                .required(false)
+                                .help(i18n::t("help-assets-set-date"))
+                                .value_parser(dates::parse_date),
+                        ),
+                )
+                .subcommand(
+                    Command::new("remove")
+                        .about(i18n::t("cli-assets-remove"))
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("name")
+                                .required(true)
+                                .help(i18n::t("help-assets-remove-name"))
+                                .value_parser(clap::value_parser!(String)),
+                        ),
+                )
+                .subcommand(
+                    Command::new("list")
+                        .about(i18n::t("cli-assets-list")),
+                ),
+        )
+        .subcommand(
+            Command::new("category")
+                .about(i18n::t("cli-category"))
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("list").about(i18n::t("cli-category-list")),
+                )
+                .subcommand(
+                    Command::new("bucket")
+                        .about(i18n::t("cli-category-bucket"))
+                        .arg_required_else_help(true)
+                        .after_help("Example:\n  expense-tracker-rs category bucket Rent Needs\n  expense-tracker-rs report breakdown --by-bucket")
+                        .arg(
+                            Arg::new("category")
+                                .required(true)
+                                .help(i18n::t("help-category-bucket-category"))
+                                .value_parser(clap::value_parser!(String)),
+                        )
+                        .arg(
+                            Arg::new("bucket")
+                                .required(false)
+                                .help(i18n::t("help-bucket"))
+                                .value_parser(clap::value_parser!(String)),
+                        ),
+                )
+                .subcommand(
+                    Command::new("preset")
+                        .about(i18n::t("cli-category-preset"))
+                        .subcommand_required(true)
+                        .arg_required_else_help(true)
+                        .subcommand(Command::new("list").about(i18n::t("cli-category-preset-list")))
+                        .subcommand(
+                            Command::new("apply")
+                                .about(i18n::t("cli-category-preset-apply"))
+                                .arg_required_else_help(true)
+                                .arg(
+                                    Arg::new("name")
+                                        .required(true)
+                                        .help(i18n::t("help-category-preset-apply-name"))
+                                        .value_parser(category::PRESETS.map(|p| p.name)),
+                                ),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("budget")
+                .about(i18n::t("cli-budget"))
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("set")
+                        .about(i18n::t("cli-budget-set"))
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("category")
+                                .required(true)
+                                .help(i18n::t("help-budget-set-category"))
+                                .value_parser(clap::value_parser!(String)),
+                        )
+                        .arg(
+                            Arg::new("amount")
+                                .required(true)
+                                .help(i18n::t("help-budget-set-amount"))
+                                .value_parser(expense::parse_amount),
+                        )
+                        .arg(
+                            Arg::new("rollover")
+                                .action(clap::ArgAction::SetTrue)
+                                .long("rollover")
+                                .help(i18n::t("help-rollover")),
+                        )
+                        .arg(
+                            Arg::new("since")
+                                .long("since")
+                                .required(false)
+                                .help(i18n::t("help-budget-set-since"))
+                                .value_parser(dates::parse_month),
+                        ),
+                )
+                .subcommand(
+                    Command::new("remove")
+                        .about(i18n::t("cli-budget-remove"))
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("category")
+                                .required(true)
+                                .help(i18n::t("help-budget-remove-category"))
+                                .value_parser(clap::value_parser!(String)),
+                        ),
+                )
+                .subcommand(
+                    Command::new("status")
+                        .about(i18n::t("cli-budget-status"))
+                        .arg(
+                            Arg::new("month")
+                                .short('m')
+                                .long("month")
+                                .required(false)
+                                .help(i18n::t("help-budget-status-month"))
+                                .value_parser(dates::parse_month),
+                        ),
+                )
+                .subcommand(
+                    Command::new("envelope")
+                        .about(i18n::t("cli-budget-envelope"))
+                        .arg(
+                            Arg::new("month")
+                                .short('m')
+                                .long("month")
+                                .required(false)
+                                .help(i18n::t("help-budget-envelope-month"))
+                                .value_parser(dates::parse_month),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("goal")
+                .about(i18n::t("cli-goal"))
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("add")
+                        .about(i18n::t("cli-goal-add"))
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("name")
+                                .required(true)
+                                .help(i18n::t("help-goal-add-name"))
+                                .value_parser(clap::value_parser!(String)),
+                        )
+                        .arg(
+                            Arg::new("target")
+                                .required(true)
+                                .help(i18n::t("help-target"))
+                                .value_parser(expense::parse_amount),
+                        )
+                        .arg(
+                            Arg::new("by")
+                                .long("by")
+                                .required(true)
+                                .help(i18n::t("help-by"))
+                                .value_parser(dates::parse_month),
+                        )
+                        .arg(
+                            Arg::new("category")
+                                .short('c')
+                                .long("category")
+                                .required_unless_present("income")
+                                .conflicts_with("income")
+                                .help(i18n::t("help-goal-add-category"))
+                                .value_parser(clap::value_parser!(String)),
+                        )
+                        .arg(
+                            Arg::new("income")
+                                .long("income")
+                                .help(i18n::t("help-income"))
+                                .value_parser(expense::parse_amount),
+                        )
+                        .arg(
+                            Arg::new("since")
+                                .long("since")
+                                .required(false)
+                                .help(i18n::t("help-goal-add-since"))
+                                .value_parser(dates::parse_month),
+                        ),
+                )
+                .subcommand(
+                    Command::new("remove")
+                        .about(i18n::t("cli-goal-remove"))
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("name")
+                                .required(true)
+                                .help(i18n::t("help-goal-remove-name"))
+                                .value_parser(clap::value_parser!(String)),
+                        ),
+                )
+                .subcommand(Command::new("status").about(i18n::t("cli-goal-status"))),
+        )
+        .subcommand(
+            Command::new("subscription")
+                .about(i18n::t("cli-subscription"))
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("add")
+                        .about(i18n::t("cli-subscription-add"))
+                        .arg_required_else_help(true)
+                        .after_help("Example:\n  expense-tracker-rs subscription add Netflix 649 --day 12 --url https://www.netflix.com/cancelplan")
+                        .arg(
+                            Arg::new("name")
+                                .required(true)
+                                .help(i18n::t("help-subscription-add-name"))
+                                .value_parser(clap::value_parser!(String)),
+                        )
+                        .arg(
+                            Arg::new("amount")
+                                .required(true)
+                                .help(i18n::t("help-subscription-add-amount"))
+                                .value_parser(expense::parse_amount),
+                        )
+                        .arg(
+                            Arg::new("day")
+                                .long("day")
+                                .required(true)
+                                .help(i18n::t("help-day"))
+                                .value_parser(clap::value_parser!(u32).range(1..=31)),
+                        )
+                        .arg(
+                            Arg::new("every")
+                                .long("every")
+                                .default_value("month")
+                                .help(i18n::t("help-every"))
+                                .value_parser(["month", "year"]),
+                        )
+                        .arg(
+                            Arg::new("month")
+                                .long("month")
+                                .required_if_eq("every", "year")
+                                .help(i18n::t("help-subscription-add-month"))
+                                .value_parser(clap::value_parser!(u32).range(1..=12)),
+                        )
+                        .arg(
+                            Arg::new("category")
+                                .short('c')
+                                .long("category")
+                                .required(false)
+                                .help(i18n::t("help-subscription-add-category"))
+                                .value_parser(clap::value_parser!(String)),
+                        )
+                        .arg(
+                            Arg::new("url")
+                                .long("url")
+                                .required(false)
+                                .help(i18n::t("help-url"))
+                                .value_parser(clap::value_parser!(String)),
+                        )
+                        .arg(
+                            Arg::new("notes")
+                                .long("notes")
+                                .required(false)
+                                .help(i18n::t("help-notes"))
+                                .value_parser(clap::value_parser!(String)),
+                        ),
+                )
+                .subcommand(
+                    Command::new("remove")
+                        .about(i18n::t("cli-subscription-remove"))
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("name")
+                                .required(true)
+                                .help(i18n::t("help-subscription-remove-name"))
+                                .value_parser(clap::value_parser!(String)),
+                        ),
+                )
+                .subcommand(
+                    Command::new("list")
+                        .about(i18n::t("cli-subscription-list")),
+                )
+                .subcommand(
+                    Command::new("upcoming")
+                        .about(i18n::t("cli-subscription-upcoming"))
+                        .arg(
+                            Arg::new("days")
+                                .long("days")
+                                .default_value("7")
+                                .help(i18n::t("help-days"))
+                                .value_parser(clap::value_parser!(u32)),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("loan")
+                .about(i18n::t("cli-loan"))
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("add")
+                        .about(i18n::t("cli-loan-add"))
+                        .arg_required_else_help(true)
+                        .after_help("The EMI is worked out from the principal, rate and tenure unless given.\n\nExample:\n  expense-tracker-rs loan add Car 500000 --rate 9.5 --months 60 --start 2025-04-05")
+                        .arg(
+                            Arg::new("name")
+                                .required(true)
+                                .help(i18n::t("help-loan-add-name"))
+                                .value_parser(clap::value_parser!(String)),
+                        )
+                        .arg(
+                            Arg::new("principal")
+                                .required(true)
+                                .help(i18n::t("help-principal"))
+                                .value_parser(expense::parse_amount),
+                        )
+                        .arg(
+                            Arg::new("rate")
+                                .long("rate")
+                                .required(true)
+                                .help(i18n::t("help-loan-add-rate"))
+                                .value_parser(expense::parse_non_negative),
+                        )
+                        .arg(
+                            Arg::new("months")
+                                .long("months")
+                                .required(true)
+                                .help(i18n::t("help-loan-add-months"))
+                                .value_parser(clap::value_parser!(u32).range(1..=600)),
+                        )
+                        .arg(
+                            Arg::new("emi")
+                                .long("emi")
+                                .required(false)
+                                .help(i18n::t("help-emi"))
+                                .value_parser(expense::parse_amount),
+                        )
+                        .arg(
+                            Arg::new("start")
+                                .long("start")
+                                .required(false)
+                                .help(i18n::t("help-start"))
+                                .value_parser(dates::parse_date),
+                        )
+                        .arg(
+                            Arg::new("category")
+                                .short('c')
+                                .long("category")
+                                .required(false)
+                                .help(i18n::t("help-loan-add-category"))
+                                .value_parser(clap::value_parser!(String)),
+                        ),
+                )
+                .subcommand(
+                    Command::new("remove")
+                        .about(i18n::t("cli-loan-remove"))
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("name")
+                                .required(true)
+                                .help(i18n::t("help-loan-remove-name"))
+                                .value_parser(clap::value_parser!(String)),
+                        ),
+                )
+                .subcommand(
+                    Command::new("list")
+                        .about(i18n::t("cli-loan-list")),
+                )
+                .subcommand(
+                    Command::new("schedule")
+                        .about(i18n::t("cli-loan-schedule"))
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("name")
+                                .required(true)
+                                .help(i18n::t("help-loan-schedule-name"))
+                                .value_parser(clap::value_parser!(String)),
+                        )
+                        .arg(
+                            Arg::new("yearly")
+                                .action(clap::ArgAction::SetTrue)
+                                .long("yearly")
+                                .help(i18n::t("help-yearly")),
+                        ),
+                )
+                .subcommand(
+                    Command::new("post")
+                        .about(i18n::t("cli-loan-post"))
+                        .after_help("Each instalment is logged once, itemised into interest and principal. Run it whenever, such as from cron.\n\nExample:\n  expense-tracker-rs loan post"),
+                ),
+        )
+        .subcommand(
+            Command::new("reimburse")
+                .about(i18n::t("cli-reimburse"))
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("list").about(i18n::t("cli-reimburse-list")).arg(
+                        Arg::new("status")
+                            .long("status")
+                            .required(false)
+                            .help(i18n::t("help-status"))
+                            .value_parser(["pending", "submitted", "reimbursed"]),
+                    ),
+                )
+                .subcommand(
+                    Command::new("submit")
+                        .about(i18n::t("cli-reimburse-submit"))
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("ids")
+                                .required(true)
+                                .num_args(1..)
+                                .help(i18n::t("help-ids"))
+                                .value_parser(clap::value_parser!(u64)),
+                        ),
+                )
+                .subcommand(
+                    Command::new("settle")
+                        .about(i18n::t("cli-reimburse-settle"))
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("ids")
+                                .required(true)
+                                .num_args(1..)
+                                .help(i18n::t("help-ids"))
+                                .value_parser(clap::value_parser!(u64)),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("view")
+                .about(i18n::t("cli-view"))
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("save")
+                        .about(i18n::t("cli-view-save"))
+                        .after_help("Example:\n  expense-tracker-rs view save work-travel --tag work --category Travel\n  expense-tracker-rs list --view work-travel")
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("name")
+                                .required(true)
+                                .help(i18n::t("help-view-save-name"))
+                                .value_parser(clap::value_parser!(String)),
+                        )
+                        .args(filters().into_iter().skip(1))
+                        .arg(
+                            Arg::new("month")
+                                .short('m')
+                                .long("month")
+                                .required(false)
+                                .help(i18n::t("help-view-save-month"))
+                                .value_parser(clap::value_parser!(u8)),
+                        ),
+                )
+                .subcommand(
+                    Command::new("remove")
+                        .about(i18n::t("cli-view-remove"))
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("name")
+                                .required(true)
+                                .help(i18n::t("help-view-remove-name"))
+                                .value_parser(clap::value_parser!(String)),
+                        ),
+                )
+                .subcommand(Command::new("list").about(i18n::t("cli-view-list"))),
+        )
+        .subcommand(
+            Command::new("template")
+                .about(i18n::t("cli-template"))
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("add")
+                        .about(i18n::t("cli-template-add"))
+                        .after_help("Example:\n  expense-tracker-rs template add chai --amount 20 --category Food\n  expense-tracker-rs add --template chai")
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("name")
+                                .required(true)
+                                .help(i18n::t("help-template-add-name"))
+                                .value_parser(clap::value_parser!(String)),
+                        )
+                        .arg(
+                            Arg::new("amount")
+                                .short('a')
+                                .long("amount")
+                                .required(true)
+                                .help(i18n::t("help-template-add-amount"))
+                                .value_parser(expense::parse_amount),
+                        )
+                        .arg(
+                            Arg::new("category")
+                                .short('c')
+                                .long("category")
+                                .required(true)
+                                .help(i18n::t("help-template-add-category"))
+                                .value_parser(clap::value_parser!(String)),
+                        )
+                        .arg(
+                            Arg::new("description")
+                                .short('d')
+                                .long("description")
+                                .required(false)
+                                .help(i18n::t("help-template-add-description")),
+                        )
+                        .arg(
+                            Arg::new("payee")
+                                .short('p')
+                                .long("payee")
+                                .required(false)
+                                .help(i18n::t("help-template-add-payee"))
+                                .value_parser(clap::value_parser!(String)),
+                        ),
+                )
+                .subcommand(
+                    Command::new("remove")
+                        .about(i18n::t("cli-template-remove"))
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("name")
+                                .required(true)
+                                .help(i18n::t("help-template-remove-name"))
+                                .value_parser(clap::value_parser!(String)),
+                        ),
+                )
+                .subcommand(Command::new("list").about(i18n::t("cli-template-list"))),
+        )
+        .subcommand(
+            Command::new("rates")
+                .about(i18n::t("cli-rates"))
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("set")
+                        .about(i18n::t("cli-rates-set"))
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("code")
+                                .required(true)
+                                .help(i18n::t("help-code"))
+                                .value_parser(currency::parse_code),
+                        )
+                        .arg(
+                            Arg::new("rate")
+                                .required(true)
+                                .help(i18n::t("help-rates-set-rate"))
+                                .value_parser(expense::parse_amount),
+                        )
+                        .arg(
+                            Arg::new("date")
+                                .short('d')
+                                .long("date")
+                                .required(false)
+                                .help(i18n::t("help-rates-set-date"))
+                                .value_parser(dates::parse_date),
+                        ),
+                )
+                .subcommand(Command::new("list").about(i18n::t("cli-rates-list")))
+                .subcommand(
+                    Command::new("update")
+                        .about(i18n::t("cli-rates-update"))
+                        .after_help(
+                            "Rates are fetched for the given day and for every day with a foreign-currency expense that isn't cached yet. Set `rates_url` in config.toml to use another API; it should answer with a JSON object whose `rates` map each currency to its units per one unit of the ledger's currency.",
+                        )
+                        .arg(
+                            Arg::new("date")
+                                .short('d')
+                                .long("date")
+                                .required(false)
+                                .help(i18n::t("help-rates-update-date"))
+                                .value_parser(dates::parse_date),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("backup")
+                .about(i18n::t("cli-backup"))
+                .after_help("The latest 10 backups are kept, plus the newest of each day for 30 days, in a backups directory next to the data file.")
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .subcommand(Command::new("list").about(i18n::t("cli-backup-list")))
+                .subcommand(
+                    Command::new("restore")
+                        .about(i18n::t("cli-backup-restore"))
+                        .arg(
+                            Arg::new("timestamp")
+                                .required(false)
+                                .help(i18n::t("help-timestamp"))
+                                .value_parser(clap::value_parser!(String)),
+                        )
+                        .arg(
+                            Arg::new("yes")
+                                .action(clap::ArgAction::SetTrue)
+                                .short('y')
+                                .long("yes")
+                                .help(i18n::t("help-backup-restore-yes")),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("encrypt")
+                .about(i18n::t("cli-encrypt"))
+                .after_help("The key is derived from the passphrase with Argon2 and the data is encrypted with AES-256-GCM. Encrypted files are decrypted transparently on load. The passphrase is read from $EXPENSE_TRACKER_PASSPHRASE or the system keyring, or asked for and then saved in the keyring unless --no-keyring is given. Attachments are not encrypted."),
+        )
+        .subcommand(
+            Command::new("decrypt").about(i18n::t("cli-decrypt")),
+        )
+        .subcommand(
+            Command::new("doctor")
+                .about(i18n::t("cli-doctor"))
+                .visible_alias("check")
+                .after_help("Reports parse errors, negative or invalid amounts, splits that don't add up, dates far in the future, repeated ids, unknown fields and attachments whose files are missing.")
+                .arg(
+                    Arg::new("fix")
+                        .action(clap::ArgAction::SetTrue)
+                        .long("fix")
+                        .help(i18n::t("help-fix")),
+                ),
+        )
+        .subcommand(
+            Command::new("migrate")
+                .about(i18n::t("cli-migrate"))
+                .after_help("Older files are also upgraded automatically when loaded and saved. This command does it explicitly, checks that no expenses were lost, and keeps the original as expenses.json.v<N>.bak.")
+                .arg(
+                    Arg::new("dry_run")
+                        .action(clap::ArgAction::SetTrue)
+                        .long("dry-run")
+                        .help(i18n::t("help-migrate-dry_run")),
+                ),
+        )
+        .subcommand(
+            Command::new("serve")
+                .about(i18n::t("cli-serve"))
+                .after_help("Example:\n  expense-tracker-rs serve --port 8080\n  curl -H \"Authorization: Bearer $TOKEN\" localhost:8080/api/summary?from=2025-01-01\n\nA dashboard for the browser is served at /. API endpoints, all under /api:\n  GET    /expenses            filtered by from, to, after, before, month, category,\n                              tag and meta (comma-separated), payee, trip, project, user,\n                              min_amount, max_amount, hours, only, view\n  POST   /expenses            {date, category, amount, description, payee, tags, ...}\n  GET    /expenses/{id}\n  PATCH  /expenses/{id}       only the fields given change\n  DELETE /expenses/{id}\n  GET    /summary             the same filters; total and per-category amounts\n  GET    /reports/monthly     ?month=2025-06\n  GET    /reports/trend       ?months=12&category=food\n\nEvery request needs the token as a bearer token. It is taken from --token, $EXPENSE_TRACKER_TOKEN or server_token in the config file; without one a random token is made up and printed. Other machines can use the served ledger with --remote, as in\n  expense-tracker-rs --remote http://host:8080 --token $TOKEN add -c Food -a 250 -d Lunch")
+                .arg(
+                    Arg::new("port")
+                        .long("port")
+                        .default_value("8080")
+                        .help(i18n::t("help-port"))
+                        .value_parser(clap::value_parser!(u16)),
+                )
+                .arg(
+                    Arg::new("bind")
+                        .long("bind")
+                        .default_value("127.0.0.1")
+                        .help(i18n::t("help-bind")),
+                )
+        )
+        .subcommand(
+            Command::new("bot")
+                .about(i18n::t("cli-bot"))
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("telegram")
+                        .about(i18n::t("cli-bot-telegram"))
+                        .after_help("Messages use the quick-add shorthand, such as `250 coffee #food @BlueTokai`; the bot replies with the day's new total, and /today shows it. Configure it in config.toml:\n\n  [telegram]\n  token = \"123456:ABC...\"   # from @BotFather\n  chats = [12345678]       # chats allowed to log expenses\n\nMessages from other chats are answered with their chat id so it can be added."),
+                ),
+        )
+        .subcommand(
+            Command::new("sync")
+                .about(i18n::t("cli-sync"))
+                .after_help("See `expense-tracker-rs guide syncing` for how to set it up.")
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("init")
+                        .about(i18n::t("cli-sync-init"))
+                        .arg(
+                            Arg::new("origin")
+                                .long("origin")
+                                .required(false)
+                                .help(i18n::t("help-origin")),
+                        ),
+                )
+                .subcommand(Command::new("push").about(i18n::t("cli-sync-push")))
+                .subcommand(
+                    Command::new("pull")
+                        .about(i18n::t("cli-sync-pull")),
+                )
+                .subcommand(
+                    Command::new("sheets")
+                        .about(i18n::t("cli-sync-sheets"))
+                        .after_help("Rows with an empty Id column, such as ones added on a phone, are added to the ledger; the sheet is then rewritten with every expense and its id, so nothing is added twice. Edits to rows with an id are overwritten.\n\nSet credentials under [sheets] in the config file to a Google service account's JSON key, and share the spreadsheet with the account's email address. The tab used is sheet, Expenses unless set, with the columns Id, Date, Category, Amount, Currency, Description and Payee.")
+                        .arg(
+                            Arg::new("spreadsheet_id")
+                                .long("spreadsheet-id")
+                                .required(false)
+                                .help(i18n::t("help-spreadsheet_id")),
+                        ),
+                )
+                .subcommand(
+                    Command::new("resolve")
+                        .about(i18n::t("cli-sync-resolve"))
+                        .arg(
+                            Arg::new("keep")
+                                .long("keep")
+                                .required(false)
+                                .value_parser(["local", "other", "newest"])
+                                .help(i18n::t("help-keep")),
+                        ),
+                )
+                .subcommand(
+                    Command::new("merge")
+                        .about(i18n::t("cli-sync-merge"))
+                        .hide(true)
+                        .arg(Arg::new("base").required(true).value_parser(clap::value_parser!(PathBuf)))
+                        .arg(Arg::new("ours").required(true).value_parser(clap::value_parser!(PathBuf)))
+                        .arg(Arg::new("theirs").required(true).value_parser(clap::value_parser!(PathBuf)))
+                        .arg(Arg::new("path").required(false).value_parser(clap::value_parser!(PathBuf))),
+                ),
+        )
+        .subcommand(
+            Command::new("close-month")
+                .about(i18n::t("cli-close-month"))
+                .arg_required_else_help(true)
+                .arg(
+                    Arg::new("month")
+                        .required(true)
+                        .help(i18n::t("help-close-month-month"))
+                        .value_parser(dates::parse_month),
+                )
+                .arg(
+                    Arg::new("checklist")
+                        .action(clap::ArgAction::SetTrue)
+                        .long("checklist")
+                        .help(i18n::t("help-checklist")),
+                )
+                .arg(
+                    Arg::new("force")
+                        .action(clap::ArgAction::SetTrue)
+                        .long("force")
+                        .requires("checklist")
+                        .help(i18n::t("help-force")),
+                )
+                .arg(
+                    Arg::new("reopen")
+                        .action(clap::ArgAction::SetTrue)
+                        .long("reopen")
+                        .conflicts_with_all(["checklist", "force"])
+                        .help(i18n::t("help-reopen")),
+                ),
+        )
+        .subcommand(
+            Command::new("guide")
+                .about(i18n::t("cli-guide"))
+                .arg(
+                    Arg::new("topic")
+                        .required(false)
+                        .help(i18n::t("help-topic"))
+                        .value_parser(clap::builder::PossibleValuesParser::new(
+                            guide::TOPICS.iter().map(|t| t.name),
+                        )),
+                )
+                .arg(
+                    Arg::new("no_pager")
+                        .action(clap::ArgAction::SetTrue)
+                        .long("no-pager")
+                        .help(i18n::t("help-no_pager")),
+                ),
+        )
+        .subcommand(
+            Command::new("import")
+                .about(i18n::t("cli-import"))
+                .arg_required_else_help(true)
+                .arg(
+                    Arg::new("file")
+                        .required(true)
+                        .help(i18n::t("help-file"))
+                        .value_parser(clap::value_parser!(PathBuf)),
+                )
+                .arg(
+                    Arg::new("format")
+                        .short('f')
+                        .long("format")
+                        .required(false)
+                        .help(i18n::t("help-import-format"))
+                        .value_parser(["ofx", "qif", "ynab", "gnucash", "mmex"]),
+                )
+                .arg(
+                    Arg::new("map")
+                        .long("map")
+                        .required(false)
+                        .help(i18n::t("help-map"))
+                        .value_parser(clap::value_parser!(PathBuf)),
+                )
+                .arg(
+                    Arg::new("category")
+                        .short('c')
+                        .long("category")
+                        .default_value("Uncategorized")
+                        .help(i18n::t("help-import-category"))
+                        .value_parser(clap::value_parser!(String)),
+                )
+                .arg(
+                    Arg::new("date_format")
+                        .long("date-format")
+                        .required(false)
+                        .help(i18n::t("help-date_format"))
+                        .value_parser(clap::value_parser!(String)),
+                )
+                .arg(
+                    Arg::new("include_duplicates")
+                        .action(clap::ArgAction::SetTrue)
+                        .long("include-duplicates")
+                        .help(i18n::t("help-include_duplicates")),
+                )
+                .arg(
+                    Arg::new("yes")
+                        .action(clap::ArgAction::SetTrue)
+                        .short('y')
+                        .long("yes")
+                        .help(i18n::t("help-import-yes")),
+                ),
+        )
+        .subcommand(
+            Command::new("export")
+                .about(i18n::t("cli-export"))
+                .arg_required_else_help(true)
+                .arg(
+                    Arg::new("format")
+                        .required(true)
+                        .short('f')
+                        .long("format")
+                        .help(i18n::t("help-export-format"))
+                        .value_parser(["ledger", "beancount", "xlsx", "parquet", "ynab"]),
+                )
+                .args(filters())
+                .arg(
+                    Arg::new("account")
+                        .long("account")
+                        .default_value("Assets:Cash")
+                        .help(i18n::t("help-account"))
+                        .value_parser(clap::value_parser!(String)),
+                )
+                .arg(
+                    Arg::new("out")
+                        .short('o')
+                        .long("out")
+                        .required(false)
+                        .help(i18n::t("help-export-out"))
+                        .value_parser(clap::value_parser!(PathBuf)),
+                ),
+        )
+        .subcommand(
+            Command::new("query")
+                .about(i18n::t("cli-query"))
+                .arg_required_else_help(true)
+                .after_help("The expenses are loaded into an in-memory SQLite database with these tables:\n  expenses (id, date, time, utc_offset, category, description, payee, trip, project, user, amount, currency, tags, private, deductible, business)\n  categories (expense_id, category, amount), one row per category of a split expense\n  tags (expense_id, tag)\n  items (expense_id, name, quantity, price, amount), the lines of itemized bills\n  meta (expense_id, key, value), the fields given with add --meta\nDates and times are text such as 2025-01-31 and 22:15, and amounts are in the report currency.\n\nExample:\n  expense-tracker-rs query \"SELECT category, SUM(amount) FROM expenses WHERE date >= '2025-01-01' GROUP BY 1\"")
+                .arg(
+                    Arg::new("sql")
+                        .required(true)
+                        .help(i18n::t("help-sql"))
+                        .value_parser(clap::value_parser!(String)),
+                )
+                .arg(
+                    Arg::new("format")
+                        .short('f')
+                        .long("format")
+                        .default_value("table")
+                        .help(i18n::t("help-query-format"))
+                        .value_parser(["table", "csv"]),
+                )
+                .arg(
+                    Arg::new("out")
+                        .short('o')
+                        .long("out")
+                        .required(false)
+                        .help(i18n::t("help-query-out"))
+                        .value_parser(clap::value_parser!(PathBuf)),
+                ),
+        )
+        .subcommand(
+            Command::new("chart")
+                .about(i18n::t("cli-chart"))
+                .arg_required_else_help(true)
+                .arg(
+                    Arg::new("type")
+                        .short('t')
+                        .long("type")
+                        .required(true)
+                        .help(i18n::t("help-type"))
+                        .value_parser(["pie", "bar", "line"]),
+                )
+                .arg(
+                    Arg::new("out")
+                        .short('o')
+                        .long("out")
+                        .required(true)
+                        .help(i18n::t("help-chart-out"))
+                        .value_parser(clap::value_parser!(PathBuf)),
+                )
+                .arg(
+                    Arg::new("month")
+                        .short('m')
+                        .long("month")
+                        .required(false)
+                        .help(i18n::t("help-chart-month"))
+                        .value_parser(dates::parse_month),
+                )
+                .arg(
+                    Arg::new("months")
+                        .short('n')
+                        .long("months")
+                        .default_value("12")
+                        .help(i18n::t("help-chart-months"))
+                        .value_parser(clap::value_parser!(u32).range(1..)),
+                ),
+        )
+        .subcommand(
+            Command::new("report")
+                .about(i18n::t("cli-report"))
+                .visible_alias("r")
+                .arg_required_else_help(true)
+                .args_conflicts_with_subcommands(true)
+                .after_help("With --template, the filtered expenses are rendered through a Tera template instead. It gets currency, generated, filter, from, to, count, total, expenses, categories (category, amount, share), months (month, total) and payees (payee, amount), and a money filter for amounts. Templates named *.html or *.html.tera have inserted values escaped.\n\nExample:\n  expense-tracker-rs report --template my_month.html.tera --from 2025-07-01 --to 2025-07-31 -o july.html")
+                .arg(
+                    Arg::new("template")
+                        .long("template")
+                        .required(false)
+                        .help(i18n::t("help-report-template"))
+                        .value_parser(clap::value_parser!(PathBuf)),
+                )
+                .args(filters().map(|arg| arg.requires("template")))
+                .arg(
+                    Arg::new("out")
+                        .short('o')
+                        .long("out")
+                        .required(false)
+                        .requires("template")
+                        .help(i18n::t("help-report-out"))
+                        .value_parser(clap::value_parser!(PathBuf)),
+                )
+                .subcommand(
+                    Command::new("payees")
+                        .about(i18n::t("cli-report-payees"))
+                        .arg(
+                            Arg::new("year")
+                                .short('y')
+                                .long("year")
+                                .required(false)
+                                .help(i18n::t("help-report-payees-year"))
+                                .value_parser(clap::value_parser!(i32).range(dates::YEARS)),
+                        ),
+                )
+                .subcommand(
+                    Command::new("trend")
+                        .about(i18n::t("cli-report-trend"))
+                        .arg(
+                            Arg::new("category")
+                                .short('c')
+                                .long("category")
+                                .required(false)
+                                .help(i18n::t("help-report-trend-category"))
+                                .value_parser(clap::value_parser!(String)),
+                        )
+                        .arg(
+                            Arg::new("months")
+                                .short('n')
+                                .long("months")
+                                .default_value("12")
+                                .help(i18n::t("help-report-trend-months"))
+                                .value_parser(clap::value_parser!(u32).range(1..)),
+                        )
+                        .arg(
+                            Arg::new("end")
+                                .long("end")
+                                .required(false)
+                                .help(i18n::t("help-end"))
+                                .value_parser(dates::parse_month),
+                        )
+                        .arg(
+                            Arg::new("sparkline")
+                                .action(clap::ArgAction::SetTrue)
+                                .long("sparkline")
+                                .help(i18n::t("help-sparkline")),
+                        )
+                        .arg(
+                            Arg::new("real")
+                                .action(clap::ArgAction::SetTrue)
+                                .long("real")
+                                .help(i18n::t("help-real")),
+                        ),
+                )
+                .subcommand(
+                    Command::new("anomalies")
+                        .about(i18n::t("cli-report-anomalies"))
+                        .after_help("Each expense is compared with every other expense in its category, and flagged when it lies more than --sigma standard deviations above their mean. Categories need at least 5 other expenses. The filters pick which expenses are checked.\n\nExample:\n  expense-tracker-rs report anomalies --from 2025-01-01")
+                        .arg(
+                            Arg::new("sigma")
+                                .long("sigma")
+                                .default_value("2")
+                                .help(i18n::t("help-sigma"))
+                                .value_parser(expense::parse_amount),
+                        )
+                        .args(filters()),
+                )
+                .subcommand(
+                    Command::new("streaks")
+                        .about(i18n::t("cli-report-streaks"))
+                        .after_help("Days run from the first matching expense, or --from, to today, or --to. The limit defaults to daily_limit in the config file.\n\nExample:\n  expense-tracker-rs report streaks --limit 500 --category food")
+                        .arg(
+                            Arg::new("limit")
+                                .long("limit")
+                                .required(false)
+                                .help(i18n::t("help-report-streaks-limit"))
+                                .value_parser(expense::parse_amount),
+                        )
+                        .args(filters()),
+                )
+                .subcommand(
+                    Command::new("networth")
+                        .about(i18n::t("cli-report-networth"))
+                        .after_help("Net worth counts the latest balance of each account recorded with `assets set` by the month's end.\n\nExample:\n  expense-tracker-rs report networth --months 12")
+                        .arg(
+                            Arg::new("months")
+                                .short('n')
+                                .long("months")
+                                .default_value("12")
+                                .help(i18n::t("help-report-networth-months"))
+                                .value_parser(clap::value_parser!(u32).range(1..)),
+                        )
+                        .arg(
+                            Arg::new("end")
+                                .long("end")
+                                .required(false)
+                                .help(i18n::t("help-end"))
+                                .value_parser(dates::parse_month),
+                        ),
+                )
+                .subcommand(
+                    Command::new("rolling")
+                        .about(i18n::t("cli-report-rolling"))
+                        .after_help("Example:\n  expense-tracker-rs report rolling --window 3 --last-year")
+                        .arg(
+                            Arg::new("window")
+                                .short('w')
+                                .long("window")
+                                .default_value("3")
+                                .help(i18n::t("help-window"))
+                                .value_parser(clap::value_parser!(u32).range(1..=24)),
+                        )
+                        .arg(
+                            Arg::new("months")
+                                .short('n')
+                                .long("months")
+                                .default_value("6")
+                                .help(i18n::t("help-report-rolling-months"))
+                                .value_parser(clap::value_parser!(u32).range(1..=24)),
+                        )
+                        .arg(
+                            Arg::new("end")
+                                .long("end")
+                                .required(false)
+                                .help(i18n::t("help-end"))
+                                .value_parser(dates::parse_month),
+                        )
+                        .arg(
+                            Arg::new("last_year")
+                                .action(clap::ArgAction::SetTrue)
+                                .long("last-year")
+                                .help(i18n::t("help-last_year")),
+                        ),
+                )
+                .subcommand(
+                    Command::new("breakdown")
+                        .about(i18n::t("cli-report-breakdown"))
+                        .arg(
+                            Arg::new("month")
+                                .short('m')
+                                .long("month")
+                                .required(false)
+                                .help(i18n::t("help-report-breakdown-month"))
+                                .value_parser(dates::parse_month),
+                        )
+                        .arg(
+                            Arg::new("chart")
+                                .action(clap::ArgAction::SetTrue)
+                                .long("chart")
+                                .help(i18n::t("help-chart")),
+                        )
+                        .arg(
+                            Arg::new("by_bucket")
+                                .action(clap::ArgAction::SetTrue)
+                                .long("by-bucket")
+                                .help(i18n::t("help-by_bucket")),
+                        ),
+                )
+                .subcommand(
+                    Command::new("trip")
+                        .about(i18n::t("cli-report-trip"))
+                        .after_help("Example:\n  expense-tracker-rs add -c Food -a 450 -d \"Beach shack\" --trip goa-2025\n  expense-tracker-rs report trip goa-2025")
+                        .arg(
+                            Arg::new("name")
+                                .required(false)
+                                .help(i18n::t("help-report-trip-name"))
+                                .value_parser(clap::value_parser!(String)),
+                        ),
+                )
+                .subcommand(
+                    Command::new("project")
+                        .about(i18n::t("cli-report-project"))
+                        .after_help("Clients and their projects are listed under [clients] in the config file:\n  [clients]\n  \"Acme Corp\" = [\"acme-website\", \"acme-app\"]\n\nExample:\n  expense-tracker-rs add -c Software -a 1200 -d \"Figma seat\" --project acme-website\n  expense-tracker-rs report project --client \"Acme Corp\" --from 2025-01-01 --to 2025-03-31 -f csv -o acme-q1.csv")
+                        .arg(
+                            Arg::new("name")
+                                .required(false)
+                                .conflicts_with("client")
+                                .help(i18n::t("help-report-project-name"))
+                                .value_parser(clap::value_parser!(String)),
+                        )
+                        .arg(
+                            Arg::new("client")
+                                .long("client")
+                                .required(false)
+                                .help(i18n::t("help-client"))
+                                .value_parser(clap::value_parser!(String)),
+                        )
+                        .arg(
+                            Arg::new("from")
+                                .long("from")
+                                .required(false)
+                                .help(i18n::t("help-report-project-from"))
+                                .value_parser(dates::parse_date),
+                        )
+                        .arg(
+                            Arg::new("to")
+                                .long("to")
+                                .required(false)
+                                .help(i18n::t("help-report-project-to"))
+                                .value_parser(dates::parse_date),
+                        )
+                        .arg(
+                            Arg::new("format")
+                                .short('f')
+                                .long("format")
+                                .default_value("table")
+                                .help(i18n::t("help-report-project-format"))
+                                .value_parser(["table", "csv"]),
+                        )
+                        .arg(
+                            Arg::new("out")
+                                .short('o')
+                                .long("out")
+                                .required(false)
+                                .help(i18n::t("help-report-project-out"))
+                                .value_parser(clap::value_parser!(PathBuf)),
+                        ),
+                )
+                .subcommand(
+                    Command::new("hours")
+                        .about(i18n::t("cli-report-hours"))
+                        .after_help("Example, late-night food orders this year:\n  expense-tracker-rs report hours --category Food --from 2025-01-01\n  expense-tracker-rs list --category Food --hours 22-4")
+                        .args(filters()),
+                )
+                .subcommand(
+                    Command::new("household")
+                        .about(i18n::t("cli-report-household"))
+                        .after_help("Expenses are spent by the member given to add --user, or user in the config file, or the login name of whoever added them.\n\nExample:\n  expense-tracker-rs add -c Groceries -a 900 --description \"Weekly shop\" --user priya\n  expense-tracker-rs report household --from 2025-04-01\n  expense-tracker-rs summary --user priya")
+                        .args(filters()),
+                )
+                .subcommand(
+                    Command::new("compare")
+                        .about(i18n::t("cli-report-compare"))
+                        .after_help("Example:\n  expense-tracker-rs report compare --a 2025-01 --b 2025-02\n  expense-tracker-rs report compare --a 2024 --b 2025")
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("a")
+                                .long("a")
+                                .required(true)
+                                .help(i18n::t("help-a"))
+                                .value_parser(report::compare::parse_period),
+                        )
+                        .arg(
+                            Arg::new("b")
+                                .long("b")
+                                .required(true)
+                                .help(i18n::t("help-b"))
+                                .value_parser(report::compare::parse_period),
+                        )
+                        .arg(
+                            Arg::new("no_color")
+                                .action(clap::ArgAction::SetTrue)
+                                .long("no-color")
+                                .help(i18n::t("help-report-compare-no_color")),
+                        ),
+                )
+                .subcommand(
+                    Command::new("calendar")
+                        .about(i18n::t("cli-report-calendar"))
+                        .arg(
+                            Arg::new("month")
+                                .short('m')
+                                .long("month")
+                                .required(false)
+                                .help(i18n::t("help-report-calendar-month"))
+                                .value_parser(dates::parse_month),
+                        )
+                        .arg(
+                            Arg::new("no_color")
+                                .action(clap::ArgAction::SetTrue)
+                                .long("no-color")
+                                .help(i18n::t("help-report-calendar-no_color")),
+                        ),
+                )
+                .subcommand(
+                    Command::new("top")
+                        .about(i18n::t("cli-report-top"))
+                        .arg(
+                            Arg::new("n")
+                                .short('n')
+                                .long("n")
+                                .default_value("10")
+                                .help(i18n::t("help-n"))
+                                .value_parser(clap::value_parser!(usize)),
+                        )
+                        .arg(
+                            Arg::new("month")
+                                .short('m')
+                                .long("month")
+                                .required(false)
+                                .help(i18n::t("help-report-top-month"))
+                                .value_parser(dates::parse_month),
+                        ),
+                )
+                .subcommand(
+                    Command::new("monthly")
+                        .about(i18n::t("cli-report-monthly"))
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("month")
+                                .short('m')
+                                .long("month")
+                                .required(true)
+                                .help(i18n::t("help-report-monthly-month"))
+                                .value_parser(dates::parse_month),
+                        )
+                        .arg(
+                            Arg::new("format")
+                                .short('f')
+                                .long("format")
+                                .default_value("md")
+                                .help(i18n::t("help-report-monthly-format"))
+                                .value_parser(["md", "html", "pdf"]),
+                        )
+                        .arg(
+                            Arg::new("out")
+                                .short('o')
+                                .long("out")
+                                .required(false)
+                                .help(i18n::t("help-report-monthly-out"))
+                                .value_parser(clap::value_parser!(PathBuf)),
+                        ),
+                )
+                .subcommand(
+                    Command::new("yearly")
+                        .about(i18n::t("cli-report-yearly"))
+                        .after_help("Example:\n  expense-tracker-rs report yearly --year 2025 -f csv -o 2025.csv")
+                        .arg(
+                            Arg::new("year")
+                                .long("year")
+                                .required(false)
+                                .help(i18n::t("help-report-yearly-year"))
+                                .value_parser(clap::value_parser!(i32).range(dates::YEARS)),
+                        )
+                        .arg(
+                            Arg::new("format")
+                                .short('f')
+                                .long("format")
+                                .default_value("table")
+                                .help(i18n::t("help-report-yearly-format"))
+                                .value_parser(["table", "csv"]),
+                        )
+                        .arg(
+                            Arg::new("out")
+                                .short('o')
+                                .long("out")
+                                .required(false)
+                                .help(i18n::t("help-report-yearly-out"))
+                                .value_parser(clap::value_parser!(PathBuf)),
+                        ),
+                )
+                .subcommand(
+                    Command::new("tax")
+                        .about(i18n::t("cli-report-tax"))
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("fy")
+                                .long("fy")
+                                .required(true)
+                                .help(i18n::t("help-report-tax-fy"))
+                                .value_parser(dates::parse_fiscal_year),
+                        )
+                        .arg(
+                            Arg::new("fy_start")
+                                .long("fy-start")
+                                .required(false)
+                                .help(i18n::t("help-fy_start"))
+                                .value_parser(clap::value_parser!(u32).range(1..=12)),
+                        )
+                        .arg(
+                            Arg::new("format")
+                                .short('f')
+                                .long("format")
+                                .default_value("table")
+                                .help(i18n::t("help-report-tax-format"))
+                                .value_parser(["table", "csv"]),
+                        )
+                        .arg(
+                            Arg::new("out")
+                                .short('o')
+                                .long("out")
+                                .required(false)
+                                .help(i18n::t("help-report-tax-out"))
+                                .value_parser(clap::value_parser!(PathBuf)),
+                        ),
+                )
+                .subcommand(
+                    Command::new("email")
+                        .about(i18n::t("cli-report-email"))
+                        .after_help("The mail server and recipients are set under [email] in the config file: host, port, security (starttls, tls or none), username, password (or $EXPENSE_TRACKER_SMTP_PASSWORD), from and to.\n\nTo send last month's report on the 1st of every month, add a crontab line such as:\n  0 8 1 * * cd ~/ledger && expense-tracker-rs report email")
+                        .arg(
+                            Arg::new("month")
+                                .short('m')
+                                .long("month")
+                                .required(false)
+                                .help(i18n::t("help-report-email-month"))
+                                .value_parser(dates::parse_month),
+                        )
+                        .arg(
+                            Arg::new("to")
+                                .long("to")
+                                .required(false)
+                                .action(clap::ArgAction::Append)
+                                .value_delimiter(',')
+                                .help(i18n::t("help-report-email-to")),
+                        ),
+                )
+                .subcommand(
+                    Command::new("custom")
+                        .about(i18n::t("cli-report-custom"))
+                        .arg_required_else_help(true)
+                        .after_help("The script gets `expenses`, an array of maps with the fields of the data file, plus `budgets` and `currency`, and can format amounts with money(). What it prints, and the value it ends with, is the report. With --mask-private, private expenses are left out.\n\nExample script:\n  let totals = #{};\n  for e in expenses { totals[e.category] = (totals[e.category] ?? 0.0) + e.amount; }\n  for c in totals.keys() { print(`${c}: ${money(totals[c])}`); }")
+                        .arg(
+                            Arg::new("script")
+                                .required(true)
+                                .help(i18n::t("help-script"))
+                                .value_parser(clap::value_parser!(PathBuf)),
+                        )
+                        .arg(
+                            Arg::new("from")
+                                .long("from")
+                                .required(false)
+                                .help(i18n::t("help-report-custom-from"))
+                                .value_parser(dates::parse_date),
+                        )
+                        .arg(
+                            Arg::new("to")
+                                .long("to")
+                                .required(false)
+                                .help(i18n::t("help-report-custom-to"))
+                                .value_parser(dates::parse_date),
+                        ),
+                ),
+        )
+}
+
+// Ids of the flags `filters` defines.
+const FILTER_IDS: [&str; 15] = [
+    "view",
+    "from",
+    "to",
+    "after",
+    "before",
+    "category",
+    "tag",
+    "payee",
+    "trip",
+    "project",
+    "user",
+    "meta",
+    "min_amount",
+    "max_amount",
+    "hours",
+];
+
+// The flags read by `Filter::from_matches`, shared by commands that select
+// expenses.
+fn filters() -> [Arg; 15] {
+    [
+        Arg::new("view")
+            .long("view")
+            .required(false)
+            .help(i18n::t("help-view"))
+            .value_parser(clap::value_parser!(String)),
+        Arg::new("from")
+            .long("from")
+            .required(false)
+            .help(i18n::t("help-filter-from"))
+            .value_parser(dates::parse_date),
+        Arg::new("to")
+            .long("to")
+            .required(false)
+            .help(i18n::t("help-filter-to"))
+            .value_parser(dates::parse_date),
+        Arg::new("after")
+            .long("after")
+            .required(false)
+            .conflicts_with("from")
+            .help(i18n::t("help-after"))
+            .value_parser(dates::parse_date),
+        Arg::new("before")
+            .long("before")
+            .required(false)
+            .conflicts_with("to")
+            .help(i18n::t("help-filter-before"))
+            .value_parser(dates::parse_date),
+        Arg::new("category")
+            .short('c')
+            .long("category")
+            .required(false)
+            .help(i18n::t("help-filter-category"))
+            .value_parser(clap::value_parser!(String)),
+        Arg::new("tag")
+            .long("tag")
+            .required(false)
+            .action(clap::ArgAction::Append)
+            .help(i18n::t("help-filter-tag"))
+            .value_parser(clap::value_parser!(String)),
+        Arg::new("payee")
+            .short('p')
+            .long("payee")
+            .required(false)
+            .help(i18n::t("help-filter-payee"))
+            .value_parser(clap::value_parser!(String)),
+        Arg::new("trip")
+            .long("trip")
+            .required(false)
+            .help(i18n::t("help-filter-trip"))
+            .value_parser(clap::value_parser!(String)),
+        Arg::new("project")
+            .long("project")
+            .required(false)
+            .help(i18n::t("help-filter-project"))
+            .value_parser(clap::value_parser!(String)),
+        Arg::new("user")
+            .long("user")
+            .required(false)
+            .help(i18n::t("help-filter-user"))
+            .value_parser(clap::value_parser!(String)),
+        Arg::new("meta")
+            .long("meta")
+            .required(false)
+            .action(clap::ArgAction::Append)
+            .help(i18n::t("help-filter-meta"))
+            .value_parser(clap::value_parser!(String)),
+        Arg::new("min_amount")
+            .long("min-amount")
+            .required(false)
+            .help(i18n::t("help-min_amount"))
+            .value_parser(expense::parse_non_negative),
+        Arg::new("max_amount")
+            .long("max-amount")
+            .required(false)
+            .help(i18n::t("help-max_amount"))
+            .value_parser(expense::parse_non_negative),
+        Arg::new("hours")
+            .long("hours")
+            .required(false)
+            .help(i18n::t("help-hours"))
+            .value_parser(filter::parse_hours),
+    ]
+}
@@ -0,0 +1,95 @@
+use crate::budget;
+use crate::currency;
+use crate::dates::month_key;
+use crate::expense::ExpenseTracker;
+use crate::reimburse;
+use chrono::NaiveDate;
+
+// A single line of the period-close checklist.
+pub struct Check {
+    pub name: &'static str,
+    pub passed: bool,
+    pub detail: String,
+}
+
+// Run every period-close check for the month starting at `month`.
+pub fn checklist(tracker: &ExpenseTracker, month: NaiveDate) -> Vec<Check> {
+    let key = month_key(month);
+    let uncategorized: Vec<usize> = tracker
+        .expenses
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| month_key(e.date) == key)
+        .filter(|(_, e)| {
+            e.category_amounts().iter().any(|(category, _)| {
+                category.trim().is_empty() || category.eq_ignore_ascii_case("uncategorized")
+            })
+        })
+        .map(|(i, _)| i + 1)
+        .collect();
+
+    let pending: Vec<usize> = tracker
+        .expenses
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| month_key(e.date) == key)
+        .filter(|(_, e)| e.reimbursement == Some(reimburse::Status::Pending))
+        .map(|(i, _)| i + 1)
+        .collect();
+
+    let overspent: Vec<String> = budget::status(tracker, month)
+        .iter()
+        .filter(|s| s.remaining() < 0.0)
+        .map(|s| {
+            format!(
+                "{} over by {}",
+                s.category,
+                currency::amount(-s.remaining())
+            )
+        })
+        .collect();
+
+    vec![
+        Check {
+            name: "No uncategorized items",
+            passed: uncategorized.is_empty(),
+            detail: if uncategorized.is_empty() {
+                String::new()
+            } else {
+                format!("rows {}", join_rows(&uncategorized))
+            },
+        },
+        Check {
+            name: "No pending reimbursements",
+            passed: pending.is_empty(),
+            detail: if pending.is_empty() {
+                String::new()
+            } else {
+                format!("rows {}", join_rows(&pending))
+            },
+        },
+        Check {
+            name: "Budgets reviewed",
+            passed: overspent.is_empty(),
+            detail: overspent.join(", "),
+        },
+    ]
+}
+
+pub fn print_checklist(checks: &[Check]) {
+    for check in checks {
+        let mark = if check.passed { "PASS" } else { "FAIL" };
+        if check.detail.is_empty() {
+            println!("[{mark}] {}", check.name);
+        } else {
+            println!("[{mark}] {} ({})", check.name, check.detail);
+        }
+    }
+}
+
+fn join_rows(rows: &[usize]) -> String {
+    rows.iter()
+        .map(|r| r.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,323 @@
+use serde::Deserialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    env, fs, io,
+    path::PathBuf,
+};
+
+use crate::currency::Grouping;
+use crate::dates::Zone;
+use crate::webhook::Event;
+
+// User settings read from `config.toml`
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    // Directory the ledger is kept in, instead of the current one
+    pub data_dir: Option<PathBuf>,
+    // Currency code for ledgers that don't record one yet
+    pub currency: Option<String>,
+    // Shown instead of the currency's usual symbol
+    pub symbol: Option<String>,
+    // Digit grouping, instead of the currency's usual one
+    pub grouping: Option<Grouping>,
+    // Exchange-rate API for `rates update`, with `{date}` and `{base}`
+    // filled in
+    pub rates_url: Option<String>,
+    // Let `add` take a missing category and payee from the latest expense
+    // with the same description
+    pub sticky: bool,
+    // Fallback fields for `add`, keyed by category
+    pub category_defaults: HashMap<String, CategoryDefaults>,
+    // How the ledger is stored
+    pub storage: StorageKind,
+    // Bearer token `serve` requires and `--remote` sends, unless given
+    // `--token`
+    pub server_token: Option<String>,
+    // Settings for `bot telegram`
+    pub telegram: Telegram,
+    // Where the ledger is kept with `storage = "webdav"`
+    pub webdav: WebDavConfig,
+    // URLs told about new expenses and overspent budgets
+    pub webhooks: Vec<Webhook>,
+    // When `add` warns that a budget is running out
+    pub budget_alerts: BudgetAlerts,
+    // Scripts run around every change to expenses
+    pub hooks: Hooks,
+    // The mail server `report email` sends through
+    pub email: Email,
+    // The Google Sheet `sync sheets` keeps in step with the ledger
+    pub sheets: SheetsConfig,
+    // Timezone new expenses are dated in, such as Asia/Tokyo, instead of
+    // the system's
+    pub timezone: Option<Zone>,
+    // The projects billed to each client, for `report project --client`
+    pub clients: HashMap<String, Vec<String>>,
+    // The spend a day `report streaks` counts as a good day, unless given
+    // `--limit`
+    pub daily_limit: Option<f64>,
+    // Month the fiscal year starts in for `summary --fy` and `report tax`;
+    // April when unset.
+    pub fiscal_year_start: Option<u32>,
+    // Day of the month budgeting periods start on, such as payday, for
+    // monthly reports, budgets and forecasts; the 1st when unset
+    pub period_start: Option<u32>,
+    // Consumer price index by `YYYY-MM` or `YYYY`, each in force until the
+    // next, for `report trend --real`
+    pub cpi: BTreeMap<String, f64>,
+    // Language and region, such as hi-IN: messages are shown in the
+    // language instead of the one the environment asks for, and dates and
+    // numbers are written the region's way
+    pub locale: Option<String>,
+    // File every run logs to, at info or more, unless given `--log-file`
+    pub log_file: Option<PathBuf>,
+    // Refuse every change to the ledger, as --read-only does
+    pub read_only: bool,
+    // The household member new expenses are spent by, unless given `add
+    // --user`; the login name when unset
+    pub user: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Default, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageKind {
+    // The whole ledger rewritten on every change
+    #[default]
+    Json,
+    // New expenses appended to a journal next to the JSON file
+    Journal,
+    // One file on a WebDAV server, cached in the JSON file
+    WebDav,
+}
+
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct CategoryDefaults {
+    pub amount: Option<f64>,
+    pub payee: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct Telegram {
+    // The token BotFather gave the bot
+    pub token: Option<String>,
+    // Ids of the chats allowed to log expenses
+    pub chats: Vec<i64>,
+}
+
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebDavConfig {
+    // The ledger file's URL, such as
+    // https://cloud.example.com/remote.php/dav/files/me/expenses.json
+    pub url: Option<String>,
+    pub username: Option<String>,
+    // Read from $EXPENSE_TRACKER_WEBDAV_PASSWORD when not set here
+    pub password: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct Webhook {
+    pub url: String,
+    // The events to send; all of them when empty
+    pub events: Vec<Event>,
+    // Signs each payload, so the receiver can check where it came from
+    pub secret: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct SheetsConfig {
+    // Used unless `--spreadsheet-id` is given
+    pub spreadsheet_id: Option<String>,
+    // The tab the expenses are kept on
+    pub sheet: String,
+    // A service account's JSON key file; the spreadsheet has to be shared
+    // with the account's email address
+    pub credentials: Option<String>,
+}
+
+impl Default for SheetsConfig {
+    fn default() -> Self {
+        SheetsConfig {
+            spreadsheet_id: None,
+            sheet: String::from("Expenses"),
+            credentials: None,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct Email {
+    pub host: Option<String>,
+    // Defaults to the usual port for `security`
+    pub port: Option<u16>,
+    pub security: Security,
+    pub username: Option<String>,
+    // Read from $EXPENSE_TRACKER_SMTP_PASSWORD when not set here
+    pub password: Option<String>,
+    pub from: Option<String>,
+    // Who gets the report unless `--to` is given
+    pub to: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Default, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Security {
+    // Upgrade the connection with STARTTLS, usually on port 587
+    #[default]
+    StartTls,
+    // TLS from the start, usually on port 465
+    Tls,
+    // Plain text, for a relay on the local machine
+    None,
+}
+
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct Hooks {
+    // Run before each change is saved; failing stops the command
+    pub pre: Option<PathBuf>,
+    // Run after each change is saved
+    pub post: Option<PathBuf>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct BudgetAlerts {
+    // Percentages of a budget that trigger a warning as spending passes them
+    pub thresholds: Vec<f64>,
+    // Thresholds for particular categories, instead of the ones above
+    pub categories: HashMap<String, Vec<f64>>,
+    // Show a desktop notification as well as the warning
+    pub desktop: bool,
+}
+
+impl Default for BudgetAlerts {
+    fn default() -> Self {
+        BudgetAlerts {
+            thresholds: vec![80.0, 100.0],
+            categories: HashMap::new(),
+            desktop: true,
+        }
+    }
+}
+
+impl BudgetAlerts {
+    pub fn thresholds(&self, category: &str) -> Vec<f64> {
+        self.categories
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(category))
+            .map_or(&self.thresholds, |(_, thresholds)| thresholds)
+            .clone()
+    }
+}
+
+impl Config {
+    pub fn category_defaults(&self, category: &str) -> Option<&CategoryDefaults> {
+        self.category_defaults
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(category))
+            .map(|(_, defaults)| defaults)
+    }
+
+    // The projects listed under `client`, with the client's name as written
+    // in the config file.
+    pub fn client(&self, client: &str) -> Option<(&str, &[String])> {
+        self.clients
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(client))
+            .map(|(name, projects)| (name.as_str(), projects.as_slice()))
+    }
+
+    // The client `project` is listed under, if any.
+    pub fn client_of(&self, project: &str) -> Option<&str> {
+        self.clients
+            .iter()
+            .find(|(_, projects)| projects.iter().any(|p| p.eq_ignore_ascii_case(project)))
+            .map(|(name, _)| name.as_str())
+    }
+
+    // Who new expenses are recorded against: `user`, or the login name.
+    pub fn user(&self) -> Option<String> {
+        self.user
+            .clone()
+            .or_else(|| env::var("USER").ok())
+            .or_else(|| env::var("USERNAME").ok())
+            .filter(|user| !user.is_empty())
+    }
+
+    // The month fiscal years start in: `fiscal_year_start`, or April.
+    pub fn fiscal_year_start(&self) -> u32 {
+        self.fiscal_year_start.unwrap_or(4)
+    }
+}
+
+// `$EXPENSE_TRACKER_CONFIG`, or `expense-tracker/config.toml` in the user's
+// config directory.
+pub fn path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("EXPENSE_TRACKER_CONFIG") {
+        return Some(PathBuf::from(path));
+    }
+    let dir = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+    Some(dir.join("expense-tracker").join("config.toml"))
+}
+
+// `expense-tracker` in the user's data directory.
+pub fn data_dir() -> Option<PathBuf> {
+    let dir = match env::var_os("XDG_DATA_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?)
+            .join(".local")
+            .join("share"),
+    };
+    Some(dir.join("expense-tracker"))
+}
+
+// `expense-tracker` in the user's cache directory.
+pub fn cache_dir() -> Option<PathBuf> {
+    let dir = match env::var_os("XDG_CACHE_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".cache"),
+    };
+    Some(dir.join("expense-tracker"))
+}
+
+// Load the config file; a missing file gives the defaults.
+pub fn load() -> io::Result<Config> {
+    let Some(path) = path() else {
+        return Ok(Config::default());
+    };
+    match fs::read_to_string(&path) {
+        Ok(text) => toml::from_str(&text)
+            .map_err(|err| err.to_string())
+            .and_then(|config: Config| match config.fiscal_year_start {
+                Some(month) if !(1..=12).contains(&month) => Err(format!(
+                    "fiscal_year_start should be a month number (1-12), not {month}"
+                )),
+                _ if config
+                    .period_start
+                    .is_some_and(|day| !(1..=31).contains(&day)) =>
+                {
+                    Err(String::from(
+                        "period_start should be a day of the month (1-31)",
+                    ))
+                }
+                _ => Ok(config),
+            })
+            .map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {}", path.display(), err),
+                )
+            }),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+        Err(err) => Err(err),
+    }
+}
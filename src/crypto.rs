@@ -0,0 +1,388 @@
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use argon2::Argon2;
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+};
+
+use crate::archive;
+use crate::backup;
+use crate::history;
+use crate::snapshot;
+
+// Encrypted data files start with this, followed by the salt, the nonce
+// and the AES-256-GCM ciphertext of the JSON.
+const MAGIC: &[u8] = b"ETRENC1\n";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+// Read instead of prompting, for scripts
+pub const PASSPHRASE_VAR: &str = "EXPENSE_TRACKER_PASSPHRASE";
+
+// The passphrase and derived key in use once an encrypted file was read or
+// `encrypt` was run. Writes are encrypted while it is set.
+struct Session {
+    passphrase: String,
+    salt: [u8; SALT_LEN],
+    key: [u8; 32],
+}
+
+static SESSION: Mutex<Option<Session>> = Mutex::new(None);
+
+// The keyring account the passphrase is kept under, when the keyring is used
+static KEYRING_ACCOUNT: OnceLock<String> = OnceLock::new();
+const KEYRING_SERVICE: &str = "expense-tracker-rs";
+
+fn error(message: impl Into<String>) -> io::Error {
+    io::Error::other(message.into())
+}
+
+pub fn is_encrypted(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+// Whether the file at `path` is encrypted, from its first bytes only.
+pub fn is_encrypted_file(path: &Path) -> bool {
+    let mut start = [0u8; MAGIC.len()];
+    fs::File::open(path)
+        .and_then(|mut file| file.read_exact(&mut start))
+        .is_ok_and(|()| is_encrypted(&start))
+}
+
+fn derive(passphrase: &str, salt: &[u8]) -> io::Result<[u8; 32]> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|err| error(err.to_string()))?;
+    Ok(key)
+}
+
+// The passphrase from the environment, or asked for without echo. A new
+// passphrase is asked for twice.
+pub fn ask_passphrase(new: bool) -> io::Result<String> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_VAR) {
+        return Ok(passphrase);
+    }
+    let passphrase = rpassword::prompt_password(if new {
+        "New passphrase: "
+    } else {
+        "Passphrase: "
+    })?;
+    if passphrase.is_empty() {
+        return Err(error("the passphrase can't be empty"));
+    }
+    if new && rpassword::prompt_password("Repeat the passphrase: ")? != passphrase {
+        return Err(error("the passphrases don't match"));
+    }
+    Ok(passphrase)
+}
+
+// Keep the passphrase of `data_file` in the system keyring, so it is only
+// asked for once.
+pub fn use_keyring(data_file: &str) {
+    let path = Path::new(data_file);
+    let account = path
+        .canonicalize()
+        .or_else(|_| std::env::current_dir().map(|dir| dir.join(path)))
+        .unwrap_or_else(|_| path.to_path_buf());
+    let _ = KEYRING_ACCOUNT.set(account.to_string_lossy().into_owned());
+}
+
+fn keyring_entry() -> Option<keyring::Entry> {
+    let account = KEYRING_ACCOUNT.get()?;
+    keyring::Entry::new(KEYRING_SERVICE, account).ok()
+}
+
+// The passphrase saved in the keyring. A passphrase in the environment
+// takes precedence.
+fn stored_passphrase() -> Option<String> {
+    if std::env::var(PASSPHRASE_VAR).is_ok() {
+        return None;
+    }
+    keyring_entry()?.get_password().ok()
+}
+
+fn store_passphrase(passphrase: &str) {
+    if std::env::var(PASSPHRASE_VAR).is_ok() {
+        return;
+    }
+    if let Some(entry) = keyring_entry()
+        && let Err(err) = entry.set_password(passphrase)
+    {
+        eprintln!("Couldn't save the passphrase to the keyring: {err}");
+    }
+}
+
+fn forget_passphrase() {
+    if let Some(entry) = keyring_entry() {
+        let _ = entry.delete_credential();
+    }
+}
+
+// Whether writes are currently encrypted.
+pub fn is_active() -> bool {
+    SESSION.lock().expect("session lock").is_some()
+}
+
+// Encrypt writes from now on with a key derived from `passphrase`.
+pub fn enable(passphrase: String) -> io::Result<()> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let key = derive(&passphrase, &salt)?;
+    *SESSION.lock().expect("session lock") = Some(Session {
+        passphrase,
+        salt,
+        key,
+    });
+    Ok(())
+}
+
+// Write plain files from now on.
+pub fn disable() {
+    *SESSION.lock().expect("session lock") = None;
+}
+
+// Read a data file, decrypting it when it is encrypted. The passphrase is
+// asked for once and kept for later reads and writes.
+pub fn read(path: &Path) -> io::Result<Vec<u8>> {
+    let bytes = fs::read(path)?;
+    if !is_encrypted(&bytes) {
+        return Ok(bytes);
+    }
+    let (salt, _, _) = parts(&bytes)?;
+    let open = |key: &[u8; 32]| unseal(&bytes, key);
+
+    let mut session = SESSION.lock().expect("session lock");
+    if let Some(session) = session.as_ref() {
+        // Backups may carry a different salt than the data file.
+        let key = if session.salt == salt {
+            session.key
+        } else {
+            derive(&session.passphrase, salt)?
+        };
+        return open(&key);
+    }
+    // A passphrase from the keyring that no longer works is asked for again.
+    let from_keyring = stored_passphrase().and_then(|passphrase| {
+        let key = derive(&passphrase, salt).ok()?;
+        let plain = open(&key).ok()?;
+        Some((passphrase, key, plain))
+    });
+    let (passphrase, key, plain) = match from_keyring {
+        Some(opened) => opened,
+        None => {
+            let passphrase = ask_passphrase(false)?;
+            let key = derive(&passphrase, salt)?;
+            let plain = open(&key)?;
+            store_passphrase(&passphrase);
+            (passphrase, key, plain)
+        }
+    };
+    *session = Some(Session {
+        passphrase,
+        salt: salt.try_into().expect("salt length"),
+        key,
+    });
+    Ok(plain)
+}
+
+// Replace `path` with `bytes` through a temporary file, so a crash
+// leaves either the old or the new contents.
+fn replace(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".tmp");
+    let temporary = path.with_file_name(name);
+    fs::write(&temporary, bytes)?;
+    fs::rename(&temporary, path)
+}
+
+// Write a data file, encrypted when a session is active.
+pub fn write(path: &Path, plain: &[u8]) -> io::Result<()> {
+    let session = SESSION.lock().expect("session lock");
+    let Some(session) = session.as_ref() else {
+        return replace(path, plain);
+    };
+    replace(path, &seal(&session.salt, &session.key, plain)?)
+}
+
+// The salt, nonce and ciphertext of an encrypted file.
+fn parts(bytes: &[u8]) -> io::Result<(&[u8], &[u8], &[u8])> {
+    let rest = &bytes[MAGIC.len()..];
+    if rest.len() < SALT_LEN + NONCE_LEN {
+        return Err(error("the encrypted data file is truncated"));
+    }
+    let (salt, rest) = rest.split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    Ok((salt, nonce, ciphertext))
+}
+
+// The plain text of an encrypted file, given the key for its salt.
+fn unseal(bytes: &[u8], key: &[u8; 32]) -> io::Result<Vec<u8>> {
+    let (_, nonce, ciphertext) = parts(bytes)?;
+    Aes256Gcm::new(key.into())
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| error("wrong passphrase, or the data file is damaged"))
+}
+
+// `plain` encrypted with `key`, in the layout described at `MAGIC`.
+fn seal(salt: &[u8; SALT_LEN], key: &[u8; 32], plain: &[u8]) -> io::Result<Vec<u8>> {
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+    let ciphertext = Aes256Gcm::new(key.into())
+        .encrypt(Nonce::from_slice(&nonce), plain)
+        .map_err(|_| error("encryption failed"))?;
+    let mut bytes = Vec::with_capacity(MAGIC.len() + SALT_LEN + NONCE_LEN + ciphertext.len());
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(salt);
+    bytes.extend_from_slice(&nonce);
+    bytes.extend_from_slice(&ciphertext);
+    Ok(bytes)
+}
+
+// The files kept alongside the data file that hold expenses: its history,
+// yearly archives and snapshots.
+fn companions(data_file: &str) -> io::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    let history = history::path(data_file);
+    if history.exists() {
+        paths.push(history);
+    }
+    paths.extend(archive::list(data_file)?);
+    paths.extend(snapshot::list(data_file)?);
+    Ok(paths)
+}
+
+// What `encrypt_ledger` converted besides the data file.
+pub struct Converted {
+    pub history: bool,
+    pub archives: usize,
+    pub snapshots: usize,
+    pub backups: usize,
+}
+
+// Encrypt the data file, its history, archives, snapshots and backups with
+// a new passphrase. A journal has to be folded in first.
+pub fn encrypt_ledger(data_file: &str) -> io::Result<Converted> {
+    let path = Path::new(data_file);
+    if !path.exists() {
+        return Err(error("there is no data file to encrypt yet"));
+    }
+    if is_encrypted(&fs::read(path)?) {
+        return Err(error("the data file is already encrypted"));
+    }
+    let passphrase = ask_passphrase(true)?;
+    store_passphrase(&passphrase);
+    enable(passphrase)?;
+    // Rewritten in place rather than through `save`, which would first
+    // back up the plain file.
+    let plain = fs::read(path)?;
+    write(path, &plain)?;
+    let mut converted = Converted {
+        history: false,
+        archives: 0,
+        snapshots: 0,
+        backups: 0,
+    };
+    let history = history::path(data_file);
+    let archives = archive::list(data_file)?;
+    for companion in companions(data_file)? {
+        let plain = fs::read(&companion)?;
+        if is_encrypted(&plain) {
+            continue;
+        }
+        write(&companion, &plain)?;
+        if companion == history {
+            converted.history = true;
+        } else if archives.contains(&companion) {
+            converted.archives += 1;
+        } else {
+            converted.snapshots += 1;
+        }
+    }
+    for (_, backup) in backup::list(data_file)? {
+        let bytes = fs::read(&backup)?;
+        if !is_encrypted(&bytes) {
+            write(&backup, &bytes)?;
+            converted.backups += 1;
+        }
+    }
+    Ok(converted)
+}
+
+// Store the data file, its history, archives and snapshots as plain text
+// again. Backups stay encrypted.
+pub fn decrypt_ledger(data_file: &str) -> io::Result<()> {
+    let path = Path::new(data_file);
+    if !path.exists() || !is_encrypted(&fs::read(path)?) {
+        return Err(error("the data file isn't encrypted"));
+    }
+    // Everything is read before anything is written, so a wrong passphrase
+    // for one file leaves them all as they were.
+    let mut plain = vec![(path.to_path_buf(), read(path)?)];
+    for companion in companions(data_file)? {
+        let bytes = read(&companion)?;
+        plain.push((companion, bytes));
+    }
+    disable();
+    for (path, bytes) in plain {
+        write(&path, &bytes)?;
+    }
+    forget_passphrase();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    const SALT: [u8; SALT_LEN] = [7; SALT_LEN];
+
+    #[test]
+    fn sealed_bytes_open_with_the_same_passphrase() {
+        let key = derive("correct horse", &SALT).unwrap();
+        let sealed = seal(&SALT, &key, b"{\"expenses\":[]}").unwrap();
+        assert!(is_encrypted(&sealed));
+        assert_eq!(parts(&sealed).unwrap().0, SALT);
+        assert_eq!(unseal(&sealed, &key).unwrap(), b"{\"expenses\":[]}");
+    }
+
+    #[test]
+    fn sealed_bytes_refuse_another_passphrase() {
+        let key = derive("correct horse", &SALT).unwrap();
+        let other = derive("battery staple", &SALT).unwrap();
+        let sealed = seal(&SALT, &key, b"secret").unwrap();
+        assert!(unseal(&sealed, &other).is_err());
+    }
+
+    #[test]
+    fn damaged_or_truncated_bytes_are_refused() {
+        let key = derive("correct horse", &SALT).unwrap();
+        let mut sealed = seal(&SALT, &key, b"secret").unwrap();
+        let last = sealed.len() - 1;
+        sealed[last] ^= 1;
+        assert!(unseal(&sealed, &key).is_err());
+        assert!(parts(&sealed[..MAGIC.len() + SALT_LEN]).is_err());
+    }
+
+    #[test]
+    fn replace_leaves_no_temporary_file() {
+        let dir = env::temp_dir().join(format!("expense-tracker-crypto-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("expenses.json");
+        fs::write(&path, b"old").unwrap();
+        replace(&path, b"new").unwrap();
+        let names: Vec<_> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        let contents = fs::read(&path).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(contents, b"new");
+        assert_eq!(names, ["expenses.json"]);
+    }
+}
@@ -0,0 +1,143 @@
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+use crate::locale;
+
+// How the digits before the decimal point are grouped
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Grouping {
+    // 12,34,567: thousands, then lakhs and crores
+    Indian,
+    // 1,234,567: every three digits
+    Western,
+}
+
+// The currency amounts are shown in
+#[derive(Debug, Clone)]
+pub struct Currency {
+    pub code: String,
+    pub symbol: String,
+    pub grouping: Grouping,
+}
+
+// Symbols for common currency codes; others are shown by their code.
+const SYMBOLS: &[(&str, &str)] = &[
+    ("INR", "₹"),
+    ("USD", "$"),
+    ("EUR", "€"),
+    ("GBP", "£"),
+    ("JPY", "¥"),
+    ("CNY", "¥"),
+    ("AUD", "A$"),
+    ("CAD", "C$"),
+    ("SGD", "S$"),
+    ("AED", "AED "),
+];
+
+pub const DEFAULT_CODE: &str = "INR";
+
+static CURRENT: OnceLock<Currency> = OnceLock::new();
+static LEDGER: OnceLock<Currency> = OnceLock::new();
+
+// Parse a three-letter currency code, for use as a clap value parser.
+pub fn parse_code(s: &str) -> Result<String, String> {
+    if s.len() == 3 && s.chars().all(|c| c.is_ascii_alphabetic()) {
+        Ok(s.to_ascii_uppercase())
+    } else {
+        Err(format!(
+            "'{s}' should be a three-letter currency code (for example, INR)"
+        ))
+    }
+}
+
+impl Currency {
+    // The usual symbol and grouping for `code`.
+    pub fn for_code(code: &str) -> Currency {
+        let symbol = SYMBOLS
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map_or_else(|| format!("{code} "), |(_, s)| s.to_string());
+        Currency {
+            code: code.to_string(),
+            symbol,
+            grouping: locale::current().grouping.unwrap_or(if code == "INR" {
+                Grouping::Indian
+            } else {
+                Grouping::Western
+            }),
+        }
+    }
+
+    // `amount` with the symbol, two decimals and grouped digits, using the
+    // locale's separators. Infinite and NaN amounts, which only a bad
+    // computation can produce, are written as they are.
+    pub fn format(&self, amount: f64) -> String {
+        if !amount.is_finite() {
+            return format!("{}{amount}", self.symbol);
+        }
+        let locale = locale::current();
+        let fixed = format!("{:.2}", amount.abs());
+        let (whole, fraction) = fixed.split_once('.').expect("fixed has decimals");
+        let sign = if amount < 0.0 && fixed != "0.00" {
+            "-"
+        } else {
+            ""
+        };
+        format!(
+            "{sign}{}{}{}{fraction}",
+            self.symbol,
+            group(whole, self.grouping, locale.thousands),
+            locale.decimal
+        )
+    }
+}
+
+// Insert separators into a string of digits.
+fn group(digits: &str, grouping: Grouping, separator: char) -> String {
+    let (head, last_three) = digits.split_at(digits.len().saturating_sub(3));
+    let size = match grouping {
+        Grouping::Indian => 2,
+        Grouping::Western => 3,
+    };
+    let mut groups: Vec<&str> = Vec::new();
+    let mut end = head.len();
+    while end > 0 {
+        let start = end.saturating_sub(size);
+        groups.push(&head[start..end]);
+        end = start;
+    }
+    groups.reverse();
+    groups.push(last_three);
+    groups.join(&separator.to_string())
+}
+
+// Set the currency used for output and the one the ledger is kept in;
+// only the first call has an effect.
+pub fn init(output: Currency, ledger: Currency) {
+    let _ = CURRENT.set(output);
+    let _ = LEDGER.set(ledger);
+}
+
+pub fn current() -> &'static Currency {
+    CURRENT.get_or_init(|| Currency::for_code(DEFAULT_CODE))
+}
+
+pub fn ledger() -> &'static Currency {
+    LEDGER.get_or_init(|| current().clone())
+}
+
+// `amount` formatted in the current currency.
+pub fn amount(amount: f64) -> String {
+    current().format(amount)
+}
+
+// `amount` formatted in the currency with `code`, or the ledger's.
+pub fn amount_in(code: Option<&str>, amount: f64) -> String {
+    match code {
+        None => ledger().format(amount),
+        Some(code) if code == current().code => current().format(amount),
+        Some(code) if code == ledger().code => ledger().format(amount),
+        Some(code) => Currency::for_code(code).format(amount),
+    }
+}
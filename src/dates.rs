@@ -0,0 +1,301 @@
+use chrono::{
+    DateTime, Datelike, FixedOffset, Local, Months, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    TimeZone, Timelike, Utc,
+};
+use chrono_tz::Tz;
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+// Parse a `YYYY-MM-DD` date, for use as a clap value parser.
+pub fn parse_date(s: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map_err(|_| format!("'{s}' should be formatted as %Y-%m-%d (for example, 2025-12-31)"))
+}
+
+// When an expense was made: a day, and the time of day and UTC offset
+// there if they are known. The day is the local one where the money was
+// spent, so a late purchase abroad counts towards the right day.
+#[derive(Clone, Copy, Debug)]
+pub struct When {
+    pub date: NaiveDate,
+    pub time: Option<NaiveTime>,
+    pub offset: Option<FixedOffset>,
+}
+
+impl When {
+    // This time of day in `zone`, unless it was given with an offset.
+    pub fn in_zone(self, zone: Option<Zone>) -> When {
+        let offset = match self.time {
+            Some(time) => self
+                .offset
+                .or_else(|| offset_at(zone, self.date.and_time(time))),
+            None => None,
+        };
+        When { offset, ..self }
+    }
+}
+
+// A timezone: an IANA name such as Asia/Tokyo, or a fixed offset such as
+// +09:00
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(try_from = "String")]
+pub enum Zone {
+    Named(Tz),
+    Fixed(FixedOffset),
+}
+
+impl TryFrom<String> for Zone {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Zone, String> {
+        parse_zone(&s)
+    }
+}
+
+// Parse a timezone, for use as a clap value parser.
+pub fn parse_zone(s: &str) -> Result<Zone, String> {
+    if let Ok(tz) = s.parse::<Tz>() {
+        return Ok(Zone::Named(tz));
+    }
+    s.parse::<FixedOffset>().map(Zone::Fixed).map_err(|_| {
+        format!("'{s}' should be a timezone such as Asia/Tokyo or an offset such as +09:00")
+    })
+}
+
+// The UTC offset of local time `at` in `zone`, or the system's timezone
+// without one. When clocks go back the earlier offset is taken; a time
+// skipped when they go forward has none.
+pub fn offset_at(zone: Option<Zone>, at: NaiveDateTime) -> Option<FixedOffset> {
+    match zone {
+        Some(Zone::Named(tz)) => tz
+            .from_local_datetime(&at)
+            .earliest()
+            .map(|t| t.offset().fix()),
+        Some(Zone::Fixed(offset)) => Some(offset),
+        None => Local
+            .from_local_datetime(&at)
+            .earliest()
+            .map(|t| t.offset().fix()),
+    }
+}
+
+// Now in `zone`, or the system's timezone without one, to the minute.
+pub fn now(zone: Option<Zone>) -> When {
+    let utc = Utc::now();
+    let now: DateTime<FixedOffset> = match zone {
+        Some(Zone::Named(tz)) => utc.with_timezone(&tz).fixed_offset(),
+        Some(Zone::Fixed(offset)) => utc.with_timezone(&offset),
+        None => utc.with_timezone(&Local).fixed_offset(),
+    };
+    When {
+        date: now.date_naive(),
+        time: now.time().with_second(0).and_then(|t| t.with_nanosecond(0)),
+        offset: Some(*now.offset()),
+    }
+}
+
+// Parse a `YYYY-MM-DD` date with an optional `HH:MM` time and UTC offset,
+// as in 2025-12-31 23:30 +09:00, for use as a clap value parser.
+pub fn parse_when(s: &str) -> Result<When, String> {
+    let invalid = || {
+        format!(
+            "'{s}' should be formatted as %Y-%m-%d or %Y-%m-%d %H:%M (for example, 2025-12-31 14:30)"
+        )
+    };
+    let mut parts = s.split([' ', 'T']).filter(|part| !part.is_empty());
+    let date = parts.next().unwrap_or_default();
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| invalid())?;
+    let time = match parts.next() {
+        Some(time) => Some(
+            NaiveTime::parse_from_str(time, "%H:%M")
+                .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M:%S"))
+                .map_err(|_| invalid())?,
+        ),
+        None => None,
+    };
+    let offset = match parts.next() {
+        Some(offset) => Some(offset.parse::<FixedOffset>().map_err(|_| invalid())?),
+        None => None,
+    };
+    if parts.next().is_some() {
+        return Err(invalid());
+    }
+    Ok(When { date, time, offset })
+}
+
+// An optional UTC offset kept as text such as +09:00, for serde's `with`.
+pub mod offset {
+    use chrono::FixedOffset;
+    use serde::{Deserialize, Deserializer, Serializer, de};
+
+    pub fn serialize<S: Serializer>(
+        offset: &Option<FixedOffset>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match offset {
+            Some(offset) => serializer.serialize_some(&offset.to_string()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<FixedOffset>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|offset| offset.parse().map_err(de::Error::custom))
+            .transpose()
+    }
+}
+
+// Parse a `YYYY-MM` month into its first day, for use as a clap value parser.
+pub fn parse_month(s: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(&format!("{s}-01"), "%Y-%m-%d")
+        .map_err(|_| format!("'{s}' should be formatted as %Y-%m (for example, 2025-12)"))
+}
+
+// The `YYYY-MM` key of the month a date falls in.
+pub fn month_key(date: NaiveDate) -> String {
+    date.format("%Y-%m").to_string()
+}
+
+// The last day of the month starting at `month`.
+pub fn month_end(month: NaiveDate) -> NaiveDate {
+    (month + Months::new(1))
+        .pred_opt()
+        .expect("dates before the month end exist")
+}
+
+// The first day of the month a date falls in.
+pub fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).expect("every month has a first day")
+}
+
+// Parse a quarter such as `2025-Q1` into its first day, for use as a clap
+// value parser.
+pub fn parse_quarter(s: &str) -> Result<NaiveDate, String> {
+    let invalid = || format!("'{s}' should be formatted as YYYY-QN (for example, 2025-Q1)");
+    let (year, quarter) = s.split_once(['-', ' ']).ok_or_else(invalid)?;
+    let year: i32 = year.parse().map_err(|_| invalid())?;
+    let quarter: u32 = quarter
+        .strip_prefix(['Q', 'q'])
+        .and_then(|q| q.parse().ok())
+        .filter(|q| (1..=4).contains(q))
+        .ok_or_else(invalid)?;
+    NaiveDate::from_ymd_opt(year, (quarter - 1) * 3 + 1, 1).ok_or_else(invalid)
+}
+
+// The day of the month budgeting periods start on, such as payday
+static PERIOD_START: OnceLock<u32> = OnceLock::new();
+
+pub fn init_period(day: Option<u32>) {
+    let _ = PERIOD_START.set(day.unwrap_or(1));
+}
+
+// The first day of the period named by the month starting at `month`: the
+// month's first day, or its period start day (the last day when shorter).
+pub fn period_start(month: NaiveDate) -> NaiveDate {
+    let day = *PERIOD_START.get_or_init(|| 1);
+    month
+        .with_day(day.min(month_end(month).day()))
+        .expect("days up to the month end exist")
+}
+
+// The last day of the period named by the month starting at `month`.
+pub fn period_end(month: NaiveDate) -> NaiveDate {
+    period_start(month + Months::new(1))
+        .pred_opt()
+        .expect("dates before the period end exist")
+}
+
+// The month naming the period a date falls in: the month the period
+// starts in.
+pub fn period_of(date: NaiveDate) -> NaiveDate {
+    let month = month_start(date);
+    if date >= period_start(month) {
+        month
+    } else {
+        month - Months::new(1)
+    }
+}
+
+// Years accepted wherever one is given; chrono can't go much past 262000
+pub const YEARS: std::ops::RangeInclusive<i64> = 1900..=9999;
+
+// Parse a fiscal year such as `2024-25` or `2024` into the year it starts
+// in, for use as a clap value parser.
+pub fn parse_fiscal_year(s: &str) -> Result<i32, String> {
+    let invalid = || format!("'{s}' should be formatted as YYYY-YY (for example, 2024-25)");
+    let (start, end) = match s.split_once('-') {
+        Some((start, end)) => (start, Some(end)),
+        None => (s, None),
+    };
+    let start: i32 = start.parse().map_err(|_| invalid())?;
+    if !YEARS.contains(&i64::from(start)) {
+        return Err(format!(
+            "'{s}' should start in a year from {} to {}",
+            YEARS.start(),
+            YEARS.end()
+        ));
+    }
+    if let Some(end) = end {
+        let end: i32 = end.parse().map_err(|_| invalid())?;
+        let expected = start + 1;
+        if end != expected && end != expected % 100 {
+            return Err(invalid());
+        }
+    }
+    Ok(start)
+}
+
+// The first and last day of the fiscal year beginning in `start_month` of
+// `start_year`, if there are such dates.
+pub fn fiscal_year(start_year: i32, start_month: u32) -> Option<(NaiveDate, NaiveDate)> {
+    let from = NaiveDate::from_ymd_opt(start_year, start_month, 1)?;
+    let to = from.checked_add_months(Months::new(12))?.pred_opt()?;
+    Some((from, to))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn fiscal_years_are_named_by_the_years_they_span() {
+        assert_eq!(parse_fiscal_year("2024-25"), Ok(2024));
+        assert_eq!(parse_fiscal_year("2024-2025"), Ok(2024));
+        assert_eq!(parse_fiscal_year("2024"), Ok(2024));
+        assert_eq!(parse_fiscal_year("1999-00"), Ok(1999));
+        for year in [
+            "2024-26", "2024-24", "24-25", "2024-", "FY24", "1899-00", "10000",
+        ] {
+            assert!(parse_fiscal_year(year).is_err(), "{year} should be refused");
+        }
+    }
+
+    #[test]
+    fn fiscal_years_run_a_year_from_their_start_month() {
+        assert_eq!(
+            fiscal_year(2024, 4),
+            Some((date(2024, 4, 1), date(2025, 3, 31)))
+        );
+        assert_eq!(
+            fiscal_year(2024, 1),
+            Some((date(2024, 1, 1), date(2024, 12, 31)))
+        );
+        // Ends on a leap day
+        assert_eq!(
+            fiscal_year(2023, 3),
+            Some((date(2023, 3, 1), date(2024, 2, 29)))
+        );
+        assert_eq!(
+            fiscal_year(2024, 12),
+            Some((date(2024, 12, 1), date(2025, 11, 30)))
+        );
+        assert_eq!(fiscal_year(2024, 13), None);
+        assert_eq!(fiscal_year(2024, 0), None);
+    }
+}
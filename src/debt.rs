@@ -0,0 +1,120 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::currency;
+use crate::expense::ExpenseTracker;
+use crate::locale;
+
+// Money lent to or borrowed from someone, or paid back
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Debt {
+    pub date: NaiveDate,
+    pub person: String,
+    // How much more the person owes the ledger owner because of it:
+    // positive when lent or repaid to them, negative when borrowed or
+    // repaid by them
+    pub amount: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub repayment: bool,
+}
+
+// What each person owes the owner (negative when the owner owes them),
+// largest first, leaving out anyone who is square.
+pub fn balances(tracker: &ExpenseTracker) -> Vec<(String, f64)> {
+    let mut balances: Vec<(String, f64)> = Vec::new();
+    for debt in tracker.debts.iter() {
+        match balances
+            .iter_mut()
+            .find(|(p, _)| p.eq_ignore_ascii_case(&debt.person))
+        {
+            Some((_, balance)) => *balance += debt.amount,
+            None => balances.push((debt.person.clone(), debt.amount)),
+        }
+    }
+    balances.retain(|(_, balance)| balance.abs() >= 0.005);
+    balances.sort_by(|a, b| b.1.total_cmp(&a.1));
+    balances
+}
+
+// What `person` owes the owner.
+pub fn balance(tracker: &ExpenseTracker, person: &str) -> f64 {
+    tracker
+        .debts
+        .iter()
+        .filter(|d| d.person.eq_ignore_ascii_case(person))
+        .map(|d| d.amount)
+        .sum()
+}
+
+pub fn print_balances(tracker: &ExpenseTracker) {
+    let balances = balances(tracker);
+    if balances.is_empty() {
+        println!("No one owes anything.");
+        return;
+    }
+    let (mut owed, mut owing) = (0.0, 0.0);
+    for (person, balance) in balances.iter() {
+        if *balance > 0.0 {
+            owed += balance;
+            println!("{:<20} owes you {}", person, currency::amount(*balance));
+        } else {
+            owing -= balance;
+            println!("{:<20} is owed  {}", person, currency::amount(-balance));
+        }
+    }
+    println!(
+        "\nOwed to you: {}, you owe: {}",
+        currency::amount(owed),
+        currency::amount(owing)
+    );
+}
+
+// Every loan and repayment, or only those with `person`, with the running
+// balance with each person.
+pub fn print_list(tracker: &ExpenseTracker, person: Option<&str>) {
+    let mut debts: Vec<&Debt> = tracker
+        .debts
+        .iter()
+        .filter(|d| person.is_none_or(|p| d.person.eq_ignore_ascii_case(p)))
+        .collect();
+    if debts.is_empty() {
+        println!("No debts recorded.");
+        return;
+    }
+    debts.sort_by_key(|d| d.date);
+    let mut running: Vec<(String, f64)> = Vec::new();
+    for debt in debts {
+        let balance = match running
+            .iter_mut()
+            .find(|(p, _)| p.eq_ignore_ascii_case(&debt.person))
+        {
+            Some((_, balance)) => {
+                *balance += debt.amount;
+                *balance
+            }
+            None => {
+                running.push((debt.person.clone(), debt.amount));
+                debt.amount
+            }
+        };
+        let what = match (debt.repayment, debt.amount > 0.0) {
+            (false, true) => format!("Lent to {}", debt.person),
+            (false, false) => format!("Borrowed from {}", debt.person),
+            (true, true) => format!("Repaid {}", debt.person),
+            (true, false) => format!("{} repaid", debt.person),
+        };
+        print!(
+            "{}  {:<30} {:>12}  balance {:>12}",
+            locale::date(debt.date),
+            what,
+            currency::amount(debt.amount.abs()),
+            currency::amount(balance)
+        );
+        match &debt.description {
+            Some(description) => println!(", {description}"),
+            None => println!(),
+        }
+    }
+}
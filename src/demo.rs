@@ -0,0 +1,229 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chrono::{Datelike, Months, NaiveDate, Weekday};
+
+use crate::budget::Budget;
+use crate::expense::{Expense, ExpenseTracker};
+
+// Amounts below are in rupees, so the demo ledger always is too.
+const CURRENCY: &str = "INR";
+
+// Demo data only has to look random, so a small splitmix64 generator does;
+// a fixed seed makes the same ledger every time.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // Uniform in [0, 1)
+    fn unit(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn chance(&mut self, p: f64) -> bool {
+        self.unit() < p
+    }
+
+    // A whole amount between `low` and `high`
+    fn amount(&mut self, low: f64, high: f64) -> f64 {
+        (low + (high - low) * self.unit()).round()
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[(self.next() % items.len() as u64) as usize]
+    }
+}
+
+// Everyday spending: category, how often it comes up relative to the
+// others, the usual range, and what it tends to be
+struct Spend {
+    category: &'static str,
+    weight: u32,
+    low: f64,
+    high: f64,
+    descriptions: &'static [&'static str],
+    payees: &'static [&'static str],
+}
+
+const EVERYDAY: [Spend; 6] = [
+    Spend {
+        category: "Food",
+        weight: 40,
+        low: 120.0,
+        high: 650.0,
+        descriptions: &["Lunch", "Dinner", "Coffee", "Takeaway", "Snacks"],
+        payees: &["Swiggy", "Zomato", "Blue Tokai", "Udupi Grand"],
+    },
+    Spend {
+        category: "Transport",
+        weight: 25,
+        low: 40.0,
+        high: 450.0,
+        descriptions: &["Cab", "Auto", "Metro top-up", "Fuel"],
+        payees: &["Uber", "Ola", "Namma Metro", "HP Petrol"],
+    },
+    Spend {
+        category: "Groceries",
+        weight: 12,
+        low: 200.0,
+        high: 1500.0,
+        descriptions: &["Groceries", "Vegetables", "Milk and bread", "Fruit"],
+        payees: &["BigBasket", "DMart", "Corner kirana", "Zepto"],
+    },
+    Spend {
+        category: "Entertainment",
+        weight: 8,
+        low: 200.0,
+        high: 1500.0,
+        descriptions: &["Movie", "Concert", "Bowling", "Game"],
+        payees: &["PVR", "BookMyShow", "Steam"],
+    },
+    Spend {
+        category: "Shopping",
+        weight: 6,
+        low: 400.0,
+        high: 4000.0,
+        descriptions: &["Clothes", "Books", "Household items", "Shoes"],
+        payees: &["Amazon", "Flipkart", "Myntra", "Decathlon"],
+    },
+    Spend {
+        category: "Health",
+        weight: 3,
+        low: 150.0,
+        high: 2500.0,
+        descriptions: &["Pharmacy", "Doctor visit", "Lab tests"],
+        payees: &["Apollo Pharmacy", "City Clinic"],
+    },
+];
+
+// Bills that come every month: day, category, description, payee, range
+const BILLS: [(u32, &str, &str, &str, f64, f64); 4] = [
+    (1, "Rent", "Rent", "Landlord", 18000.0, 18000.0),
+    (5, "Utilities", "Electricity bill", "BESCOM", 1100.0, 2600.0),
+    (8, "Utilities", "Internet", "ACT Fibernet", 999.0, 999.0),
+    (15, "Utilities", "Mobile recharge", "Jio", 299.0, 299.0),
+];
+
+// Rare purchases, around one in four months: category, description, range
+const BIG: [(&str, &str, f64, f64); 5] = [
+    ("Shopping", "New laptop", 45000.0, 90000.0),
+    ("Shopping", "Phone", 15000.0, 60000.0),
+    ("Travel", "Flights and hotel", 12000.0, 35000.0),
+    ("Health", "Dental work", 8000.0, 25000.0),
+    ("Shopping", "Furniture", 10000.0, 40000.0),
+];
+
+// Budgets set up alongside, so budget reports have something to show
+const BUDGETS: [(&str, f64); 4] = [
+    ("Food", 9000.0),
+    ("Groceries", 6000.0),
+    ("Transport", 4000.0),
+    ("Entertainment", 3000.0),
+];
+
+// Salary lands on the first, so spending bunches up in the week after.
+fn payday_boost(day: u32) -> f64 {
+    match day {
+        1..=7 => 1.6,
+        8..=21 => 1.0,
+        _ => 0.6,
+    }
+}
+
+pub fn seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64)
+}
+
+// A ledger of `months` months of made-up spending, ending today.
+pub fn generate(months: u32, today: NaiveDate, seed: u64) -> ExpenseTracker {
+    let mut rng = Rng(seed);
+    let mut tracker = ExpenseTracker::new();
+    tracker.currency = Some(String::from(CURRENCY));
+
+    let this_month = today.with_day(1).expect("every month has a first day");
+    let first = this_month - Months::new(months.saturating_sub(1));
+    let total_weight: u32 = EVERYDAY.iter().map(|s| s.weight).sum();
+    let mut date = first;
+    while date <= today {
+        let day = date.day();
+        for (bill_day, category, description, payee, low, high) in BILLS {
+            if day == bill_day {
+                tracker.add_expense(Expense {
+                    date,
+                    category: category.to_string(),
+                    amount: rng.amount(low, high),
+                    description: description.to_string(),
+                    payee: Some(payee.to_string()),
+                    ..Default::default()
+                });
+            }
+        }
+
+        let weekend = matches!(date.weekday(), Weekday::Sat | Weekday::Sun);
+        let rate = 1.8 * payday_boost(day) * if weekend { 1.3 } else { 1.0 };
+        // The whole part of the rate, plus one more that often
+        let count = (rate + rng.unit()) as u32;
+        for _ in 0..count {
+            let mut roll = (rng.next() % total_weight as u64) as u32;
+            let spend = EVERYDAY
+                .iter()
+                .find(|s| {
+                    if roll < s.weight {
+                        return true;
+                    }
+                    roll -= s.weight;
+                    false
+                })
+                .expect("roll is below the total weight");
+            tracker.add_expense(Expense {
+                date,
+                category: spend.category.to_string(),
+                amount: rng.amount(spend.low, spend.high),
+                description: rng.pick(spend.descriptions).to_string(),
+                payee: Some(rng.pick(spend.payees).to_string()),
+                ..Default::default()
+            });
+        }
+
+        // A big purchase, soon after payday
+        if day == 1 && rng.chance(0.25) {
+            let (category, description, low, high) = *rng.pick(&BIG);
+            let on = date + chrono::Days::new(rng.next() % 10);
+            if on <= today {
+                tracker.add_expense(Expense {
+                    date: on,
+                    category: category.to_string(),
+                    amount: rng.amount(low, high),
+                    description: description.to_string(),
+                    ..Default::default()
+                });
+            }
+        }
+        date = date.succ_opt().expect("dates run well past today");
+    }
+    tracker.expenses.sort_by_key(|e| e.date);
+
+    for (category, amount) in BUDGETS {
+        tracker.budgets.push(Budget {
+            category: category.to_string(),
+            amount,
+            rollover: false,
+            since: first,
+        });
+    }
+    for spend in EVERYDAY.iter() {
+        tracker.categories.push(spend.category.to_string());
+    }
+    tracker
+        .categories
+        .extend(["Rent", "Utilities", "Travel"].map(String::from));
+    tracker
+}
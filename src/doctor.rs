@@ -0,0 +1,382 @@
+use chrono::{Days, Local};
+use serde_json::Value;
+use std::{fs, io, path::Path};
+
+use crate::attachments;
+use crate::crypto;
+use crate::expense::ExpenseTracker;
+use crate::migrate;
+use crate::storage::Storage;
+
+// A problem found in the data file, and whether `--fix` can repair it
+struct Issue {
+    message: String,
+    fixable: bool,
+}
+
+// A repeated id given a fresh one, whose attachments move with it
+struct Renumbered {
+    from: u64,
+    to: u64,
+    attachments: Vec<String>,
+}
+
+// Expenses dated further ahead than this are reported
+const FUTURE_DAYS: u64 = 365;
+
+// Check `filename` for problems, repairing the safe ones when `fix` is set.
+// Repairs are saved through `storage`, so they are backed up, recorded in
+// the history and synced like any other change. Returns whether the file
+// is healthy afterwards.
+pub fn run(filename: &str, storage: &dyn Storage, fix: bool) -> io::Result<bool> {
+    let text = match crypto::read(Path::new(filename)) {
+        Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            println!("No data file yet.");
+            return Ok(true);
+        }
+        Err(err) => return Err(err),
+    };
+    // Parse from the text first so errors point at a line and column.
+    let original: Value = match serde_json::from_str(&text) {
+        Ok(value) => value,
+        Err(err) => {
+            print_parse_error(&text, &err);
+            return Ok(false);
+        }
+    };
+    if let Err(err) = serde_json::from_str::<ExpenseTracker>(&text) {
+        print_parse_error(&text, &err);
+        return Ok(false);
+    }
+    let mut upgraded = original.clone();
+    if let Err(err) = migrate::upgrade(&mut upgraded) {
+        println!("{err}");
+        return Ok(false);
+    }
+    let mut tracker: ExpenseTracker = serde_json::from_value(upgraded)?;
+
+    let mut issues = Vec::new();
+    let known = serde_json::to_value(&tracker)?;
+    let mut unknown = Vec::new();
+    unknown_fields(&original, &known, "", &mut unknown);
+    for path in unknown {
+        issues.push(Issue {
+            message: format!("Unknown field {path}; it is ignored and dropped on save."),
+            fixable: true,
+        });
+    }
+    check_expenses(&tracker, &mut issues);
+    let renumbered = fix_ids(&mut tracker, fix, &mut issues);
+    check_attachments(filename, &mut tracker, &renumbered, fix, &mut issues);
+
+    if issues.is_empty() {
+        println!("No problems found.");
+        return Ok(true);
+    }
+    for issue in issues.iter() {
+        if fix && issue.fixable {
+            println!("{} (fixed)", issue.message);
+        } else {
+            println!("{}", issue.message);
+        }
+    }
+    let fixable = issues.iter().filter(|i| i.fixable).count();
+    if fix && fixable > 0 {
+        storage.save(&tracker)?;
+        move_attachments(filename, &tracker, &renumbered)?;
+        println!("\n{} problem(s) found, {} fixed.", issues.len(), fixable);
+        return Ok(fixable == issues.len());
+    }
+    println!("\n{} problem(s) found.", issues.len());
+    if fixable > 0 {
+        println!("Run with --fix to repair {fixable} of them.");
+    }
+    Ok(false)
+}
+
+fn print_parse_error(text: &str, err: &serde_json::Error) {
+    println!("The data file can't be read: {err}");
+    // Data files are written on one line, so show a window around the column.
+    if let Some(line) = text.lines().nth(err.line().saturating_sub(1)) {
+        let start = err.column().saturating_sub(40);
+        let snippet: String = line.chars().skip(start).take(80).collect();
+        println!("  {snippet}");
+        println!("  {}^", " ".repeat(err.column().saturating_sub(start + 1)));
+    }
+}
+
+// Fields in `original` that don't survive a round trip through the ledger
+// types. Empty values are skipped since they're omitted when written.
+fn unknown_fields(original: &Value, known: &Value, path: &str, found: &mut Vec<String>) {
+    match (original, known) {
+        (Value::Object(original), Value::Object(known)) => {
+            for (key, value) in original {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                match known.get(key) {
+                    Some(known) => unknown_fields(value, known, &path, found),
+                    None if !is_empty(value) => found.push(path),
+                    None => {}
+                }
+            }
+        }
+        (Value::Array(original), Value::Array(known)) => {
+            for (i, (value, known)) in original.iter().zip(known).enumerate() {
+                unknown_fields(value, known, &format!("{path}[{i}]"), found);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null | Value::Bool(false) => true,
+        Value::Array(values) => values.is_empty(),
+        Value::Object(map) => map.is_empty(),
+        _ => false,
+    }
+}
+
+// Problems that need a person to decide on, so they are only reported.
+fn check_expenses(tracker: &ExpenseTracker, issues: &mut Vec<Issue>) {
+    let horizon = Local::now().date_naive() + Days::new(FUTURE_DAYS);
+    for expense in tracker.expenses.iter() {
+        let mut report = |message: String| {
+            issues.push(Issue {
+                message: format!("Expense #{}: {message}", expense.id),
+                fixable: false,
+            })
+        };
+        if !expense.amount.is_finite() {
+            report(String::from("the amount is not a number."));
+        } else if expense.amount < 0.0 {
+            report(format!("the amount {} is negative.", expense.amount));
+        }
+        if !expense.splits_balance() {
+            report(String::from("the splits don't add up to the amount."));
+        }
+        if expense.date > horizon {
+            report(format!("dated {}, far in the future.", expense.date));
+        }
+        if expense.category.trim().is_empty() {
+            report(String::from("the category is empty."));
+        }
+    }
+}
+
+// Repeated ids are given fresh ones after the first, and the next id is
+// moved past every id in use. Returns the expenses renumbered.
+fn fix_ids(tracker: &mut ExpenseTracker, fix: bool, issues: &mut Vec<Issue>) -> Vec<Renumbered> {
+    let highest = tracker.expenses.iter().map(|e| e.id).max().unwrap_or(0);
+    if tracker.next_id <= highest {
+        issues.push(Issue {
+            message: format!(
+                "The next id is {} but ids up to {} are in use.",
+                tracker.next_id, highest
+            ),
+            fixable: true,
+        });
+    }
+    let mut next_id = tracker.next_id.max(highest + 1);
+    let mut seen = Vec::new();
+    let mut renumbered = Vec::new();
+    for expense in tracker.expenses.iter_mut() {
+        if seen.contains(&expense.id) {
+            let message = if fix {
+                format!("Duplicate id #{}, now #{}.", expense.id, next_id)
+            } else {
+                format!("Duplicate id #{} ({}).", expense.id, expense.description)
+            };
+            issues.push(Issue {
+                message,
+                fixable: true,
+            });
+            if fix {
+                renumbered.push(Renumbered {
+                    from: expense.id,
+                    to: next_id,
+                    attachments: expense.attachments.clone(),
+                });
+                expense.id = next_id;
+                next_id += 1;
+            }
+        }
+        seen.push(expense.id);
+    }
+    if fix {
+        tracker.next_id = next_id;
+    }
+    renumbered
+}
+
+// Move the files of renumbered expenses to their new id's directory, once
+// the ledger is saved. A file the expense keeping the old id also lists is
+// copied instead.
+fn move_attachments(
+    filename: &str,
+    tracker: &ExpenseTracker,
+    renumbered: &[Renumbered],
+) -> io::Result<()> {
+    for moved in renumbered {
+        let kept: &[String] = tracker
+            .find_by_id(moved.from)
+            .map_or(&[], |e| e.attachments.as_slice());
+        let (from, to) = (
+            attachments::dir(filename, moved.from),
+            attachments::dir(filename, moved.to),
+        );
+        for name in moved.attachments.iter() {
+            if !from.join(name).exists() {
+                continue;
+            }
+            fs::create_dir_all(&to)?;
+            if kept.contains(name) {
+                fs::copy(from.join(name), to.join(name))?;
+            } else {
+                fs::rename(from.join(name), to.join(name))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// Attachments recorded on an expense whose file is gone. Renumbered
+// expenses' files are still under their old id.
+fn check_attachments(
+    filename: &str,
+    tracker: &mut ExpenseTracker,
+    renumbered: &[Renumbered],
+    fix: bool,
+    issues: &mut Vec<Issue>,
+) {
+    for expense in tracker.expenses.iter_mut() {
+        let id = renumbered
+            .iter()
+            .find(|r| r.to == expense.id)
+            .map_or(expense.id, |r| r.from);
+        let dir = attachments::dir(filename, id);
+        let (present, missing): (Vec<String>, Vec<String>) = expense
+            .attachments
+            .iter()
+            .cloned()
+            .partition(|name| dir.join(name).exists());
+        for name in missing.iter() {
+            issues.push(Issue {
+                message: format!("Expense #{}: attachment {name} is missing.", expense.id),
+                fixable: true,
+            });
+        }
+        if fix {
+            expense.attachments = present;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expense::Expense;
+    use chrono::NaiveDate;
+    use std::env;
+
+    fn expense(id: u64, attachments: &[&str]) -> Expense {
+        Expense {
+            id,
+            date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            category: String::from("Food"),
+            amount: 100.0,
+            attachments: attachments.iter().map(|a| a.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn ledger(expenses: Vec<Expense>, next_id: u64) -> ExpenseTracker {
+        let mut tracker = ExpenseTracker::new();
+        tracker.expenses = expenses;
+        tracker.next_id = next_id;
+        tracker
+    }
+
+    #[test]
+    fn duplicate_ids_are_only_reported_without_fix() {
+        let mut tracker = ledger(vec![expense(1, &[]), expense(1, &[])], 2);
+        let mut issues = Vec::new();
+        let renumbered = fix_ids(&mut tracker, false, &mut issues);
+        assert!(renumbered.is_empty());
+        assert_eq!(issues.len(), 1);
+        assert_eq!(tracker.expenses[1].id, 1);
+    }
+
+    #[test]
+    fn duplicate_ids_are_renumbered_past_every_id() {
+        let mut tracker = ledger(
+            vec![
+                expense(3, &[]),
+                expense(3, &["receipt.pdf"]),
+                expense(5, &[]),
+            ],
+            2,
+        );
+        let mut issues = Vec::new();
+        let renumbered = fix_ids(&mut tracker, true, &mut issues);
+        let ids: Vec<u64> = tracker.expenses.iter().map(|e| e.id).collect();
+        assert_eq!(ids, [3, 6, 5]);
+        assert_eq!(tracker.next_id, 7);
+        // The stale next id and the duplicate
+        assert_eq!(issues.len(), 2);
+        assert_eq!(renumbered.len(), 1);
+        assert_eq!((renumbered[0].from, renumbered[0].to), (3, 6));
+        assert_eq!(renumbered[0].attachments, ["receipt.pdf"]);
+    }
+
+    #[test]
+    fn renumbered_attachments_are_kept_and_moved() {
+        let dir = env::temp_dir().join(format!("expense-tracker-doctor-{}", std::process::id()));
+        let filename = dir.join("expenses.json").to_string_lossy().into_owned();
+        let old = attachments::dir(&filename, 1);
+        fs::create_dir_all(&old).unwrap();
+        for name in ["a.txt", "b.txt", "shared.txt"] {
+            fs::write(old.join(name), name).unwrap();
+        }
+        let mut tracker = ledger(
+            vec![
+                expense(1, &["a.txt", "shared.txt"]),
+                expense(1, &["b.txt", "shared.txt", "gone.txt"]),
+            ],
+            2,
+        );
+        let mut issues = Vec::new();
+        let renumbered = fix_ids(&mut tracker, true, &mut issues);
+        check_attachments(&filename, &mut tracker, &renumbered, true, &mut issues);
+        move_attachments(&filename, &tracker, &renumbered).unwrap();
+        let new = attachments::dir(&filename, 2);
+        let files = |dir: &Path| {
+            let mut names: Vec<String> = fs::read_dir(dir)
+                .unwrap()
+                .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+                .collect();
+            names.sort();
+            names
+        };
+        let (old_files, new_files) = (files(&old), files(&new));
+        fs::remove_dir_all(&dir).unwrap();
+
+        // Only the file that really is missing is reported and dropped.
+        let messages: Vec<&str> = issues.iter().map(|i| i.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "Duplicate id #1, now #2.",
+                "Expense #2: attachment gone.txt is missing."
+            ]
+        );
+        assert_eq!(tracker.expenses[1].attachments, ["b.txt", "shared.txt"]);
+        assert_eq!(old_files, ["a.txt", "shared.txt"]);
+        assert_eq!(new_files, ["b.txt", "shared.txt"]);
+    }
+}
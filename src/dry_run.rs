@@ -0,0 +1,81 @@
+use std::io;
+
+use crate::expense::{Expense, ExpenseTracker};
+use crate::history::{self, Change};
+use crate::storage::Storage;
+
+// A storage backend for --dry-run: changes are printed instead of saved.
+// The run ends where it would have written, so nothing that follows a save
+// (success messages, removing attachments, webhooks) happens either.
+pub struct DryRun {
+    inner: Box<dyn Storage>,
+    mask_private: bool,
+}
+
+pub fn wrap(storage: Box<dyn Storage>, mask_private: bool) -> Box<dyn Storage> {
+    Box::new(DryRun {
+        inner: storage,
+        mask_private,
+    })
+}
+
+impl DryRun {
+    fn print(&self, changes: &[Change]) {
+        for change in changes {
+            match change {
+                Change::Added { expense } => {
+                    println!("Would add     {}", expense.line(self.mask_private))
+                }
+                Change::Deleted { expense } => {
+                    println!("Would delete  {}", expense.line(self.mask_private))
+                }
+                Change::Edited { before, after } => {
+                    println!("Would edit    #{}", after.id);
+                    if self.mask_private && (before.private || after.private) {
+                        continue;
+                    }
+                    for difference in history::differences(before, after) {
+                        println!("{:<14}{}", "", difference);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Storage for DryRun {
+    fn load(&self) -> io::Result<ExpenseTracker> {
+        self.inner.load()
+    }
+
+    fn load_metadata(&self) -> io::Result<ExpenseTracker> {
+        self.inner.load_metadata()
+    }
+
+    fn save(&self, tracker: &ExpenseTracker) -> io::Result<()> {
+        let before = self.inner.load()?;
+        let changes = history::changes(&before, tracker);
+        self.print(&changes);
+        // Budgets, goals and the like aren't expenses, so only say whether
+        // they would change. The next id and the currency noted on load are
+        // bookkeeping.
+        let mut rest = tracker.clone();
+        rest.expenses = before.expenses.clone();
+        rest.next_id = before.next_id;
+        rest.currency = before.currency.clone();
+        if serde_json::to_value(&rest)? != serde_json::to_value(&before)? {
+            println!("Would update the ledger's settings.");
+        } else if changes.is_empty() {
+            println!("Would change nothing.");
+        }
+        println!("Dry run: nothing was saved.");
+        std::process::exit(0)
+    }
+
+    fn append(&self, mut expense: Expense) -> io::Result<u64> {
+        expense.id = self.inner.load_metadata()?.next_id;
+        self.print(&[Change::Added { expense }]);
+        println!("Dry run: nothing was saved.");
+        std::process::exit(0)
+    }
+}
@@ -0,0 +1,355 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::expense::{self, Expense, ExpenseTracker};
+use crate::prompt;
+
+// One change made to every expense selected by `edit`
+#[derive(Debug, Clone)]
+pub enum Change {
+    Category(String),
+    Payee(Option<String>),
+    Trip(Option<String>),
+    Project(Option<String>),
+    User(Option<String>),
+    Business(bool),
+    // A field of the user's own; no value removes it
+    Meta(String, Option<String>),
+    Description(String),
+    AddTag(String),
+    RemoveTag(String),
+}
+
+// Parse a `field=value` assignment, for use as a clap value parser. An
+// empty payee, trip, project or user clears it, and business takes yes or no.
+// meta.KEY sets a field of the user's own, or removes it when empty.
+pub fn parse_set(s: &str) -> Result<Change, String> {
+    let (field, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("'{s}' should be formatted as field=value"))?;
+    let value = value.trim().to_string();
+    if let Some(key) = field.trim().strip_prefix("meta.") {
+        if key.trim().is_empty() {
+            return Err(format!("'{s}' needs a key after meta."));
+        }
+        return Ok(Change::Meta(
+            key.trim().to_string(),
+            (!value.is_empty()).then_some(value),
+        ));
+    }
+    match field.trim().to_lowercase().as_str() {
+        "category" if value.is_empty() => Err(String::from("category can't be empty")),
+        "category" => Ok(Change::Category(value)),
+        "payee" => Ok(Change::Payee((!value.is_empty()).then_some(value))),
+        "trip" => Ok(Change::Trip((!value.is_empty()).then_some(value))),
+        "project" => Ok(Change::Project((!value.is_empty()).then_some(value))),
+        "user" => Ok(Change::User((!value.is_empty()).then_some(value))),
+        "business" => match value.to_lowercase().as_str() {
+            "yes" | "true" => Ok(Change::Business(true)),
+            "no" | "false" => Ok(Change::Business(false)),
+            _ => Err(format!("business should be yes or no, not '{value}'")),
+        },
+        "description" => Ok(Change::Description(value)),
+        other => Err(format!(
+            "'{other}' can't be set; use category, payee, trip, project, user, business, meta.KEY or description"
+        )),
+    }
+}
+
+// Apply `changes` to `expense`, returning whether anything changed. When
+// the selection was by category, a new category also renames the matching
+// legs of split expenses.
+pub fn apply(expense: &mut Expense, changes: &[Change], selected_category: Option<&str>) -> bool {
+    let before = expense.clone();
+    for change in changes {
+        match change {
+            Change::Category(category) => match selected_category {
+                Some(selected) if !expense.splits.is_empty() => {
+                    for split in expense.splits.iter_mut() {
+                        if split.category.eq_ignore_ascii_case(selected) {
+                            split.category = category.clone();
+                        }
+                    }
+                    if expense.category.eq_ignore_ascii_case(selected) {
+                        expense.category = category.clone();
+                    }
+                }
+                _ => expense.category = category.clone(),
+            },
+            Change::Payee(payee) => expense.payee = payee.clone(),
+            Change::Trip(trip) => expense.trip = trip.clone(),
+            Change::Project(project) => expense.project = project.clone(),
+            Change::User(user) => expense.user = user.clone(),
+            Change::Business(business) => expense.business = *business,
+            Change::Meta(key, value) => {
+                expense.meta.retain(|k, _| !k.eq_ignore_ascii_case(key));
+                if let Some(value) = value {
+                    expense.meta.insert(key.clone(), value.clone());
+                }
+            }
+            Change::Description(description) => expense.description = description.clone(),
+            Change::AddTag(tag) => {
+                if !expense.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                    expense.tags.push(tag.clone());
+                }
+            }
+            Change::RemoveTag(tag) => expense.tags.retain(|t| !t.eq_ignore_ascii_case(tag)),
+        }
+    }
+    *expense != before
+}
+
+// Written above the expenses opened with `edit --editor`
+const HEADER: &str = "\
+# Edit the expenses below and save to apply the changes.
+# Remove an [[expense]] block to delete that expense. Ids can't be changed.
+";
+
+// The file opened in the editor
+#[derive(Serialize, Deserialize)]
+struct Document {
+    #[serde(default)]
+    expense: Vec<Expense>,
+}
+
+// What was saved from the editor, relative to the expenses opened
+pub struct Edited {
+    pub changed: Vec<Expense>,
+    pub deleted: Vec<u64>,
+}
+
+// Open the expenses at `rows` in `$VISUAL` or `$EDITOR` as TOML and read
+// back the result. Invalid edits can be fixed by reopening the file. None
+// when the user gives up.
+pub fn in_editor(tracker: &ExpenseTracker, rows: &[usize]) -> Result<Option<Edited>, String> {
+    let opened: Vec<Expense> = rows
+        .iter()
+        .map(|row| tracker.expenses[*row].clone())
+        .collect();
+    let body = toml::to_string(&Document {
+        expense: opened.clone(),
+    })
+    .map_err(|err| err.to_string())?;
+    let dir = private_dir().map_err(|err| format!("Couldn't make a file to edit: {err}"))?;
+    let path = dir.join("expenses.toml");
+    let mut text = format!("{HEADER}\n{body}");
+    loop {
+        let saved = write_private(&path, &text)
+            .map_err(|err| err.to_string())
+            .and_then(|()| open_editor(&path))
+            .and_then(|_| fs::read_to_string(&path).map_err(|err| err.to_string()));
+        let saved = match saved {
+            Ok(saved) => saved,
+            Err(err) => {
+                let _ = fs::remove_dir_all(&dir);
+                return Err(err);
+            }
+        };
+        match read(tracker, &opened, &saved) {
+            Ok(edited) => {
+                let _ = fs::remove_dir_all(&dir);
+                return Ok(Some(edited));
+            }
+            Err(err) => {
+                eprintln!("{err}");
+                if !prompt::confirm("Edit again?") {
+                    let _ = fs::remove_dir_all(&dir);
+                    return Ok(None);
+                }
+                // Keep the user's edits, with the problem noted at the top
+                let kept: Vec<&str> = saved
+                    .lines()
+                    .skip_while(|line| line.starts_with("# Error:"))
+                    .collect();
+                text = format!("# Error: {err}\n{}\n", kept.join("\n"));
+            }
+        }
+    }
+}
+
+// A new directory under the temporary one that only the user can open,
+// with a name that can't be guessed. The expenses are written there in
+// plain text, even from an encrypted ledger, so no one else may read them
+// or put a link where the file will be.
+fn private_dir() -> io::Result<PathBuf> {
+    use aes_gcm::aead::{OsRng, rand_core::RngCore};
+    let mut bytes = [0u8; 16];
+    OsRng.fill_bytes(&mut bytes);
+    let name: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    let dir = std::env::temp_dir().join(format!("expense-tracker-{name}"));
+    let mut builder = fs::DirBuilder::new();
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    // Fails rather than reuse a directory that is already there
+    builder.create(&dir)?;
+    Ok(dir)
+}
+
+// Write `text` to `path`, readable by the user alone.
+fn write_private(path: &Path, text: &str) -> io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(path)?.write_all(text.as_bytes())
+}
+
+fn open_editor(path: &Path) -> Result<(), String> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| String::from("vi"));
+    // Through the shell, so editors given with arguments like `code --wait` work
+    let status = process::Command::new("sh")
+        .arg("-c")
+        .arg(format!("{editor} \"$1\""))
+        .arg("sh")
+        .arg(path)
+        .status()
+        .map_err(|err| format!("Couldn't start {editor}: {err}"))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{editor} exited with {status}"))
+    }
+}
+
+// Check the saved file against the expenses that were opened.
+fn read(tracker: &ExpenseTracker, opened: &[Expense], saved: &str) -> Result<Edited, String> {
+    let document: Document = toml::from_str(saved).map_err(|err| err.to_string())?;
+    let mut changed = Vec::new();
+    let mut seen = Vec::new();
+    for expense in document.expense {
+        let Some(original) = opened.iter().find(|e| e.id == expense.id) else {
+            return Err(format!(
+                "No opened expense has id {}; ids can't be added or changed.",
+                expense.id
+            ));
+        };
+        if seen.contains(&expense.id) {
+            return Err(format!("Expense #{} appears twice.", expense.id));
+        }
+        seen.push(expense.id);
+        if expense == *original {
+            continue;
+        }
+        if expense.category.trim().is_empty() {
+            return Err(format!("Expense #{} needs a category.", expense.id));
+        }
+        // Checked as `add` and `edit` check them
+        if !expense::valid_amount(expense.amount) {
+            return Err(format!("Expense #{} has an invalid amount.", expense.id));
+        }
+        if expense
+            .splits
+            .iter()
+            .any(|s| !expense::valid_amount(s.amount))
+        {
+            return Err(format!(
+                "Expense #{} has a split with an invalid amount.",
+                expense.id
+            ));
+        }
+        if expense
+            .items
+            .iter()
+            .any(|i| !expense::valid_amount(i.quantity) || !expense::valid_amount(i.price))
+        {
+            return Err(format!(
+                "Expense #{} has an item with an invalid quantity or price.",
+                expense.id
+            ));
+        }
+        if !expense.splits_balance() {
+            return Err(format!(
+                "The splits of expense #{} don't add up to its amount.",
+                expense.id
+            ));
+        }
+        if !expense.items_balance() {
+            return Err(format!(
+                "The items of expense #{} don't add up to its amount.",
+                expense.id
+            ));
+        }
+        if tracker.is_month_closed(original.date) || tracker.is_month_closed(expense.date) {
+            return Err(format!("Expense #{} is in a closed month.", expense.id));
+        }
+        changed.push(expense);
+    }
+    let deleted: Vec<u64> = opened
+        .iter()
+        .filter(|e| !seen.contains(&e.id))
+        .map(|e| e.id)
+        .collect();
+    if let Some(expense) = opened
+        .iter()
+        .find(|e| deleted.contains(&e.id) && tracker.is_month_closed(e.date))
+    {
+        return Err(format!("Expense #{} is in a closed month.", expense.id));
+    }
+    Ok(Edited { changed, deleted })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expense::Split;
+    use chrono::NaiveDate;
+
+    fn opened() -> Vec<Expense> {
+        vec![Expense {
+            id: 1,
+            date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            category: String::from("Food"),
+            amount: 100.0,
+            description: String::from("Groceries"),
+            ..Default::default()
+        }]
+    }
+
+    fn saved(expense: Expense) -> String {
+        toml::to_string(&Document {
+            expense: vec![expense],
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn amounts_are_checked_like_add_checks_them() {
+        let tracker = ExpenseTracker::new();
+        let opened = opened();
+        for amount in [0.0, -5.0] {
+            let expense = Expense {
+                amount,
+                ..opened[0].clone()
+            };
+            assert!(read(&tracker, &opened, &saved(expense)).is_err());
+        }
+        let split = Expense {
+            splits: vec![
+                Split {
+                    category: String::from("Food"),
+                    amount: 120.0,
+                },
+                Split {
+                    category: String::from("Home"),
+                    amount: -20.0,
+                },
+            ],
+            ..opened[0].clone()
+        };
+        assert!(read(&tracker, &opened, &saved(split)).is_err());
+        let edited = Expense {
+            amount: 80.0,
+            ..opened[0].clone()
+        };
+        let edited = read(&tracker, &opened, &saved(edited)).unwrap();
+        assert_eq!(edited.changed[0].amount, 80.0);
+        assert!(edited.deleted.is_empty());
+    }
+}
@@ -0,0 +1,98 @@
+use chrono::NaiveDate;
+
+use crate::dates;
+
+// The core fields of a new expense, however they were entered
+pub struct Entry {
+    pub date: NaiveDate,
+    pub category: String,
+    pub amount: f64,
+    pub description: String,
+    pub payee: Option<String>,
+}
+
+fn parse_amount(s: &str) -> Result<f64, String> {
+    match s.trim().parse::<f64>() {
+        Ok(amount) if amount > 0.0 => Ok(amount),
+        _ => Err(format!("'{}' is not a valid amount", s.trim())),
+    }
+}
+
+// Parse the quick-add shorthand: an amount, then the description, with a
+// `#category` and optionally an `@payee` and a `YYYY-MM-DD` date anywhere
+// after it, as in `250 coffee with Sam #food @BlueTokai 2025-07-01`.
+pub fn parse_quick(line: &str, today: NaiveDate) -> Result<Entry, String> {
+    let mut words = line.split_whitespace();
+    let amount = parse_amount(words.next().ok_or("empty line")?)?;
+    let mut category = None;
+    let mut payee = None;
+    let mut date = today;
+    let mut description = Vec::new();
+    for word in words {
+        if let Some(tag) = word.strip_prefix('#').filter(|t| !t.is_empty()) {
+            category = Some(tag.to_string());
+        } else if let Some(name) = word.strip_prefix('@').filter(|n| !n.is_empty()) {
+            payee = Some(name.to_string());
+        } else if let Ok(day) = dates::parse_date(word) {
+            date = day;
+        } else {
+            description.push(word);
+        }
+    }
+    if description.is_empty() {
+        return Err(String::from("missing a description"));
+    }
+    Ok(Entry {
+        date,
+        category: category.ok_or("missing a #category")?,
+        amount,
+        description: description.join(" "),
+        payee,
+    })
+}
+
+// Parse a `date,category,amount,description[,payee]` CSV record; an empty
+// date means `today`.
+pub fn parse_csv(record: &csv::StringRecord, today: NaiveDate) -> Result<Entry, String> {
+    if !(4..=5).contains(&record.len()) {
+        return Err(format!("expected 4 or 5 fields, found {}", record.len()));
+    }
+    let field = |i: usize| record.get(i).unwrap_or("").trim();
+    let required = |i: usize, name: &str| {
+        let value = field(i);
+        if value.is_empty() {
+            Err(format!("missing a {name}"))
+        } else {
+            Ok(value.to_string())
+        }
+    };
+    Ok(Entry {
+        date: if field(0).is_empty() {
+            today
+        } else {
+            dates::parse_date(field(0))?
+        },
+        category: required(1, "category")?,
+        amount: parse_amount(field(2))?,
+        description: required(3, "description")?,
+        payee: Some(field(4)).filter(|p| !p.is_empty()).map(String::from),
+    })
+}
+
+// Parse one line of batch input: CSV when it has commas and starts with a
+// date or an empty date field, the quick-add shorthand otherwise.
+pub fn parse_line(line: &str, today: NaiveDate) -> Result<Entry, String> {
+    let first = line.split(',').next().unwrap_or("").trim();
+    if line.contains(',') && (first.is_empty() || dates::parse_date(first).is_ok()) {
+        let record = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader(line.as_bytes())
+            .records()
+            .next()
+            .ok_or("empty line")?
+            .map_err(|err| err.to_string())?;
+        parse_csv(&record, today)
+    } else {
+        parse_quick(line, today)
+    }
+}
@@ -0,0 +1,24 @@
+// Exit codes, so scripts can tell failures apart. Errors go to standard
+// error and everything else to standard output.
+
+use std::io;
+
+// Anything not covered below, such as a failed network request
+pub const FAILURE: i32 = 1;
+// Arguments that can't work together or are missing; clap uses this too
+pub const USAGE: i32 = 2;
+// The ledger, config file or an input file can't be read, parsed or saved
+pub const DATA: i32 = 3;
+// The expense, row, name or backup asked for doesn't exist
+pub const NOT_FOUND: i32 = 4;
+// The change clashes with the ledger: a closed month, a name already taken
+pub const CONFLICT: i32 = 5;
+
+// NOT_FOUND for a missing file or backup, DATA for anything else that
+// went wrong reading or writing one.
+pub fn of(err: &io::Error) -> i32 {
+    match err.kind() {
+        io::ErrorKind::NotFound => NOT_FOUND,
+        _ => DATA,
+    }
+}
@@ -0,0 +1,636 @@
+use crate::asset::Account;
+use crate::budget::Budget;
+use crate::crypto;
+use crate::currency;
+use crate::dates::month_key;
+use crate::debt::Debt;
+use crate::filter::{Filter, View};
+use crate::goal::Goal;
+use crate::i18n;
+use crate::loan::Loan;
+use crate::locale;
+use crate::logging;
+use crate::migrate;
+use crate::rates::Rate;
+use crate::reimburse;
+use crate::shared::Settlement;
+use crate::subscription::Subscription;
+use crate::sync::Conflict;
+use crate::template::Template;
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveTime};
+use serde::{Deserialize, Serialize};
+use serde_json::Result;
+use serde_json::value::RawValue;
+use std::{
+    collections::BTreeMap,
+    fmt,
+    io::{self},
+    path::Path,
+};
+use tracing::{debug, info};
+
+// Define Expense struct
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct Expense {
+    // Stable identifier, assigned when the expense is first stored
+    #[serde(default)]
+    pub id: u64,
+    pub date: NaiveDate,
+    // Time of day the expense was made, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time: Option<NaiveTime>,
+    // UTC offset where the expense was made, along with its time
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::dates::offset"
+    )]
+    pub offset: Option<FixedOffset>,
+    pub category: String,
+    pub amount: f64,
+    // Code of the currency `amount` is in, when it isn't the ledger's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payee: Option<String>,
+    // The trip or place the expense was logged under, such as goa-2025
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trip: Option<String>,
+    // The client project the expense is billed to, such as acme-website
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    // The member of the household who spent it, such as alice
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    // Fields of the user's own, such as warranty_until = 2026-03-01
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub meta: BTreeMap<String, String>,
+    // Category legs of a split expense; they sum to `amount`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub splits: Vec<Split>,
+    // What was bought, for an itemized bill; the items sum to `amount`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<Item>,
+    // People this expense was shared equally with, besides the owner
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shared_with: Vec<String>,
+    // Who paid for a shared expense, when it wasn't the owner
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paid_by: Option<String>,
+    // Tracked in totals but hidden by `--mask-private`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub private: bool,
+    // File names of receipts copied into the attachments directory
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<String>,
+    // Counts towards the year-end tax report
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deductible: bool,
+    // A cost of running a business rather than a personal one
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub business: bool,
+    // Claim status of an expense fronted for someone else to repay
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reimbursement: Option<reimburse::Status>,
+    // When the expense was last added or changed, to tell which copy is
+    // newer when merging ledgers from two machines
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified: Option<DateTime<Local>>,
+}
+
+// One (category, amount) leg of a split expense
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Split {
+    pub category: String,
+    pub amount: f64,
+}
+
+// One line of an itemized bill
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Item {
+    pub name: String,
+    pub quantity: f64,
+    // The price of one
+    pub price: f64,
+}
+
+impl Item {
+    pub fn amount(&self) -> f64 {
+        self.quantity * self.price
+    }
+}
+
+// Parse a `name=price` or `name=QUANTITYxPRICE` item, for use as a clap
+// value parser.
+pub fn parse_item(s: &str) -> std::result::Result<Item, String> {
+    let (name, cost) = s
+        .rsplit_once('=')
+        .ok_or_else(|| format!("'{s}' should be formatted as name=price or name=2x45"))?;
+    let number = |text: &str| {
+        text.trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|n| valid_amount(*n))
+            .ok_or_else(|| format!("'{}' is not a valid amount", text.trim()))
+    };
+    let (quantity, price) = match cost.split_once(['x', '*']) {
+        Some((quantity, price)) => (number(quantity)?, number(price)?),
+        None => (1.0, number(cost)?),
+    };
+    if name.trim().is_empty() {
+        return Err(format!("'{s}' needs a name"));
+    }
+    Ok(Item {
+        name: name.trim().to_string(),
+        quantity,
+        price,
+    })
+}
+
+// Parse a `category=amount` split leg, for use as a clap value parser. Leg
+// amounts are checked like `parse_amount`'s.
+pub fn parse_split(s: &str) -> std::result::Result<Split, String> {
+    let (category, amount) = s
+        .rsplit_once('=')
+        .ok_or_else(|| format!("'{s}' should be formatted as category=amount"))?;
+    if category.trim().is_empty() {
+        return Err(format!("'{s}' needs a category"));
+    }
+    let amount = parse_amount(amount)?;
+    Ok(Split {
+        category: category.trim().to_string(),
+        amount,
+    })
+}
+
+// Parse an amount above zero, for use as a clap value parser.
+pub fn parse_amount(s: &str) -> std::result::Result<f64, String> {
+    s.trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|n| valid_amount(*n))
+        .ok_or_else(|| format!("'{s}' is not a valid amount"))
+}
+
+// Whether `amount` may be saved as an amount or price: above zero and
+// finite. Infinite and NaN amounts would be saved as null and stop the
+// ledger from loading.
+pub fn valid_amount(amount: f64) -> bool {
+    amount > 0.0 && amount.is_finite()
+}
+
+// Parse an amount that may also be zero, such as a balance or an interest
+// rate, for use as a clap value parser.
+pub fn parse_non_negative(s: &str) -> std::result::Result<f64, String> {
+    s.trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|n| *n >= 0.0 && n.is_finite())
+        .ok_or_else(|| format!("'{s}' should be a number, zero or more"))
+}
+
+// Parse a `key=value` field of the user's own, for use as a clap value
+// parser.
+pub fn parse_meta(s: &str) -> std::result::Result<(String, String), String> {
+    let (key, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("'{s}' should be formatted as key=value"))?;
+    let (key, value) = (key.trim(), value.trim());
+    if key.is_empty() || value.is_empty() {
+        return Err(format!("'{s}' needs both a key and a value"));
+    }
+    Ok((key.to_string(), value.to_string()))
+}
+
+impl Expense {
+    // The value of the field of the user's own named `key`, in any case.
+    pub fn meta(&self, key: &str) -> Option<&str> {
+        self.meta
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value.as_str())
+    }
+
+    // The amount this expense contributes to each category, honouring splits.
+    pub fn category_amounts(&self) -> Vec<(&str, f64)> {
+        if self.splits.is_empty() {
+            vec![(self.category.as_str(), self.amount)]
+        } else {
+            self.splits
+                .iter()
+                .map(|s| (s.category.as_str(), s.amount))
+                .collect()
+        }
+    }
+
+    // Whether this counts towards personal spending; reimbursed expenses
+    // were paid back and no longer do.
+    pub fn is_personal(&self) -> bool {
+        self.reimbursement != Some(reimburse::Status::Reimbursed)
+    }
+
+    // Whether the split legs add up to the total amount.
+    pub fn splits_balance(&self) -> bool {
+        self.splits.is_empty()
+            || (self.splits.iter().map(|s| s.amount).sum::<f64>() - self.amount).abs() < 0.005
+    }
+
+    // Whether the items add up to the total amount.
+    pub fn items_balance(&self) -> bool {
+        self.items.is_empty()
+            || (self.items.iter().map(Item::amount).sum::<f64>() - self.amount).abs() < 0.005
+    }
+}
+
+// Define the structure of the JSON data file
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ExpenseTracker {
+    // Format version of the data file; see `migrate`
+    #[serde(default)]
+    pub version: u32,
+    pub expenses: Vec<Expense>,
+    // Code of the currency the amounts are in, such as `INR`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    // The id the next stored expense receives
+    #[serde(default)]
+    pub next_id: u64,
+    // Months locked by `close-month`, as `YYYY-MM` keys
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub closed_months: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub settlements: Vec<Settlement>,
+    // Money lent to and borrowed from people outside shared expenses
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub debts: Vec<Debt>,
+    // Balances of accounts and liabilities, for net worth
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub accounts: Vec<Account>,
+    // Categories set up ahead of use, offered when adding interactively
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<String>,
+    // The bucket each category rolls up into, such as needs or wants
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub buckets: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub budgets: Vec<Budget>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub goals: Vec<Goal>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subscriptions: Vec<Subscription>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub loans: Vec<Loan>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub templates: Vec<Template>,
+    // Filters saved by name
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub views: Vec<View>,
+    // Years moved out to archive files by `archive`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub archived_years: Vec<i32>,
+    // Exchange rates into the ledger's currency
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rates: Vec<Rate>,
+    // Expenses changed differently on two machines, for `sync resolve`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conflicts: Vec<Conflict>,
+}
+
+impl ExpenseTracker {
+    pub fn new() -> ExpenseTracker {
+        ExpenseTracker {
+            version: migrate::CURRENT,
+            expenses: Vec::new(),
+            currency: None,
+            next_id: 1,
+            closed_months: Vec::new(),
+            settlements: Vec::new(),
+            debts: Vec::new(),
+            accounts: Vec::new(),
+            categories: Vec::new(),
+            buckets: BTreeMap::new(),
+            budgets: Vec::new(),
+            goals: Vec::new(),
+            subscriptions: Vec::new(),
+            loans: Vec::new(),
+            rates: Vec::new(),
+            templates: Vec::new(),
+            views: Vec::new(),
+            archived_years: Vec::new(),
+            conflicts: Vec::new(),
+        }
+    }
+
+    pub fn is_month_closed(&self, date: NaiveDate) -> bool {
+        self.closed_months.contains(&month_key(date))
+    }
+
+    pub fn close_month(&mut self, month: NaiveDate) {
+        if !self.is_month_closed(month) {
+            self.closed_months.push(month_key(month));
+            self.closed_months.sort();
+        }
+    }
+
+    pub fn reopen_month(&mut self, month: NaiveDate) {
+        let key = month_key(month);
+        self.closed_months.retain(|m| *m != key);
+    }
+
+    pub fn add_expense(&mut self, mut expense: Expense) {
+        if expense.id == 0 {
+            expense.id = self.next_id;
+        }
+        self.next_id = self.next_id.max(expense.id + 1);
+        self.expenses.push(expense);
+    }
+
+    pub fn find_by_id(&self, id: u64) -> Option<&Expense> {
+        self.expenses.iter().find(|e| e.id == id)
+    }
+
+    // The most recent expense with the same description, ignoring case and
+    // surrounding whitespace.
+    pub fn find_similar(&self, description: &str) -> Option<&Expense> {
+        let description = description.trim();
+        self.expenses
+            .iter()
+            .filter(|e| e.description.trim().eq_ignore_ascii_case(description))
+            .max_by_key(|e| (e.date, e.id))
+    }
+
+    pub fn delete_expense(&mut self, row_number: usize) -> Expense {
+        if self.expenses.is_empty() {
+            println!("{}", i18n::t("no-expenses"));
+        }
+        self.expenses.remove(row_number - 1)
+    }
+
+    // Expenses that count towards personal spending totals.
+    pub fn personal_expenses(&self) -> impl Iterator<Item = &Expense> {
+        self.expenses.iter().filter(|e| e.is_personal())
+    }
+
+    // Total of the personal expenses matching `filter`.
+    pub fn summary(&self, filter: &Filter) -> f64 {
+        self.personal_expenses()
+            .filter(|e| filter.matches(e))
+            .fold(0.0, |sum, e| sum + filter.amount_of(e))
+    }
+
+    pub fn save_to_json(&self, filename: &str) -> io::Result<()> {
+        let _timer = logging::time("save");
+        let json = serde_json::to_vec(&self)?;
+        crypto::write(Path::new(filename), &json)?;
+        info!(
+            file = filename,
+            expenses = self.expenses.len(),
+            bytes = json.len(),
+            "saved ledger"
+        );
+        Ok(())
+    }
+
+    pub fn load_from_json(filename: &str) -> Result<ExpenseTracker> {
+        let _timer = logging::time("load");
+        let path = Path::new(filename);
+        if !path.exists() {
+            info!(
+                file = filename,
+                "no data file yet; starting an empty ledger"
+            );
+            return Ok(ExpenseTracker::new());
+        }
+        let bytes = crypto::read(path).map_err(serde_json::Error::io)?;
+        debug!(
+            file = filename,
+            bytes = bytes.len(),
+            encrypted = crypto::is_active(),
+            "read data file"
+        );
+        // Older files are upgraded in memory and written back on the next save.
+        let mut value: serde_json::Value = serde_json::from_slice(&bytes)?;
+        let version = migrate::upgrade(&mut value).map_err(serde::de::Error::custom)?;
+        if version != migrate::CURRENT {
+            info!(
+                from = version,
+                to = migrate::CURRENT,
+                "upgraded data file in memory"
+            );
+        }
+        let tracker: ExpenseTracker = serde_json::from_value(value)?;
+        info!(
+            file = filename,
+            expenses = tracker.expenses.len(),
+            "loaded ledger"
+        );
+        Ok(tracker)
+    }
+
+    // The ledger in `filename` without its expenses, for commands such as
+    // `add` that only need its settings and next id. The expenses are
+    // skipped over rather than parsed. Files from before versioning are
+    // loaded in full, since their next id comes from the expenses.
+    pub fn load_metadata_from_json(filename: &str) -> Result<ExpenseTracker> {
+        let _timer = logging::time("load metadata");
+        let path = Path::new(filename);
+        if !path.exists() {
+            return Ok(ExpenseTracker::new());
+        }
+        let bytes = crypto::read(path).map_err(serde_json::Error::io)?;
+        let fields: BTreeMap<String, &RawValue> = serde_json::from_slice(&bytes)?;
+        let version = fields
+            .get("version")
+            .and_then(|v| v.get().parse::<u32>().ok())
+            .unwrap_or(0);
+        if version != migrate::CURRENT {
+            return ExpenseTracker::load_from_json(filename);
+        }
+        let mut file = serde_json::Map::new();
+        for (key, raw) in fields {
+            if key != "expenses" {
+                file.insert(key, serde_json::from_str(raw.get())?);
+            }
+        }
+        file.insert(
+            String::from("expenses"),
+            serde_json::Value::Array(Vec::new()),
+        );
+        let tracker: ExpenseTracker = serde_json::from_value(serde_json::Value::Object(file))?;
+        debug!(file = filename, "loaded ledger settings");
+        Ok(tracker)
+    }
+
+    pub fn print_all_expenses(&self, mask_private: bool) {
+        for (i, expense) in self.expenses.iter().enumerate() {
+            println!("{}. {}", i + 1, expense.line(mask_private));
+        }
+    }
+}
+
+// Placeholder shown instead of private details in masked output
+pub const MASK: &str = "•••";
+
+// An expense formatted for output, optionally hiding private details
+pub struct ExpenseLine<'a> {
+    pub expense: &'a Expense,
+    pub mask: bool,
+}
+
+impl Expense {
+    pub fn line(&self, mask_private: bool) -> ExpenseLine<'_> {
+        ExpenseLine {
+            expense: self,
+            mask: mask_private && self.private,
+        }
+    }
+}
+
+impl fmt::Display for Expense {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.line(false).fmt(f)
+    }
+}
+
+impl fmt::Display for ExpenseLine<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let expense = self.expense;
+        let amount = |amount: f64| {
+            if self.mask {
+                MASK.to_string()
+            } else {
+                currency::amount_in(expense.currency.as_deref(), amount)
+            }
+        };
+        write!(
+            f,
+            "#{} {}: {}{}, {}: {}, {}: {}, {}: {}",
+            expense.id,
+            i18n::t("label-date"),
+            locale::date(expense.date),
+            match (expense.time, expense.offset) {
+                (Some(time), Some(offset)) => format!(" {} {offset}", time.format("%H:%M")),
+                (Some(time), None) => time.format(" %H:%M").to_string(),
+                _ => String::new(),
+            },
+            i18n::t("label-category"),
+            expense.category,
+            i18n::t("label-amount"),
+            amount(expense.amount),
+            i18n::t("label-description"),
+            if self.mask {
+                MASK
+            } else {
+                &expense.description
+            },
+        )?;
+        if !expense.splits.is_empty() {
+            let legs: Vec<String> = expense
+                .splits
+                .iter()
+                .map(|s| format!("{} {}", s.category, amount(s.amount)))
+                .collect();
+            write!(f, " [{}]", legs.join(", "))?;
+        }
+        if let Some(payee) = &expense.payee {
+            write!(
+                f,
+                ", {}: {}",
+                i18n::t("label-payee"),
+                if self.mask { MASK } else { payee }
+            )?;
+        }
+        if let Some(trip) = &expense.trip {
+            write!(f, ", Trip: {}", trip)?;
+        }
+        if let Some(project) = &expense.project {
+            write!(f, ", Project: {}", project)?;
+        }
+        if let Some(user) = &expense.user {
+            write!(f, ", User: {}", user)?;
+        }
+        if !expense.tags.is_empty() {
+            write!(f, ", Tags: {}", expense.tags.join(", "))?;
+        }
+        if !expense.meta.is_empty() {
+            let fields: Vec<String> = expense
+                .meta
+                .iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect();
+            write!(f, ", {}", fields.join(", "))?;
+        }
+        if !expense.shared_with.is_empty() {
+            write!(f, ", Shared with: {}", expense.shared_with.join(", "))?;
+        }
+        if let Some(paid_by) = &expense.paid_by {
+            write!(f, ", Paid by: {}", paid_by)?;
+        }
+        if !expense.items.is_empty() {
+            write!(f, ", Items: {}", expense.items.len())?;
+        }
+        if !expense.attachments.is_empty() {
+            write!(f, ", Attachments: {}", expense.attachments.len())?;
+        }
+        if let Some(status) = expense.reimbursement {
+            write!(f, ", Reimbursement: {}", status)?;
+        }
+        if expense.business {
+            write!(f, " (business)")?;
+        }
+        if expense.deductible {
+            write!(f, " (deductible)")?;
+        }
+        if expense.private && !self.mask {
+            write!(f, " (private)")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_legs_take_a_category_and_an_amount() {
+        let split = parse_split(" Groceries = 300.5 ").unwrap();
+        assert_eq!(split.category, "Groceries");
+        assert_eq!(split.amount, 300.5);
+    }
+
+    #[test]
+    fn split_legs_refuse_amounts_that_are_not_positive_and_finite() {
+        for leg in [
+            "Food=0", "Food=-20", "Food=inf", "Food=NaN", "Food=abc", "Food", "=20",
+        ] {
+            assert!(parse_split(leg).is_err(), "{leg} should be refused");
+        }
+    }
+
+    #[test]
+    fn amounts_are_positive_and_finite() {
+        assert_eq!(parse_amount("12.50"), Ok(12.5));
+        for amount in ["0", "-1", "inf", "-inf", "NaN", ""] {
+            assert!(parse_amount(amount).is_err(), "{amount} should be refused");
+        }
+        assert_eq!(parse_non_negative("0"), Ok(0.0));
+        assert!(parse_non_negative("-0.5").is_err());
+        assert!(parse_non_negative("inf").is_err());
+    }
+
+    #[test]
+    fn items_take_a_price_or_a_quantity_and_price() {
+        let item = parse_item("Milk=2x45").unwrap();
+        assert_eq!(
+            (item.name.as_str(), item.quantity, item.price),
+            ("Milk", 2.0, 45.0)
+        );
+        assert_eq!(parse_item("Bread=40").unwrap().amount(), 40.0);
+        assert!(parse_item("Bread=0").is_err());
+        assert!(parse_item("=40").is_err());
+    }
+}
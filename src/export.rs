@@ -0,0 +1,363 @@
+use crate::expense::Expense;
+use chrono::NaiveDate;
+use parquet::{
+    basic::Compression,
+    data_type::{BoolType, ByteArray, ByteArrayType, DataType, DoubleType, Int32Type, Int64Type},
+    errors::ParquetError,
+    file::{
+        properties::WriterProperties,
+        writer::{SerializedFileWriter, SerializedRowGroupWriter},
+    },
+    schema::parser::parse_message_type,
+};
+use rust_xlsxwriter::{Format, Workbook, XlsxError};
+use std::{fmt::Write, sync::Arc};
+
+// Journal entries in the plain-text format shared by ledger and hledger.
+// Each expense debits `Expenses:<Category>` (one posting per split leg) and
+// credits `funding_account`.
+pub fn ledger(expenses: &[&Expense], currency: &str, funding_account: &str) -> String {
+    let mut out = String::new();
+    for expense in expenses {
+        let description = match &expense.payee {
+            Some(payee) => format!("{} | {}", payee.trim(), expense.description.trim()),
+            None => expense.description.trim().to_string(),
+        };
+        let _ = writeln!(out, "{} {}", expense.date, description);
+        for (category, amount) in expense.category_amounts() {
+            let _ = writeln!(
+                out,
+                "    {:<38}  {:.2} {}",
+                format!("Expenses:{}", ledger_account(category)),
+                amount,
+                currency
+            );
+        }
+        let _ = writeln!(out, "    {}", funding_account);
+        out.push('\n');
+    }
+    out
+}
+
+// Make a category usable as a ledger account segment: no `:` separators and
+// no runs of spaces, which ledger reads as the end of the account name.
+fn ledger_account(category: &str) -> String {
+    let name = category
+        .replace(':', "-")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if name.is_empty() {
+        String::from("Uncategorized")
+    } else {
+        name
+    }
+}
+
+// A beancount ledger, with an `open` directive for every account used so the
+// output passes `bean-check` as-is.
+pub fn beancount(expenses: &[&Expense], currency: &str, funding_account: &str) -> String {
+    let currency = beancount_currency(currency);
+    let funding_account = funding_account
+        .split(':')
+        .map(beancount_component)
+        .collect::<Vec<_>>()
+        .join(":");
+
+    let mut accounts = vec![funding_account.clone()];
+    for expense in expenses {
+        for (category, _) in expense.category_amounts() {
+            let account = format!("Expenses:{}", beancount_component(category));
+            if !accounts.contains(&account) {
+                accounts.push(account);
+            }
+        }
+    }
+
+    let mut out = String::new();
+    let _ = writeln!(out, "option \"operating_currency\" \"{}\"\n", currency);
+    if let Some(opened) = expenses.iter().map(|e| e.date).min() {
+        for account in accounts.iter() {
+            let _ = writeln!(out, "{} open {} {}", opened, account, currency);
+        }
+        out.push('\n');
+    }
+    for expense in expenses {
+        let _ = match &expense.payee {
+            Some(payee) => writeln!(
+                out,
+                "{} * \"{}\" \"{}\"",
+                expense.date,
+                beancount_string(payee),
+                beancount_string(&expense.description)
+            ),
+            None => writeln!(
+                out,
+                "{} * \"{}\"",
+                expense.date,
+                beancount_string(&expense.description)
+            ),
+        };
+        for (category, amount) in expense.category_amounts() {
+            let _ = writeln!(
+                out,
+                "  {:<38}  {:.2} {}",
+                format!("Expenses:{}", beancount_component(category)),
+                amount,
+                currency
+            );
+        }
+        let _ = writeln!(out, "  {}", funding_account);
+        out.push('\n');
+    }
+    out
+}
+
+// Beancount account components start with a capital letter or digit and may
+// only contain letters, digits and dashes: "home stuff" becomes "Home-Stuff".
+fn beancount_component(name: &str) -> String {
+    let words: Vec<String> = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            let first = chars.next().expect("words are non-empty");
+            first.to_ascii_uppercase().to_string() + chars.as_str()
+        })
+        .collect();
+    if words.is_empty() {
+        String::from("Uncategorized")
+    } else {
+        words.join("-")
+    }
+}
+
+fn beancount_currency(currency: &str) -> String {
+    let code: String = currency
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_ascii_uppercase();
+    if code.is_empty() {
+        String::from("INR")
+    } else {
+        code
+    }
+}
+
+fn beancount_string(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// A CSV in the layout YNAB's file import reads: Date, Payee, Memo, Outflow
+// and Inflow. Expenses are all outflows.
+pub fn ynab(expenses: &[&Expense]) -> Result<String, csv::Error> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(["Date", "Payee", "Memo", "Outflow", "Inflow"])?;
+    for expense in expenses {
+        writer.write_record([
+            expense.date.to_string(),
+            expense.payee.clone().unwrap_or_default(),
+            expense.description.clone(),
+            format!("{:.2}", expense.amount),
+            String::new(),
+        ])?;
+    }
+    let bytes = writer.into_inner().map_err(|err| err.into_error())?;
+    Ok(String::from_utf8(bytes).expect("CSV of strings is valid UTF-8"))
+}
+
+// A workbook with the expenses on one sheet, with dates and amounts as
+// typed cells, and a sheet of monthly totals per category.
+pub fn xlsx(expenses: &[&Expense], currency: &str) -> Result<Vec<u8>, XlsxError> {
+    let mut workbook = Workbook::new();
+    let bold = Format::new().set_bold();
+    let date = Format::new().set_num_format("yyyy-mm-dd");
+    let money = Format::new().set_num_format("#,##0.00");
+    let money_bold = Format::new().set_num_format("#,##0.00").set_bold();
+
+    let sheet = workbook.add_worksheet().set_name("Expenses")?;
+    let headers = [
+        "Id",
+        "Date",
+        "Category",
+        "Description",
+        "Payee",
+        &format!("Amount ({currency})"),
+        "Tags",
+    ]
+    .map(str::to_string);
+    for (col, header) in (0..).zip(headers.iter()) {
+        sheet.write_string_with_format(0, col, header, &bold)?;
+    }
+    for (row, expense) in (1..).zip(expenses) {
+        sheet.write_number(row, 0, expense.id as f64)?;
+        sheet.write_datetime_with_format(row, 1, expense.date, &date)?;
+        sheet.write_string(row, 2, &expense.category)?;
+        sheet.write_string(row, 3, &expense.description)?;
+        if let Some(payee) = &expense.payee {
+            sheet.write_string(row, 4, payee)?;
+        }
+        sheet.write_number_with_format(row, 5, expense.amount, &money)?;
+        sheet.write_string(row, 6, expense.tags.join(", "))?;
+    }
+    sheet.set_freeze_panes(1, 0)?;
+    sheet.autofilter(0, 0, expenses.len() as u32, 6)?;
+    for (col, width) in [(1, 11), (2, 16), (3, 36), (4, 20), (5, 14), (6, 20)] {
+        sheet.set_column_width(col, width)?;
+    }
+
+    // Categories down the side and months across, with totals for both.
+    let mut months: Vec<String> = expenses
+        .iter()
+        .map(|e| e.date.format("%Y-%m").to_string())
+        .collect();
+    months.sort();
+    months.dedup();
+    let mut categories: Vec<&str> = expenses
+        .iter()
+        .flat_map(|e| e.category_amounts())
+        .map(|(category, _)| category)
+        .collect();
+    categories.sort_by_key(|c| c.to_lowercase());
+    categories.dedup();
+    let amount = |category: &str, month: &str| {
+        expenses
+            .iter()
+            .filter(|e| e.date.format("%Y-%m").to_string() == month)
+            .flat_map(|e| e.category_amounts())
+            .filter(|(c, _)| *c == category)
+            .fold(0.0, |sum, (_, amount)| sum + amount)
+    };
+
+    let sheet = workbook.add_worksheet().set_name("By month")?;
+    let total_col = months.len() as u16 + 1;
+    let total_row = categories.len() as u32 + 1;
+    sheet.write_string_with_format(0, 0, "Category", &bold)?;
+    for (col, month) in (1..).zip(months.iter()) {
+        sheet.write_string_with_format(0, col, month, &bold)?;
+    }
+    sheet.write_string_with_format(0, total_col, "Total", &bold)?;
+    let mut month_totals = vec![0.0; months.len()];
+    for (row, category) in (1..).zip(categories.iter()) {
+        sheet.write_string(row, 0, *category)?;
+        let mut category_total = 0.0;
+        for ((col, month), month_total) in (1..).zip(months.iter()).zip(month_totals.iter_mut()) {
+            let amount = amount(category, month);
+            category_total += amount;
+            *month_total += amount;
+            if amount != 0.0 {
+                sheet.write_number_with_format(row, col, amount, &money)?;
+            }
+        }
+        sheet.write_number_with_format(row, total_col, category_total, &money_bold)?;
+    }
+    sheet.write_string_with_format(total_row, 0, "Total", &bold)?;
+    for (col, total) in (1..).zip(month_totals.iter()) {
+        sheet.write_number_with_format(total_row, col, *total, &money_bold)?;
+    }
+    let total: f64 = month_totals.iter().sum();
+    sheet.write_number_with_format(total_row, total_col, total, &money_bold)?;
+    sheet.set_freeze_panes(1, 1)?;
+    sheet.set_column_width(0, 18)?;
+
+    workbook.save_to_buffer()
+}
+
+// Columns of the Parquet export, one row per expense
+const PARQUET_SCHEMA: &str = "
+message expense {
+    REQUIRED INT64 id (INTEGER(64, false));
+    REQUIRED INT32 date (DATE);
+    REQUIRED BYTE_ARRAY category (STRING);
+    REQUIRED BYTE_ARRAY description (STRING);
+    OPTIONAL BYTE_ARRAY payee (STRING);
+    REQUIRED DOUBLE amount;
+    REQUIRED BYTE_ARRAY currency (STRING);
+    REQUIRED group tags (LIST) {
+        REPEATED group list {
+            REQUIRED BYTE_ARRAY element (STRING);
+        }
+    }
+    REQUIRED BOOLEAN private;
+    REQUIRED BOOLEAN deductible;
+}
+";
+
+// Write the next column of `row_group`, with the definition and repetition
+// levels its optional and repeated fields need.
+fn parquet_column<T: DataType>(
+    row_group: &mut SerializedRowGroupWriter<Vec<u8>>,
+    values: &[T::T],
+    defined: Option<&[i16]>,
+    repeated: Option<&[i16]>,
+) -> Result<(), ParquetError> {
+    let mut column = row_group.next_column()?.expect("a column per schema field");
+    column.typed::<T>().write_batch(values, defined, repeated)?;
+    column.close()
+}
+
+// A Snappy-compressed Parquet file of the expenses, with typed dates and
+// amounts, that pandas, polars and DuckDB read as they are.
+pub fn parquet(expenses: &[&Expense], currency: &str) -> Result<Vec<u8>, ParquetError> {
+    let schema = Arc::new(parse_message_type(PARQUET_SCHEMA)?);
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    let mut writer = SerializedFileWriter::new(Vec::new(), schema, Arc::new(properties))?;
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).expect("valid date");
+    let strings = |field: fn(&Expense) -> &str| -> Vec<ByteArray> {
+        expenses.iter().map(|e| ByteArray::from(field(e))).collect()
+    };
+
+    // Only present payees are written; the levels mark the missing ones.
+    let payees: Vec<ByteArray> = expenses
+        .iter()
+        .filter_map(|e| e.payee.as_deref().map(ByteArray::from))
+        .collect();
+    let payees_defined: Vec<i16> = expenses.iter().map(|e| e.payee.is_some() as i16).collect();
+    // An expense without tags is one undefined entry; each tag after an
+    // expense's first repeats its list.
+    let mut tags = Vec::new();
+    let mut tags_defined = Vec::new();
+    let mut tags_repeated = Vec::new();
+    for expense in expenses {
+        if expense.tags.is_empty() {
+            tags_defined.push(0);
+            tags_repeated.push(0);
+        }
+        for (i, tag) in expense.tags.iter().enumerate() {
+            tags.push(ByteArray::from(tag.as_str()));
+            tags_defined.push(1);
+            tags_repeated.push((i > 0) as i16);
+        }
+    }
+
+    let mut row_group = writer.next_row_group()?;
+    let ids: Vec<i64> = expenses.iter().map(|e| e.id as i64).collect();
+    parquet_column::<Int64Type>(&mut row_group, &ids, None, None)?;
+    let dates: Vec<i32> = expenses
+        .iter()
+        .map(|e| (e.date - epoch).num_days() as i32)
+        .collect();
+    parquet_column::<Int32Type>(&mut row_group, &dates, None, None)?;
+    parquet_column::<ByteArrayType>(&mut row_group, &strings(|e| &e.category), None, None)?;
+    parquet_column::<ByteArrayType>(&mut row_group, &strings(|e| &e.description), None, None)?;
+    parquet_column::<ByteArrayType>(&mut row_group, &payees, Some(&payees_defined), None)?;
+    let amounts: Vec<f64> = expenses.iter().map(|e| e.amount).collect();
+    parquet_column::<DoubleType>(&mut row_group, &amounts, None, None)?;
+    let currencies = vec![ByteArray::from(currency); expenses.len()];
+    parquet_column::<ByteArrayType>(&mut row_group, &currencies, None, None)?;
+    parquet_column::<ByteArrayType>(
+        &mut row_group,
+        &tags,
+        Some(&tags_defined),
+        Some(&tags_repeated),
+    )?;
+    let private: Vec<bool> = expenses.iter().map(|e| e.private).collect();
+    parquet_column::<BoolType>(&mut row_group, &private, None, None)?;
+    let deductible: Vec<bool> = expenses.iter().map(|e| e.deductible).collect();
+    parquet_column::<BoolType>(&mut row_group, &deductible, None, None)?;
+    row_group.close()?;
+    writer.into_inner()
+}
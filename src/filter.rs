@@ -0,0 +1,315 @@
+use chrono::{Datelike, NaiveDate, Timelike};
+use clap::ArgMatches;
+use serde::{Deserialize, Serialize};
+
+use crate::dates;
+use crate::expense::Expense;
+
+// Conditions an expense must meet to be included; unset ones match
+// everything. Commands build one from the shared filter flags.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Filter {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<NaiveDate>,
+    // A month number, in any year
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub month: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    // Every one of these tags must be present
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payee: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trip: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    // Fields of the user's own the expense must have, as KEY or KEY=VALUE
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub meta: Vec<String>,
+    // Only business expenses when true, only personal ones when false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub business: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_amount: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_amount: Option<f64>,
+    // Expenses without a time of day never match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hours: Option<Hours>,
+}
+
+// Hours of the day from `start` up to `end`, running past midnight when
+// `end` comes first, so 22-4 is late at night
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Hours {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl Hours {
+    pub fn contains(self, hour: u32) -> bool {
+        if self.start <= self.end {
+            (self.start..self.end).contains(&hour)
+        } else {
+            hour >= self.start || hour < self.end
+        }
+    }
+}
+
+// Parse an hour range such as `22-4`, for use as a clap value parser.
+pub fn parse_hours(s: &str) -> Result<Hours, String> {
+    let invalid = || format!("'{s}' should be two hours from 0 to 24 (for example, 22-4)");
+    let (start, end) = s.split_once('-').ok_or_else(invalid)?;
+    let hour = |h: &str| h.trim().parse::<u32>().ok().filter(|h| *h <= 24);
+    match (hour(start), hour(end)) {
+        (Some(start), Some(end)) if start != end => Ok(Hours {
+            start: start % 24,
+            end: end % 24,
+        }),
+        _ => Err(invalid()),
+    }
+}
+
+// `business` or `personal`, for `--only`; true for business.
+pub fn parse_class(s: &str) -> Result<bool, String> {
+    match s.trim().to_lowercase().as_str() {
+        "business" => Ok(true),
+        "personal" => Ok(false),
+        _ => Err(format!("'{s}' should be business or personal")),
+    }
+}
+
+// A filter saved under a name with `view save`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct View {
+    pub name: String,
+    #[serde(flatten)]
+    pub filter: Filter,
+}
+
+impl Filter {
+    // Read whichever of the shared filter flags the command defines.
+    pub fn from_matches(matches: &ArgMatches) -> Filter {
+        let get = |id: &str| matches.try_get_one::<String>(id).ok().flatten().cloned();
+        let get_date = |id: &str| matches.try_get_one::<NaiveDate>(id).ok().flatten().copied();
+        let get_amount = |id: &str| matches.try_get_one::<f64>(id).ok().flatten().copied();
+        // --after and --before exclude the day itself
+        Filter {
+            from: get_date("from").or(get_date("after").and_then(|d| d.succ_opt())),
+            to: get_date("to").or(get_date("before").and_then(|d| d.pred_opt())),
+            month: matches.try_get_one::<u8>("month").ok().flatten().copied(),
+            category: get("category"),
+            tags: matches
+                .try_get_many::<String>("tag")
+                .ok()
+                .flatten()
+                .map(|tags| tags.cloned().collect())
+                .unwrap_or_default(),
+            payee: get("payee"),
+            trip: get("trip"),
+            project: get("project"),
+            user: get("user"),
+            meta: matches
+                .try_get_many::<String>("meta")
+                .ok()
+                .flatten()
+                .map(|fields| fields.cloned().collect())
+                .unwrap_or_default(),
+            business: matches.try_get_one::<bool>("only").ok().flatten().copied(),
+            min_amount: get_amount("min_amount"),
+            max_amount: get_amount("max_amount"),
+            hours: matches
+                .try_get_one::<Hours>("hours")
+                .ok()
+                .flatten()
+                .copied(),
+        }
+    }
+
+    // Only the expenses on `date`.
+    pub fn on(mut self, date: NaiveDate) -> Filter {
+        self.from = Some(date);
+        self.to = Some(date);
+        self
+    }
+
+    // This filter with any conditions it leaves unset taken from `base`;
+    // tags and fields of the user's own from both apply.
+    pub fn or(self, base: &Filter) -> Filter {
+        let mut tags = base.tags.clone();
+        tags.extend(self.tags);
+        let mut meta = base.meta.clone();
+        meta.extend(self.meta);
+        Filter {
+            from: self.from.or(base.from),
+            to: self.to.or(base.to),
+            month: self.month.or(base.month),
+            category: self.category.or(base.category.clone()),
+            tags,
+            payee: self.payee.or(base.payee.clone()),
+            trip: self.trip.or(base.trip.clone()),
+            project: self.project.or(base.project.clone()),
+            user: self.user.or(base.user.clone()),
+            meta,
+            business: self.business.or(base.business),
+            min_amount: self.min_amount.or(base.min_amount),
+            max_amount: self.max_amount.or(base.max_amount),
+            hours: self.hours.or(base.hours),
+        }
+    }
+
+    // The conditions in words, for listing saved views.
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(from) = self.from {
+            parts.push(format!("from {from}"));
+        }
+        if let Some(to) = self.to {
+            parts.push(format!("to {to}"));
+        }
+        if let Some(month) = self.month {
+            parts.push(format!("month {month}"));
+        }
+        if let Some(category) = &self.category {
+            parts.push(format!("category {category}"));
+        }
+        for tag in self.tags.iter() {
+            parts.push(format!("tag {tag}"));
+        }
+        if let Some(payee) = &self.payee {
+            parts.push(format!("payee {payee}"));
+        }
+        if let Some(trip) = &self.trip {
+            parts.push(format!("trip {trip}"));
+        }
+        if let Some(project) = &self.project {
+            parts.push(format!("project {project}"));
+        }
+        if let Some(user) = &self.user {
+            parts.push(format!("user {user}"));
+        }
+        for field in self.meta.iter() {
+            parts.push(format!("meta {field}"));
+        }
+        match self.business {
+            Some(true) => parts.push(String::from("business only")),
+            Some(false) => parts.push(String::from("personal only")),
+            None => {}
+        }
+        if let Some(min) = self.min_amount {
+            parts.push(format!("at least {min}"));
+        }
+        if let Some(max) = self.max_amount {
+            parts.push(format!("at most {max}"));
+        }
+        if let Some(hours) = self.hours {
+            parts.push(format!("from {:02}:00 to {:02}:00", hours.start, hours.end));
+        }
+        if parts.is_empty() {
+            String::from("everything")
+        } else {
+            parts.join(", ")
+        }
+    }
+
+    // Whether any condition is set at all.
+    pub fn is_empty(&self) -> bool {
+        self.from.is_none()
+            && self.to.is_none()
+            && self.month.is_none()
+            && self.category.is_none()
+            && self.tags.is_empty()
+            && self.payee.is_none()
+            && self.trip.is_none()
+            && self.project.is_none()
+            && self.user.is_none()
+            && self.meta.is_empty()
+            && self.business.is_none()
+            && !self.has_amount_range()
+            && self.hours.is_none()
+    }
+
+    // Whether amounts are compared, so they need to be in one currency.
+    pub fn has_amount_range(&self) -> bool {
+        self.min_amount.is_some() || self.max_amount.is_some()
+    }
+
+    pub fn matches(&self, expense: &Expense) -> bool {
+        self.from.is_none_or(|from| expense.date >= from)
+            && self.to.is_none_or(|to| expense.date <= to)
+            && self
+                .month
+                .is_none_or(|month| dates::period_of(expense.date).month() == month as u32)
+            && self.category.as_deref().is_none_or(|category| {
+                expense
+                    .category_amounts()
+                    .iter()
+                    .any(|(leg, _)| leg.eq_ignore_ascii_case(category))
+            })
+            && self
+                .tags
+                .iter()
+                .all(|tag| expense.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+            && self.payee.as_deref().is_none_or(|payee| {
+                expense
+                    .payee
+                    .as_deref()
+                    .is_some_and(|p| p.eq_ignore_ascii_case(payee))
+            })
+            && self.trip.as_deref().is_none_or(|trip| {
+                expense
+                    .trip
+                    .as_deref()
+                    .is_some_and(|t| t.eq_ignore_ascii_case(trip))
+            })
+            && self.project.as_deref().is_none_or(|project| {
+                expense
+                    .project
+                    .as_deref()
+                    .is_some_and(|p| p.eq_ignore_ascii_case(project))
+            })
+            && self.user.as_deref().is_none_or(|user| {
+                expense
+                    .user
+                    .as_deref()
+                    .is_some_and(|u| u.eq_ignore_ascii_case(user))
+            })
+            && self.meta.iter().all(|field| match field.split_once('=') {
+                Some((key, value)) => expense
+                    .meta(key.trim())
+                    .is_some_and(|v| v.eq_ignore_ascii_case(value.trim())),
+                None => expense.meta(field.trim()).is_some(),
+            })
+            && self
+                .business
+                .is_none_or(|business| expense.business == business)
+            && self.min_amount.is_none_or(|min| expense.amount >= min)
+            && self.max_amount.is_none_or(|max| expense.amount <= max)
+            && self
+                .hours
+                .is_none_or(|hours| expense.time.is_some_and(|time| hours.contains(time.hour())))
+    }
+
+    // How much of a matching expense counts: with a category filter only
+    // the legs in that category of a split expense.
+    pub fn amount_of(&self, expense: &Expense) -> f64 {
+        match &self.category {
+            Some(category) => expense
+                .category_amounts()
+                .iter()
+                .filter(|(leg, _)| leg.eq_ignore_ascii_case(category))
+                .fold(0.0, |sum, (_, amount)| sum + amount),
+            None => expense.amount,
+        }
+    }
+
+    pub fn apply<'a>(&self, expenses: impl IntoIterator<Item = &'a Expense>) -> Vec<&'a Expense> {
+        expenses.into_iter().filter(|e| self.matches(e)).collect()
+    }
+}
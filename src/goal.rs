@@ -0,0 +1,85 @@
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::currency;
+use crate::dates::month_end;
+use crate::expense::ExpenseTracker;
+
+// A savings target to reach by a given month
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Goal {
+    pub name: String,
+    pub target: f64,
+    // The month the target should be reached by
+    pub by: NaiveDate,
+    // The month saving towards the goal started
+    pub since: NaiveDate,
+    // Count money put into this category as saved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    // Otherwise count this monthly income minus all spending as saved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monthly_income: Option<f64>,
+}
+
+// Months from `from` to `to` inclusive, counting partial months as whole.
+fn months_between(from: NaiveDate, to: NaiveDate) -> i32 {
+    (to.year() - from.year()) * 12 + to.month() as i32 - from.month() as i32 + 1
+}
+
+// How much has been saved towards the goal up to `today`.
+pub fn saved(tracker: &ExpenseTracker, goal: &Goal, today: NaiveDate) -> f64 {
+    let in_period = tracker
+        .personal_expenses()
+        .filter(|e| e.date >= goal.since && e.date <= today);
+    match (&goal.category, goal.monthly_income) {
+        (Some(category), _) => in_period
+            .flat_map(|e| e.category_amounts())
+            .filter(|(c, _)| c.eq_ignore_ascii_case(category))
+            .fold(0.0, |sum, (_, amount)| sum + amount),
+        (None, Some(income)) => {
+            let months = months_between(goal.since, today).max(0);
+            income * months as f64 - in_period.fold(0.0, |sum, e| sum + e.amount)
+        }
+        (None, None) => 0.0,
+    }
+}
+
+pub fn print_status(tracker: &ExpenseTracker, today: NaiveDate) {
+    if tracker.goals.is_empty() {
+        println!("No goals set.");
+        return;
+    }
+    for goal in tracker.goals.iter() {
+        let saved = saved(tracker, goal, today).max(0.0);
+        let progress = (saved / goal.target).clamp(0.0, 1.0);
+        let filled = (progress * 30.0).round() as usize;
+        println!(
+            "{}: {} of {} by {}",
+            goal.name,
+            currency::amount(saved),
+            currency::amount(goal.target),
+            goal.by.format("%Y-%m")
+        );
+        println!(
+            "  [{}{}] {:.0}%",
+            "█".repeat(filled),
+            "░".repeat(30 - filled),
+            progress * 100.0
+        );
+        let remaining = goal.target - saved;
+        if remaining <= 0.0 {
+            println!("  Reached!");
+        } else if today > month_end(goal.by) {
+            println!("  Deadline passed, {} short.", currency::amount(remaining));
+        } else {
+            // The current month still counts towards the pace
+            let months_left = months_between(today, goal.by).max(1);
+            println!(
+                "  Needs {}/month over the next {} month(s).",
+                currency::amount(remaining / months_left as f64),
+                months_left
+            );
+        }
+    }
+}
@@ -0,0 +1,423 @@
+use std::io::{IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+// A built-in tutorial shown by `guide <topic>`
+pub struct Topic {
+    pub name: &'static str,
+    pub title: &'static str,
+    pub summary: &'static str,
+    pub sections: &'static [Section],
+}
+
+pub struct Section {
+    pub heading: &'static str,
+    pub body: &'static str,
+    pub examples: &'static [Example],
+}
+
+pub struct Example {
+    pub command: &'static str,
+    pub explanation: &'static str,
+}
+
+pub const TOPICS: &[Topic] = &[
+    Topic {
+        name: "getting-started",
+        title: "Getting started",
+        summary: "Record, list and delete expenses.",
+        sections: &[
+            Section {
+                heading: "Recording an expense",
+                body: "Every expense has a category, an amount and a description. The date \
+                       defaults to today; pass --when to back-date it. A payee is optional but \
+                       makes per-merchant reports possible.",
+                examples: &[
+                    Example {
+                        command: "expense-tracker-rs add -c Food -a 250 -d \"Lunch\"",
+                        explanation: "Record a lunch for today.",
+                    },
+                    Example {
+                        command: "expense-tracker-rs add -c Rent -a 15500 -d \"July rent\" -w 2025-07-01",
+                        explanation: "Record an expense on a specific date.",
+                    },
+                    Example {
+                        command: "expense-tracker-rs add -c Shopping -a 999 -d \"Headphones\" -p Amazon",
+                        explanation: "Record who you paid.",
+                    },
+                ],
+            },
+            Section {
+                heading: "Reviewing and correcting",
+                body: "`list` numbers every expense. Those row numbers are what `delete` takes.",
+                examples: &[
+                    Example {
+                        command: "expense-tracker-rs list",
+                        explanation: "Show all expenses with their row numbers.",
+                    },
+                    Example {
+                        command: "expense-tracker-rs delete 3",
+                        explanation: "Remove the third expense, after showing it and asking; -y skips the question.",
+                    },
+                ],
+            },
+        ],
+    },
+    Topic {
+        name: "splits",
+        title: "Splitting an expense across categories",
+        summary: "Record one bill that covers several categories.",
+        sections: &[Section {
+            heading: "Split legs",
+            body: "A supermarket bill is often part groceries and part household. Give one \
+                   --split category=amount per leg; the legs must add up to --amount. Category \
+                   summaries count each leg under its own category.",
+            examples: &[
+                Example {
+                    command: "expense-tracker-rs add -a 500 -d \"Supermarket\" -s Groceries=300 -s Household=200",
+                    explanation: "Record a bill split across two categories.",
+                },
+                Example {
+                    command: "expense-tracker-rs summary -c Groceries",
+                    explanation: "Includes the 300 groceries leg.",
+                },
+            ],
+        }],
+    },
+    Topic {
+        name: "reports",
+        title: "Summaries and reports",
+        summary: "Answer how much went where.",
+        sections: &[
+            Section {
+                heading: "Summaries",
+                body: "`summary` gives a single total, either overall or filtered by category, \
+                       exact date or month number.",
+                examples: &[
+                    Example {
+                        command: "expense-tracker-rs summary --all",
+                        explanation: "Total of every expense.",
+                    },
+                    Example {
+                        command: "expense-tracker-rs summary --month 7",
+                        explanation: "Total for July.",
+                    },
+                ],
+            },
+            Section {
+                heading: "Filters",
+                body: "list, summary, total, search, export, edit and delete share the same filters: \
+                       --from and --to (or the exclusive --after and --before), --category, --tag, --payee, --min-amount and \
+                       --max-amount. Any combination can be used; an expense must match all \
+                       of them. Save a combination with `view save` and reuse it with --view.",
+                examples: &[
+                    Example {
+                        command: "expense-tracker-rs list --category Travel --tag work --from 2025-07-01",
+                        explanation: "Work travel since July.",
+                    },
+                    Example {
+                        command: "expense-tracker-rs delete --payee Swiggy --to 2024-12-31",
+                        explanation: "Delete old Swiggy orders after confirming the list.",
+                    },
+                    Example {
+                        command: "expense-tracker-rs edit --payee BigBasket --set category=Groceries",
+                        explanation: "Recategorise every BigBasket expense at once.",
+                    },
+                    Example {
+                        command: "expense-tracker-rs view save work-travel --category Travel --tag work",
+                        explanation: "Save those filters; then `summary --view work-travel`.",
+                    },
+                ],
+            },
+            Section {
+                heading: "Reports",
+                body: "`report` groups spending into tables.",
+                examples: &[Example {
+                    command: "expense-tracker-rs report payees --year 2025",
+                    explanation: "Total spend per payee for 2025.",
+                }],
+            },
+        ],
+    },
+    Topic {
+        name: "budgeting",
+        title: "Budgeting workflow",
+        summary: "Set monthly category budgets and track envelopes.",
+        sections: &[
+            Section {
+                heading: "Setting budgets",
+                body: "A budget is a monthly limit for one category. Setting a budget for a \
+                       category that already has one replaces it. `budget status` shows how \
+                       much of each budget is used this month.",
+                examples: &[
+                    Example {
+                        command: "expense-tracker-rs budget set Food 8000",
+                        explanation: "Allow 8000 a month for Food.",
+                    },
+                    Example {
+                        command: "expense-tracker-rs budget status --month 2025-07",
+                        explanation: "Review July against the budgets.",
+                    },
+                ],
+            },
+            Section {
+                heading: "Envelopes and rollover",
+                body: "With --rollover, whatever is left of a month's budget carries into the \
+                       next month, and overspending reduces it, like cash in an envelope. \
+                       `budget envelope` shows each envelope's carried-in amount and balance.",
+                examples: &[
+                    Example {
+                        command: "expense-tracker-rs budget set Travel 3000 --rollover --since 2025-01",
+                        explanation: "Save up for travel from January onwards.",
+                    },
+                    Example {
+                        command: "expense-tracker-rs budget envelope",
+                        explanation: "Show current envelope balances.",
+                    },
+                ],
+            },
+            Section {
+                heading: "Keeping an eye on it",
+                body: "`forecast` compares the projected month-end spend with each budget, the \
+                       monthly report includes a budget status table, and the month-end \
+                       checklist flags categories that went over.",
+                examples: &[Example {
+                    command: "expense-tracker-rs forecast",
+                    explanation: "Will this month stay within budget?",
+                }],
+            },
+        ],
+    },
+    Topic {
+        name: "importing",
+        title: "Importing bank statements",
+        summary: "Bring OFX and QIF statements into the ledger.",
+        sections: &[
+            Section {
+                heading: "Importing",
+                body: "`import` reads the debits from an OFX or QIF statement and shows them \
+                       before asking for confirmation. Credits such as salary are ignored. \
+                       Transactions on the same date and for the same amount as an existing \
+                       expense are flagged as possible duplicates and skipped unless you pass \
+                       --include-duplicates.",
+                examples: &[
+                    Example {
+                        command: "expense-tracker-rs import statement.ofx",
+                        explanation: "Preview and import an OFX statement.",
+                    },
+                    Example {
+                        command: "expense-tracker-rs import july.qif --date-format %d/%m/%Y",
+                        explanation: "Import a QIF file whose dates are day-first.",
+                    },
+                ],
+            },
+            Section {
+                heading: "Categorizing afterwards",
+                body: "Statements rarely carry categories, so imported expenses land in \
+                       Uncategorized (or the category given with --category). The month-end \
+                       checklist will not pass until they are recategorized.",
+                examples: &[Example {
+                    command: "expense-tracker-rs import statement.ofx --category Card",
+                    explanation: "Put uncategorized transactions under Card.",
+                }],
+            },
+        ],
+    },
+    Topic {
+        name: "month-end",
+        title: "Closing a month",
+        summary: "Review and lock a finished month.",
+        sections: &[Section {
+            heading: "Closing",
+            body: "Once a month is reviewed, close it so it cannot be changed by accident. \
+                   With --checklist the month is only closed when every check passes; --force \
+                   closes it anyway. --reopen unlocks it again.",
+            examples: &[
+                Example {
+                    command: "expense-tracker-rs close-month 2025-07 --checklist",
+                    explanation: "Verify July and lock it.",
+                },
+                Example {
+                    command: "expense-tracker-rs close-month 2025-07 --reopen",
+                    explanation: "Allow changes to July again.",
+                },
+            ],
+        }],
+    },
+    Topic {
+        name: "currencies",
+        title: "Spending in other currencies",
+        summary: "Record foreign expenses and report in one currency.",
+        sections: &[
+            Section {
+                heading: "Recording",
+                body: "The ledger is kept in one currency, set by `currency` in config.toml \
+                       and INR by default. Pass --currency to `add` for an expense paid in \
+                       another currency; it is stored and listed as entered.",
+                examples: &[Example {
+                    command: "expense-tracker-rs add -c Food -a 18.50 -d \"Lunch\" --currency EUR",
+                    explanation: "Record a lunch paid in euros.",
+                }],
+            },
+            Section {
+                heading: "Rates and reports",
+                body: "Reports convert every amount to the ledger's currency, or to the one \
+                       given with --currency, using the latest rate on or before each \
+                       expense's date. Rates are either set by hand or fetched with `rates \
+                       update`, which caches them on disk so reports keep working offline. \
+                       The rates used are noted after the report.",
+                examples: &[
+                    Example {
+                        command: "expense-tracker-rs rates update",
+                        explanation: "Fetch today's rates and any missing for foreign expenses.",
+                    },
+                    Example {
+                        command: "expense-tracker-rs rates set EUR 90.5 -d 2025-07-01",
+                        explanation: "One euro is worth 90.5 in the ledger's currency from July.",
+                    },
+                    Example {
+                        command: "expense-tracker-rs total --currency USD",
+                        explanation: "Show the total in dollars; needs a USD rate.",
+                    },
+                ],
+            },
+        ],
+    },
+    Topic {
+        name: "syncing",
+        title: "Sharing the ledger between machines",
+        summary: "Keep copies on several machines in step through git.",
+        sections: &[
+            Section {
+                heading: "Setting up",
+                body: "Keep the data file in a directory of its own and run `sync init` there. \
+                       It makes the directory a git repository and commits every change as it \
+                       is made, with a message describing it. Backups stay out of the \
+                       repository. On the other machine, clone the repository and run `sync \
+                       init` in the clone too, so its merges are set up.",
+                examples: &[
+                    Example {
+                        command: "expense-tracker-rs sync init --origin git@example.com:me/ledger.git",
+                        explanation: "Start syncing and push to this repository.",
+                    },
+                    Example {
+                        command: "git clone git@example.com:me/ledger.git && cd ledger && expense-tracker-rs sync init",
+                        explanation: "Set up the second machine.",
+                    },
+                ],
+            },
+            Section {
+                heading: "Pushing and pulling",
+                body: "`sync push` sends the commits to origin and `sync pull` brings in the \
+                       other machine's. Data files are merged expense by expense: additions \
+                       from both sides are kept, with new ids where both picked the same one, \
+                       and an edit or deletion is applied when the other side left that \
+                       expense alone.",
+                examples: &[
+                    Example {
+                        command: "expense-tracker-rs sync pull",
+                        explanation: "Merge the other machine's changes.",
+                    },
+                    Example {
+                        command: "expense-tracker-rs sync push",
+                        explanation: "Share this machine's changes.",
+                    },
+                ],
+            },
+            Section {
+                heading: "Conflicts",
+                body: "An expense changed differently on both machines, or edited on one and \
+                       deleted on the other, keeps this machine's version until `sync resolve` \
+                       settles it. It shows what differs and asks which version to keep; \
+                       --keep picks for every conflict at once, with newest going by when each \
+                       version was saved.",
+                examples: &[
+                    Example {
+                        command: "expense-tracker-rs sync resolve",
+                        explanation: "Choose between the versions one conflict at a time.",
+                    },
+                    Example {
+                        command: "expense-tracker-rs sync resolve --keep newest",
+                        explanation: "Keep the most recently saved version of each.",
+                    },
+                ],
+            },
+        ],
+    },
+];
+
+pub fn find(name: &str) -> Option<&'static Topic> {
+    TOPICS.iter().find(|t| t.name == name)
+}
+
+pub fn render_index() -> String {
+    let mut out = String::from("Available guides:\n\n");
+    for topic in TOPICS {
+        out.push_str(&format!("  {:<18} {}\n", topic.name, topic.summary));
+    }
+    out.push_str("\nRun `expense-tracker-rs guide <topic>` to read one.\n");
+    out
+}
+
+pub fn render(topic: &Topic) -> String {
+    let mut out = format!(
+        "{}\n{}\n",
+        topic.title,
+        "=".repeat(topic.title.chars().count())
+    );
+    for section in topic.sections {
+        out.push_str(&format!(
+            "\n{}\n{}\n\n",
+            section.heading,
+            "-".repeat(section.heading.chars().count())
+        ));
+        out.push_str(&wrap(section.body, 78));
+        out.push('\n');
+        for example in section.examples {
+            out.push_str(&format!(
+                "\n    $ {}\n      {}\n",
+                example.command, example.explanation
+            ));
+        }
+    }
+    out
+}
+
+// Show text through $PAGER (or `less`) when writing to a terminal.
+pub fn page(text: &str, use_pager: bool) {
+    if use_pager && std::io::stdout().is_terminal() {
+        let pager = std::env::var("PAGER").unwrap_or_else(|_| String::from("less -R"));
+        let mut parts = pager.split_whitespace();
+        if let Some(program) = parts.next()
+            && let Ok(mut child) = Command::new(program)
+                .args(parts)
+                .stdin(Stdio::piped())
+                .spawn()
+        {
+            if let Some(mut stdin) = child.stdin.take() {
+                let _ = stdin.write_all(text.as_bytes());
+            }
+            if child.wait().is_ok() {
+                return;
+            }
+        }
+    }
+    print!("{text}");
+}
+
+fn wrap(text: &str, width: usize) -> String {
+    let mut out = String::new();
+    let mut line_len = 0;
+    for word in text.split_whitespace() {
+        let len = word.chars().count();
+        if line_len > 0 && line_len + 1 + len > width {
+            out.push('\n');
+            line_len = 0;
+        } else if line_len > 0 {
+            out.push(' ');
+            line_len += 1;
+        }
+        out.push_str(word);
+        line_len += len;
+    }
+    out
+}
@@ -0,0 +1,224 @@
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    fs::OpenOptions,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::crypto;
+use crate::expense::{Expense, ExpenseTracker};
+
+// One change to the ledger's expenses, as kept in the history file
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Event {
+    pub at: DateTime<Local>,
+    pub by: String,
+    #[serde(flatten)]
+    pub change: Change,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "event", rename_all = "lowercase")]
+pub enum Change {
+    Added {
+        expense: Expense,
+    },
+    Edited {
+        before: Box<Expense>,
+        after: Box<Expense>,
+    },
+    Deleted {
+        expense: Expense,
+    },
+}
+
+impl Change {
+    pub fn id(&self) -> u64 {
+        match self {
+            Change::Added { expense } | Change::Deleted { expense } => expense.id,
+            Change::Edited { after, .. } => after.id,
+        }
+    }
+}
+
+// Recorded as the author of the expenses present when history started
+const BASELINE: &str = "(existing)";
+
+// The history file, next to the data file.
+pub fn path(data_file: &str) -> PathBuf {
+    Path::new(data_file).with_extension("history")
+}
+
+// Whoever is running the command.
+fn user() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| String::from("unknown"))
+}
+
+// The changes that turn `before`'s expenses into `after`'s, matched by id.
+pub fn changes(before: &ExpenseTracker, after: &ExpenseTracker) -> Vec<Change> {
+    let mut changes = Vec::new();
+    for expense in after.expenses.iter() {
+        match before.find_by_id(expense.id) {
+            None => changes.push(Change::Added {
+                expense: expense.clone(),
+            }),
+            Some(old) if old != expense => changes.push(Change::Edited {
+                before: Box::new(old.clone()),
+                after: Box::new(expense.clone()),
+            }),
+            Some(_) => {}
+        }
+    }
+    for expense in before.expenses.iter() {
+        if after.find_by_id(expense.id).is_none() {
+            changes.push(Change::Deleted {
+                expense: expense.clone(),
+            });
+        }
+    }
+    changes
+}
+
+// Append `changes` to the history. The first time, the expenses already in
+// `before` are recorded as a baseline so the ledger can be rebuilt from the
+// history alone. An encrypted ledger's history is read, added to and
+// encrypted again as a whole.
+pub fn record(data_file: &str, before: &ExpenseTracker, changes: Vec<Change>) -> io::Result<()> {
+    if changes.is_empty() {
+        return Ok(());
+    }
+    let path = path(data_file);
+    let now = Local::now();
+    let mut events = Vec::new();
+    if !path.exists() {
+        events.extend(before.expenses.iter().map(|expense| Event {
+            at: now,
+            by: String::from(BASELINE),
+            change: Change::Added {
+                expense: expense.clone(),
+            },
+        }));
+    }
+    let by = user();
+    events.extend(changes.into_iter().map(|change| Event {
+        at: now,
+        by: by.clone(),
+        change,
+    }));
+    let mut text = String::new();
+    for event in events {
+        text.push_str(&serde_json::to_string(&event)?);
+        text.push('\n');
+    }
+    if crypto::is_active() || crypto::is_encrypted_file(&path) {
+        let mut bytes = match crypto::read(&path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(err),
+        };
+        bytes.extend_from_slice(text.as_bytes());
+        return crypto::write(&path, &bytes);
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(text.as_bytes())
+}
+
+pub fn load(data_file: &str) -> io::Result<Vec<Event>> {
+    // Encrypted along with an encrypted ledger
+    let text = match crypto::read(&path(data_file)) {
+        Ok(bytes) => String::from_utf8(bytes).map_err(io::Error::other)?,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).map_err(io::Error::from))
+        .collect()
+}
+
+// The expenses as they stood at the end of `date`, rebuilt from the history.
+pub fn at(events: &[Event], date: NaiveDate) -> Vec<Expense> {
+    let mut expenses: Vec<Expense> = Vec::new();
+    for event in events.iter().filter(|e| e.at.date_naive() <= date) {
+        match &event.change {
+            Change::Added { expense } => expenses.push(expense.clone()),
+            Change::Edited { after, .. } => {
+                if let Some(stored) = expenses.iter_mut().find(|e| e.id == after.id) {
+                    *stored = (**after).clone();
+                }
+            }
+            Change::Deleted { expense } => expenses.retain(|e| e.id != expense.id),
+        }
+    }
+    expenses
+}
+
+fn show(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::from("(none)"),
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Array(values)) => values
+            .iter()
+            .map(|v| show(Some(v)))
+            .collect::<Vec<_>>()
+            .join(", "),
+        Some(value) => value.to_string(),
+    }
+}
+
+// The fields that differ between two versions of an expense, leaving out
+// the time it was modified.
+pub fn differences(before: &Expense, after: &Expense) -> Vec<String> {
+    let (Ok(Value::Object(before)), Ok(Value::Object(after))) =
+        (serde_json::to_value(before), serde_json::to_value(after))
+    else {
+        return Vec::new();
+    };
+    before
+        .keys()
+        .chain(after.keys().filter(|key| !before.contains_key(*key)))
+        .filter(|key| *key != "modified" && before.get(*key) != after.get(*key))
+        .map(|key| {
+            format!(
+                "{key}: {} → {}",
+                show(before.get(key)),
+                show(after.get(key))
+            )
+        })
+        .collect()
+}
+
+pub fn print(events: &[&Event], mask_private: bool) {
+    if events.is_empty() {
+        println!("No history recorded.");
+        return;
+    }
+    for event in events {
+        let when = event.at.format("%Y-%m-%d %H:%M");
+        match &event.change {
+            Change::Added { expense } => println!(
+                "{when}  {:<12} added    {}",
+                event.by,
+                expense.line(mask_private)
+            ),
+            Change::Deleted { expense } => println!(
+                "{when}  {:<12} deleted  {}",
+                event.by,
+                expense.line(mask_private)
+            ),
+            Change::Edited { before, after } => {
+                println!("{when}  {:<12} edited   #{}", event.by, after.id);
+                if mask_private && (before.private || after.private) {
+                    continue;
+                }
+                for difference in differences(before, after) {
+                    println!("{:<34}{}", "", difference);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use std::{
+    io::{self, Write},
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use crate::config::Hooks;
+use crate::expense::{Expense, ExpenseTracker};
+use crate::history::{self, Change};
+use crate::storage::Storage;
+
+// A storage backend that runs the configured hook scripts around every
+// change to expenses. The pre hook can refuse a change by failing; the post
+// hook is told about it once it is saved.
+pub struct Hooked {
+    inner: Box<dyn Storage>,
+    hooks: Hooks,
+    command: String,
+}
+
+// `storage` with `hooks` run around its changes, for the subcommand
+// `command`. Without hooks configured it is returned as it is.
+pub fn wrap(storage: Box<dyn Storage>, hooks: &Hooks, command: &str) -> Box<dyn Storage> {
+    if hooks.pre.is_none() && hooks.post.is_none() {
+        return storage;
+    }
+    Box::new(Hooked {
+        inner: storage,
+        hooks: hooks.clone(),
+        command: command.to_string(),
+    })
+}
+
+// Run `script` with `change` as JSON on its standard input, returning its
+// complaint if it fails.
+fn run(script: &Path, stage: &str, command: &str, change: &Change) -> io::Result<()> {
+    let mut child = Command::new(script)
+        .env("EXPENSE_TRACKER_HOOK", stage)
+        .env("EXPENSE_TRACKER_COMMAND", command)
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| io::Error::other(format!("{}: {err}", script.display())))?;
+    let mut json = serde_json::to_vec(change)?;
+    json.push(b'\n');
+    if let Some(mut stdin) = child.stdin.take() {
+        // A hook that doesn't read its input is fine.
+        let _ = stdin.write_all(&json);
+    }
+    let output = child.wait_with_output()?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Err(io::Error::other(match stderr.trim() {
+        "" => format!("{} failed ({})", script.display(), output.status),
+        message => message.to_string(),
+    }))
+}
+
+impl Hooked {
+    fn pre(&self, changes: &[Change]) -> io::Result<()> {
+        let Some(script) = &self.hooks.pre else {
+            return Ok(());
+        };
+        for change in changes {
+            run(script, "pre", &self.command, change)
+                .map_err(|err| io::Error::other(format!("the pre hook refused: {err}")))?;
+        }
+        Ok(())
+    }
+
+    // The change is already saved, so a failure is only reported.
+    fn post(&self, changes: &[Change]) {
+        let Some(script) = &self.hooks.post else {
+            return;
+        };
+        for change in changes {
+            if let Err(err) = run(script, "post", &self.command, change) {
+                eprintln!("The post hook failed: {err}");
+            }
+        }
+    }
+}
+
+impl Storage for Hooked {
+    fn load(&self) -> io::Result<ExpenseTracker> {
+        self.inner.load()
+    }
+
+    fn load_metadata(&self) -> io::Result<ExpenseTracker> {
+        self.inner.load_metadata()
+    }
+
+    fn save(&self, tracker: &ExpenseTracker) -> io::Result<()> {
+        let changes = history::changes(&self.inner.load()?, tracker);
+        self.pre(&changes)?;
+        self.inner.save(tracker)?;
+        self.post(&changes);
+        Ok(())
+    }
+
+    fn append(&self, expense: Expense) -> io::Result<u64> {
+        // The pre hook sees the expense as given, before the backend settles
+        // its id.
+        self.pre(&[Change::Added {
+            expense: expense.clone(),
+        }])?;
+        let mut expense = expense;
+        let id = self.inner.append(expense.clone())?;
+        expense.id = id;
+        self.post(&[Change::Added { expense }]);
+        Ok(id)
+    }
+}
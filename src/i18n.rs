@@ -0,0 +1,157 @@
+use std::{collections::HashMap, env, fmt::Display, sync::OnceLock};
+
+// Messages are kept in Fluent files, one per language, but read here rather
+// than with the fluent crate, and only the parts of Fluent they use are
+// understood: `id = text` lines, indented continuation lines, comments and
+// `{ $name }` placeables. There are no select expressions, so no plural
+// forms, and no terms, attributes or functions; a message needing them has
+// to be worded so one form fits every count.
+const LOCALES: [(&str, &str); 2] = [
+    ("en", include_str!("locales/en.ftl")),
+    ("hi", include_str!("locales/hi.ftl")),
+];
+
+const FALLBACK: &str = "en";
+
+struct Catalog {
+    messages: HashMap<&'static str, String>,
+    fallback: HashMap<&'static str, String>,
+}
+
+static CATALOG: OnceLock<Catalog> = OnceLock::new();
+
+fn parse(source: &'static str) -> HashMap<&'static str, String> {
+    let mut messages: HashMap<&'static str, String> = HashMap::new();
+    let mut current: Option<&'static str> = None;
+    for line in source.lines() {
+        if line.trim_start().starts_with('#') || line.trim().is_empty() {
+            current = None;
+        } else if line.starts_with(' ')
+            && let Some(id) = current
+        {
+            let text = messages.entry(id).or_default();
+            text.push(' ');
+            text.push_str(line.trim());
+        } else if let Some((id, text)) = line.split_once('=') {
+            let id = id.trim();
+            messages.insert(id, text.trim().to_string());
+            current = Some(id);
+        }
+    }
+    messages
+}
+
+fn source(code: &str) -> &'static str {
+    LOCALES
+        .iter()
+        .find(|(c, _)| *c == code)
+        .map(|(_, source)| *source)
+        .expect("only known languages are looked up")
+}
+
+// The language part of a locale such as hi_IN.UTF-8, if we have it.
+fn known(locale: &str) -> Option<&'static str> {
+    let language = locale
+        .split(['_', '-', '.', '@'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    LOCALES
+        .iter()
+        .map(|(code, _)| *code)
+        .find(|code| *code == language)
+}
+
+// Pick the language from `configured`, then $EXPENSE_TRACKER_LANG, then
+// the usual locale variables, falling back to English.
+pub fn init(configured: Option<&str>) {
+    let from_env = ["EXPENSE_TRACKER_LANG", "LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|value| !value.is_empty());
+    let language = configured
+        .and_then(known)
+        .or_else(|| from_env.as_deref().and_then(known))
+        .unwrap_or(FALLBACK);
+    let _ = CATALOG.set(Catalog {
+        messages: parse(source(language)),
+        fallback: parse(source(FALLBACK)),
+    });
+}
+
+fn catalog() -> &'static Catalog {
+    CATALOG.get_or_init(|| Catalog {
+        messages: HashMap::new(),
+        fallback: parse(source(FALLBACK)),
+    })
+}
+
+// The message `id` in the chosen language, or English, or the id itself.
+pub fn t(id: &str) -> String {
+    let catalog = catalog();
+    catalog
+        .messages
+        .get(id)
+        .or_else(|| catalog.fallback.get(id))
+        .cloned()
+        .unwrap_or_else(|| id.to_string())
+}
+
+// The message `id` with its `{ $name }` placeables filled in from `args`.
+pub fn t_args(id: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut text = t(id);
+    for (name, value) in args {
+        text = text.replace(&format!("{{ ${name} }}"), &value.to_string());
+    }
+    text
+}
+
+// Whether `answer` to a yes/no question means yes.
+pub fn is_yes(answer: &str) -> bool {
+    let answer = answer.trim().to_lowercase();
+    t("yes-answers")
+        .split(',')
+        .map(str::trim)
+        .any(|yes| !yes.is_empty() && yes == answer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placeables(text: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = text
+            .match_indices("{ $")
+            .filter_map(|(start, _)| text[start..].split_once(" }").map(|(name, _)| name))
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn every_language_has_every_message_with_the_same_placeables() {
+        let english = parse(source(FALLBACK));
+        for (code, source) in LOCALES {
+            let messages = parse(source);
+            for (id, text) in &english {
+                let translated = messages
+                    .get(id)
+                    .unwrap_or_else(|| panic!("{code} has no {id}"));
+                assert_eq!(
+                    placeables(translated),
+                    placeables(text),
+                    "{code} {id} has different placeables"
+                );
+            }
+            assert_eq!(messages.len(), english.len(), "{code} has unknown ids");
+        }
+    }
+
+    #[test]
+    fn continuation_lines_join_the_message_above() {
+        let messages = parse("# A comment\nfirst = One\n  and two\n\nsecond = { $n } more\n");
+        assert_eq!(messages["first"], "One and two");
+        assert_eq!(messages["second"], "{ $n } more");
+        assert_eq!(messages.len(), 2);
+    }
+}
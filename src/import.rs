@@ -0,0 +1,396 @@
+use chrono::NaiveDate;
+use std::{collections::BTreeMap, fs, path::Path};
+
+// A spending transaction read from a bank statement
+pub struct Transaction {
+    pub date: NaiveDate,
+    pub amount: f64,
+    pub payee: Option<String>,
+    pub description: String,
+    pub category: Option<String>,
+}
+
+// Transactions parsed from a statement, plus how many credits were left out.
+pub struct Statement {
+    pub transactions: Vec<Transaction>,
+    pub skipped_credits: usize,
+}
+
+// Parse an OFX statement (SGML 1.x or XML 2.x). Only debits are returned.
+pub fn parse_ofx(text: &str) -> Result<Statement, String> {
+    let mut statement = Statement {
+        transactions: Vec::new(),
+        skipped_credits: 0,
+    };
+    let upper = text.to_ascii_uppercase();
+    let mut rest = 0;
+    while let Some(start) = upper[rest..].find("<STMTTRN>") {
+        let start = rest + start + "<STMTTRN>".len();
+        let end = upper[start..]
+            .find("</STMTTRN>")
+            .map(|e| start + e)
+            .ok_or("unterminated <STMTTRN> block")?;
+        let block = &text[start..end];
+        rest = end;
+
+        let posted = ofx_field(block, "DTPOSTED").ok_or("transaction without <DTPOSTED>")?;
+        let date = posted
+            .get(..8)
+            .and_then(|d| NaiveDate::parse_from_str(d, "%Y%m%d").ok())
+            .ok_or_else(|| format!("invalid <DTPOSTED> '{posted}'"))?;
+        let raw_amount = ofx_field(block, "TRNAMT").ok_or("transaction without <TRNAMT>")?;
+        let amount = parse_amount(&raw_amount)?;
+        if amount >= 0.0 {
+            statement.skipped_credits += 1;
+            continue;
+        }
+        let name = ofx_field(block, "NAME");
+        let memo = ofx_field(block, "MEMO");
+        statement.transactions.push(Transaction {
+            date,
+            amount: -amount,
+            description: memo.or_else(|| name.clone()).unwrap_or_default(),
+            payee: name,
+            category: None,
+        });
+    }
+    Ok(statement)
+}
+
+// The value of `<TAG>value` inside an OFX block, with or without a closing tag.
+fn ofx_field(block: &str, tag: &str) -> Option<String> {
+    let open = format!("<{tag}>");
+    let start = block.to_ascii_uppercase().find(&open)? + open.len();
+    let value = &block[start..];
+    let end = value.find(['<', '\n', '\r']).unwrap_or(value.len());
+    let value = value[..end].trim();
+    (!value.is_empty()).then(|| unescape_sgml(value))
+}
+
+fn unescape_sgml(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+// Parse a QIF bank export. Dates are tried against `date_format` if given,
+// otherwise against the common US (MM/DD) and ISO layouts.
+pub fn parse_qif(text: &str, date_format: Option<&str>) -> Result<Statement, String> {
+    let mut statement = Statement {
+        transactions: Vec::new(),
+        skipped_credits: 0,
+    };
+    let (mut date, mut amount, mut payee, mut memo, mut category) = (None, None, None, None, None);
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim_end();
+        let Some(code) = line.chars().next() else {
+            continue;
+        };
+        let value = line[code.len_utf8()..].trim();
+        match code {
+            '!' => {}
+            'D' => {
+                date = Some(
+                    parse_qif_date(value, date_format)
+                        .ok_or_else(|| format!("line {}: invalid date '{value}'", number + 1))?,
+                )
+            }
+            'T' | 'U' => {
+                amount = Some(parse_amount(value).map_err(|e| format!("line {}: {e}", number + 1))?)
+            }
+            'P' => payee = Some(value.to_string()),
+            'M' => memo = Some(value.to_string()),
+            'L' => category = Some(value.trim_matches(['[', ']']).to_string()),
+            '^' => {
+                let (Some(d), Some(a)) = (date.take(), amount.take()) else {
+                    return Err(format!(
+                        "line {}: entry without a date or amount",
+                        number + 1
+                    ));
+                };
+                let (payee, memo, category) = (payee.take(), memo.take(), category.take());
+                if a >= 0.0 {
+                    statement.skipped_credits += 1;
+                    continue;
+                }
+                statement.transactions.push(Transaction {
+                    date: d,
+                    amount: -a,
+                    description: memo.or_else(|| payee.clone()).unwrap_or_default(),
+                    payee,
+                    category: category.filter(|c| !c.is_empty()),
+                });
+            }
+            _ => {}
+        }
+    }
+    Ok(statement)
+}
+
+fn parse_qif_date(value: &str, date_format: Option<&str>) -> Option<NaiveDate> {
+    // Quicken writes two-digit years after an apostrophe: 7/12'25
+    let value = value.replace('\'', "/").replace(' ', "");
+    let formats: Vec<&str> = match date_format {
+        Some(format) => vec![format],
+        None => vec!["%m/%d/%Y", "%m/%d/%y", "%Y-%m-%d", "%m-%d-%Y"],
+    };
+    formats
+        .iter()
+        .find_map(|f| NaiveDate::parse_from_str(&value, f).ok())
+}
+
+// Parse a CSV in YNAB's layout: Date, Payee, Memo, Outflow and Inflow, as
+// YNAB imports them, plus the Category column of its register exports.
+// Column names are matched case-insensitively and in any order; rows with
+// only an inflow are credits.
+pub fn parse_ynab(text: &str, date_format: Option<&str>) -> Result<Statement, String> {
+    let mut statement = Statement {
+        transactions: Vec::new(),
+        skipped_credits: 0,
+    };
+    let (mut reader, headers) = csv_reader(text)?;
+    let column = |name: &str| headers.iter().position(|h| h == name);
+    let date_column = column("date").ok_or("no Date column")?;
+    let outflow_column = column("outflow").ok_or("no Outflow column")?;
+    let (payee_column, memo_column) = (column("payee"), column("memo"));
+    let (inflow_column, category_column) = (column("inflow"), column("category"));
+
+    for (number, record) in (2..).zip(reader.records()) {
+        let record = record.map_err(|err| format!("line {number}: {err}"))?;
+        let field = |column: Option<usize>| {
+            column
+                .and_then(|c| record.get(c))
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+        let Some(raw_date) = field(Some(date_column)) else {
+            continue;
+        };
+        let date = parse_qif_date(&raw_date, date_format)
+            .ok_or_else(|| format!("line {number}: invalid date '{raw_date}'"))?;
+        let money = |column| match field(column) {
+            Some(value) => parse_money(&value).map_err(|err| format!("line {number}: {err}")),
+            None => Ok(0.0),
+        };
+        let outflow = money(Some(outflow_column))?;
+        let inflow = money(inflow_column)?;
+        if outflow - inflow <= 0.0 {
+            statement.skipped_credits += 1;
+            continue;
+        }
+        let payee = field(payee_column);
+        statement.transactions.push(Transaction {
+            date,
+            amount: outflow - inflow,
+            description: field(memo_column)
+                .or_else(|| payee.clone())
+                .unwrap_or_default(),
+            payee,
+            category: field(category_column),
+        });
+    }
+    Ok(statement)
+}
+
+// Categories for the accounts or categories of another finance app, read
+// from a TOML file of `"Expenses:Food:Dining" = "Dining"` lines. A name
+// also maps everything below it, such as Expenses:Food:Dining:Lunch; the
+// longest match wins.
+#[derive(Default)]
+pub struct Mapping(BTreeMap<String, String>);
+
+impl Mapping {
+    pub fn load(path: &Path) -> Result<Mapping, String> {
+        let text = fs::read_to_string(path).map_err(|err| format!("{}: {err}", path.display()))?;
+        let map = toml::from_str(&text).map_err(|err| format!("{}: {err}", path.display()))?;
+        Ok(Mapping(map))
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .filter(|(from, _)| {
+                name == from.as_str()
+                    || name
+                        .strip_prefix(from.as_str())
+                        .is_some_and(|rest| rest.starts_with(':'))
+            })
+            .max_by_key(|(from, _)| from.len())
+            .map(|(_, to)| to.as_str())
+    }
+}
+
+// Parse GnuCash's "Export Transactions to CSV" file, in either layout. The
+// splits to expense accounts become the transactions: those under the
+// top-level Expenses account, and any account in `mapping`. Unmapped
+// accounts are named by their last part, so Expenses:Auto:Fuel is Fuel.
+pub fn parse_gnucash(
+    text: &str,
+    date_format: Option<&str>,
+    mapping: &Mapping,
+) -> Result<Statement, String> {
+    let mut statement = Statement {
+        transactions: Vec::new(),
+        skipped_credits: 0,
+    };
+    let (mut reader, headers) = csv_reader(text)?;
+    let column = |name: &str| headers.iter().position(|h| h == name);
+    let date_column = column("date").ok_or("no Date column")?;
+    let account_column = column("full account name").ok_or("no Full Account Name column")?;
+    let amount_column = headers
+        .iter()
+        .position(|h| h.starts_with("amount num"))
+        .ok_or("no Amount Num. column")?;
+    let (description_column, notes_column) = (column("description"), column("notes"));
+    let memo_column = column("memo");
+
+    // Split rows after a transaction's first leave its date and
+    // description empty.
+    let mut current: Option<(NaiveDate, Option<String>, Option<String>)> = None;
+    for (number, record) in (2..).zip(reader.records()) {
+        let record = record.map_err(|err| format!("line {number}: {err}"))?;
+        let field = |column: Option<usize>| {
+            column
+                .and_then(|c| record.get(c))
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+        if let Some(raw_date) = field(Some(date_column)) {
+            let date = parse_qif_date(&raw_date, date_format)
+                .ok_or_else(|| format!("line {number}: invalid date '{raw_date}'"))?;
+            current = Some((date, field(description_column), field(notes_column)));
+        }
+        let Some((date, description, notes)) = &current else {
+            return Err(format!("line {number}: split before any transaction"));
+        };
+        let Some(account) = field(Some(account_column)) else {
+            continue;
+        };
+        let category = match mapping.get(&account) {
+            Some(category) => category.to_string(),
+            None if account.starts_with("Expenses:") => {
+                account.rsplit(':').next().unwrap_or_default().to_string()
+            }
+            None => continue,
+        };
+        let amount = match field(Some(amount_column)) {
+            Some(value) => parse_money(&value).map_err(|err| format!("line {number}: {err}"))?,
+            None => continue,
+        };
+        // A credit to an expense account is a refund.
+        if amount <= 0.0 {
+            statement.skipped_credits += 1;
+            continue;
+        }
+        statement.transactions.push(Transaction {
+            date: *date,
+            amount,
+            description: field(memo_column)
+                .or_else(|| notes.clone())
+                .or_else(|| description.clone())
+                .unwrap_or_default(),
+            payee: description.clone(),
+            category: Some(category),
+        });
+    }
+    Ok(statement)
+}
+
+// Parse a Money Manager EX CSV export. Withdrawals are the transactions;
+// without a Type column, negative amounts are. Categories are read as
+// Category:SubCategory and looked up in `mapping`.
+pub fn parse_mmex(
+    text: &str,
+    date_format: Option<&str>,
+    mapping: &Mapping,
+) -> Result<Statement, String> {
+    let mut statement = Statement {
+        transactions: Vec::new(),
+        skipped_credits: 0,
+    };
+    let (mut reader, headers) = csv_reader(text)?;
+    let column = |name: &str| headers.iter().position(|h| h == name);
+    let date_column = column("date").ok_or("no Date column")?;
+    let amount_column = column("amount").ok_or("no Amount column")?;
+    let (payee_column, notes_column) = (column("payee"), column("notes"));
+    let (category_column, subcategory_column) = (column("category"), column("subcategory"));
+    let type_column = column("type");
+
+    for (number, record) in (2..).zip(reader.records()) {
+        let record = record.map_err(|err| format!("line {number}: {err}"))?;
+        let field = |column: Option<usize>| {
+            column
+                .and_then(|c| record.get(c))
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+        let Some(raw_date) = field(Some(date_column)) else {
+            continue;
+        };
+        let date = parse_qif_date(&raw_date, date_format)
+            .ok_or_else(|| format!("line {number}: invalid date '{raw_date}'"))?;
+        let raw_amount = field(Some(amount_column)).unwrap_or_default();
+        let amount = parse_money(&raw_amount).map_err(|err| format!("line {number}: {err}"))?;
+        let spent = match field(type_column).map(|t| t.to_ascii_lowercase()) {
+            Some(kind) if kind == "withdrawal" => amount.abs(),
+            // Deposits and transfers between accounts aren't spending.
+            Some(_) => 0.0,
+            None => -amount,
+        };
+        if spent <= 0.0 {
+            statement.skipped_credits += 1;
+            continue;
+        }
+        let category = match (field(category_column), field(subcategory_column)) {
+            (Some(category), Some(sub)) => Some(format!("{category}:{sub}")),
+            (category, _) => category,
+        };
+        let payee = field(payee_column);
+        statement.transactions.push(Transaction {
+            date,
+            amount: spent,
+            description: field(notes_column)
+                .or_else(|| payee.clone())
+                .unwrap_or_default(),
+            payee,
+            category: category.map(|c| mapping.get(&c).map_or(c.clone(), str::to_string)),
+        });
+    }
+    Ok(statement)
+}
+
+// A CSV reader over `text` and its column names, lowercased.
+fn csv_reader(text: &str) -> Result<(csv::Reader<&[u8]>, Vec<String>), String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(text.trim_start_matches('\u{feff}').as_bytes());
+    let headers = reader
+        .headers()
+        .map_err(|err| err.to_string())?
+        .iter()
+        .map(|h| h.trim().to_ascii_lowercase())
+        .collect();
+    Ok((reader, headers))
+}
+
+// An amount as YNAB writes it, with a currency symbol such as $1,234.50.
+fn parse_money(value: &str) -> Result<f64, String> {
+    let digits: String = value
+        .chars()
+        .filter(|c| c.is_ascii_digit() || matches!(c, '.' | '-'))
+        .collect();
+    parse_amount(&digits).map_err(|_| format!("invalid amount '{value}'"))
+}
+
+fn parse_amount(value: &str) -> Result<f64, String> {
+    value
+        .replace(',', "")
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid amount '{value}'"))
+}
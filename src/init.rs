@@ -0,0 +1,173 @@
+use std::{fs, io, path::PathBuf};
+
+use crate::budget::Budget;
+use crate::config::{self, Config};
+use crate::currency;
+use crate::dates;
+use crate::prompt;
+use crate::storage;
+
+// Categories offered to start with
+pub const STARTER_CATEGORIES: [&str; 10] = [
+    "Food",
+    "Groceries",
+    "Transport",
+    "Rent",
+    "Utilities",
+    "Health",
+    "Entertainment",
+    "Shopping",
+    "Travel",
+    "Education",
+];
+
+// Ask until `parse` accepts the answer. None when input has ended.
+fn ask_until<T>(
+    question: &str,
+    default: Option<&str>,
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> Option<T> {
+    loop {
+        let answer = prompt::ask(question, default)?;
+        match parse(&answer) {
+            Ok(value) => return Some(value),
+            Err(err) => println!("{err}"),
+        }
+    }
+}
+
+// Parse `Food=8000, Rent=20000` into budgets per category.
+fn parse_budgets(s: &str) -> Result<Vec<(String, f64)>, String> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let invalid = || format!("'{part}' should be CATEGORY=AMOUNT (for example, Food=8000)");
+            let (category, amount) = part.split_once('=').ok_or_else(invalid)?;
+            let amount: f64 = amount.trim().parse().map_err(|_| invalid())?;
+            if category.trim().is_empty() || amount <= 0.0 {
+                return Err(invalid());
+            }
+            Ok((category.trim().to_string(), amount))
+        })
+        .collect()
+}
+
+// Ask for the data location, currency, categories and budgets, then write
+// the config file and set up the ledger. Settings already in the config
+// file are kept unless answered anew.
+pub fn run(config: &Config) -> io::Result<()> {
+    let Some(path) = config::path() else {
+        return Err(io::Error::other(
+            "no config directory found; set $EXPENSE_TRACKER_CONFIG",
+        ));
+    };
+    let existing = match fs::read_to_string(&path) {
+        Ok(text) => Some(text),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+        Err(err) => return Err(err),
+    };
+    if existing.is_some()
+        && !prompt::confirm(&format!(
+            "{} already exists. Update it, keeping its other settings?",
+            path.display()
+        ))
+    {
+        println!("Nothing changed.");
+        return Ok(());
+    }
+    let ended = || io::Error::new(io::ErrorKind::UnexpectedEof, "input ended");
+
+    let default_dir = config
+        .data_dir
+        .clone()
+        .or_else(config::data_dir)
+        .unwrap_or_else(|| PathBuf::from("."));
+    let data_dir = PathBuf::from(
+        prompt::ask(
+            "Where to keep your expenses",
+            Some(&default_dir.display().to_string()),
+        )
+        .ok_or_else(ended)?,
+    );
+    let code = ask_until(
+        "Currency",
+        Some(config.currency.as_deref().unwrap_or(currency::DEFAULT_CODE)),
+        currency::parse_code,
+    )
+    .ok_or_else(ended)?;
+
+    println!("\nStarter categories: {}", STARTER_CATEGORIES.join(", "));
+    let mut categories: Vec<String> = Vec::new();
+    if prompt::confirm("Use them?") {
+        categories.extend(STARTER_CATEGORIES.iter().map(|c| c.to_string()));
+    }
+    let extra = prompt::ask("Other categories, separated by commas", Some("")).ok_or_else(ended)?;
+    for category in extra.split(',').map(str::trim).filter(|c| !c.is_empty()) {
+        if !categories.iter().any(|c| c.eq_ignore_ascii_case(category)) {
+            categories.push(category.to_string());
+        }
+    }
+    let budgets = ask_until(
+        "Monthly budgets, such as Food=8000, Rent=20000",
+        Some(""),
+        parse_budgets,
+    )
+    .ok_or_else(ended)?;
+
+    // The config file, with the answers replacing any earlier values
+    let mut table: toml::Table = match &existing {
+        Some(text) => toml::from_str(text).map_err(io::Error::other)?,
+        None => toml::Table::new(),
+    };
+    table.insert(
+        String::from("data_dir"),
+        toml::Value::String(data_dir.display().to_string()),
+    );
+    table.insert(String::from("currency"), toml::Value::String(code.clone()));
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, toml::to_string(&table).map_err(io::Error::other)?)?;
+    fs::create_dir_all(&data_dir)?;
+
+    // The ledger, added to if one is already there
+    let data_file = data_dir.join("expenses.json").display().to_string();
+    let config = Config {
+        data_dir: Some(data_dir),
+        currency: Some(code.clone()),
+        ..config.clone()
+    };
+    let storage = storage::open(&data_file, &config);
+    let mut tracker = storage.load()?;
+    if tracker.currency.is_none() {
+        tracker.currency = Some(code);
+    }
+    for category in categories {
+        if !tracker
+            .categories
+            .iter()
+            .any(|c| c.eq_ignore_ascii_case(&category))
+        {
+            tracker.categories.push(category);
+        }
+    }
+    let this_month = dates::period_of(dates::now(config.timezone).date);
+    for (category, amount) in budgets {
+        tracker
+            .budgets
+            .retain(|b| !b.category.eq_ignore_ascii_case(&category));
+        tracker.budgets.push(Budget {
+            category,
+            amount,
+            rollover: false,
+            since: this_month,
+        });
+    }
+    storage.save(&tracker)?;
+
+    println!("\nWrote {}.", path.display());
+    println!("Expenses are kept in {data_file}.");
+    println!("Add the first one with: expense-tracker-rs add");
+    Ok(())
+}
@@ -0,0 +1,219 @@
+use chrono::{Datelike, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::currency;
+use crate::expense::{Expense, ExpenseTracker, Item};
+use crate::locale;
+
+// Category EMIs are logged in unless the loan names one
+pub const CATEGORY: &str = "Loan";
+
+// Money borrowed and paid back in equal monthly instalments
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Loan {
+    pub name: String,
+    pub principal: f64,
+    // Yearly interest rate, in percent
+    pub rate: f64,
+    pub emi: f64,
+    // How many instalments it runs for
+    pub months: u32,
+    // The date of the first instalment
+    pub start: NaiveDate,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    // How many instalments `loan post` has logged as expenses
+    #[serde(default)]
+    pub posted: u32,
+}
+
+// One monthly payment and how it splits
+pub struct Instalment {
+    pub number: u32,
+    pub date: NaiveDate,
+    pub payment: f64,
+    pub interest: f64,
+    pub principal: f64,
+    // What is still owed after it
+    pub balance: f64,
+}
+
+fn cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+// The instalment that pays off `principal` over `months` months at a
+// yearly `rate` percent.
+pub fn emi(principal: f64, rate: f64, months: u32) -> f64 {
+    let r = rate / 1200.0;
+    if r == 0.0 {
+        return cents(principal / f64::from(months));
+    }
+    let growth = (1.0 + r).powi(months as i32);
+    cents(principal * r * growth / (growth - 1.0))
+}
+
+impl Loan {
+    pub fn category(&self) -> &str {
+        self.category.as_deref().unwrap_or(CATEGORY)
+    }
+
+    // Every instalment until the loan is paid off. The last one clears
+    // whatever rounding or a short EMI left over.
+    pub fn schedule(&self) -> Vec<Instalment> {
+        let r = self.rate / 1200.0;
+        let mut balance = self.principal;
+        let mut instalments = Vec::new();
+        for number in 1..=self.months {
+            if balance <= 0.0 {
+                break;
+            }
+            let interest = cents(balance * r);
+            let payment = if number == self.months {
+                balance + interest
+            } else {
+                self.emi.min(balance + interest)
+            };
+            let principal = cents(payment - interest);
+            balance = cents(balance - principal);
+            instalments.push(Instalment {
+                number,
+                date: self.start + Months::new(number - 1),
+                payment: cents(payment),
+                interest,
+                principal,
+                balance,
+            });
+        }
+        instalments
+    }
+
+    // What is still owed once every instalment up to `today` is paid
+    pub fn outstanding(&self, today: NaiveDate) -> f64 {
+        self.schedule()
+            .iter()
+            .take_while(|i| i.date <= today)
+            .last()
+            .map_or(self.principal, |i| i.balance)
+    }
+
+    // The instalment as an expense, itemised into interest and principal.
+    pub fn expense(&self, instalment: &Instalment) -> Expense {
+        Expense {
+            date: instalment.date,
+            category: self.category().to_string(),
+            amount: instalment.payment,
+            description: format!(
+                "EMI {} of {}: {}",
+                instalment.number, self.months, self.name
+            ),
+            items: vec![
+                Item {
+                    name: String::from("Interest"),
+                    quantity: 1.0,
+                    price: instalment.interest,
+                },
+                Item {
+                    name: String::from("Principal"),
+                    quantity: 1.0,
+                    price: instalment.principal,
+                },
+            ],
+            ..Default::default()
+        }
+    }
+}
+
+pub fn find<'a>(tracker: &'a ExpenseTracker, name: &str) -> Option<&'a Loan> {
+    tracker
+        .loans
+        .iter()
+        .find(|l| l.name.eq_ignore_ascii_case(name))
+}
+
+pub fn print_list(tracker: &ExpenseTracker, today: NaiveDate) {
+    if tracker.loans.is_empty() {
+        println!("No loans.");
+        return;
+    }
+    for loan in tracker.loans.iter() {
+        let schedule = loan.schedule();
+        let paid: Vec<&Instalment> = schedule.iter().filter(|i| i.date <= today).collect();
+        let interest_paid: f64 = paid.iter().map(|i| i.interest).sum();
+        let interest_total: f64 = schedule.iter().map(|i| i.interest).sum();
+        println!(
+            "{}: {} at {}% over {} months, EMI {}",
+            loan.name,
+            currency::amount(loan.principal),
+            loan.rate,
+            loan.months,
+            currency::amount(loan.emi)
+        );
+        println!(
+            "  Paid {} of {} instalment(s), {} outstanding",
+            paid.len(),
+            schedule.len(),
+            currency::amount(loan.outstanding(today))
+        );
+        println!(
+            "  Interest: {} paid of {} in total",
+            currency::amount(interest_paid),
+            currency::amount(interest_total)
+        );
+    }
+}
+
+// The loan's instalments, or their totals a year when `yearly`.
+pub fn print_schedule(loan: &Loan, yearly: bool) {
+    let schedule = loan.schedule();
+    if yearly {
+        println!(
+            "{:<6} {:>14} {:>14} {:>14} {:>14}",
+            "Year", "Paid", "Interest", "Principal", "Balance"
+        );
+        let mut years: Vec<(i32, f64, f64, f64, f64)> = Vec::new();
+        for i in schedule.iter() {
+            match years.last_mut() {
+                Some(year) if year.0 == i.date.year() => {
+                    year.1 += i.payment;
+                    year.2 += i.interest;
+                    year.3 += i.principal;
+                    year.4 = i.balance;
+                }
+                _ => years.push((i.date.year(), i.payment, i.interest, i.principal, i.balance)),
+            }
+        }
+        for (year, paid, interest, principal, balance) in years {
+            println!(
+                "{:<6} {:>14} {:>14} {:>14} {:>14}",
+                year,
+                currency::amount(paid),
+                currency::amount(interest),
+                currency::amount(principal),
+                currency::amount(balance)
+            );
+        }
+    } else {
+        println!(
+            "{:>4} {:<10} {:>14} {:>14} {:>14} {:>14}",
+            "#", "Date", "Payment", "Interest", "Principal", "Balance"
+        );
+        for i in schedule.iter() {
+            println!(
+                "{:>4} {:<10} {:>14} {:>14} {:>14} {:>14}",
+                i.number,
+                locale::date(i.date),
+                currency::amount(i.payment),
+                currency::amount(i.interest),
+                currency::amount(i.principal),
+                currency::amount(i.balance)
+            );
+        }
+    }
+    let interest: f64 = schedule.iter().map(|i| i.interest).sum();
+    println!(
+        "\nTotal interest: {}, total paid: {}",
+        currency::amount(interest),
+        currency::amount(loan.principal + interest)
+    );
+}
@@ -0,0 +1,88 @@
+use std::sync::OnceLock;
+
+use chrono::NaiveDate;
+
+use crate::currency::Grouping;
+
+// How a region writes dates and numbers
+#[derive(Debug, Clone, Copy)]
+pub struct Format {
+    // strftime format of a day
+    pub date: &'static str,
+    // Digit grouping, when the region has its own rather than the currency's
+    pub grouping: Option<Grouping>,
+    pub thousands: char,
+    pub decimal: char,
+}
+
+// Without a region in the locale: ISO dates and the currency's grouping
+const DEFAULT: Format = Format {
+    date: "%Y-%m-%d",
+    grouping: None,
+    thousands: ',',
+    decimal: '.',
+};
+
+const fn western(date: &'static str, thousands: char, decimal: char) -> Format {
+    Format {
+        date,
+        grouping: Some(Grouping::Western),
+        thousands,
+        decimal,
+    }
+}
+
+// Regions by their two-letter code
+const REGIONS: [(&str, Format); 12] = [
+    (
+        "IN",
+        Format {
+            date: "%d-%m-%Y",
+            grouping: Some(Grouping::Indian),
+            thousands: ',',
+            decimal: '.',
+        },
+    ),
+    ("US", western("%m/%d/%Y", ',', '.')),
+    ("GB", western("%d/%m/%Y", ',', '.')),
+    ("AU", western("%d/%m/%Y", ',', '.')),
+    ("CA", western("%Y-%m-%d", ',', '.')),
+    ("SG", western("%d/%m/%Y", ',', '.')),
+    ("AE", western("%d/%m/%Y", ',', '.')),
+    ("JP", western("%Y/%m/%d", ',', '.')),
+    ("CN", western("%Y/%m/%d", ',', '.')),
+    ("DE", western("%d.%m.%Y", '.', ',')),
+    ("FR", western("%d/%m/%Y", ' ', ',')),
+    ("ES", western("%d/%m/%Y", '.', ',')),
+];
+
+static FORMAT: OnceLock<Format> = OnceLock::new();
+
+// The region of a locale such as en-IN or hi_IN.UTF-8, if it has one.
+fn region(locale: &str) -> Option<&str> {
+    let locale = locale.split(['.', '@']).next().unwrap_or_default();
+    locale.split(['_', '-']).nth(1)
+}
+
+// Write dates and numbers the way the region of `locale` does. Only the
+// configured locale counts, so output doesn't change with the environment.
+pub fn init(locale: Option<&str>) {
+    let format = locale
+        .and_then(region)
+        .and_then(|region| {
+            REGIONS
+                .iter()
+                .find(|(code, _)| code.eq_ignore_ascii_case(region))
+        })
+        .map_or(DEFAULT, |(_, format)| *format);
+    let _ = FORMAT.set(format);
+}
+
+pub fn current() -> &'static Format {
+    FORMAT.get_or_init(|| DEFAULT)
+}
+
+// `date` as the region writes it.
+pub fn date(date: NaiveDate) -> String {
+    date.format(current().date).to_string()
+}
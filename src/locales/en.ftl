@@ -0,0 +1,416 @@
+# Messages in English, in Fluent syntax. Other languages use the same ids,
+# and any they leave out are shown in English.
+
+## The command line
+
+cli-about = Keeps track of your expenses.
+cli-exit-codes = Exit codes: 0 success, 1 other failure, 2 usage error, 3 data error (the ledger or an input can't be read or saved), 4 not found, 5 conflict (such as a closed month or a name already taken).
+cli-init = Set up the config file and ledger by answering a few questions.
+cli-generate-demo = Make a ledger of realistic made-up expenses to try things out on.
+cli-add = Add a new expense.
+cli-delete = Delete an expense by row number, or every expense matching filters.
+cli-edit = Change the category, payee, description or tags of every expense matching filters.
+cli-summary = Summarize expenses by filtering or view all expenses.
+cli-list = List all expenses.
+cli-budget = Set and review monthly category budgets.
+cli-report = Reports over your expenses.
+cli-total = Print total expenses, optionally filtered.
+cli-search = Find expenses by description, payee or category.
+cli-stats = Descriptive statistics of expense amounts, overall and per category.
+cli-forecast = Project this month's total from spending so far.
+cli-archive = Move old expenses into one archive file per year.
+cli-snapshot = Save named copies of the ledger to compare with `diff`.
+cli-snapshot-create = Save the ledger as it is now, replacing a snapshot with the same name.
+cli-snapshot-remove = Remove a snapshot.
+cli-snapshot-list = List the snapshots.
+cli-diff = Show the expenses added, removed and modified since a snapshot.
+cli-history = Show who added, edited or deleted expenses, and when.
+cli-show = Show every field of an expense, with its items, attachments and history.
+cli-balances = Show who owes whom for shared expenses.
+cli-settle = Record a payment that settles shared expenses.
+cli-debt = Track money lent to or borrowed from people.
+cli-debt-add = Record money lent to someone, or borrowed with --borrowed.
+cli-debt-repay = Record a repayment, either way, of what is owed with someone.
+cli-debt-list = Every loan and repayment with the running balance.
+cli-debt-balances = What each person owes you or is owed.
+cli-assets = Note the balances of accounts, investments and liabilities.
+cli-assets-set = Record an account's balance, adding the account if it's new.
+cli-assets-remove = Remove an account and its balances.
+cli-assets-list = The latest balance of every account and the net worth.
+cli-category = Set up categories and the buckets they roll up into.
+cli-category-list = The categories set up and their buckets.
+cli-category-bucket = Put a category in a bucket, such as Needs, Wants or Savings.
+cli-category-preset = Ready-made sets of categories.
+cli-category-preset-list = The presets and their categories.
+cli-category-preset-apply = Add a preset's categories and buckets, keeping those already set up.
+cli-budget-set = Set the monthly budget of a category.
+cli-budget-remove = Remove the budget of a category.
+cli-budget-status = Budget, spend and remaining amount per category.
+cli-budget-envelope = Envelope balances including amounts rolled over.
+cli-goal = Set savings goals and track progress.
+cli-goal-add = Add a savings goal.
+cli-goal-remove = Remove a savings goal.
+cli-goal-status = Progress towards every goal.
+cli-subscription = Keep track of recurring services and when they renew.
+cli-subscription-add = Add a subscription.
+cli-subscription-remove = Remove a subscription.
+cli-subscription-list = Every subscription and what they cost a month and a year.
+cli-subscription-upcoming = Subscriptions renewing soon.
+cli-loan = Track loans paid back in monthly instalments (EMIs).
+cli-loan-add = Add a loan.
+cli-loan-remove = Remove a loan. Instalments already logged are kept.
+cli-loan-list = Every loan with what is paid and still outstanding.
+cli-loan-schedule = How each instalment splits into interest and principal.
+cli-loan-post = Log every instalment due by today as an expense.
+cli-reimburse = Track reimbursable expenses from pending to reimbursed.
+cli-reimburse-list = List reimbursable expenses.
+cli-reimburse-submit = Mark pending expenses as submitted for reimbursement.
+cli-reimburse-settle = Mark submitted expenses as reimbursed.
+cli-view = Save filter combinations under a name to reuse with --view.
+cli-view-save = Save the given filters as a view, replacing any with the same name.
+cli-view-remove = Remove a saved view.
+cli-view-list = List the saved views.
+cli-template = Named templates for expenses you log often.
+cli-template-add = Define a template, replacing any with the same name.
+cli-template-remove = Remove a template.
+cli-template-list = List the templates.
+cli-rates = Exchange rates for expenses in other currencies.
+cli-rates-set = Set what one unit of a currency is worth in the ledger's currency.
+cli-rates-list = List the exchange rates set.
+cli-rates-update = Fetch and cache daily rates for the ledger's currency.
+cli-backup = Backups taken automatically before every change to the data file.
+cli-backup-list = List the backups, newest first.
+cli-backup-restore = Replace the data file with a backup. The current file is backed up first.
+cli-encrypt = Encrypt the data file and its backups with a passphrase.
+cli-decrypt = Store the data file as plain JSON again.
+cli-doctor = Check the data file for problems.
+cli-migrate = Upgrade the data file to the current format version.
+cli-serve = Serve the ledger over HTTP as a JSON API.
+cli-bot = Log expenses sent as chat messages.
+cli-bot-telegram = Run a Telegram bot that adds the expenses it is sent.
+cli-sync = Share the ledger between machines through git.
+cli-sync-init = Make the data directory a git repository that commits every change.
+cli-sync-push = Send the committed changes to origin.
+cli-sync-pull = Fetch changes from origin and merge them expense by expense.
+cli-sync-sheets = Add the rows added to a Google Sheet, then rewrite the sheet with every expense.
+cli-sync-resolve = Choose between the versions of expenses both machines changed.
+cli-sync-merge = The git merge driver for data files.
+cli-close-month = Lock a month against further changes.
+cli-guide = Read built-in tutorials.
+cli-import = Import expenses from a bank statement.
+cli-export = Export expenses to another tool's format.
+cli-query = Answer a question about the expenses in SQL.
+cli-chart = Render spending charts to SVG or PNG files.
+cli-report-payees = Total spend per payee.
+cli-report-trend = Monthly totals and month-over-month change.
+cli-report-anomalies = Expenses much larger than is usual for their category, such as typos with an extra zero.
+cli-report-streaks = Runs of days without spending, or spending under a daily limit.
+cli-report-networth = Net worth at the end of each month, next to the spending so far.
+cli-report-rolling = Rolling average spend per category, to smooth out lumpy months.
+cli-report-breakdown = Spend per category and its share of the total.
+cli-report-trip = Totals and a breakdown of a trip, or every trip when none is named.
+cli-report-project = An expense statement for a project or client, or every project when none is named.
+cli-report-hours = Spend by hour of the day, for expenses recorded with a time.
+cli-report-household = What each household member spent per category, with their share of the total.
+cli-report-compare = Spend per category in two months or years side by side.
+cli-report-calendar = A month calendar of daily spending.
+cli-report-top = The largest expenses and categories.
+cli-report-monthly = A monthly spending document in Markdown, HTML or PDF.
+cli-report-yearly = Spend per category in each month of a year.
+cli-report-tax = Deductible spend per category for a fiscal year.
+cli-report-email = Send the monthly report by email.
+cli-report-custom = Run a report written as a Rhai script.
+
+## Command line options
+
+# Options whose help is the same wherever they appear are named after the
+# option alone, others after the command too.
+help-mask_private = Hide amounts and descriptions of private expenses in output.
+help-no_keyring = Don't read or save the encryption passphrase in the system keyring.
+help-remote = Use the ledger served by `serve` at this URL instead of the local file; only add, list and summary work this way. (for example, http://host:8080)
+help-token = The API token `serve` requires and --remote sends, instead of $EXPENSE_TRACKER_TOKEN or server_token in the config file.
+help-verbose = Log what's happening to standard error; -vv for more detail, -vvv for everything.
+help-log_file = Also log to this file, instead of log_file in the config file.
+help-filter-dry_run = Show what would change in the ledger without saving it.
+help-read_only = Refuse any change to the ledger, such as on a machine that should only run reports.
+help-data_dir = Use the ledger in this directory instead of the configured one, such as one made by `generate-demo`.
+help-currency = Report in this currency, converting at the stored rates; with `add`, the currency the expense was paid in. (for example, USD)
+help-only = Only count business or only personal expenses, in every report and listing. (business or personal)
+help-generate-demo-months = How many months of expenses to make, ending today.
+help-dir = Where to put the demo ledger. (default: demo, next to your data directory's ledger)
+help-seed = Make the same ledger every time for this seed.
+help-add-category = The category of the expense.
+help-add-amount = The expense amount.
+help-add-description = A description for the expense.
+help-when = When the expense was made, with or without a time; defaults to now. (format: 2025-12-31 or 2025-12-31 14:30)
+help-tz = The timezone the expense was made in, such as Asia/Tokyo or +09:00; defaults to timezone in the config file, or the system's.
+help-add-payee = The payee or merchant of the expense.
+help-add-trip = The trip or place to log the expense under, e.g. goa-2025.
+help-add-project = The client project to bill the expense to, e.g. acme-website.
+help-add-user = The household member who spent it; defaults to user in the config file, or your login name.
+help-split = Split the expense across categories. (format: groceries=300, repeatable)
+help-item = Itemize the bill; the items must add up to the amount, which they make up when it isn't given. (format: milk=2x45 or bread=40, repeatable)
+help-split_with = Share the expense equally with these people. (format: alice,bob)
+help-paid_by = Who paid for a shared expense, if not you.
+help-add-tag = Tag the expense, e.g. --tag work,travel. (repeatable)
+help-add-meta = Record a field of your own, e.g. --meta warranty_until=2026-03-01. (repeatable)
+help-add-template = Fill in the fields from this template; other flags override them.
+help-stdin = Read one expense per line from standard input, as `date,category,amount,description[,payee]` CSV or quick-add shorthand like `250 coffee #food @Cafe`.
+help-private = Hide this expense's details when output is masked.
+help-deductible = Include this expense in the year-end tax report.
+help-business = Log this as a business expense rather than a personal one.
+help-reimbursable = Track this expense as one to be paid back.
+help-attach = Attach a receipt image or PDF. (repeatable)
+help-row_number = Delete an expense by row number.
+help-delete-yes = Delete without asking.
+help-purge_attachments = Also delete the expense's attached receipts.
+help-set = Set category, payee, trip, project, user, business (yes or no), meta.KEY or description, as field=value. (repeatable)
+help-add_tag = Add this tag. (repeatable)
+help-remove_tag = Remove this tag. (repeatable)
+help-edit-yes = Edit matching expenses without asking.
+help-editor = Open the matching expenses, or all of them, in $EDITOR as TOML. Removing one deletes it.
+help-summary-date = Filter by exact date. (format: 2025-12-31)
+help-summary-month = Filter by month.
+help-summary-fy = Filter by fiscal year, starting in the month set by fiscal_year_start in the config file, or April. (format: 2024-25 or 2024)
+help-quarter = Filter by calendar quarter. (format: 2025-Q1)
+help-summary-year = Filter by calendar year. (format: 2025)
+help-all = Total expenses.
+help-total-month = Only count this month.
+help-query = Text to look for, ignoring case.
+help-regex = Treat the query as a regular expression.
+help-stats-month = Only include this month. (format: 2025-01)
+help-stats-from = Only include expenses on or after this date. (format: 2025-01-01)
+help-stats-to = Only include expenses on or before this date. (format: 2025-12-31)
+help-as_of = Forecast as if today were this date. (format: 2025-12-31)
+help-history = How many previous months to average daily spend over.
+help-cumulative = List by date with a running total for each month.
+help-budget = Compare the running total with this monthly amount, or with the budgets set when no amount is given.
+help-archive-before = Archive the expenses dated before this day. (format: 2024-01-01)
+help-snapshot-create-name = The name of the snapshot.
+help-snapshot-remove-name = The name of the snapshot.
+help-snapshot = The snapshot to compare from.
+help-other = The snapshot to compare to; the current ledger when omitted.
+help-history-id = Show every change to the expense with this id.
+help-history-limit = How many of the latest changes to show without an id.
+help-at = List the expenses as they stood at the end of this day instead. (format: 2025-03-01)
+help-show-id = The id of the expense.
+help-open = Open the attachments with the default application.
+help-settle-amount = The amount paid.
+help-settle-from = Who paid.
+help-settle-to = Who was paid.
+help-debt-add-person = Who the money went to or came from.
+help-debt-add-amount = The amount lent or borrowed.
+help-debt-add-description = What it was for.
+help-borrowed = The money was borrowed from them rather than lent.
+help-debt-add-date = When; defaults to today. (format: 2025-12-31)
+help-debt-repay-person = Who paid back or was paid back.
+help-debt-repay-amount = The amount repaid; defaults to everything owed.
+help-debt-repay-date = When; defaults to today. (format: 2025-12-31)
+help-debt-list-person = Only list those with this person.
+help-assets-set-name = The name of the account.
+help-assets-set-amount = The balance.
+help-liability = A new account is owed rather than owned, such as a loan or credit card.
+help-assets-set-date = The day of the balance; defaults to today. (format: 2025-12-31)
+help-assets-remove-name = The name of the account.
+help-category-bucket-category = The category.
+help-bucket = The bucket; leave out to take the category out of its bucket.
+help-category-preset-apply-name = The preset.
+help-budget-set-category = The category to budget.
+help-budget-set-amount = The monthly budget.
+help-rollover = Carry unspent budget and overspend into the next month.
+help-budget-set-since = The first month the budget applies to; defaults to this month. (format: 2025-01)
+help-budget-remove-category = The category whose budget to remove.
+help-budget-status-month = The month to review; defaults to this month. (format: 2025-01)
+help-budget-envelope-month = The month to review; defaults to this month. (format: 2025-01)
+help-goal-add-name = The name of the goal.
+help-target = The amount to save.
+help-by = The month to reach the target by. (format: 2026-06)
+help-goal-add-category = Count expenses in this savings category as saved.
+help-income = Count this monthly income minus all spending as saved.
+help-goal-add-since = The month saving started; defaults to this month. (format: 2025-01)
+help-goal-remove-name = The name of the goal.
+help-subscription-add-name = The name of the service.
+help-subscription-add-amount = What each renewal costs.
+help-day = The day of the month it renews on.
+help-every = How often it renews.
+help-subscription-add-month = The month of the year a yearly subscription renews in. (1-12)
+help-subscription-add-category = The category its expenses go in.
+help-url = Where to cancel it.
+help-notes = Anything else worth remembering, such as how to cancel.
+help-subscription-remove-name = The name of the service.
+help-days = How many days ahead to look.
+help-loan-add-name = The name of the loan.
+help-principal = The amount borrowed.
+help-loan-add-rate = The yearly interest rate, in percent.
+help-loan-add-months = How many monthly instalments it runs for.
+help-emi = The monthly instalment, if the lender's differs from the usual formula.
+help-start = The date of the first instalment; defaults to today. (format: 2025-01-05)
+help-loan-add-category = The category instalments are logged in; defaults to Loan.
+help-loan-remove-name = The name of the loan.
+help-loan-schedule-name = The name of the loan.
+help-yearly = Total the instalments of each year.
+help-status = Only list claims in this state.
+help-ids = The ids of the expenses.
+help-view-save-name = The name of the view.
+help-view-save-month = Only include this month number, in any year.
+help-view-remove-name = The name of the view.
+help-template-add-name = The name of the template.
+help-template-add-amount = The usual amount.
+help-template-add-category = The category.
+help-template-add-description = The description; defaults to the template's name.
+help-template-add-payee = The payee or merchant.
+help-template-remove-name = The name of the template.
+help-code = The currency code. (for example, EUR)
+help-rates-set-rate = The value of one unit.
+help-rates-set-date = The day the rate applies from; defaults to today. (format: 2025-12-31)
+help-rates-update-date = The day to fetch; defaults to today. (format: 2025-12-31)
+help-timestamp = The backup to restore, as shown by `backup list`; a prefix picks the newest match, and the latest is used when omitted. (format: 20250131-184500.250)
+help-backup-restore-yes = Restore without asking.
+help-fix = Repair ids, drop unknown fields and forget missing attachments.
+help-migrate-dry_run = Check the upgrade without writing anything.
+help-port = The port to listen on.
+help-bind = The address to listen on; 0.0.0.0 for every interface.
+help-origin = The repository to push to and pull from.
+help-spreadsheet_id = The spreadsheet, from its URL, instead of spreadsheet_id under [sheets] in the config file.
+help-keep = Settle every conflict this way instead of asking.
+help-close-month-month = The month to close. (format: 2025-12)
+help-checklist = Verify the month is ready before closing it.
+help-force = Close the month even if checks fail.
+help-reopen = Unlock a previously closed month.
+help-topic = The guide to show. Lists all guides when omitted.
+help-no_pager = Print the guide instead of opening it in a pager.
+help-file = The statement file.
+help-import-format = The statement format; ynab is a CSV with YNAB's Date, Payee, Memo, Outflow and Inflow columns, gnucash GnuCash's transaction CSV export and mmex Money Manager EX's CSV export. Guessed from the file extension when omitted.
+help-map = A TOML file mapping GnuCash accounts or MMEX categories to categories, as "Expenses:Food:Dining" = "Dining" lines.
+help-import-category = Category for transactions the statement doesn't categorize.
+help-date_format = strftime format of dates in QIF and CSV files, e.g. %d/%m/%Y for day-first banks.
+help-include_duplicates = Also import transactions that look like existing expenses.
+help-import-yes = Import without asking for confirmation.
+help-export-format = The output format; xlsx is a spreadsheet of the expenses and their monthly totals per category, parquet a table of the expenses for pandas, polars or DuckDB, ynab a CSV for YNAB's file import.
+help-account = The account expenses are paid from.
+help-export-out = Write to this file instead of standard output.
+help-sql = The SELECT statement to run.
+help-query-format = The output format.
+help-query-out = Write CSV to this file instead of standard output.
+help-type = pie: categories, bar: monthly totals, line: cumulative spend.
+help-chart-out = The image file to write; .png for PNG, anything else for SVG.
+help-chart-month = Chart this month (pie and line), or the months up to it (bar). (format: 2025-01)
+help-chart-months = How many months the bar chart covers.
+help-report-template = Render this Tera template with the filtered expenses and their totals.
+help-report-out = Write the rendered template to this file instead of standard output.
+help-report-payees-year = Only include expenses from this year.
+help-report-trend-category = Only include this category.
+help-report-trend-months = How many months to show.
+help-end = The last month to show; defaults to this month. (format: 2025-12)
+help-sparkline = Also draw the totals as a sparkline.
+help-real = Adjust past totals for inflation, in the money of the latest index in the config file's [cpi] table.
+help-sigma = How many standard deviations above the mean count as unusual.
+help-report-streaks-limit = Also count the days that spent less than this.
+help-report-networth-months = How many months to show.
+help-window = How many months each average covers.
+help-report-rolling-months = How many months to show.
+help-last_year = Also compare the last month with the same month a year before, for seasonal spending.
+help-report-breakdown-month = Only include this month. (format: 2025-01)
+help-chart = Draw a bar for each category.
+help-by_bucket = Roll categories up into their buckets, such as needs, wants and savings.
+help-report-trip-name = The trip, as given to add --trip.
+help-report-project-name = The project, as given to add --project.
+help-client = Cover every project of this client.
+help-report-project-from = The first day to include. (format: 2025-01-01)
+help-report-project-to = The last day to include. (format: 2025-01-31)
+help-report-project-format = The output format.
+help-report-project-out = Write CSV to this file instead of standard output.
+help-a = The earlier period. (format: 2025-01 or 2025)
+help-b = The period to compare it with. (format: 2025-02 or 2025)
+help-report-compare-no_color = Mark the categories that grew most with ▲ instead of in red.
+help-report-calendar-month = The month to show; defaults to this month. (format: 2025-01)
+help-report-calendar-no_color = Don't shade days by spending.
+help-n = How many expenses and categories to show.
+help-report-top-month = Only include this month. (format: 2025-01)
+help-report-monthly-month = The month to report on. (format: 2025-01)
+help-report-monthly-format = The document format.
+help-report-monthly-out = Write to this file instead of standard output.
+help-report-yearly-year = The year to report on; defaults to this year.
+help-report-yearly-format = The output format.
+help-report-yearly-out = Write CSV to this file instead of standard output.
+help-report-tax-fy = The fiscal year to report on. (format: 2024-25)
+help-fy_start = The month the fiscal year starts in (1-12); defaults to fiscal_year_start in the config file, or April.
+help-report-tax-format = The output format.
+help-report-tax-out = Write CSV to this file instead of standard output.
+help-report-email-month = The month to report on; defaults to last month. (format: 2025-01)
+help-report-email-to = Send to these addresses instead of the ones in the config file. (repeatable)
+help-script = The script to run.
+help-report-custom-from = Only include expenses on or after this date. (format: 2025-01-01)
+help-report-custom-to = Only include expenses on or before this date. (format: 2025-12-31)
+help-view = Start from the filters saved in this view; other filter flags override them.
+help-filter-from = Only include expenses on or after this date. (format: 2025-01-01)
+help-filter-to = Only include expenses on or before this date. (format: 2025-12-31)
+help-after = Only include expenses after this date, not on it.
+help-filter-before = Only include expenses before this date, not on it.
+help-filter-category = Only include this category.
+help-filter-tag = Only include expenses with this tag. (repeatable)
+help-filter-payee = Only include this payee.
+help-filter-trip = Only include expenses logged under this trip.
+help-filter-project = Only include expenses billed to this project.
+help-filter-user = Only include expenses spent by this household member.
+help-filter-meta = Only include expenses with this field of their own, as KEY or KEY=VALUE. (repeatable)
+help-min_amount = Only include expenses of at least this amount.
+help-max_amount = Only include expenses of at most this amount.
+help-hours = Only include expenses made between these hours, e.g. 22-4 for late at night.
+
+## Questions
+
+# Answers taken as yes, separated by commas
+yes-answers = y, yes
+ask-amount = Amount
+ask-category = Category
+ask-category-hint = Category (e.g. { $examples })
+ask-pick = Pick a number, or press Enter to use "{ $answer }"
+ask-date = Date
+ask-description = Description
+ask-payee = Payee (optional)
+invalid-amount = '{ $answer }' is not a valid amount.
+invalid-pick = '{ $pick }' is not one of the choices.
+confirm-save = Save this expense?
+confirm-delete = Delete this expense?
+confirm-delete-many = Delete { $count } expense(s)?
+
+## Outcomes
+
+expense-added = Expense added successfully!
+expense-not-added = Expense not added.
+nothing-deleted = Nothing deleted.
+deleted-many = Deleted { $count } expense(s).
+no-expenses = No expenses found.
+total-expenses = Total expenses: { $total }
+
+## Labels
+
+label-date = Date
+label-category = Category
+label-amount = Amount
+label-description = Description
+label-payee = Payee
+label-budget = Budget
+label-spent = Spent
+label-remaining = Remaining
+label-used = Used
+label-total = Total
+label-share = Share
+label-change = Change
+label-change-percent = Change %
+label-month = Month
+label-net-worth = Net worth
+label-spent-to-date = Spent to date
+label-to-date = To date
+label-per-day = Per day
+label-projected = Projected
+label-count = Count
+label-mean = Mean
+label-median = Median
+label-min = Min
+label-max = Max
+label-std-dev = Std dev
+label-envelope = Envelope
+label-carried-in = Carried in
+label-balance = Balance
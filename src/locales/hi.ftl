@@ -0,0 +1,412 @@
+# हिन्दी में संदेश
+
+## The command line
+
+cli-about = आपके ख़र्चों का हिसाब रखता है।
+cli-exit-codes = निकास कोड: 0 सफल, 1 अन्य विफलता, 2 ग़लत प्रयोग, 3 डेटा त्रुटि (खाता या इनपुट पढ़ा या सहेजा नहीं जा सका), 4 नहीं मिला, 5 टकराव (जैसे बंद महीना या पहले से लिया गया नाम)।
+cli-init = कुछ सवालों के जवाब देकर कॉन्फ़िग फ़ाइल और खाता तैयार करें।
+cli-generate-demo = आज़माने के लिए बनावटी ख़र्चों का एक खाता बनाएँ।
+cli-add = नया ख़र्च जोड़ें।
+cli-delete = पंक्ति संख्या से एक ख़र्च, या फ़िल्टर से मेल खाने वाले सभी ख़र्च हटाएँ।
+cli-edit = फ़िल्टर से मेल खाने वाले हर ख़र्च की श्रेणी, प्राप्तकर्ता, विवरण या टैग बदलें।
+cli-summary = फ़िल्टर करके ख़र्चों का सारांश देखें, या सभी ख़र्च देखें।
+cli-list = सभी ख़र्चों की सूची।
+cli-budget = श्रेणियों के मासिक बजट तय करें और देखें।
+cli-report = आपके ख़र्चों पर रिपोर्टें।
+cli-total = कुल ख़र्च दिखाएँ, चाहें तो फ़िल्टर करके।
+cli-search = विवरण, प्राप्तकर्ता या श्रेणी से ख़र्च खोजें।
+cli-stats = ख़र्च की राशियों के आँकड़े, कुल और हर श्रेणी के।
+cli-forecast = अब तक के ख़र्च से इस महीने के कुल का अनुमान लगाएँ।
+cli-archive = पुराने ख़र्चों को हर साल की एक संग्रह फ़ाइल में ले जाएँ।
+cli-snapshot = खाते की नामित प्रतियाँ सहेजें, ताकि `diff` से तुलना हो सके।
+cli-snapshot-create = खाते को अभी की स्थिति में सहेजें; इसी नाम का स्नैपशॉट हो तो उसे बदल दें।
+cli-snapshot-remove = एक स्नैपशॉट हटाएँ।
+cli-snapshot-list = स्नैपशॉट की सूची।
+cli-diff = किसी स्नैपशॉट के बाद जोड़े, हटाए और बदले गए ख़र्च दिखाएँ।
+cli-history = किसने कब ख़र्च जोड़े, बदले या हटाए, यह दिखाएँ।
+cli-show = किसी ख़र्च का हर विवरण, उसकी मदों, संलग्नकों और इतिहास के साथ दिखाएँ।
+cli-balances = साझा ख़र्चों में किसका किस पर कितना बकाया है, दिखाएँ।
+cli-settle = साझा ख़र्चों का हिसाब चुकाने वाला भुगतान दर्ज करें।
+cli-debt = लोगों को दिए या उनसे लिए उधार का हिसाब रखें।
+cli-debt-add = किसी को दिया उधार दर्ज करें, या --borrowed के साथ लिया उधार।
+cli-debt-repay = किसी के साथ बकाया राशि की वापसी दर्ज करें, किसी भी ओर से।
+cli-debt-list = हर उधार और वापसी, चलते शेष के साथ।
+cli-debt-balances = हर व्यक्ति पर आपका कितना बकाया है या आप पर उनका कितना।
+cli-assets = खातों, निवेशों और देनदारियों की शेष राशि दर्ज करें।
+cli-assets-set = किसी खाते की शेष राशि दर्ज करें; खाता नया हो तो उसे जोड़ दें।
+cli-assets-remove = एक खाता और उसकी शेष राशियाँ हटाएँ।
+cli-assets-list = हर खाते की ताज़ा शेष राशि और कुल संपत्ति।
+cli-category = श्रेणियाँ और उनके समूह तय करें।
+cli-category-list = तय की गई श्रेणियाँ और उनके समूह।
+cli-category-bucket = किसी श्रेणी को एक समूह में रखें, जैसे Needs, Wants या Savings।
+cli-category-preset = श्रेणियों के तैयार सेट।
+cli-category-preset-list = तैयार सेट और उनकी श्रेणियाँ।
+cli-category-preset-apply = किसी तैयार सेट की श्रेणियाँ और समूह जोड़ें; पहले से तय श्रेणियाँ वैसी ही रहेंगी।
+cli-budget-set = किसी श्रेणी का मासिक बजट तय करें।
+cli-budget-remove = किसी श्रेणी का बजट हटाएँ।
+cli-budget-status = हर श्रेणी का बजट, ख़र्च और शेष राशि।
+cli-budget-envelope = लिफ़ाफ़ों की शेष राशि, आगे ले जाई गई राशि के साथ।
+cli-goal = बचत के लक्ष्य तय करें और प्रगति देखें।
+cli-goal-add = बचत का एक लक्ष्य जोड़ें।
+cli-goal-remove = बचत का एक लक्ष्य हटाएँ।
+cli-goal-status = हर लक्ष्य की ओर प्रगति।
+cli-subscription = बार-बार चलने वाली सेवाओं और उनके नवीनीकरण का हिसाब रखें।
+cli-subscription-add = एक सदस्यता जोड़ें।
+cli-subscription-remove = एक सदस्यता हटाएँ।
+cli-subscription-list = हर सदस्यता और उसका महीने और साल का ख़र्च।
+cli-subscription-upcoming = जल्द नवीनीकृत होने वाली सदस्यताएँ।
+cli-loan = मासिक किस्तों (EMI) में चुकाए जाने वाले कर्ज़ों का हिसाब रखें।
+cli-loan-add = एक ऋण जोड़ें।
+cli-loan-remove = एक ऋण हटाएँ। पहले दर्ज की गई किस्तें बनी रहेंगी।
+cli-loan-list = हर ऋण, चुकाई गई और बाक़ी राशि के साथ।
+cli-loan-schedule = हर किस्त ब्याज और मूलधन में कैसे बँटती है।
+cli-loan-post = आज तक देय हर किस्त को ख़र्च के रूप में दर्ज करें।
+cli-reimburse = प्रतिपूर्ति योग्य ख़र्चों को लंबित से प्रतिपूर्ति तक देखें।
+cli-reimburse-list = प्रतिपूर्ति योग्य ख़र्चों की सूची।
+cli-reimburse-submit = लंबित ख़र्चों को प्रतिपूर्ति के लिए जमा किया हुआ चिह्नित करें।
+cli-reimburse-settle = जमा किए गए ख़र्चों को प्रतिपूर्ति हो चुका चिह्नित करें।
+cli-view = फ़िल्टर के मेल को एक नाम से सहेजें, ताकि --view से फिर उपयोग हो।
+cli-view-save = दिए गए फ़िल्टर को एक व्यू के रूप में सहेजें; इसी नाम का व्यू हो तो उसे बदल दें।
+cli-view-remove = सहेजा गया एक व्यू हटाएँ।
+cli-view-list = सहेजे गए व्यू की सूची।
+cli-template = बार-बार दर्ज होने वाले ख़र्चों के नामित साँचे।
+cli-template-add = एक टेम्पलेट बनाएँ; इसी नाम का टेम्पलेट हो तो उसे बदल दें।
+cli-template-remove = एक टेम्पलेट हटाएँ।
+cli-template-list = टेम्पलेट की सूची।
+cli-rates = दूसरी मुद्राओं वाले ख़र्चों के लिए विनिमय दरें।
+cli-rates-set = किसी मुद्रा की एक इकाई का मूल्य खाते की मुद्रा में तय करें।
+cli-rates-list = तय की गई विनिमय दरों की सूची।
+cli-rates-update = खाते की मुद्रा की दैनिक दरें लाएँ और सहेज कर रखें।
+cli-backup = डेटा फ़ाइल में हर बदलाव से पहले अपने-आप लिए गए बैकअप।
+cli-backup-list = बैकअप की सूची, सबसे नए पहले।
+cli-backup-restore = डेटा फ़ाइल को किसी बैकअप से बदलें। मौजूदा फ़ाइल का पहले बैकअप लिया जाता है।
+cli-encrypt = डेटा फ़ाइल और उसके बैकअप को पासफ़्रेज़ से एन्क्रिप्ट करें।
+cli-decrypt = डेटा फ़ाइल को फिर से सादे JSON में रखें।
+cli-doctor = डेटा फ़ाइल में समस्याएँ जाँचें।
+cli-migrate = डेटा फ़ाइल को मौजूदा प्रारूप संस्करण में बदलें।
+cli-serve = खाते को HTTP पर JSON API के रूप में उपलब्ध कराएँ।
+cli-bot = चैट संदेशों से भेजे गए ख़र्च दर्ज करें।
+cli-bot-telegram = एक Telegram बॉट चलाएँ जो उसे भेजे गए ख़र्च जोड़ता है।
+cli-sync = git के ज़रिए खाते को कई मशीनों में साझा करें।
+cli-sync-init = डेटा डायरेक्टरी को एक git रिपॉज़िटरी बनाएँ जो हर बदलाव कमिट करे।
+cli-sync-push = कमिट किए गए बदलाव origin पर भेजें।
+cli-sync-pull = origin से बदलाव लाएँ और उन्हें ख़र्च-दर-ख़र्च मिलाएँ।
+cli-sync-sheets = Google Sheet में जोड़ी गई पंक्तियाँ जोड़ें, फिर शीट को हर ख़र्च के साथ दोबारा लिखें।
+cli-sync-resolve = उन ख़र्चों के संस्करणों में से चुनें जिन्हें दोनों मशीनों ने बदला।
+cli-sync-merge = डेटा फ़ाइलों के लिए git मर्ज ड्राइवर।
+cli-close-month = किसी महीने को आगे के बदलावों से बंद करें।
+cli-guide = अंतर्निहित ट्यूटोरियल पढ़ें।
+cli-import = बैंक विवरण से ख़र्च आयात करें।
+cli-export = ख़र्चों को किसी दूसरे टूल के प्रारूप में निर्यात करें।
+cli-query = ख़र्चों के बारे में SQL में सवाल पूछें।
+cli-chart = ख़र्च के चार्ट SVG या PNG फ़ाइलों में बनाएँ।
+cli-report-payees = हर प्राप्तकर्ता पर कुल ख़र्च।
+cli-report-trend = मासिक कुल और पिछले महीने से बदलाव।
+cli-report-anomalies = अपनी श्रेणी के सामान्य से बहुत बड़े ख़र्च, जैसे एक अतिरिक्त शून्य वाली टाइपिंग की ग़लती।
+cli-report-streaks = बिना ख़र्च वाले, या दैनिक सीमा से कम ख़र्च वाले लगातार दिन।
+cli-report-networth = हर महीने के अंत में कुल संपत्ति, अब तक के ख़र्च के साथ।
+cli-report-rolling = हर श्रेणी का चलता औसत ख़र्च, ताकि असमान महीने बराबर दिखें।
+cli-report-breakdown = हर श्रेणी का ख़र्च और कुल में उसका हिस्सा।
+cli-report-trip = किसी यात्रा का कुल और ब्योरा, या नाम न देने पर हर यात्रा का।
+cli-report-project = किसी परियोजना या ग्राहक का ख़र्च विवरण, या नाम न देने पर हर परियोजना का।
+cli-report-hours = दिन के हर घंटे का ख़र्च, उन ख़र्चों के लिए जिनका समय दर्ज है।
+cli-report-household = घर के हर सदस्य ने हर श्रेणी में कितना ख़र्च किया, कुल में उनके हिस्से के साथ।
+cli-report-compare = दो महीनों या सालों का हर श्रेणी का ख़र्च साथ-साथ।
+cli-report-calendar = दैनिक ख़र्च का मासिक कैलेंडर।
+cli-report-top = सबसे बड़े ख़र्च और श्रेणियाँ।
+cli-report-monthly = Markdown, HTML या PDF में मासिक ख़र्च का दस्तावेज़।
+cli-report-yearly = किसी साल के हर महीने में हर श्रेणी का ख़र्च।
+cli-report-tax = किसी वित्त वर्ष में हर श्रेणी का कर-कटौती योग्य ख़र्च।
+cli-report-email = मासिक रिपोर्ट ईमेल से भेजें।
+cli-report-custom = Rhai स्क्रिप्ट में लिखी रिपोर्ट चलाएँ।
+
+## Command line options
+
+help-mask_private = आउटपुट में निजी ख़र्चों की राशि और विवरण छिपाएँ।
+help-no_keyring = एन्क्रिप्शन पासफ़्रेज़ को सिस्टम कीरिंग से न पढ़ें और न उसमें सहेजें।
+help-remote = स्थानीय फ़ाइल के बजाय इस URL पर `serve` से दिया गया खाता उपयोग करें; इस तरह केवल add, list और summary चलते हैं। (जैसे http://host:8080)
+help-token = `serve` को चाहिए और --remote से भेजा जाने वाला API टोकन, $EXPENSE_TRACKER_TOKEN या कॉन्फ़िग फ़ाइल के server_token के बजाय।
+help-verbose = क्या हो रहा है, यह मानक त्रुटि पर लॉग करें; अधिक विवरण के लिए -vv, सब कुछ के लिए -vvv।
+help-log_file = कॉन्फ़िग फ़ाइल के log_file के बजाय इस फ़ाइल में भी लॉग करें।
+help-filter-dry_run = खाते में क्या बदलेगा यह दिखाएँ, पर सहेजें नहीं।
+help-read_only = खाते में हर बदलाव से इनकार करें, जैसे उस मशीन पर जिसे केवल रिपोर्टें चलानी हों।
+help-data_dir = कॉन्फ़िग में तय डायरेक्टरी के बजाय इस डायरेक्टरी का खाता उपयोग करें, जैसे `generate-demo` से बना खाता।
+help-currency = इस मुद्रा में रिपोर्ट करें, सहेजी गई दरों पर बदलकर; `add` के साथ, वह मुद्रा जिसमें ख़र्च चुकाया गया। (जैसे USD)
+help-only = हर रिपोर्ट और सूची में केवल व्यावसायिक या केवल निजी ख़र्च गिनें। (business या personal)
+help-generate-demo-months = आज तक ख़त्म होने वाले कितने महीनों के ख़र्च बनाने हैं।
+help-dir = डेमो खाता कहाँ रखना है। (डिफ़ॉल्ट: demo, आपकी डेटा डायरेक्टरी के खाते के पास)
+help-seed = इस सीड के लिए हर बार एक जैसा खाता बनाएँ।
+help-add-category = ख़र्च की श्रेणी।
+help-add-amount = ख़र्च की राशि।
+help-add-description = ख़र्च का विवरण।
+help-when = ख़र्च कब किया गया, समय के साथ या बिना; डिफ़ॉल्ट अभी है। (प्रारूप: 2025-12-31 या 2025-12-31 14:30)
+help-tz = वह समय क्षेत्र जिसमें ख़र्च किया गया, जैसे Asia/Tokyo या +09:00; डिफ़ॉल्ट कॉन्फ़िग फ़ाइल का timezone, या सिस्टम का।
+help-add-payee = ख़र्च का प्राप्तकर्ता या दुकानदार।
+help-add-trip = वह यात्रा या जगह जिसके तहत ख़र्च दर्ज करना है, जैसे goa-2025।
+help-add-project = ग्राहक की वह परियोजना जिसके बिल में ख़र्च जोड़ना है, जैसे acme-website।
+help-add-user = घर का वह सदस्य जिसने ख़र्च किया; डिफ़ॉल्ट कॉन्फ़िग फ़ाइल का user, या आपका लॉगिन नाम।
+help-split = ख़र्च को श्रेणियों में बाँटें। (प्रारूप: groceries=300, दोहराया जा सकता है)
+help-item = बिल की मदें लिखें; मदों का जोड़ राशि के बराबर होना चाहिए, और राशि न दी हो तो वही राशि बनती है। (प्रारूप: milk=2x45 या bread=40, दोहराया जा सकता है)
+help-split_with = ख़र्च को इन लोगों के साथ बराबर बाँटें। (प्रारूप: alice,bob)
+help-paid_by = साझा ख़र्च किसने चुकाया, अगर आपने नहीं।
+help-add-tag = ख़र्च पर टैग लगाएँ, जैसे --tag work,travel। (दोहराया जा सकता है)
+help-add-meta = अपना कोई फ़ील्ड दर्ज करें, जैसे --meta warranty_until=2026-03-01। (दोहराया जा सकता है)
+help-add-template = फ़ील्ड इस टेम्पलेट से भरें; दूसरे फ़्लैग उन्हें बदल देते हैं।
+help-stdin = मानक इनपुट से हर पंक्ति में एक ख़र्च पढ़ें, `date,category,amount,description[,payee]` CSV के रूप में या `250 coffee #food @Cafe` जैसे संक्षिप्त रूप में।
+help-private = आउटपुट छिपाए जाने पर इस ख़र्च का विवरण छिपाएँ।
+help-deductible = इस ख़र्च को साल के अंत की कर रिपोर्ट में शामिल करें।
+help-business = इसे निजी के बजाय व्यावसायिक ख़र्च के रूप में दर्ज करें।
+help-reimbursable = इस ख़र्च को वापस मिलने वाले ख़र्च के रूप में ट्रैक करें।
+help-attach = रसीद की छवि या PDF संलग्न करें। (दोहराया जा सकता है)
+help-row_number = पंक्ति संख्या से एक ख़र्च हटाएँ।
+help-delete-yes = बिना पूछे हटाएँ।
+help-purge_attachments = ख़र्च की संलग्न रसीदें भी हटाएँ।
+help-set = category, payee, trip, project, user, business (yes या no), meta.KEY या description को field=value के रूप में बदलें। (दोहराया जा सकता है)
+help-add_tag = यह टैग जोड़ें। (दोहराया जा सकता है)
+help-remove_tag = यह टैग हटाएँ। (दोहराया जा सकता है)
+help-edit-yes = मेल खाने वाले ख़र्च बिना पूछे बदलें।
+help-editor = मेल खाने वाले ख़र्च, या सभी, $EDITOR में TOML के रूप में खोलें। किसी को मिटाने पर वह हट जाता है।
+help-summary-date = ठीक इस तारीख़ से फ़िल्टर करें। (प्रारूप: 2025-12-31)
+help-summary-month = महीने से फ़िल्टर करें।
+help-summary-fy = वित्त वर्ष से फ़िल्टर करें, जो कॉन्फ़िग फ़ाइल के fiscal_year_start में तय महीने से, या अप्रैल से, शुरू होता है। (प्रारूप: 2024-25 या 2024)
+help-quarter = कैलेंडर तिमाही से फ़िल्टर करें। (प्रारूप: 2025-Q1)
+help-summary-year = कैलेंडर वर्ष से फ़िल्टर करें। (प्रारूप: 2025)
+help-all = कुल ख़र्च।
+help-total-month = केवल यह महीना गिनें।
+help-query = खोजने का पाठ, बड़े-छोटे अक्षरों का भेद किए बिना।
+help-regex = खोज को रेगुलर एक्सप्रेशन मानें।
+help-stats-month = केवल यह महीना शामिल करें। (प्रारूप: 2025-01)
+help-stats-from = केवल इस तारीख़ को या उसके बाद के ख़र्च शामिल करें। (प्रारूप: 2025-01-01)
+help-stats-to = केवल इस तारीख़ को या उससे पहले के ख़र्च शामिल करें। (प्रारूप: 2025-12-31)
+help-as_of = ऐसे अनुमान लगाएँ मानो आज यह तारीख़ हो। (प्रारूप: 2025-12-31)
+help-history = दैनिक ख़र्च का औसत कितने पिछले महीनों पर निकालना है।
+help-cumulative = तारीख़ के क्रम में सूची, हर महीने के चलते कुल के साथ।
+help-budget = चलते कुल की तुलना इस मासिक राशि से करें, या राशि न दी हो तो तय बजटों से।
+help-archive-before = इस दिन से पहले की तारीख़ वाले ख़र्च संग्रह में ले जाएँ। (प्रारूप: 2024-01-01)
+help-snapshot-create-name = स्नैपशॉट का नाम।
+help-snapshot-remove-name = स्नैपशॉट का नाम।
+help-snapshot = वह स्नैपशॉट जिससे तुलना शुरू करनी है।
+help-other = वह स्नैपशॉट जिससे तुलना करनी है; न देने पर मौजूदा खाता।
+help-history-id = इस id वाले ख़र्च का हर बदलाव दिखाएँ।
+help-history-limit = id न देने पर कितने ताज़ा बदलाव दिखाने हैं।
+help-at = इसके बजाय ख़र्चों की सूची वैसी दिखाएँ जैसी वे इस दिन के अंत में थे। (प्रारूप: 2025-03-01)
+help-show-id = ख़र्च की id।
+help-open = संलग्नकों को डिफ़ॉल्ट ऐप्लिकेशन से खोलें।
+help-settle-amount = चुकाई गई राशि।
+help-settle-from = किसने चुकाया।
+help-settle-to = किसे चुकाया गया।
+help-debt-add-person = पैसा किसे गया या किससे आया।
+help-debt-add-amount = दी या ली गई उधार राशि।
+help-debt-add-description = यह किस लिए था।
+help-borrowed = पैसा उन्हें दिया नहीं, उनसे लिया गया था।
+help-debt-add-date = कब; डिफ़ॉल्ट आज है। (प्रारूप: 2025-12-31)
+help-debt-repay-person = किसने लौटाया या किसे लौटाया गया।
+help-debt-repay-amount = लौटाई गई राशि; डिफ़ॉल्ट पूरा बकाया है।
+help-debt-repay-date = कब; डिफ़ॉल्ट आज है। (प्रारूप: 2025-12-31)
+help-debt-list-person = केवल इस व्यक्ति के साथ वाले दिखाएँ।
+help-assets-set-name = खाते का नाम।
+help-assets-set-amount = शेष राशि।
+help-liability = नया खाता संपत्ति नहीं, देनदारी है, जैसे ऋण या क्रेडिट कार्ड।
+help-assets-set-date = शेष राशि का दिन; डिफ़ॉल्ट आज है। (प्रारूप: 2025-12-31)
+help-assets-remove-name = खाते का नाम।
+help-category-bucket-category = श्रेणी।
+help-bucket = समूह; श्रेणी को उसके समूह से निकालने के लिए छोड़ दें।
+help-category-preset-apply-name = तैयार सेट।
+help-budget-set-category = वह श्रेणी जिसका बजट तय करना है।
+help-budget-set-amount = मासिक बजट।
+help-rollover = बचा हुआ बजट और अधिक ख़र्च अगले महीने में ले जाएँ।
+help-budget-set-since = वह पहला महीना जिस पर बजट लागू होता है; डिफ़ॉल्ट यह महीना है। (प्रारूप: 2025-01)
+help-budget-remove-category = वह श्रेणी जिसका बजट हटाना है।
+help-budget-status-month = देखने का महीना; डिफ़ॉल्ट यह महीना है। (प्रारूप: 2025-01)
+help-budget-envelope-month = देखने का महीना; डिफ़ॉल्ट यह महीना है। (प्रारूप: 2025-01)
+help-goal-add-name = लक्ष्य का नाम।
+help-target = बचाने की राशि।
+help-by = वह महीना जब तक लक्ष्य पाना है। (प्रारूप: 2026-06)
+help-goal-add-category = इस बचत श्रेणी के ख़र्चों को बचत गिनें।
+help-income = इस मासिक आय में से सारा ख़र्च घटाकर बचत गिनें।
+help-goal-add-since = वह महीना जब बचत शुरू हुई; डिफ़ॉल्ट यह महीना है। (प्रारूप: 2025-01)
+help-goal-remove-name = लक्ष्य का नाम।
+help-subscription-add-name = सेवा का नाम।
+help-subscription-add-amount = हर नवीनीकरण की लागत।
+help-day = महीने का वह दिन जिस दिन यह नवीनीकृत होती है।
+help-every = यह कितनी बार नवीनीकृत होती है।
+help-subscription-add-month = साल का वह महीना जिसमें वार्षिक सदस्यता नवीनीकृत होती है। (1-12)
+help-subscription-add-category = वह श्रेणी जिसमें इसके ख़र्च जाते हैं।
+help-url = इसे कहाँ रद्द करें।
+help-notes = याद रखने लायक कुछ और, जैसे इसे रद्द कैसे करें।
+help-subscription-remove-name = सेवा का नाम।
+help-days = कितने दिन आगे तक देखना है।
+help-loan-add-name = ऋण का नाम।
+help-principal = उधार ली गई राशि।
+help-loan-add-rate = वार्षिक ब्याज दर, प्रतिशत में।
+help-loan-add-months = यह कितनी मासिक किस्तों तक चलता है।
+help-emi = मासिक किस्त, अगर ऋणदाता की किस्त सामान्य सूत्र से अलग है।
+help-start = पहली किस्त की तारीख़; डिफ़ॉल्ट आज है। (प्रारूप: 2025-01-05)
+help-loan-add-category = वह श्रेणी जिसमें किस्तें दर्ज होती हैं; डिफ़ॉल्ट Loan है।
+help-loan-remove-name = ऋण का नाम।
+help-loan-schedule-name = ऋण का नाम।
+help-yearly = हर साल की किस्तों का जोड़ दिखाएँ।
+help-status = केवल इस स्थिति वाले दावे दिखाएँ।
+help-ids = ख़र्चों की id।
+help-view-save-name = व्यू का नाम।
+help-view-save-month = केवल यह महीना संख्या शामिल करें, किसी भी साल में।
+help-view-remove-name = व्यू का नाम।
+help-template-add-name = टेम्पलेट का नाम।
+help-template-add-amount = सामान्य राशि।
+help-template-add-category = श्रेणी।
+help-template-add-description = विवरण; डिफ़ॉल्ट टेम्पलेट का नाम है।
+help-template-add-payee = प्राप्तकर्ता या दुकानदार।
+help-template-remove-name = टेम्पलेट का नाम।
+help-code = मुद्रा कोड। (जैसे EUR)
+help-rates-set-rate = एक इकाई का मूल्य।
+help-rates-set-date = वह दिन जिससे दर लागू होती है; डिफ़ॉल्ट आज है। (प्रारूप: 2025-12-31)
+help-rates-update-date = किस दिन की दरें लानी हैं; डिफ़ॉल्ट आज है। (प्रारूप: 2025-12-31)
+help-timestamp = बहाल करने वाला बैकअप, जैसा `backup list` दिखाता है; उपसर्ग देने पर सबसे नया मेल चुना जाता है, और न देने पर सबसे ताज़ा। (प्रारूप: 20250131-184500.250)
+help-backup-restore-yes = बिना पूछे बहाल करें।
+help-fix = id ठीक करें, अज्ञात फ़ील्ड हटाएँ और गुम संलग्नकों को भूल जाएँ।
+help-migrate-dry_run = कुछ लिखे बिना अपग्रेड की जाँच करें।
+help-port = सुनने का पोर्ट।
+help-bind = सुनने का पता; हर इंटरफ़ेस के लिए 0.0.0.0।
+help-origin = वह रिपॉज़िटरी जिसमें push और जिससे pull करना है।
+help-spreadsheet_id = स्प्रेडशीट, उसके URL से, कॉन्फ़िग फ़ाइल में [sheets] के spreadsheet_id के बजाय।
+help-keep = हर टकराव पूछने के बजाय इसी तरह सुलझाएँ।
+help-close-month-month = बंद करने का महीना। (प्रारूप: 2025-12)
+help-checklist = बंद करने से पहले जाँचें कि महीना तैयार है।
+help-force = जाँचें विफल हों तब भी महीना बंद करें।
+help-reopen = पहले बंद किया गया महीना खोलें।
+help-topic = दिखाने वाली गाइड। न देने पर सभी गाइडों की सूची।
+help-no_pager = गाइड को पेजर में खोलने के बजाय छापें।
+help-file = स्टेटमेंट फ़ाइल।
+help-import-format = स्टेटमेंट का प्रारूप; ynab YNAB के Date, Payee, Memo, Outflow और Inflow कॉलम वाली CSV है, gnucash GnuCash का लेन-देन CSV निर्यात और mmex Money Manager EX का CSV निर्यात। न देने पर फ़ाइल के एक्सटेंशन से अनुमान लगाया जाता है।
+help-map = GnuCash खातों या MMEX श्रेणियों को श्रेणियों से जोड़ने वाली TOML फ़ाइल, "Expenses:Food:Dining" = "Dining" जैसी पंक्तियों के रूप में।
+help-import-category = उन लेन-देन की श्रेणी जिन्हें स्टेटमेंट श्रेणी नहीं देता।
+help-date_format = QIF और CSV फ़ाइलों में तारीख़ों का strftime प्रारूप, जैसे दिन पहले लिखने वाले बैंकों के लिए %d/%m/%Y।
+help-include_duplicates = मौजूदा ख़र्चों जैसे दिखने वाले लेन-देन भी आयात करें।
+help-import-yes = पुष्टि पूछे बिना आयात करें।
+help-export-format = आउटपुट प्रारूप; xlsx ख़र्चों और हर श्रेणी के मासिक कुल की स्प्रेडशीट है, parquet pandas, polars या DuckDB के लिए ख़र्चों की तालिका, ynab YNAB के फ़ाइल आयात के लिए CSV।
+help-account = वह खाता जिससे ख़र्च चुकाए जाते हैं।
+help-export-out = मानक आउटपुट के बजाय इस फ़ाइल में लिखें।
+help-sql = चलाने वाला SELECT स्टेटमेंट।
+help-query-format = आउटपुट प्रारूप।
+help-query-out = CSV मानक आउटपुट के बजाय इस फ़ाइल में लिखें।
+help-type = pie: श्रेणियाँ, bar: मासिक कुल, line: संचयी ख़र्च।
+help-chart-out = लिखने वाली छवि फ़ाइल; PNG के लिए .png, बाक़ी कुछ भी SVG के लिए।
+help-chart-month = इस महीने का चार्ट (pie और line), या इस तक के महीनों का (bar)। (प्रारूप: 2025-01)
+help-chart-months = bar चार्ट कितने महीनों का हो।
+help-report-template = फ़िल्टर किए गए ख़र्चों और उनके कुल के साथ यह Tera टेम्पलेट रेंडर करें।
+help-report-out = रेंडर किया गया टेम्पलेट मानक आउटपुट के बजाय इस फ़ाइल में लिखें।
+help-report-payees-year = केवल इस साल के ख़र्च शामिल करें।
+help-report-trend-category = केवल यह श्रेणी शामिल करें।
+help-report-trend-months = कितने महीने दिखाने हैं।
+help-end = दिखाने वाला आख़िरी महीना; डिफ़ॉल्ट यह महीना है। (प्रारूप: 2025-12)
+help-sparkline = कुल को स्पार्कलाइन के रूप में भी बनाएँ।
+help-real = पिछले कुल को महँगाई के हिसाब से समायोजित करें, कॉन्फ़िग फ़ाइल की [cpi] तालिका के ताज़ा सूचकांक के मूल्य में।
+help-sigma = औसत से कितने मानक विचलन ऊपर असामान्य गिना जाए।
+help-report-streaks-limit = इससे कम ख़र्च वाले दिन भी गिनें।
+help-report-networth-months = कितने महीने दिखाने हैं।
+help-window = हर औसत कितने महीनों का हो।
+help-report-rolling-months = कितने महीने दिखाने हैं।
+help-last_year = मौसमी ख़र्च के लिए पिछले महीने की तुलना एक साल पहले के उसी महीने से भी करें।
+help-report-breakdown-month = केवल यह महीना शामिल करें। (प्रारूप: 2025-01)
+help-chart = हर श्रेणी के लिए एक बार बनाएँ।
+help-by_bucket = श्रेणियों को उनके समूहों में जोड़ें, जैसे needs, wants और savings।
+help-report-trip-name = यात्रा, जैसी add --trip में दी गई।
+help-report-project-name = परियोजना, जैसी add --project में दी गई।
+help-client = इस ग्राहक की हर परियोजना शामिल करें।
+help-report-project-from = शामिल करने का पहला दिन। (प्रारूप: 2025-01-01)
+help-report-project-to = शामिल करने का आख़िरी दिन। (प्रारूप: 2025-01-31)
+help-report-project-format = आउटपुट प्रारूप।
+help-report-project-out = CSV मानक आउटपुट के बजाय इस फ़ाइल में लिखें।
+help-a = पहले की अवधि। (प्रारूप: 2025-01 या 2025)
+help-b = वह अवधि जिससे तुलना करनी है। (प्रारूप: 2025-02 या 2025)
+help-report-compare-no_color = सबसे ज़्यादा बढ़ी श्रेणियों को लाल रंग के बजाय ▲ से चिह्नित करें।
+help-report-calendar-month = दिखाने का महीना; डिफ़ॉल्ट यह महीना है। (प्रारूप: 2025-01)
+help-report-calendar-no_color = दिनों को ख़र्च के हिसाब से रंगें नहीं।
+help-n = कितने ख़र्च और श्रेणियाँ दिखानी हैं।
+help-report-top-month = केवल यह महीना शामिल करें। (प्रारूप: 2025-01)
+help-report-monthly-month = रिपोर्ट का महीना। (प्रारूप: 2025-01)
+help-report-monthly-format = दस्तावेज़ का प्रारूप।
+help-report-monthly-out = मानक आउटपुट के बजाय इस फ़ाइल में लिखें।
+help-report-yearly-year = रिपोर्ट का साल; डिफ़ॉल्ट यह साल है।
+help-report-yearly-format = आउटपुट प्रारूप।
+help-report-yearly-out = CSV मानक आउटपुट के बजाय इस फ़ाइल में लिखें।
+help-report-tax-fy = रिपोर्ट का वित्त वर्ष। (प्रारूप: 2024-25)
+help-fy_start = वह महीना जिसमें वित्त वर्ष शुरू होता है (1-12); डिफ़ॉल्ट कॉन्फ़िग फ़ाइल का fiscal_year_start, या अप्रैल।
+help-report-tax-format = आउटपुट प्रारूप।
+help-report-tax-out = CSV मानक आउटपुट के बजाय इस फ़ाइल में लिखें।
+help-report-email-month = रिपोर्ट का महीना; डिफ़ॉल्ट पिछला महीना है। (प्रारूप: 2025-01)
+help-report-email-to = कॉन्फ़िग फ़ाइल के पतों के बजाय इन पतों पर भेजें। (दोहराया जा सकता है)
+help-script = चलाने वाली स्क्रिप्ट।
+help-report-custom-from = केवल इस तारीख़ को या उसके बाद के ख़र्च शामिल करें। (प्रारूप: 2025-01-01)
+help-report-custom-to = केवल इस तारीख़ को या उससे पहले के ख़र्च शामिल करें। (प्रारूप: 2025-12-31)
+help-view = इस व्यू में सहेजे गए फ़िल्टर से शुरू करें; दूसरे फ़िल्टर फ़्लैग उन्हें बदल देते हैं।
+help-filter-from = केवल इस तारीख़ को या उसके बाद के ख़र्च शामिल करें। (प्रारूप: 2025-01-01)
+help-filter-to = केवल इस तारीख़ को या उससे पहले के ख़र्च शामिल करें। (प्रारूप: 2025-12-31)
+help-after = केवल इस तारीख़ के बाद के ख़र्च शामिल करें, उस दिन के नहीं।
+help-filter-before = केवल इस तारीख़ से पहले के ख़र्च शामिल करें, उस दिन के नहीं।
+help-filter-category = केवल यह श्रेणी शामिल करें।
+help-filter-tag = केवल इस टैग वाले ख़र्च शामिल करें। (दोहराया जा सकता है)
+help-filter-payee = केवल यह प्राप्तकर्ता शामिल करें।
+help-filter-trip = केवल इस यात्रा के तहत दर्ज ख़र्च शामिल करें।
+help-filter-project = केवल इस परियोजना के बिल में जोड़े गए ख़र्च शामिल करें।
+help-filter-user = केवल घर के इस सदस्य के ख़र्च शामिल करें।
+help-filter-meta = केवल अपने इस फ़ील्ड वाले ख़र्च शामिल करें, KEY या KEY=VALUE के रूप में। (दोहराया जा सकता है)
+help-min_amount = केवल कम से कम इस राशि के ख़र्च शामिल करें।
+help-max_amount = केवल अधिक से अधिक इस राशि के ख़र्च शामिल करें।
+help-hours = केवल इन घंटों के बीच किए गए ख़र्च शामिल करें, जैसे देर रात के लिए 22-4।
+
+## Questions
+
+yes-answers = y, yes, हाँ, हां, ha, haan
+ask-amount = राशि
+ask-category = श्रेणी
+ask-category-hint = श्रेणी (जैसे { $examples })
+ask-pick = कोई संख्या चुनें, या "{ $answer }" रखने के लिए Enter दबाएँ
+ask-date = तारीख़
+ask-description = विवरण
+ask-payee = प्राप्तकर्ता (वैकल्पिक)
+invalid-amount = '{ $answer }' मान्य राशि नहीं है।
+invalid-pick = '{ $pick }' विकल्पों में नहीं है।
+confirm-save = यह ख़र्च सहेजें?
+confirm-delete = यह ख़र्च हटाएँ?
+confirm-delete-many = { $count } ख़र्च हटाएँ?
+
+## Outcomes
+
+expense-added = ख़र्च जोड़ दिया गया!
+expense-not-added = ख़र्च नहीं जोड़ा गया।
+nothing-deleted = कुछ नहीं हटाया गया।
+deleted-many = { $count } ख़र्च हटाए गए।
+no-expenses = कोई ख़र्च नहीं मिला।
+total-expenses = कुल ख़र्च: { $total }
+
+## Labels
+
+label-date = तारीख़
+label-category = श्रेणी
+label-amount = राशि
+label-description = विवरण
+label-payee = प्राप्तकर्ता
+label-budget = बजट
+label-spent = ख़र्च
+label-remaining = शेष
+label-used = उपयोग
+label-total = कुल
+label-share = हिस्सा
+label-change = बदलाव
+label-change-percent = बदलाव %
+label-month = महीना
+label-net-worth = कुल संपत्ति
+label-spent-to-date = अब तक ख़र्च
+label-to-date = अब तक
+label-per-day = प्रति दिन
+label-projected = अनुमानित
+label-count = संख्या
+label-mean = औसत
+label-median = माध्यिका
+label-min = न्यूनतम
+label-max = अधिकतम
+label-std-dev = मानक विचलन
+label-envelope = लिफ़ाफ़ा
+label-carried-in = पिछला शेष
+label-balance = शेष राशि
@@ -0,0 +1,163 @@
+use std::{
+    fmt::{self, Write as _},
+    fs::{File, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    sync::Mutex,
+    time::Instant,
+};
+
+use chrono::Local;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber, debug};
+
+// Writes log events to standard error and a log file, each up to its own
+// level. Spans aren't used; phases log how long they took instead.
+struct Logger {
+    stderr: Option<Level>,
+    file: Option<(Mutex<File>, Level)>,
+    start: Instant,
+}
+
+// An event's message followed by its fields, as `message key=value`
+#[derive(Default)]
+struct Line {
+    message: String,
+    fields: String,
+}
+
+impl Visit for Line {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+impl Logger {
+    // Our own events up to `level`; dependencies' only at trace, -vvv.
+    fn shows(&self, level: Level, metadata: &Metadata<'_>) -> bool {
+        *metadata.level() <= level
+            && (level == Level::TRACE || metadata.target().starts_with(env!("CARGO_CRATE_NAME")))
+    }
+}
+
+impl Subscriber for Logger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        self.stderr.is_some_and(|level| self.shows(level, metadata))
+            || self
+                .file
+                .as_ref()
+                .is_some_and(|(_, level)| self.shows(*level, metadata))
+    }
+
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let metadata = event.metadata();
+        let mut line = Line::default();
+        event.record(&mut line);
+        if self.stderr.is_some_and(|level| self.shows(level, metadata)) {
+            eprintln!(
+                "[{:>8.3}s {:<5}] {}{}",
+                self.start.elapsed().as_secs_f64(),
+                metadata.level(),
+                line.message,
+                line.fields
+            );
+        }
+        if let Some((file, level)) = &self.file
+            && self.shows(*level, metadata)
+            && let Ok(mut file) = file.lock()
+        {
+            // A log that can't be written mustn't stop the command.
+            let _ = writeln!(
+                file,
+                "{} {:<5} {}{}",
+                Local::now().format("%Y-%m-%dT%H:%M:%S%.3f%:z"),
+                metadata.level(),
+                line.message,
+                line.fields
+            );
+        }
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+fn level(verbosity: u8) -> Level {
+    match verbosity {
+        0 => Level::WARN,
+        1 => Level::INFO,
+        2 => Level::DEBUG,
+        _ => Level::TRACE,
+    }
+}
+
+// Log to standard error with -v (info), -vv (debug) or -vvv (everything,
+// dependencies included), and to `file`, when given, at info or more.
+pub fn init(verbosity: u8, file: Option<&Path>) -> io::Result<()> {
+    let file = match file {
+        Some(path) => {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|err| io::Error::other(format!("{}: {err}", path.display())))?;
+            Some((Mutex::new(file), level(verbosity.max(1))))
+        }
+        None => None,
+    };
+    let stderr = (verbosity > 0).then(|| level(verbosity));
+    if stderr.is_none() && file.is_none() {
+        return Ok(());
+    }
+    let logger = Logger {
+        stderr,
+        file,
+        start: Instant::now(),
+    };
+    tracing::subscriber::set_global_default(logger).map_err(io::Error::other)
+}
+
+// Logs how long a phase took once it's dropped.
+pub struct Timer {
+    phase: &'static str,
+    start: Instant,
+}
+
+pub fn time(phase: &'static str) -> Timer {
+    Timer {
+        phase,
+        start: Instant::now(),
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        debug!(
+            phase = self.phase,
+            ms = self.start.elapsed().as_millis(),
+            "phase done"
+        );
+    }
+}
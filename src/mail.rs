@@ -0,0 +1,72 @@
+use lettre::{
+    Message, SmtpTransport, Transport,
+    message::{Mailbox, MultiPart},
+    transport::smtp::authentication::Credentials,
+};
+
+use crate::config::{Email, Security};
+
+// Read when the config file doesn't hold the password
+pub const PASSWORD_VAR: &str = "EXPENSE_TRACKER_SMTP_PASSWORD";
+
+fn mailbox(address: &str) -> Result<Mailbox, String> {
+    address
+        .parse()
+        .map_err(|err| format!("'{address}' is not an email address: {err}"))
+}
+
+// Send a message with `text` and `html` versions of the same body to `to`,
+// or to the addresses in the config when `to` is empty.
+pub fn send(
+    email: &Email,
+    to: &[String],
+    subject: &str,
+    text: String,
+    html: String,
+) -> Result<(), String> {
+    let host = email
+        .host
+        .as_deref()
+        .ok_or("set host under [email] in the config file")?;
+    let from = email
+        .from
+        .as_deref()
+        .ok_or("set from under [email] in the config file")?;
+    let to = if to.is_empty() { &email.to[..] } else { to };
+    if to.is_empty() {
+        return Err(String::from(
+            "no one to send to; give --to or set to under [email] in the config file",
+        ));
+    }
+
+    let mut message = Message::builder().from(mailbox(from)?).subject(subject);
+    for address in to {
+        message = message.to(mailbox(address)?);
+    }
+    let message = message
+        .multipart(MultiPart::alternative_plain_html(text, html))
+        .map_err(|err| err.to_string())?;
+
+    let mut transport = match email.security {
+        Security::StartTls => SmtpTransport::starttls_relay(host),
+        Security::Tls => SmtpTransport::relay(host),
+        Security::None => Ok(SmtpTransport::builder_dangerous(host)),
+    }
+    .map_err(|err| format!("{host}: {err}"))?;
+    if let Some(port) = email.port {
+        transport = transport.port(port);
+    }
+    if let Some(username) = &email.username {
+        let password = email
+            .password
+            .clone()
+            .or_else(|| std::env::var(PASSWORD_VAR).ok())
+            .unwrap_or_default();
+        transport = transport.credentials(Credentials::new(username.clone(), password));
+    }
+    transport
+        .build()
+        .send(&message)
+        .map(|_| ())
+        .map_err(|err| format!("{host}: {err}"))
+}
@@ -1,253 +1,640 @@
-use chrono::{Datelike, Local, NaiveDate};
-use clap::{Arg, Command};
-use serde::{Deserialize, Serialize};
-use serde_json::Result;
-use std::{
-    fs::{File, OpenOptions},
-    io::{self},
-    path::Path,
-};
-
-// Define Expense struct
-#[derive(Serialize, Deserialize, Debug)]
-struct Expense {
-    date: NaiveDate,
-    category: String,
-    amount: f64,
-    description: String,
-}
+mod alert;
+mod archive;
+mod asset;
+mod attachments;
+mod backup;
+mod bot;
+mod budget;
+mod category;
+mod cli;
+mod close;
+mod config;
+mod crypto;
+mod currency;
+mod dates;
+mod debt;
+mod demo;
+mod doctor;
+mod dry_run;
+mod edit;
+mod entry;
+mod exit;
+mod expense;
+mod export;
+mod filter;
+mod goal;
+mod guide;
+mod history;
+mod hooks;
+mod i18n;
+mod import;
+mod init;
+mod loan;
+mod locale;
+mod logging;
+mod mail;
+mod migrate;
+mod plot;
+mod prompt;
+mod query;
+mod rates;
+mod read_only;
+mod reimburse;
+mod remote;
+mod report;
+mod search;
+mod server;
+mod shared;
+mod sheets;
+mod show;
+mod snapshot;
+mod storage;
+mod subscription;
+mod sync;
+mod template;
+mod webdav;
+mod webhook;
+mod wizard;
+
+use budget::Budget;
+use chrono::{Datelike, Months, NaiveDate};
+use clap::ArgMatches;
+use expense::{Expense, ExpenseTracker, Item, Split};
+use filter::Filter;
+use goal::Goal;
+use shared::Settlement;
+use std::io::{IsTerminal, Write};
+use std::path::PathBuf;
+use storage::Storage;
+use subscription::{Cycle, Subscription};
+use tracing::info;
 
-// Define the structure of the JSON data file
-#[derive(Serialize, Deserialize, Debug)]
-struct ExpenseTracker {
-    expenses: Vec<Expense>,
+// Subcommands that --dry-run refuses. `migrate` has a --dry-run of its own.
+const NO_DRY_RUN: [&str; 11] = [
+    "init",
+    "generate-demo",
+    "backup",
+    "encrypt",
+    "decrypt",
+    "doctor",
+    "sync",
+    "archive",
+    "snapshot",
+    "serve",
+    "bot",
+];
+
+// Whether the command changes the ledger or its files other than by saving
+// through the storage backend, so --read-only has to refuse it up front.
+fn writes_directly(matches: &ArgMatches) -> bool {
+    match matches.subcommand() {
+        Some(("backup", sub)) => sub.subcommand_name() == Some("restore"),
+        Some(("snapshot", sub)) => matches!(sub.subcommand_name(), Some("create" | "remove")),
+        Some(("doctor", sub)) => sub.get_flag("fix"),
+        Some(("migrate", sub)) => !sub.get_flag("dry_run"),
+        // Attachments are copied in before the expense is saved.
+        Some(("add", sub)) => matches.contains_id("remote") || sub.contains_id("attach"),
+        Some(("init" | "encrypt" | "decrypt" | "sync" | "archive", _)) => true,
+        _ => false,
+    }
 }
 
-impl ExpenseTracker {
-    fn new() -> ExpenseTracker {
-        ExpenseTracker {
-            expenses: Vec::new(),
+fn main() {
+    // Help text is translated, so the language is settled before the CLI is
+    // built, from the config file if it loads.
+    i18n::init(config::load().ok().and_then(|c| c.locale).as_deref());
+    let matches = cli::build().get_matches();
+
+    // Guides are static text and don't need the ledger.
+    if let Some(("guide", sub_matches)) = matches.subcommand() {
+        let text = match sub_matches.get_one::<String>("topic") {
+            Some(name) => guide::render(guide::find(name).expect("topic should be valid")),
+            None => guide::render_index(),
+        };
+        guide::page(&text, !sub_matches.get_flag("no_pager"));
+        return;
+    }
+
+    let mut config = config::load().unwrap_or_else(|err| {
+        eprintln!("Error loading config: {}", err);
+        std::process::exit(exit::DATA);
+    });
+    if let Some(dir) = matches.get_one::<PathBuf>("data_dir") {
+        config.data_dir = Some(dir.clone());
+    }
+    let log_file = matches
+        .get_one::<PathBuf>("log_file")
+        .or(config.log_file.as_ref());
+    if let Err(err) = logging::init(matches.get_count("verbose"), log_file.map(|p| p.as_path())) {
+        eprintln!("Error opening the log file: {}", err);
+        std::process::exit(exit::FAILURE);
+    }
+    info!(
+        command = matches.subcommand_name().unwrap_or_default(),
+        config = config::path().map_or(String::from("none"), |p| p.display().to_string()),
+        "starting"
+    );
+    let dry_run = matches.get_flag("dry_run");
+    if dry_run {
+        // These write files besides the ledger, or somewhere else entirely,
+        // which a dry run can't hold back.
+        let name = matches.subcommand_name().unwrap_or_default();
+        if matches.contains_id("remote") || NO_DRY_RUN.contains(&name) {
+            eprintln!("--dry-run can't be used with {name}.");
+            std::process::exit(exit::USAGE);
         }
     }
+    let read_only = config.read_only || matches.get_flag("read_only");
+    if read_only && writes_directly(&matches) {
+        eprintln!(
+            "{} changes the ledger, which is read-only here.",
+            matches.subcommand_name().unwrap_or_default()
+        );
+        // The same code as when a save is refused
+        std::process::exit(exit::DATA);
+    }
+    dates::init_period(config.period_start);
+    locale::init(config.locale.as_deref());
+    let mask_private = matches.get_flag("mask_private");
+
+    // A remote ledger is used through its API; the local file isn't touched.
+    if let Some(url) = matches.get_one::<String>("remote") {
+        remote_command(&matches, url, &config, mask_private);
+        return;
+    }
 
-    fn add_expense(&mut self, expense: Expense) {
-        self.expenses.push(expense);
+    // Setting up has to work before there's a config file or ledger.
+    if let Some(("init", _)) = matches.subcommand() {
+        if let Err(err) = init::run(&config) {
+            eprintln!("Error: {}", err);
+            std::process::exit(exit::FAILURE);
+        }
+        return;
     }
 
-    fn delete_expense(&mut self, row_number: usize) {
-        if self.expenses.is_empty() {
-            println!("No expenses found.");
+    // The demo ledger is a separate one, so it's made before ours loads.
+    if let Some(("generate-demo", sub_matches)) = matches.subcommand() {
+        let dir = match sub_matches.get_one::<PathBuf>("dir") {
+            Some(dir) => dir.clone(),
+            None => config
+                .data_dir
+                .clone()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join("demo"),
+        };
+        let data_file = dir.join("expenses.json");
+        if data_file.exists() {
+            eprintln!(
+                "Error: {} already exists; remove it or choose another --dir.",
+                data_file.display()
+            );
+            std::process::exit(exit::CONFLICT);
+        }
+        let months = *sub_matches
+            .get_one::<u32>("months")
+            .expect("months has a default");
+        let seed = sub_matches
+            .get_one::<u64>("seed")
+            .copied()
+            .unwrap_or_else(demo::seed);
+        let tracker = demo::generate(months, dates::now(config.timezone).date, seed);
+        let saved = std::fs::create_dir_all(&dir)
+            .and_then(|()| tracker.save_to_json(&data_file.display().to_string()));
+        if let Err(err) = saved {
+            eprintln!("Error saving data: {}", err);
+            std::process::exit(exit::DATA);
         }
-        self.expenses.remove(row_number - 1);
+        println!(
+            "Made {} expenses over {} months in {}.",
+            tracker.expenses.len(),
+            months,
+            data_file.display()
+        );
+        println!(
+            "Try it with: expense-tracker-rs --data-dir {} summary --all",
+            dir.display()
+        );
+        return;
     }
 
-    fn summary_all(&self) -> f64 {
-        self.expenses.iter().map(|e| e.amount).sum()
+    let data_file = match &config.data_dir {
+        Some(dir) => dir.join("expenses.json").display().to_string(),
+        None => String::from("expenses.json"),
+    };
+    let filename = data_file.as_str();
+    info!(file = filename, storage = ?config.storage, "using ledger");
+    let mut storage = hooks::wrap(
+        storage::open(filename, &config),
+        &config.hooks,
+        matches.subcommand_name().unwrap_or_default(),
+    );
+    if dry_run {
+        storage = dry_run::wrap(storage, mask_private);
     }
-    fn summary_by_category(&self, category: &str) -> f64 {
-        let mut sum = 0 as f64;
-        for expense in self.expenses.iter() {
-            if expense.category == *category {
-                sum = sum + expense.amount;
-            }
+    if read_only {
+        storage = read_only::wrap(storage);
+    }
+    if !matches.get_flag("no_keyring") {
+        crypto::use_keyring(filename);
+    }
+
+    // Migrating reads the file as it is on disk rather than loading it.
+    if let Some(("migrate", sub_matches)) = matches.subcommand() {
+        if let Err(err) = migrate::run(filename, sub_matches.get_flag("dry_run")) {
+            eprintln!("Error migrating data: {}", err);
+            std::process::exit(exit::DATA);
+        }
+        return;
+    }
+
+    // Restoring has to work when the data file is broken.
+    if let Some(("backup", sub_matches)) = matches.subcommand() {
+        // A journal is folded in first, so the backup taken of the current
+        // file has its expenses. Before finding the backup to restore, as
+        // saving may prune it.
+        if let Some(("restore", _)) = sub_matches.subcommand()
+            && storage::journal_exists(filename)
+            && let Err(err) = storage.load().and_then(|tracker| storage.save(&tracker))
+        {
+            eprintln!("Couldn't fold in the journal: {err}");
+        }
+        let result = match sub_matches.subcommand() {
+            Some(("list", _)) => backup::print_list(filename),
+            Some(("restore", restore_matches)) => backup::find(
+                filename,
+                restore_matches
+                    .get_one::<String>("timestamp")
+                    .map(|s| s.as_str()),
+            )
+            .and_then(|(stamp, path)| {
+                let when = stamp.format("%Y-%m-%d %H:%M:%S");
+                let backed_up = ExpenseTracker::load_from_json(&path.to_string_lossy())?;
+                // The current file may be why we're restoring, so it needn't load.
+                let current = ExpenseTracker::load_from_json(filename)
+                    .map_or(String::from("can't be read"), |t| {
+                        format!("has {} expense(s)", t.expenses.len())
+                    });
+                println!(
+                    "The backup from {when} has {} expense(s); the data file {current}.",
+                    backed_up.expenses.len()
+                );
+                if !restore_matches.get_flag("yes")
+                    && !prompt::confirm("Replace the data file with the backup?")
+                {
+                    println!("Nothing restored.");
+                    return Ok(());
+                }
+                let aside = backup::restore(filename, &path)?;
+                println!("Restored the backup from {when}.");
+                if let Some(aside) = aside {
+                    println!(
+                        "Expenses still in the journal were moved to {}.",
+                        aside.display()
+                    );
+                }
+                Ok(())
+            }),
+            _ => unreachable!("backup requires a subcommand"),
+        };
+        if let Err(err) = result {
+            eprintln!("Error: {}", err);
+            std::process::exit(exit::of(&err));
         }
-        sum
+        return;
     }
-    fn summary_by_date(&self, date: NaiveDate) -> f64 {
-        let mut sum = 0 as f64;
-        for expense in self.expenses.iter() {
-            if expense.date == date {
-                sum = sum + expense.amount;
+
+    // Converting rewrites the file without loading it as a ledger, once
+    // any journal, which is plain text, is folded in.
+    if let Some(("encrypt", _)) = matches.subcommand() {
+        let folded = if storage::journal_exists(filename) {
+            storage.load().and_then(|tracker| storage.save(&tracker))
+        } else {
+            Ok(())
+        };
+        match folded.and_then(|()| crypto::encrypt_ledger(filename)) {
+            Ok(converted) => println!(
+                "Encrypted {filename}{}, {} archive(s), {} snapshot(s) and {} backup(s). Attachments are not encrypted.",
+                if converted.history {
+                    ", its history"
+                } else {
+                    ""
+                },
+                converted.archives,
+                converted.snapshots,
+                converted.backups,
+            ),
+            Err(err) => {
+                eprintln!("Error encrypting data: {}", err);
+                std::process::exit(exit::DATA);
             }
         }
-        sum
+        return;
     }
-    fn summary_by_month(&self, month: &u8) -> f64 {
-        let mut sum = 0 as f64;
-        for expense in self.expenses.iter() {
-            if expense.date.month() as u8 == *month {
-                sum = sum + expense.amount;
+    if let Some(("decrypt", _)) = matches.subcommand() {
+        match crypto::decrypt_ledger(filename) {
+            Ok(()) => println!(
+                "Decrypted {filename} and its history, archives and snapshots. Backups stay encrypted."
+            ),
+            Err(err) => {
+                eprintln!("Error decrypting data: {}", err);
+                std::process::exit(exit::DATA);
             }
         }
-        sum
+        return;
     }
 
-    fn save_to_json(&self, filename: &str) -> io::Result<()> {
-        let file = OpenOptions::new()
-            .create(true)
-            .write(true)
-            .truncate(true)
-            .open(filename)?;
-        serde_json::to_writer(file, &self)?;
-        Ok(())
+    // Checking has to cope with files that don't load.
+    if let Some(("doctor", sub_matches)) = matches.subcommand() {
+        let fix = sub_matches.get_flag("fix");
+        // Repairs are saved over the data file, so a journal is folded in
+        // first; a file that won't load is reported by the checks.
+        if fix
+            && storage::journal_exists(filename)
+            && let Err(err) = storage.load().and_then(|tracker| storage.save(&tracker))
+        {
+            eprintln!("Couldn't fold in the journal: {err}");
+        }
+        match doctor::run(filename, storage.as_ref(), fix) {
+            Ok(true) => {}
+            Ok(false) => std::process::exit(exit::DATA),
+            Err(err) => {
+                eprintln!("Error checking data: {}", err);
+                std::process::exit(exit::DATA);
+            }
+        }
+        return;
     }
 
-    fn load_from_json(filename: &str) -> Result<ExpenseTracker> {
-        let path = Path::new(filename);
-        if !path.exists() {
-            return Ok(ExpenseTracker::new());
+    // Syncing works on the data directory's repository; git runs `merge`
+    // on files other than the data file.
+    if let Some(("sync", sub_matches)) = matches.subcommand() {
+        let result = match sub_matches.subcommand() {
+            Some(("init", init_matches)) => {
+                // A journal is folded in first so other machines see it.
+                let folded = if storage::journal_exists(filename) {
+                    storage.load().and_then(|tracker| storage.save(&tracker))
+                } else {
+                    Ok(())
+                };
+                folded
+                    .and_then(|()| {
+                        sync::init(
+                            filename,
+                            init_matches.get_one::<String>("origin").map(|s| s.as_str()),
+                        )
+                    })
+                    .map(|()| println!("Changes to {filename} are now committed as they are made."))
+            }
+            Some(("push", _)) => sync::push(filename),
+            Some(("pull", _)) => sync::pull(filename).and_then(|()| {
+                let waiting = storage.load()?.conflicts.len();
+                if waiting > 0 {
+                    println!(
+                        "{waiting} expense(s) were changed on both machines; run `sync resolve` to choose."
+                    );
+                }
+                Ok(())
+            }),
+            Some(("sheets", sheets_matches)) => {
+                let Some(spreadsheet) = sheets_matches
+                    .get_one::<String>("spreadsheet_id")
+                    .or(config.sheets.spreadsheet_id.as_ref())
+                else {
+                    eprintln!(
+                        "Give --spreadsheet-id or set spreadsheet_id under [sheets] in the config file."
+                    );
+                    std::process::exit(exit::USAGE);
+                };
+                let ledger = storage
+                    .load_metadata()
+                    .map(|metadata| ledger_currency(&config, &metadata));
+                ledger
+                    .and_then(|ledger| {
+                        sheets::sync(storage.as_ref(), &config.sheets, spreadsheet, &ledger)
+                            .map_err(std::io::Error::other)
+                    })
+                    .map(|outcome| {
+                        println!(
+                            "Added {} expense(s) from the sheet; it now lists {}.",
+                            outcome.pulled.len(),
+                            outcome.pushed
+                        );
+                        for (row, err) in outcome.skipped.iter() {
+                            println!("  row {row} was left as it is: {err}");
+                        }
+                    })
+            }
+            Some(("resolve", resolve_matches)) => storage.load().and_then(|mut tracker| {
+                if tracker.conflicts.is_empty() {
+                    println!("No conflicts to resolve.");
+                    return Ok(());
+                }
+                let keep = resolve_matches.get_one::<String>("keep").map(|s| s.as_str());
+                let settled = sync::resolve(&mut tracker, keep);
+                storage.save(&tracker)?;
+                println!(
+                    "Resolved {settled} conflict(s); {} left.",
+                    tracker.conflicts.len()
+                );
+                Ok(())
+            }),
+            Some(("merge", merge_matches)) => {
+                let path = |id: &str| merge_matches.get_one::<PathBuf>(id).expect("required");
+                sync::merge(
+                    path("base"),
+                    path("ours"),
+                    path("theirs"),
+                    merge_matches
+                        .get_one::<PathBuf>("path")
+                        .map(|p| p.as_path()),
+                )
+            }
+            _ => unreachable!("sync requires a subcommand"),
+        };
+        if let Err(err) = result {
+            eprintln!("Error syncing: {}", err);
+            std::process::exit(exit::FAILURE);
         }
-        let file = File::open(filename).expect("File should exist");
-        let tracker: ExpenseTracker = serde_json::from_reader(file)?;
-        Ok(tracker)
+        return;
     }
 
-    fn print_all_expenses(&self) -> () {
-        for (i, expense) in self.expenses.iter().enumerate() {
-            println!(
-                "{}. Date: {}, Category: {}, Amount: ₹{}, Description: {}",
-                i + 1,
-                expense.date,
-                expense.category,
-                expense.amount,
-                expense.description,
-            );
+    // `add` only appends, so it skips loading the whole ledger.
+    if let Some(("add", sub_matches)) = matches.subcommand() {
+        if sub_matches.get_flag("stdin") {
+            add_batch(storage.as_ref(), &config, sub_matches);
+        } else {
+            add(storage.as_ref(), filename, &config, sub_matches);
         }
+        return;
     }
-}
-
-fn main() {
-    let matches = Command::new("Expense Tracker CLI")
-        .version("1.0")
-        .author("Arijit Gogoi <arijit@email.com>")
-        .about("Keeps track of your expenses.")
-        .subcommand_required(true)
-        .arg_required_else_help(true)
-        .subcommand(
-            Command::new("add")
-                .about("Add a new expense.")
-                .visible_alias("a")
-                .arg_required_else_help(true)
-                .arg(
-                    Arg::new("category")
-                        .required(true)
-                        .short('c')
-                        .long("category")
-                        .help("The category of the expense.")
-                        .value_parser(clap::value_parser!(String)),
-                )
-                .arg(
-                    Arg::new("amount")
-                        .required(true)
-                        .short('a')
-                        .long("amount")
-                        .help("The expense amount.")
-                        .value_parser(clap::value_parser!(f64)),
-                )
-                .arg(
-                    Arg::new("description")
-                        .required(true)
-                        .short('d')
-                        .long("description")
-                        .help("A description for the expense."),
-                )
-                .arg(
-                    Arg::new("when")
-                        .required(false)
-                        .short('w')
-                        .long("when")
-                        .help("The date of expense. (format: 2025-12-31)")
-                        .value_parser(clap::value_parser!(String)),
-                ),
-        )
-        .subcommand(
-            Command::new("delete")
-                .about("Delete an expense by row number.")
-                .visible_alias("d")
-                .arg_required_else_help(true)
-                .arg(
-                    Arg::new("row_number")
-                        .required(true)
-                        .help("Delete an expense by row number.")
-                        .value_parser(clap::value_parser!(usize)),
-                ),
-        )
-        .subcommand(
-            Command::new("summary")
-                .about("Summarize expenses by filtering or view all expenses.")
-                .visible_alias("s")
-                .arg_required_else_help(true)
-                .arg(
-                    Arg::new("category")
-                        .short('c')
-                        .long("category")
-                        .required(false)
-                        .help("Filter by category.")
-                        .value_parser(clap::value_parser!(String)),
-                )
-                .arg(
-                    Arg::new("date")
-                        .short('d')
-                        .long("date")
-                        .required(false)
-                        .help("Filter by exact date.")
-                        .value_parser(clap::value_parser!(String)),
-                )
-                .arg(
-                    Arg::new("month")
-                        .short('m')
-                        .long("month")
-                        .required(false)
-                        .help("Filter by month.")
-                        .value_parser(clap::value_parser!(u8)),
-                )
-                .arg(
-                    Arg::new("all")
-                        .action(clap::ArgAction::SetTrue)
-                        .short('a')
-                        .long("all")
-                        .required(false)
-                        .help("Total expenses."),
-                ),
-        )
-        .subcommand(
-            Command::new("list")
-                .about("List all expenses.")
-                .visible_alias("l"),
-        )
-        .get_matches();
 
-    let filename = "expenses.json";
-    let mut tracker = ExpenseTracker::load_from_json(filename).unwrap_or_else(|err| {
+    let mut tracker = storage.load().unwrap_or_else(|err| {
         eprintln!("Error loading data: {}", err);
-        std::process::exit(1);
+        std::process::exit(exit::DATA);
     });
+    let _command = logging::time("command");
+    // Record the currency so the ledger keeps it if the config changes.
+    let ledger = ledger_currency(&config, &tracker);
+    tracker.currency = Some(ledger.clone());
+    let currency = init_currency(matches.get_one::<String>("currency"), &ledger, &config);
+    // Rates used by converted reports, noted after their output
+    let mut rates_used = Vec::new();
+    // --only business or --only personal
+    let only = matches.get_one::<bool>("only").copied();
 
     match matches.subcommand() {
-        Some(("add", sub_matches)) => {
-            let date_string_opt = sub_matches.get_one::<String>("when");
-            let date_string = match date_string_opt {
-                Some(d) => d,
-                None => &Local::now().date_naive().to_string(),
+        Some(("delete", sub_matches)) if !sub_matches.contains_id("row_number") => {
+            let filter = filter_for(sub_matches, &tracker);
+            let rows = select(&tracker, &filter, &currency, &mut rates_used);
+            if rows.is_empty() {
+                println!("No matching expenses.");
+                return;
+            }
+            for row in rows.iter() {
+                println!("{}. {}", row + 1, tracker.expenses[*row].line(mask_private));
+            }
+            if let Some(row) = rows
+                .iter()
+                .find(|row| tracker.is_month_closed(tracker.expenses[**row].date))
+            {
+                eprintln!("Row {} is in a closed month.", row + 1);
+                std::process::exit(exit::CONFLICT);
+            }
+            if !sub_matches.get_flag("yes")
+                && !prompt::confirm(&i18n::t_args(
+                    "confirm-delete-many",
+                    &[("count", &rows.len())],
+                ))
+            {
+                println!("{}", i18n::t("nothing-deleted"));
+                return;
+            }
+
+            // Remove from the back so the remaining row numbers stay valid.
+            let removed: Vec<Expense> = rows
+                .iter()
+                .rev()
+                .map(|row| tracker.delete_expense(row + 1))
+                .collect();
+            if let Err(err) = storage.save(&tracker) {
+                eprintln!("Error deleting: {}", err);
+                std::process::exit(exit::DATA);
+            }
+            if sub_matches.get_flag("purge_attachments") {
+                for expense in removed.iter() {
+                    if let Err(err) = attachments::remove_all(filename, expense.id) {
+                        eprintln!("Error removing attachments: {}", err);
+                        std::process::exit(exit::FAILURE);
+                    }
+                }
+            }
+            println!(
+                "{}",
+                i18n::t_args("deleted-many", &[("count", &removed.len())])
+            );
+        }
+        Some(("edit", sub_matches)) if sub_matches.get_flag("editor") => {
+            let filter = filter_for(sub_matches, &tracker);
+            let rows = select(&tracker, &filter, &currency, &mut rates_used);
+            if rows.is_empty() {
+                println!("No matching expenses.");
+                return;
+            }
+            let edited = match edit::in_editor(&tracker, &rows) {
+                Ok(Some(edited)) => edited,
+                Ok(None) => {
+                    println!("Nothing changed.");
+                    return;
+                }
+                Err(err) => {
+                    eprintln!("Error editing: {}", err);
+                    std::process::exit(exit::FAILURE);
+                }
             };
-            let date = NaiveDate::parse_from_str(date_string, "%Y-%m-%d")
-                .expect("Should be correctly formatted: %Y-%m-%d (for example, 2025-12-31)");
-            let category = sub_matches
-                .get_one::<String>("category")
-                .expect("Category of the expense should be provided.");
-            let amount: f64 = *sub_matches
-                .try_get_one::<f64>("amount")
-                .expect("amount should be a number")
-                .expect("amount should be a float");
-            let description = sub_matches
-                .get_one::<String>("description")
-                .expect("Description of the expense should be provided.");
-
-            let expense = Expense {
-                date,
-                amount,
-                category: category.clone(),
-                description: description.clone(),
+            if edited.changed.is_empty() && edited.deleted.is_empty() {
+                println!("Nothing changed.");
+                return;
+            }
+            for expense in edited.changed.iter() {
+                if let Some(stored) = tracker.expenses.iter_mut().find(|e| e.id == expense.id) {
+                    *stored = expense.clone();
+                }
+            }
+            tracker.expenses.retain(|e| !edited.deleted.contains(&e.id));
+            if let Err(err) = storage.save(&tracker) {
+                eprintln!("Error saving data: {}", err);
+                std::process::exit(exit::DATA);
+            }
+            println!(
+                "Updated {} and deleted {} expense(s).",
+                edited.changed.len(),
+                edited.deleted.len()
+            );
+        }
+        Some(("edit", sub_matches)) => {
+            let filter = filter_for(sub_matches, &tracker);
+            if filter.is_empty() {
+                eprintln!("Give at least one filter to select the expenses to edit.");
+                std::process::exit(exit::USAGE);
+            }
+            let tags = |id: &str| {
+                sub_matches
+                    .get_many::<String>(id)
+                    .into_iter()
+                    .flatten()
+                    .cloned()
+                    .collect::<Vec<String>>()
             };
+            let mut changes: Vec<edit::Change> = sub_matches
+                .get_many::<edit::Change>("set")
+                .into_iter()
+                .flatten()
+                .cloned()
+                .collect();
+            changes.extend(tags("add_tag").into_iter().map(edit::Change::AddTag));
+            changes.extend(tags("remove_tag").into_iter().map(edit::Change::RemoveTag));
+            if changes.is_empty() {
+                eprintln!("Nothing to change; use --set, --add-tag or --remove-tag.");
+                std::process::exit(exit::USAGE);
+            }
 
-            tracker.add_expense(expense);
-            if let Err(err) = tracker.save_to_json(filename) {
-                eprintln!("Error saving data: {}", err);
-                std::process::exit(1);
+            let rows = select(&tracker, &filter, &currency, &mut rates_used);
+            if rows.is_empty() {
+                println!("No matching expenses.");
+                return;
+            }
+            for row in rows.iter() {
+                println!("{}. {}", row + 1, tracker.expenses[*row].line(mask_private));
+            }
+            if let Some(row) = rows
+                .iter()
+                .find(|row| tracker.is_month_closed(tracker.expenses[**row].date))
+            {
+                eprintln!("Row {} is in a closed month.", row + 1);
+                std::process::exit(exit::CONFLICT);
+            }
+            if !sub_matches.get_flag("yes")
+                && !prompt::confirm(&format!("Edit {} expense(s)?", rows.len()))
+            {
+                println!("Nothing changed.");
+                return;
             }
 
-            println!("Expense added successfully!\n");
-            tracker.print_all_expenses();
+            let changed = rows
+                .iter()
+                .filter(|row| {
+                    edit::apply(
+                        &mut tracker.expenses[**row],
+                        &changes,
+                        filter.category.as_deref(),
+                    )
+                })
+                .count();
+            if let Err(err) = storage.save(&tracker) {
+                eprintln!("Error saving data: {}", err);
+                std::process::exit(exit::DATA);
+            }
+            println!("Updated {} expense(s).", changed);
         }
         Some(("delete", sub_matches)) => {
             let row_number = sub_matches
@@ -255,55 +642,2318 @@ fn main() {
                 .expect("row number should be a number")
                 .expect("some number should be given");
 
-            let length = &tracker.expenses.len();
-            if row_number > length {
-                eprintln!("Row number should be less than {length}")
+            let length = tracker.expenses.len();
+            if *row_number == 0 || *row_number > length {
+                eprintln!("No row {row_number}; there are {length} expense(s).");
+                std::process::exit(exit::NOT_FOUND);
             }
-            tracker.delete_expense(*row_number);
+            if tracker.is_month_closed(tracker.expenses[*row_number - 1].date) {
+                eprintln!("Row {row_number} is in a closed month.");
+                std::process::exit(exit::CONFLICT);
+            }
+            println!(
+                "{}. {}",
+                row_number,
+                tracker.expenses[*row_number - 1].line(mask_private)
+            );
+            if !sub_matches.get_flag("yes") && !prompt::confirm(&i18n::t("confirm-delete")) {
+                println!("{}", i18n::t("nothing-deleted"));
+                return;
+            }
+            let removed = tracker.delete_expense(*row_number);
 
-            if let Err(err) = tracker.save_to_json(filename) {
+            if let Err(err) = storage.save(&tracker) {
                 eprintln!("Error deleting: {}", err);
-                std::process::exit(1);
+                std::process::exit(exit::DATA);
+            }
+            if sub_matches.get_flag("purge_attachments")
+                && let Err(err) = attachments::remove_all(filename, removed.id)
+            {
+                eprintln!("Error removing attachments: {}", err);
+                std::process::exit(exit::FAILURE);
             }
         }
         Some(("summary", sub_matches)) => {
-            if sub_matches.get_flag("all") {
-                println!("Total expenses: ₹{:.2}", tracker.summary_all());
+            let mut filter = filter_for(sub_matches, &tracker);
+            if let Some(date) = sub_matches.get_one::<NaiveDate>("date") {
+                filter = filter.on(*date);
+            }
+            if let Some((from, to)) = summary_period(sub_matches, &config) {
+                filter.from = Some(from);
+                filter.to = Some(to);
+            }
+            let all = sub_matches.get_flag("all");
+            if !all && filter.is_empty() {
+                eprintln!(
+                    "Please provide a valid option for summary (e.g., --all, --category <name>, --date <YYYY-MM-DD>, --month <number>)."
+                );
+                std::process::exit(exit::USAGE);
+            }
+            include_archives(&mut tracker, filename, filter.from, filter.to);
+            let tracker = report_view(&tracker, &currency, only, &mut rates_used);
+            let total = currency::amount(tracker.summary(&filter));
+            if all {
+                println!("{}", i18n::t_args("total-expenses", &[("total", &total)]));
             } else {
-                match (
-                    sub_matches.get_one::<String>("category"),
-                    sub_matches.get_one::<String>("date"),
-                    sub_matches.get_one::<u8>("month"),
-                ) {
-                    (Some(category), _, _) => {
-                        println!("Expenses: ₹{:.2}", tracker.summary_by_category(&category))
-                    }
-                    (_, Some(date), _) => {
-                        let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").expect(
-                            "Should be correctly formatted: %Y-%m-%d (for example, 2025-12-31)",
-                        );
-                        println!("Expenses: ₹{:.2}", tracker.summary_by_date(date));
+                println!("Expenses: {total}");
+            }
+            print_business_split(&tracker, &filter);
+            // With only a month number, how that month is going
+            if filter.from.is_none() && filter.to.is_none() {
+                let today = dates::now(config.timezone).date;
+                if let Some(month) = filter.month.and_then(|m| report::pace::latest(m, today)) {
+                    report::pace::print(&tracker, &filter, month, today);
+                }
+            }
+        }
+        Some(("total", sub_matches)) => {
+            let filter = filter_for(sub_matches, &tracker);
+            include_archives(&mut tracker, filename, filter.from, filter.to);
+            let tracker = report_view(&tracker, &currency, only, &mut rates_used);
+            let total = tracker.summary(&filter);
+            println!(
+                "{}",
+                i18n::t_args("total-expenses", &[("total", &currency::amount(total))])
+            );
+            print_business_split(&tracker, &filter);
+        }
+        Some(("stats", sub_matches)) => {
+            let (from, to) = match sub_matches.get_one::<NaiveDate>("month") {
+                Some(month) => (Some(*month), Some(dates::month_end(*month))),
+                None => (
+                    sub_matches.get_one::<NaiveDate>("from").copied(),
+                    sub_matches.get_one::<NaiveDate>("to").copied(),
+                ),
+            };
+            include_archives(&mut tracker, filename, from, to);
+            let tracker = report_view(&tracker, &currency, only, &mut rates_used);
+            let expenses = report::in_range(&tracker, from, to);
+            let amounts: Vec<f64> = expenses.iter().map(|e| e.amount).collect();
+            let Some(overall) = report::stats::describe(&amounts) else {
+                println!("{}", i18n::t("no-expenses"));
+                return;
+            };
+
+            let mut rows = vec![(String::from("All"), overall)];
+            for (category, _) in report::category_totals(&expenses) {
+                let legs: Vec<f64> = expenses
+                    .iter()
+                    .flat_map(|e| e.category_amounts())
+                    .filter(|(c, _)| *c == category)
+                    .map(|(_, amount)| amount)
+                    .collect();
+                if let Some(stats) = report::stats::describe(&legs) {
+                    rows.push((category, stats));
+                }
+            }
+            report::stats::print_table(&rows);
+        }
+        Some(("forecast", sub_matches)) => {
+            let tracker = report_view(&tracker, &currency, only, &mut rates_used);
+            let as_of = sub_matches
+                .get_one::<NaiveDate>("as_of")
+                .copied()
+                .unwrap_or_else(|| dates::now(config.timezone).date);
+            let history = *sub_matches.get_one::<u32>("history").expect("has default");
+            report::forecast::print(&report::forecast::build(&tracker, as_of, history));
+        }
+        Some(("list", sub_matches)) => {
+            let filter = filter_for(sub_matches, &tracker);
+            if tracker.expenses.is_empty() {
+                println!("{}", i18n::t("no-expenses"));
+            } else if sub_matches.get_flag("cumulative") {
+                let rows: Vec<usize> = if filter.is_empty() {
+                    (0..tracker.expenses.len()).collect()
+                } else {
+                    select(&tracker, &filter, &currency, &mut rates_used)
+                };
+                let limit = sub_matches.contains_id("budget").then(|| {
+                    match sub_matches.get_one::<f64>("budget") {
+                        Some(amount) => report::running::Limit::Fixed(*amount),
+                        None => report::running::Limit::Budgets,
+                    }
+                });
+                let view = converted(&tracker, &currency, &mut rates_used);
+                report::running::print(&tracker, &view, &rows, &filter, limit, mask_private);
+            } else if filter.is_empty() {
+                tracker.print_all_expenses(mask_private);
+            } else {
+                // Rows keep their numbers so they can be passed to `delete`.
+                for row in select(&tracker, &filter, &currency, &mut rates_used) {
+                    println!("{}. {}", row + 1, tracker.expenses[row].line(mask_private));
+                }
+            }
+        }
+        Some(("search", sub_matches)) => {
+            let query = sub_matches
+                .get_one::<String>("query")
+                .expect("query should be provided");
+            let matcher = search::Matcher::new(query, sub_matches.get_flag("regex"))
+                .unwrap_or_else(|err| {
+                    eprintln!("Invalid regex: {}", err);
+                    std::process::exit(exit::USAGE);
+                });
+            let filter = filter_for(sub_matches, &tracker);
+            let view = converted(&tracker, &currency, &mut rates_used);
+            let matching: Vec<(&Expense, &Expense)> = tracker
+                .expenses
+                .iter()
+                .zip(view.expenses.iter())
+                .filter(|(expense, converted)| {
+                    matcher.matches(expense) && filter.matches(converted)
+                })
+                .collect();
+            if matching.is_empty() {
+                println!("No matching expenses.");
+                return;
+            }
+            for (expense, _) in matching.iter() {
+                println!("{}", expense.line(mask_private));
+            }
+            let total = matching
+                .iter()
+                .fold(0.0, |sum, (_, converted)| sum + converted.amount);
+            println!(
+                "\n{} match(es), totalling {}",
+                matching.len(),
+                currency::amount(total)
+            );
+        }
+        Some(("archive", sub_matches)) => {
+            let before = *sub_matches
+                .get_one::<NaiveDate>("before")
+                .expect("before should be provided");
+            let moved = archive::archive(filename, &mut tracker, before).unwrap_or_else(|err| {
+                eprintln!("Error archiving: {}", err);
+                std::process::exit(exit::DATA);
+            });
+            if moved.is_empty() {
+                println!("No expenses before {before}.");
+                return;
+            }
+            if let Err(err) = storage.save(&tracker) {
+                eprintln!("Error saving data: {}", err);
+                std::process::exit(exit::DATA);
+            }
+            for (year, count) in moved {
+                println!(
+                    "Moved {count} expense(s) to {}.",
+                    archive::path(filename, year).display()
+                );
+            }
+        }
+        Some(("snapshot", sub_matches)) => {
+            let result = match sub_matches.subcommand() {
+                Some(("create", snapshot_matches)) => {
+                    let name = snapshot_matches
+                        .get_one::<String>("name")
+                        .expect("name should be provided");
+                    snapshot::create(filename, name, &tracker).map(|_| {
+                        println!(
+                            "Snapshot {name} saved with {} expense(s).",
+                            tracker.expenses.len()
+                        )
+                    })
+                }
+                Some(("remove", snapshot_matches)) => {
+                    let name = snapshot_matches
+                        .get_one::<String>("name")
+                        .expect("name should be provided");
+                    std::fs::remove_file(snapshot::path(filename, name))
+                        .map(|_| println!("Snapshot {name} removed."))
+                }
+                Some(("list", _)) => snapshot::print_list(filename),
+                _ => unreachable!("snapshot requires a subcommand"),
+            };
+            if let Err(err) = result {
+                eprintln!("Error: {}", err);
+                std::process::exit(exit::of(&err));
+            }
+        }
+        Some(("diff", sub_matches)) => {
+            let load = |name: &String| {
+                snapshot::load(filename, name).unwrap_or_else(|err| {
+                    eprintln!("Error: {}", err);
+                    std::process::exit(exit::of(&err));
+                })
+            };
+            let before = load(
+                sub_matches
+                    .get_one::<String>("snapshot")
+                    .expect("snapshot should be provided"),
+            );
+            // Against the current ledger unless another snapshot is named
+            let after = match sub_matches.get_one::<String>("other") {
+                Some(other) => load(other),
+                None => tracker.clone(),
+            };
+            snapshot::print_diff(&before, &after, mask_private);
+        }
+        Some(("history", sub_matches)) => {
+            let events = history::load(filename).unwrap_or_else(|err| {
+                eprintln!("Error reading history: {}", err);
+                std::process::exit(exit::DATA);
+            });
+            if let Some(date) = sub_matches.get_one::<NaiveDate>("at") {
+                let expenses = history::at(&events, *date);
+                if expenses.is_empty() {
+                    println!("No expenses recorded by the end of {date}.");
+                }
+                for expense in expenses.iter() {
+                    println!("{}", expense.line(mask_private));
+                }
+                return;
+            }
+            let id = sub_matches.get_one::<u64>("id");
+            let matching: Vec<&history::Event> = events
+                .iter()
+                .filter(|e| id.is_none_or(|id| e.change.id() == *id))
+                .collect();
+            let limit = *sub_matches
+                .get_one::<usize>("limit")
+                .expect("limit has a default");
+            // Every change to one expense, or the latest changes overall
+            let shown = if id.is_some() {
+                &matching[..]
+            } else {
+                &matching[matching.len().saturating_sub(limit)..]
+            };
+            history::print(shown, mask_private);
+        }
+        Some(("show", sub_matches)) => {
+            let id = *sub_matches
+                .get_one::<u64>("id")
+                .expect("id should be provided");
+            let Some(expense) = tracker.find_by_id(id) else {
+                eprintln!("No expense with id {id}.");
+                std::process::exit(exit::NOT_FOUND);
+            };
+            show::print(
+                expense,
+                filename,
+                mask_private,
+                sub_matches.get_flag("open"),
+            );
+        }
+        Some(("balances", _)) => {
+            shared::print_balances(&converted(&tracker, &currency, &mut rates_used))
+        }
+        Some(("settle", sub_matches)) => {
+            let amount = *sub_matches
+                .get_one::<f64>("amount")
+                .expect("amount should be provided");
+            let from = sub_matches.get_one::<String>("from").expect("has default");
+            let to = sub_matches.get_one::<String>("to").expect("has default");
+            if from.eq_ignore_ascii_case(to) {
+                eprintln!("A settlement needs two different people (use --from and/or --to).");
+                std::process::exit(exit::USAGE);
+            }
+
+            tracker.settlements.push(Settlement {
+                date: dates::now(config.timezone).date,
+                from: from.clone(),
+                to: to.clone(),
+                amount,
+            });
+            if let Err(err) = storage.save(&tracker) {
+                eprintln!("Error saving data: {}", err);
+                std::process::exit(exit::DATA);
+            }
+            println!("Recorded: {from} paid {to} {}", currency::amount(amount));
+        }
+        Some(("debt", sub_matches)) => match sub_matches.subcommand() {
+            Some(("add", debt_matches)) => {
+                let person = debt_matches
+                    .get_one::<String>("person")
+                    .expect("person should be provided");
+                let amount = *debt_matches
+                    .get_one::<f64>("amount")
+                    .expect("amount should be provided");
+                let borrowed = debt_matches.get_flag("borrowed");
+                tracker.debts.push(debt::Debt {
+                    date: debt_matches
+                        .get_one::<NaiveDate>("date")
+                        .copied()
+                        .unwrap_or_else(|| dates::now(config.timezone).date),
+                    person: person.clone(),
+                    amount: if borrowed { -amount } else { amount },
+                    description: debt_matches.get_one::<String>("description").cloned(),
+                    repayment: false,
+                });
+                if let Err(err) = storage.save(&tracker) {
+                    eprintln!("Error saving data: {}", err);
+                    std::process::exit(exit::DATA);
+                }
+                if borrowed {
+                    println!(
+                        "Recorded: borrowed {} from {person}",
+                        currency::amount(amount)
+                    );
+                } else {
+                    println!("Recorded: lent {person} {}", currency::amount(amount));
+                }
+            }
+            Some(("repay", debt_matches)) => {
+                let person = debt_matches
+                    .get_one::<String>("person")
+                    .expect("person should be provided");
+                let owed = debt::balance(&tracker, person);
+                if owed.abs() < 0.005 {
+                    eprintln!("Nothing is owed with {person}.");
+                    std::process::exit(exit::CONFLICT);
+                }
+                let amount = debt_matches
+                    .get_one::<f64>("amount")
+                    .copied()
+                    .unwrap_or(owed.abs());
+                if amount > owed.abs() + 0.005 {
+                    eprintln!(
+                        "Only {} is owed with {person}.",
+                        currency::amount(owed.abs())
+                    );
+                    std::process::exit(exit::CONFLICT);
+                }
+                // A repayment moves the balance back towards zero.
+                tracker.debts.push(debt::Debt {
+                    date: debt_matches
+                        .get_one::<NaiveDate>("date")
+                        .copied()
+                        .unwrap_or_else(|| dates::now(config.timezone).date),
+                    person: person.clone(),
+                    amount: -amount.copysign(owed),
+                    description: None,
+                    repayment: true,
+                });
+                if let Err(err) = storage.save(&tracker) {
+                    eprintln!("Error saving data: {}", err);
+                    std::process::exit(exit::DATA);
+                }
+                let left = debt::balance(&tracker, person);
+                if owed > 0.0 {
+                    println!("Recorded: {person} repaid {}", currency::amount(amount));
+                } else {
+                    println!("Recorded: repaid {person} {}", currency::amount(amount));
+                }
+                if left.abs() < 0.005 {
+                    println!("All square with {person}.");
+                } else {
+                    println!("{} still owed.", currency::amount(left.abs()));
+                }
+            }
+            Some(("list", debt_matches)) => debt::print_list(
+                &converted(&tracker, &currency, &mut rates_used),
+                debt_matches.get_one::<String>("person").map(String::as_str),
+            ),
+            Some(("balances", _)) => {
+                debt::print_balances(&converted(&tracker, &currency, &mut rates_used))
+            }
+            _ => unreachable!("debt requires a subcommand"),
+        },
+        Some(("assets", sub_matches)) => match sub_matches.subcommand() {
+            Some(("set", asset_matches)) => {
+                let name = asset_matches
+                    .get_one::<String>("name")
+                    .expect("name should be provided");
+                let amount = *asset_matches
+                    .get_one::<f64>("amount")
+                    .expect("amount should be provided");
+                let date = asset_matches
+                    .get_one::<NaiveDate>("date")
+                    .copied()
+                    .unwrap_or_else(|| dates::now(config.timezone).date);
+                match tracker
+                    .accounts
+                    .iter_mut()
+                    .find(|a| a.name.eq_ignore_ascii_case(name))
+                {
+                    Some(account) => account.set(date, amount),
+                    None => tracker.accounts.push(asset::Account {
+                        name: name.clone(),
+                        liability: asset_matches.get_flag("liability"),
+                        balances: vec![asset::Snapshot { date, amount }],
+                    }),
+                }
+                if let Err(err) = storage.save(&tracker) {
+                    eprintln!("Error saving data: {}", err);
+                    std::process::exit(exit::DATA);
+                }
+                println!("{name}: {} on {date}", currency::amount(amount));
+            }
+            Some(("remove", asset_matches)) => {
+                let name = asset_matches
+                    .get_one::<String>("name")
+                    .expect("name should be provided");
+                let before = tracker.accounts.len();
+                tracker
+                    .accounts
+                    .retain(|a| !a.name.eq_ignore_ascii_case(name));
+                if tracker.accounts.len() == before {
+                    eprintln!("No account named {name}.");
+                    std::process::exit(exit::NOT_FOUND);
+                }
+                if let Err(err) = storage.save(&tracker) {
+                    eprintln!("Error saving data: {}", err);
+                    std::process::exit(exit::DATA);
+                }
+                println!("Account {name} removed.");
+            }
+            Some(("list", _)) => asset::print_list(
+                &converted(&tracker, &currency, &mut rates_used),
+                dates::now(config.timezone).date,
+            ),
+            _ => unreachable!("assets requires a subcommand"),
+        },
+        Some(("category", sub_matches)) => match sub_matches.subcommand() {
+            Some(("list", _)) => category::print_list(&tracker),
+            Some(("bucket", category_matches)) => {
+                let name = category_matches
+                    .get_one::<String>("category")
+                    .expect("category should be provided");
+                let bucket = category_matches.get_one::<String>("bucket");
+                category::set_bucket(&mut tracker, name, bucket.map(String::as_str));
+                if let Err(err) = storage.save(&tracker) {
+                    eprintln!("Error saving data: {}", err);
+                    std::process::exit(exit::DATA);
+                }
+                match bucket {
+                    Some(bucket) => println!("{name} is in {bucket}."),
+                    None => println!("{name} is in no bucket."),
+                }
+            }
+            Some(("preset", preset_matches)) => match preset_matches.subcommand() {
+                Some(("list", _)) => category::print_presets(),
+                Some(("apply", apply_matches)) => {
+                    let name = apply_matches
+                        .get_one::<String>("name")
+                        .expect("name should be provided");
+                    let preset = category::find_preset(name).expect("clap checks the name");
+                    let added = category::apply(&mut tracker, preset);
+                    if let Err(err) = storage.save(&tracker) {
+                        eprintln!("Error saving data: {}", err);
+                        std::process::exit(exit::DATA);
+                    }
+                    println!("Applied {}: {added} new categories.", preset.name);
+                }
+                _ => unreachable!("preset requires a subcommand"),
+            },
+            _ => unreachable!("category requires a subcommand"),
+        },
+        Some(("budget", sub_matches)) => {
+            let this_month = dates::period_of(dates::now(config.timezone).date);
+            match sub_matches.subcommand() {
+                Some(("set", budget_matches)) => {
+                    let category = budget_matches
+                        .get_one::<String>("category")
+                        .expect("category should be provided");
+                    let budget = Budget {
+                        category: category.clone(),
+                        amount: *budget_matches
+                            .get_one::<f64>("amount")
+                            .expect("amount should be provided"),
+                        rollover: budget_matches.get_flag("rollover"),
+                        since: budget_matches
+                            .get_one::<NaiveDate>("since")
+                            .copied()
+                            .unwrap_or(this_month),
+                    };
+                    tracker
+                        .budgets
+                        .retain(|b| !b.category.eq_ignore_ascii_case(category));
+                    tracker.budgets.push(budget);
+                    if let Err(err) = storage.save(&tracker) {
+                        eprintln!("Error saving data: {}", err);
+                        std::process::exit(exit::DATA);
+                    }
+                    println!("Budget set for {category}.");
+                }
+                Some(("remove", budget_matches)) => {
+                    let category = budget_matches
+                        .get_one::<String>("category")
+                        .expect("category should be provided");
+                    let before = tracker.budgets.len();
+                    tracker
+                        .budgets
+                        .retain(|b| !b.category.eq_ignore_ascii_case(category));
+                    if tracker.budgets.len() == before {
+                        eprintln!("No budget set for {category}.");
+                        std::process::exit(exit::NOT_FOUND);
+                    }
+                    if let Err(err) = storage.save(&tracker) {
+                        eprintln!("Error saving data: {}", err);
+                        std::process::exit(exit::DATA);
+                    }
+                    println!("Budget removed for {category}.");
+                }
+                Some(("status", budget_matches)) => {
+                    let month = budget_matches
+                        .get_one::<NaiveDate>("month")
+                        .copied()
+                        .unwrap_or(this_month);
+                    let tracker = report_view(&tracker, &currency, only, &mut rates_used);
+                    budget::print_status(&budget::status(&tracker, month));
+                }
+                Some(("envelope", budget_matches)) => {
+                    let month = budget_matches
+                        .get_one::<NaiveDate>("month")
+                        .copied()
+                        .unwrap_or(this_month);
+                    let tracker = report_view(&tracker, &currency, only, &mut rates_used);
+                    budget::print_envelopes(&budget::status(&tracker, month));
+                }
+                _ => unreachable!("budget requires a subcommand"),
+            }
+        }
+        Some(("goal", sub_matches)) => {
+            let today = dates::now(config.timezone).date;
+            match sub_matches.subcommand() {
+                Some(("add", goal_matches)) => {
+                    let name = goal_matches
+                        .get_one::<String>("name")
+                        .expect("name should be provided");
+                    if tracker
+                        .goals
+                        .iter()
+                        .any(|g| g.name.eq_ignore_ascii_case(name))
+                    {
+                        eprintln!("A goal named {name} already exists.");
+                        std::process::exit(exit::CONFLICT);
                     }
-                    (_, _, Some(month)) => {
-                        println!("Expenses: ₹{:.2}", tracker.summary_by_month(month));
+                    tracker.goals.push(Goal {
+                        name: name.clone(),
+                        target: *goal_matches
+                            .get_one::<f64>("target")
+                            .expect("target should be provided"),
+                        by: *goal_matches
+                            .get_one::<NaiveDate>("by")
+                            .expect("by should be provided"),
+                        since: goal_matches
+                            .get_one::<NaiveDate>("since")
+                            .copied()
+                            .unwrap_or_else(|| dates::month_start(today)),
+                        category: goal_matches.get_one::<String>("category").cloned(),
+                        monthly_income: goal_matches.get_one::<f64>("income").copied(),
+                    });
+                    if let Err(err) = storage.save(&tracker) {
+                        eprintln!("Error saving data: {}", err);
+                        std::process::exit(exit::DATA);
                     }
-                    _ => {
+                    println!("Goal {name} added.");
+                }
+                Some(("remove", goal_matches)) => {
+                    let name = goal_matches
+                        .get_one::<String>("name")
+                        .expect("name should be provided");
+                    let before = tracker.goals.len();
+                    tracker.goals.retain(|g| !g.name.eq_ignore_ascii_case(name));
+                    if tracker.goals.len() == before {
+                        eprintln!("No goal named {name}.");
+                        std::process::exit(exit::NOT_FOUND);
+                    }
+                    if let Err(err) = storage.save(&tracker) {
+                        eprintln!("Error saving data: {}", err);
+                        std::process::exit(exit::DATA);
+                    }
+                    println!("Goal {name} removed.");
+                }
+                Some(("status", _)) => goal::print_status(
+                    &report_view(&tracker, &currency, only, &mut rates_used),
+                    today,
+                ),
+                _ => unreachable!("goal requires a subcommand"),
+            }
+        }
+        Some(("subscription", sub_matches)) => {
+            let today = dates::now(config.timezone).date;
+            match sub_matches.subcommand() {
+                Some(("add", subscription_matches)) => {
+                    let name = subscription_matches
+                        .get_one::<String>("name")
+                        .expect("name should be provided");
+                    if tracker
+                        .subscriptions
+                        .iter()
+                        .any(|s| s.name.eq_ignore_ascii_case(name))
+                    {
+                        eprintln!("A subscription named {name} already exists.");
+                        std::process::exit(exit::CONFLICT);
+                    }
+                    let cycle = match subscription_matches
+                        .get_one::<String>("every")
+                        .map(String::as_str)
+                    {
+                        Some("year") => Cycle::Yearly,
+                        _ => Cycle::Monthly,
+                    };
+                    tracker.subscriptions.push(Subscription {
+                        name: name.clone(),
+                        amount: *subscription_matches
+                            .get_one::<f64>("amount")
+                            .expect("amount should be provided"),
+                        cycle,
+                        day: *subscription_matches
+                            .get_one::<u32>("day")
+                            .expect("day should be provided"),
+                        month: match cycle {
+                            Cycle::Yearly => subscription_matches.get_one::<u32>("month").copied(),
+                            Cycle::Monthly => None,
+                        },
+                        category: subscription_matches.get_one::<String>("category").cloned(),
+                        url: subscription_matches.get_one::<String>("url").cloned(),
+                        notes: subscription_matches.get_one::<String>("notes").cloned(),
+                    });
+                    if let Err(err) = storage.save(&tracker) {
+                        eprintln!("Error saving data: {}", err);
+                        std::process::exit(exit::DATA);
+                    }
+                    println!("Subscription {name} added.");
+                }
+                Some(("remove", subscription_matches)) => {
+                    let name = subscription_matches
+                        .get_one::<String>("name")
+                        .expect("name should be provided");
+                    let before = tracker.subscriptions.len();
+                    tracker
+                        .subscriptions
+                        .retain(|s| !s.name.eq_ignore_ascii_case(name));
+                    if tracker.subscriptions.len() == before {
+                        eprintln!("No subscription named {name}.");
+                        std::process::exit(exit::NOT_FOUND);
+                    }
+                    if let Err(err) = storage.save(&tracker) {
+                        eprintln!("Error saving data: {}", err);
+                        std::process::exit(exit::DATA);
+                    }
+                    println!("Subscription {name} removed.");
+                }
+                Some(("list", _)) => subscription::print_list(
+                    &converted(&tracker, &currency, &mut rates_used),
+                    today,
+                ),
+                Some(("upcoming", subscription_matches)) => subscription::print_upcoming(
+                    &converted(&tracker, &currency, &mut rates_used),
+                    today,
+                    *subscription_matches
+                        .get_one::<u32>("days")
+                        .expect("has default"),
+                ),
+                _ => unreachable!("subscription requires a subcommand"),
+            }
+        }
+        Some(("loan", sub_matches)) => {
+            let today = dates::now(config.timezone).date;
+            match sub_matches.subcommand() {
+                Some(("add", loan_matches)) => {
+                    let name = loan_matches
+                        .get_one::<String>("name")
+                        .expect("name should be provided");
+                    if loan::find(&tracker, name).is_some() {
+                        eprintln!("A loan named {name} already exists.");
+                        std::process::exit(exit::CONFLICT);
+                    }
+                    let principal = *loan_matches
+                        .get_one::<f64>("principal")
+                        .expect("principal should be provided");
+                    let rate = *loan_matches
+                        .get_one::<f64>("rate")
+                        .expect("rate should be provided");
+                    let months = *loan_matches
+                        .get_one::<u32>("months")
+                        .expect("months should be provided");
+                    let emi = loan_matches
+                        .get_one::<f64>("emi")
+                        .copied()
+                        .unwrap_or_else(|| loan::emi(principal, rate, months));
+                    // Otherwise nothing is paid off and it all falls on the
+                    // last instalment.
+                    let interest = principal * rate / 1200.0;
+                    if emi <= interest {
                         eprintln!(
-                            "Please provide a valid option for summary (e.g., --all, --category <name>, --date <YYYY-MM-DD>, --month <number>)."
+                            "An EMI of {} doesn't cover the first month's interest of {}.",
+                            currency::amount(emi),
+                            currency::amount(interest)
                         );
+                        std::process::exit(exit::USAGE);
                     }
+                    let loan = loan::Loan {
+                        name: name.clone(),
+                        principal,
+                        rate,
+                        emi,
+                        months,
+                        start: loan_matches
+                            .get_one::<NaiveDate>("start")
+                            .copied()
+                            .unwrap_or(today),
+                        category: loan_matches.get_one::<String>("category").cloned(),
+                        posted: 0,
+                    };
+                    tracker.loans.push(loan);
+                    if let Err(err) = storage.save(&tracker) {
+                        eprintln!("Error saving data: {}", err);
+                        std::process::exit(exit::DATA);
+                    }
+                    println!(
+                        "Loan {name} added with an EMI of {}.",
+                        currency::amount(emi)
+                    );
+                }
+                Some(("remove", loan_matches)) => {
+                    let name = loan_matches
+                        .get_one::<String>("name")
+                        .expect("name should be provided");
+                    let before = tracker.loans.len();
+                    tracker.loans.retain(|l| !l.name.eq_ignore_ascii_case(name));
+                    if tracker.loans.len() == before {
+                        eprintln!("No loan named {name}.");
+                        std::process::exit(exit::NOT_FOUND);
+                    }
+                    if let Err(err) = storage.save(&tracker) {
+                        eprintln!("Error saving data: {}", err);
+                        std::process::exit(exit::DATA);
+                    }
+                    println!("Loan {name} removed.");
                 }
+                Some(("list", _)) => {
+                    loan::print_list(&converted(&tracker, &currency, &mut rates_used), today)
+                }
+                Some(("schedule", loan_matches)) => {
+                    let name = loan_matches
+                        .get_one::<String>("name")
+                        .expect("name should be provided");
+                    let view = converted(&tracker, &currency, &mut rates_used);
+                    let Some(loan) = loan::find(&view, name) else {
+                        eprintln!("No loan named {name}.");
+                        std::process::exit(exit::NOT_FOUND);
+                    };
+                    loan::print_schedule(loan, loan_matches.get_flag("yearly"));
+                }
+                Some(("post", _)) => {
+                    let mut pending = Vec::new();
+                    for loan in tracker.loans.iter() {
+                        for instalment in loan.schedule().iter().skip(loan.posted as usize) {
+                            if instalment.date > today {
+                                break;
+                            }
+                            if tracker.is_month_closed(instalment.date) {
+                                eprintln!(
+                                    "EMI {} of {} falls in a closed month; reopen {} to log it.",
+                                    instalment.number,
+                                    loan.name,
+                                    dates::month_key(instalment.date)
+                                );
+                                break;
+                            }
+                            pending.push((loan.name.clone(), loan.expense(instalment)));
+                        }
+                    }
+                    if pending.is_empty() {
+                        println!("No instalments due.");
+                        return;
+                    }
+                    let mut logged = Vec::new();
+                    for (name, expense) in pending {
+                        if let Some(loan) = tracker.loans.iter_mut().find(|l| l.name == name) {
+                            loan.posted += 1;
+                        }
+                        logged.push(expense.description.clone());
+                        tracker.add_expense(expense);
+                    }
+                    if let Err(err) = storage.save(&tracker) {
+                        eprintln!("Error saving data: {}", err);
+                        std::process::exit(exit::DATA);
+                    }
+                    for description in logged.iter() {
+                        println!("Logged {description}.");
+                    }
+                    println!("Logged {} instalment(s).", logged.len());
+                }
+                _ => unreachable!("loan requires a subcommand"),
             }
         }
-        Some(("list", _)) => {
-            if tracker.expenses.is_empty() {
-                println!("No expenses found.");
+        Some(("reimburse", sub_matches)) => match sub_matches.subcommand() {
+            Some(("list", reimburse_matches)) => {
+                let status =
+                    reimburse_matches
+                        .get_one::<String>("status")
+                        .map(|s| match s.as_str() {
+                            "pending" => reimburse::Status::Pending,
+                            "submitted" => reimburse::Status::Submitted,
+                            _ => reimburse::Status::Reimbursed,
+                        });
+                reimburse::print_list(&tracker, status, mask_private);
+            }
+            Some((step @ ("submit" | "settle"), reimburse_matches)) => {
+                let (from, to) = if step == "submit" {
+                    (reimburse::Status::Pending, reimburse::Status::Submitted)
+                } else {
+                    (reimburse::Status::Submitted, reimburse::Status::Reimbursed)
+                };
+                for id in reimburse_matches
+                    .get_many::<u64>("ids")
+                    .expect("ids should be provided")
+                {
+                    if let Err(err) = reimburse::advance(&mut tracker, *id, from, to) {
+                        eprintln!("{err}");
+                        std::process::exit(exit::CONFLICT);
+                    }
+                }
+                if let Err(err) = storage.save(&tracker) {
+                    eprintln!("Error saving data: {}", err);
+                    std::process::exit(exit::DATA);
+                }
+                println!("Marked as {to}.");
+            }
+            _ => unreachable!("reimburse requires a subcommand"),
+        },
+        Some(("view", sub_matches)) => match sub_matches.subcommand() {
+            Some(("save", view_matches)) => {
+                let name = view_matches
+                    .get_one::<String>("name")
+                    .expect("name should be provided");
+                let filter = Filter::from_matches(view_matches);
+                if filter.is_empty() {
+                    eprintln!("A view needs at least one filter.");
+                    std::process::exit(exit::USAGE);
+                }
+                tracker.views.retain(|v| !v.name.eq_ignore_ascii_case(name));
+                println!("View {name} saved: {}.", filter.describe());
+                tracker.views.push(filter::View {
+                    name: name.clone(),
+                    filter,
+                });
+                if let Err(err) = storage.save(&tracker) {
+                    eprintln!("Error saving data: {}", err);
+                    std::process::exit(exit::DATA);
+                }
+            }
+            Some(("remove", view_matches)) => {
+                let name = view_matches
+                    .get_one::<String>("name")
+                    .expect("name should be provided");
+                let before = tracker.views.len();
+                tracker.views.retain(|v| !v.name.eq_ignore_ascii_case(name));
+                if tracker.views.len() == before {
+                    eprintln!("No view named {name}.");
+                    std::process::exit(exit::NOT_FOUND);
+                }
+                if let Err(err) = storage.save(&tracker) {
+                    eprintln!("Error saving data: {}", err);
+                    std::process::exit(exit::DATA);
+                }
+                println!("View {name} removed.");
+            }
+            Some(("list", _)) => {
+                if tracker.views.is_empty() {
+                    println!("No views saved.");
+                }
+                for view in tracker.views.iter() {
+                    println!("{:<20} {}", view.name, view.filter.describe());
+                }
+            }
+            _ => unreachable!("view requires a subcommand"),
+        },
+        Some(("template", sub_matches)) => match sub_matches.subcommand() {
+            Some(("add", template_matches)) => {
+                let name = template_matches
+                    .get_one::<String>("name")
+                    .expect("name should be provided");
+                tracker
+                    .templates
+                    .retain(|t| !t.name.eq_ignore_ascii_case(name));
+                tracker.templates.push(template::Template {
+                    name: name.clone(),
+                    category: template_matches
+                        .get_one::<String>("category")
+                        .expect("category should be provided")
+                        .clone(),
+                    amount: *template_matches
+                        .get_one::<f64>("amount")
+                        .expect("amount should be provided"),
+                    description: template_matches.get_one::<String>("description").cloned(),
+                    payee: template_matches.get_one::<String>("payee").cloned(),
+                });
+                if let Err(err) = storage.save(&tracker) {
+                    eprintln!("Error saving data: {}", err);
+                    std::process::exit(exit::DATA);
+                }
+                println!("Template {name} saved.");
+            }
+            Some(("remove", template_matches)) => {
+                let name = template_matches
+                    .get_one::<String>("name")
+                    .expect("name should be provided");
+                let before = tracker.templates.len();
+                tracker
+                    .templates
+                    .retain(|t| !t.name.eq_ignore_ascii_case(name));
+                if tracker.templates.len() == before {
+                    eprintln!("No template named {name}.");
+                    std::process::exit(exit::NOT_FOUND);
+                }
+                if let Err(err) = storage.save(&tracker) {
+                    eprintln!("Error saving data: {}", err);
+                    std::process::exit(exit::DATA);
+                }
+                println!("Template {name} removed.");
+            }
+            Some(("list", _)) => template::print_list(&tracker),
+            _ => unreachable!("template requires a subcommand"),
+        },
+        Some(("rates", sub_matches)) => match sub_matches.subcommand() {
+            Some(("set", rates_matches)) => {
+                let code = rates_matches
+                    .get_one::<String>("code")
+                    .expect("code should be provided");
+                if *code == ledger {
+                    eprintln!("{code} is the ledger's own currency.");
+                    std::process::exit(exit::USAGE);
+                }
+                let rate = rates::Rate {
+                    date: rates_matches
+                        .get_one::<NaiveDate>("date")
+                        .copied()
+                        .unwrap_or_else(|| dates::now(config.timezone).date),
+                    currency: code.clone(),
+                    rate: *rates_matches
+                        .get_one::<f64>("rate")
+                        .expect("rate should be provided"),
+                };
+                tracker
+                    .rates
+                    .retain(|r| !(r.currency == rate.currency && r.date == rate.date));
+                println!(
+                    "Set 1 {} = {} from {}.",
+                    rate.currency,
+                    currency::amount_in(None, rate.rate),
+                    rate.date
+                );
+                tracker.rates.push(rate);
+                if let Err(err) = storage.save(&tracker) {
+                    eprintln!("Error saving data: {}", err);
+                    std::process::exit(exit::DATA);
+                }
+            }
+            Some(("list", _)) => rates::print_list(&tracker),
+            Some(("update", rates_matches)) => {
+                let url = config.rates_url.as_deref().unwrap_or(rates::DEFAULT_URL);
+                let mut days: Vec<NaiveDate> = tracker
+                    .expenses
+                    .iter()
+                    .filter(|e| e.currency.as_ref().is_some_and(|c| *c != ledger))
+                    .map(|e| e.date)
+                    .filter(|date| !rates::is_cached(&ledger, *date))
+                    .collect();
+                days.push(
+                    rates_matches
+                        .get_one::<NaiveDate>("date")
+                        .copied()
+                        .unwrap_or_else(|| dates::now(config.timezone).date),
+                );
+                days.sort();
+                days.dedup();
+                for date in days {
+                    match rates::fetch(url, &ledger, date) {
+                        Ok(count) => println!("Fetched {count} rate(s) for {date}."),
+                        Err(err) => {
+                            eprintln!("Error fetching rates: {}", err);
+                            std::process::exit(exit::FAILURE);
+                        }
+                    }
+                }
+            }
+            _ => unreachable!("rates requires a subcommand"),
+        },
+        Some(("close-month", sub_matches)) => {
+            let month = *sub_matches
+                .get_one::<NaiveDate>("month")
+                .expect("month should be provided");
+            let key = dates::month_key(month);
+
+            if sub_matches.get_flag("reopen") {
+                tracker.reopen_month(month);
             } else {
-                tracker.print_all_expenses();
+                if sub_matches.get_flag("checklist") {
+                    let checks =
+                        close::checklist(&converted(&tracker, &currency, &mut rates_used), month);
+                    close::print_checklist(&checks);
+                    if checks.iter().any(|c| !c.passed) && !sub_matches.get_flag("force") {
+                        eprintln!("Not closing {key}: resolve the failed checks or pass --force.");
+                        std::process::exit(exit::CONFLICT);
+                    }
+                }
+                tracker.close_month(month);
+            }
+
+            if let Err(err) = storage.save(&tracker) {
+                eprintln!("Error saving data: {}", err);
+                std::process::exit(exit::DATA);
+            }
+            if sub_matches.get_flag("reopen") {
+                println!("Reopened {key}.");
+            } else {
+                println!("Closed {key}.");
+            }
+        }
+        Some(("import", sub_matches)) => {
+            let path = sub_matches
+                .get_one::<PathBuf>("file")
+                .expect("file should be provided");
+            let format = match sub_matches.get_one::<String>("format") {
+                Some(format) => format.clone(),
+                None => path
+                    .extension()
+                    .map(|e| e.to_string_lossy().to_lowercase())
+                    .unwrap_or_default(),
+            };
+            let text = std::fs::read_to_string(path).unwrap_or_else(|err| {
+                eprintln!("Error reading {}: {}", path.display(), err);
+                std::process::exit(exit::DATA);
+            });
+            let date_format = sub_matches.get_one::<String>("date_format");
+            let mapping = match sub_matches.get_one::<PathBuf>("map") {
+                Some(path) => import::Mapping::load(path).unwrap_or_else(|err| {
+                    eprintln!("Error reading the mapping file: {}", err);
+                    std::process::exit(exit::DATA);
+                }),
+                None => import::Mapping::default(),
+            };
+            let statement = match format.as_str() {
+                "ofx" => import::parse_ofx(&text),
+                "qif" => import::parse_qif(&text, date_format.map(String::as_str)),
+                "ynab" => import::parse_ynab(&text, date_format.map(String::as_str)),
+                "gnucash" => {
+                    import::parse_gnucash(&text, date_format.map(String::as_str), &mapping)
+                }
+                "mmex" => import::parse_mmex(&text, date_format.map(String::as_str), &mapping),
+                _ => Err(String::from(
+                    "unknown statement format, pass --format ofx, qif, ynab, gnucash or mmex",
+                )),
+            }
+            .unwrap_or_else(|err| {
+                eprintln!("Error parsing {}: {}", path.display(), err);
+                std::process::exit(exit::DATA);
+            });
+
+            let default_category = sub_matches
+                .get_one::<String>("category")
+                .expect("has default");
+            let include_duplicates = sub_matches.get_flag("include_duplicates");
+            let mut pending = Vec::new();
+            let mut skipped = 0;
+            for tx in statement.transactions {
+                let duplicate = tracker
+                    .expenses
+                    .iter()
+                    .find(|e| e.date == tx.date && (e.amount - tx.amount).abs() < 0.005);
+                let closed = tracker.is_month_closed(tx.date);
+                let note = match (duplicate, closed) {
+                    (_, true) => String::from(" (closed month, skipped)"),
+                    (Some(existing), _) if !include_duplicates => {
+                        format!(" (possible duplicate of #{}, skipped)", existing.id)
+                    }
+                    (Some(existing), _) => format!(" (possible duplicate of #{})", existing.id),
+                    (None, _) => String::new(),
+                };
+                println!(
+                    "  {} {} {}{}",
+                    tx.date,
+                    currency::amount(tx.amount),
+                    tx.description,
+                    note
+                );
+                if closed || (duplicate.is_some() && !include_duplicates) {
+                    skipped += 1;
+                    continue;
+                }
+                pending.push(Expense {
+                    date: tx.date,
+                    category: tx.category.unwrap_or_else(|| default_category.clone()),
+                    amount: tx.amount,
+                    description: tx.description,
+                    payee: tx.payee,
+                    ..Default::default()
+                });
+            }
+            if statement.skipped_credits > 0 {
+                println!("Ignoring {} credit(s).", statement.skipped_credits);
+            }
+            if pending.is_empty() {
+                println!("Nothing to import.");
+                return;
+            }
+            if !sub_matches.get_flag("yes")
+                && !prompt::confirm(&format!(
+                    "Import {} expense(s), skipping {}?",
+                    pending.len(),
+                    skipped
+                ))
+            {
+                println!("Import cancelled.");
+                return;
+            }
+
+            let count = pending.len();
+            for expense in pending {
+                tracker.add_expense(expense);
+            }
+            if let Err(err) = storage.save(&tracker) {
+                eprintln!("Error saving data: {}", err);
+                std::process::exit(exit::DATA);
+            }
+            println!("Imported {count} expense(s).");
+        }
+        Some(("export", sub_matches)) => {
+            let filter = filter_for(sub_matches, &tracker);
+            include_archives(&mut tracker, filename, filter.from, filter.to);
+            let tracker = report_view(&tracker, &currency, only, &mut rates_used);
+            let expenses = filter.apply(tracker.expenses.iter());
+
+            let output = match sub_matches.get_one::<String>("format").map(String::as_str) {
+                Some("ledger") => export::ledger(
+                    &expenses,
+                    &currency,
+                    sub_matches
+                        .get_one::<String>("account")
+                        .expect("has default"),
+                )
+                .into_bytes(),
+                Some("beancount") => export::beancount(
+                    &expenses,
+                    &currency,
+                    sub_matches
+                        .get_one::<String>("account")
+                        .expect("has default"),
+                )
+                .into_bytes(),
+                Some("xlsx") => export::xlsx(&expenses, &currency).unwrap_or_else(|err| {
+                    eprintln!("Error writing the workbook: {}", err);
+                    std::process::exit(exit::FAILURE);
+                }),
+                Some("ynab") => export::ynab(&expenses)
+                    .unwrap_or_else(|err| {
+                        eprintln!("Error writing CSV: {}", err);
+                        std::process::exit(exit::FAILURE);
+                    })
+                    .into_bytes(),
+                Some("parquet") => export::parquet(&expenses, &currency).unwrap_or_else(|err| {
+                    eprintln!("Error writing the Parquet file: {}", err);
+                    std::process::exit(exit::FAILURE);
+                }),
+                _ => unreachable!("format is validated by clap"),
+            };
+            write_output(sub_matches.get_one::<PathBuf>("out"), &output);
+        }
+        Some(("query", sub_matches)) => {
+            include_archives(&mut tracker, filename, None, None);
+            let tracker = report_view(&tracker, &currency, only, &mut rates_used);
+            let expenses: Vec<&Expense> = tracker.expenses.iter().collect();
+            let sql = sub_matches.get_one::<String>("sql").expect("required");
+            let table = query::run(&expenses, &currency, sql, mask_private).unwrap_or_else(|err| {
+                eprintln!("Error running the query: {}", err);
+                std::process::exit(exit::FAILURE);
+            });
+            match sub_matches.get_one::<String>("format").map(String::as_str) {
+                Some("csv") => match query::csv(&table) {
+                    Ok(csv) => write_output(sub_matches.get_one::<PathBuf>("out"), csv),
+                    Err(err) => {
+                        eprintln!("Error writing CSV: {}", err);
+                        std::process::exit(exit::FAILURE);
+                    }
+                },
+                _ => query::print_table(&table),
+            }
+        }
+        Some(("chart", sub_matches)) => {
+            let month = sub_matches.get_one::<NaiveDate>("month").copied();
+            include_archives(
+                &mut tracker,
+                filename,
+                month.map(dates::period_start),
+                month.map(dates::period_end),
+            );
+            let tracker = report_view(&tracker, &currency, only, &mut rates_used);
+            let expenses = match month {
+                Some(month) => report::in_month(&tracker, month),
+                None => report::personal(&tracker),
+            };
+            let period = month.map_or(String::from("all time"), |m| m.format("%B %Y").to_string());
+            let (chart, title) = match sub_matches.get_one::<String>("type").map(String::as_str) {
+                Some("pie") => (
+                    plot::Chart::Pie(report::category_totals(&expenses)),
+                    format!("Spending by category, {period}"),
+                ),
+                Some("bar") => {
+                    let end =
+                        month.unwrap_or_else(|| dates::period_of(dates::now(config.timezone).date));
+                    let months = *sub_matches.get_one::<u32>("months").expect("has default");
+                    (
+                        plot::Chart::Bar(report::trend::monthly_totals(
+                            &tracker, None, end, months,
+                        )),
+                        String::from("Monthly spending"),
+                    )
+                }
+                _ => (
+                    plot::Chart::Line(report::cumulative_by_day(&expenses)),
+                    format!("Cumulative spending, {period}"),
+                ),
+            };
+            let out = sub_matches
+                .get_one::<PathBuf>("out")
+                .expect("out is required");
+            if let Err(err) = plot::render(&chart, &title, out) {
+                eprintln!("Error drawing chart: {}", err);
+                std::process::exit(exit::FAILURE);
+            }
+            println!("Wrote {}", out.display());
+        }
+        Some(("report", sub_matches)) => {
+            include_archives(&mut tracker, filename, None, None);
+            let tracker = report_view(&tracker, &currency, only, &mut rates_used);
+            match sub_matches.subcommand() {
+                Some(("payees", report_matches)) => {
+                    let year = report_matches.get_one::<i32>("year").copied();
+                    report::print_payees(&tracker, year, mask_private);
+                }
+                Some(("trend", report_matches)) => {
+                    let end = match report_matches.get_one::<NaiveDate>("end") {
+                        Some(end) => *end,
+                        None => dates::period_of(dates::now(config.timezone).date),
+                    };
+                    let rows = report::trend::monthly_totals(
+                        &tracker,
+                        report_matches
+                            .get_one::<String>("category")
+                            .map(String::as_str),
+                        end,
+                        *report_matches
+                            .get_one::<u32>("months")
+                            .expect("has default"),
+                    );
+                    let rows = if report_matches.get_flag("real") {
+                        let Some((latest, _)) = config.cpi.last_key_value() else {
+                            eprintln!(
+                                "No price index set; add a [cpi] table to the config file, such as \"2025-01\" = 190.6."
+                            );
+                            std::process::exit(exit::USAGE);
+                        };
+                        println!("In {latest} money\n");
+                        report::trend::real(&rows, &config.cpi)
+                    } else {
+                        rows
+                    };
+                    report::trend::print(&rows, report_matches.get_flag("sparkline"));
+                }
+                Some(("anomalies", report_matches)) => {
+                    let filter = filter_for(report_matches, &tracker);
+                    let history = report::personal(&tracker);
+                    let checked = filter.apply(history.iter().copied());
+                    let sigmas = *report_matches.get_one::<f64>("sigma").expect("has default");
+                    report::anomalies::print(
+                        &report::anomalies::find(&checked, &history, sigmas),
+                        mask_private,
+                    );
+                }
+                Some(("streaks", report_matches)) => {
+                    let filter = filter_for(report_matches, &tracker);
+                    let expenses = filter.apply(report::personal(&tracker));
+                    let today = dates::now(config.timezone).date;
+                    let from = filter
+                        .from
+                        .or_else(|| expenses.iter().map(|e| e.date).min());
+                    let to = filter.to.unwrap_or(today);
+                    let totals = match from {
+                        Some(from) => report::streaks::daily_totals(&expenses, from, to),
+                        None => Vec::new(),
+                    };
+                    let limit = report_matches
+                        .get_one::<f64>("limit")
+                        .copied()
+                        .or(config.daily_limit);
+                    report::streaks::print(&totals, limit);
+                }
+                Some(("networth", report_matches)) => {
+                    let end = match report_matches.get_one::<NaiveDate>("end") {
+                        Some(end) => *end,
+                        None => dates::period_of(dates::now(config.timezone).date),
+                    };
+                    let months = *report_matches
+                        .get_one::<u32>("months")
+                        .expect("has default");
+                    report::networth::print(&report::networth::build(&tracker, end, months));
+                }
+                Some(("rolling", report_matches)) => {
+                    let end = match report_matches.get_one::<NaiveDate>("end") {
+                        Some(end) => *end,
+                        None => dates::period_of(dates::now(config.timezone).date),
+                    };
+                    let months = *report_matches
+                        .get_one::<u32>("months")
+                        .expect("has default");
+                    let window = *report_matches
+                        .get_one::<u32>("window")
+                        .expect("has default");
+                    report::rolling::print(
+                        &report::rolling::build(&tracker, end, months, window),
+                        end,
+                        months,
+                        window,
+                        report_matches.get_flag("last_year"),
+                    );
+                }
+                Some(("breakdown", report_matches)) => {
+                    let expenses = match report_matches.get_one::<NaiveDate>("month") {
+                        Some(month) => report::in_month(&tracker, *month),
+                        None => report::personal(&tracker),
+                    };
+                    let mut totals = report::category_totals(&expenses);
+                    if report_matches.get_flag("by_bucket") {
+                        totals = category::bucket_totals(&tracker, &totals);
+                    }
+                    report::breakdown::print(&totals, report_matches.get_flag("chart"));
+                }
+                Some(("trip", report_matches)) => {
+                    let expenses = report::personal(&tracker);
+                    match report_matches.get_one::<String>("name") {
+                        Some(name) => {
+                            let filter = Filter {
+                                trip: Some(name.clone()),
+                                ..Default::default()
+                            };
+                            report::trip::print(&filter.apply(expenses), name);
+                        }
+                        None => report::trip::print_list(&expenses),
+                    }
+                }
+                Some(("project", report_matches)) => {
+                    let expenses: Vec<&Expense> = tracker.expenses.iter().collect();
+                    let selected = match (
+                        report_matches.get_one::<String>("client"),
+                        report_matches.get_one::<String>("name"),
+                    ) {
+                        (Some(client), _) => match config.client(client) {
+                            Some((name, projects)) => Some((name.to_string(), projects.to_vec())),
+                            None => {
+                                eprintln!(
+                                    "No client named {client}; list its projects under [clients] in the config file."
+                                );
+                                std::process::exit(exit::NOT_FOUND);
+                            }
+                        },
+                        (None, Some(name)) => Some((name.clone(), vec![name.clone()])),
+                        (None, None) => None,
+                    };
+                    match selected {
+                        None => report::project::print_list(&expenses, &config),
+                        Some((title, projects)) => {
+                            let statement = report::project::build(
+                                &expenses,
+                                &title,
+                                &projects,
+                                report_matches.get_one::<NaiveDate>("from").copied(),
+                                report_matches.get_one::<NaiveDate>("to").copied(),
+                            );
+                            match report_matches
+                                .get_one::<String>("format")
+                                .map(String::as_str)
+                            {
+                                Some("csv") => match report::project::csv(&statement, mask_private)
+                                {
+                                    Ok(output) => write_output(
+                                        report_matches.get_one::<PathBuf>("out"),
+                                        &output,
+                                    ),
+                                    Err(err) => {
+                                        eprintln!("Error writing CSV: {}", err);
+                                        std::process::exit(exit::FAILURE);
+                                    }
+                                },
+                                _ => report::project::print(&statement, mask_private),
+                            }
+                        }
+                    }
+                }
+                Some(("hours", report_matches)) => {
+                    let filter = filter_for(report_matches, &tracker);
+                    report::hours::print(&filter.apply(tracker.personal_expenses()));
+                }
+                Some(("household", report_matches)) => {
+                    let filter = filter_for(report_matches, &tracker);
+                    report::household::print(&report::household::build(
+                        &filter.apply(tracker.personal_expenses()),
+                    ));
+                }
+                Some(("compare", report_matches)) => {
+                    let a = *report_matches
+                        .get_one::<report::compare::Period>("a")
+                        .expect("a is required");
+                    let b = *report_matches
+                        .get_one::<report::compare::Period>("b")
+                        .expect("b is required");
+                    let color =
+                        !report_matches.get_flag("no_color") && std::io::stdout().is_terminal();
+                    report::compare::print(&report::compare::build(&tracker, a, b), a, b, color);
+                }
+                Some(("calendar", report_matches)) => {
+                    let month = match report_matches.get_one::<NaiveDate>("month") {
+                        Some(month) => *month,
+                        None => dates::month_start(dates::now(config.timezone).date),
+                    };
+                    let color =
+                        !report_matches.get_flag("no_color") && std::io::stdout().is_terminal();
+                    report::calendar::print(&tracker, month, color);
+                }
+                Some(("top", report_matches)) => {
+                    let expenses = match report_matches.get_one::<NaiveDate>("month") {
+                        Some(month) => report::in_month(&tracker, *month),
+                        None => report::personal(&tracker),
+                    };
+                    let n = *report_matches.get_one::<usize>("n").expect("has default");
+                    report::top::print(&expenses, n, mask_private);
+                }
+                Some(("monthly", report_matches)) => {
+                    let month = *report_matches
+                        .get_one::<NaiveDate>("month")
+                        .expect("month should be provided");
+                    let monthly = report::monthly::build(&tracker, month);
+                    let output = match report_matches
+                        .get_one::<String>("format")
+                        .map(String::as_str)
+                    {
+                        Some("html") => report::monthly::html(&monthly, mask_private).into_bytes(),
+                        Some("pdf") => report::monthly::pdf(&monthly, mask_private),
+                        _ => report::monthly::markdown(&monthly, mask_private).into_bytes(),
+                    };
+                    write_output(report_matches.get_one::<PathBuf>("out"), &output);
+                }
+                Some(("yearly", report_matches)) => {
+                    let year = report_matches
+                        .get_one::<i32>("year")
+                        .copied()
+                        .unwrap_or_else(|| dates::now(config.timezone).date.year());
+                    let yearly = report::yearly::build(&tracker, year);
+                    match report_matches
+                        .get_one::<String>("format")
+                        .map(String::as_str)
+                    {
+                        Some("csv") => match report::yearly::csv(&yearly) {
+                            Ok(output) => {
+                                write_output(report_matches.get_one::<PathBuf>("out"), &output)
+                            }
+                            Err(err) => {
+                                eprintln!("Error writing CSV: {}", err);
+                                std::process::exit(exit::FAILURE);
+                            }
+                        },
+                        _ => report::yearly::print(&yearly),
+                    }
+                }
+                Some(("tax", report_matches)) => {
+                    let year = *report_matches
+                        .get_one::<i32>("fy")
+                        .expect("fy should be provided");
+                    let start_month = report_matches
+                        .get_one::<u32>("fy_start")
+                        .copied()
+                        .unwrap_or_else(|| config.fiscal_year_start());
+                    let Some(tax) = report::tax::build(&tracker, year, start_month) else {
+                        eprintln!(
+                            "There is no fiscal year {year} starting in month {start_month}."
+                        );
+                        std::process::exit(exit::USAGE);
+                    };
+                    match report_matches
+                        .get_one::<String>("format")
+                        .map(String::as_str)
+                    {
+                        Some("csv") => match report::tax::csv(&tax) {
+                            Ok(output) => {
+                                write_output(report_matches.get_one::<PathBuf>("out"), &output)
+                            }
+                            Err(err) => {
+                                eprintln!("Error writing CSV: {}", err);
+                                std::process::exit(exit::FAILURE);
+                            }
+                        },
+                        _ => report::tax::print(&tax),
+                    }
+                }
+                Some(("email", report_matches)) => {
+                    let month = match report_matches.get_one::<NaiveDate>("month") {
+                        Some(month) => *month,
+                        None => dates::period_of(dates::now(config.timezone).date) - Months::new(1),
+                    };
+                    let to: Vec<String> = report_matches
+                        .get_many::<String>("to")
+                        .map(|to| to.cloned().collect())
+                        .unwrap_or_default();
+                    let monthly = report::monthly::build(&tracker, month);
+                    let sent = mail::send(
+                        &config.email,
+                        &to,
+                        &format!("Expenses for {}", month.format("%B %Y")),
+                        report::monthly::markdown(&monthly, mask_private),
+                        report::monthly::html(&monthly, mask_private),
+                    );
+                    match sent {
+                        Ok(()) => println!("Sent the report for {}.", month.format("%B %Y")),
+                        Err(err) => {
+                            eprintln!("Error sending the report: {}", err);
+                            std::process::exit(exit::FAILURE);
+                        }
+                    }
+                }
+                Some(("custom", report_matches)) => {
+                    let expenses: Vec<&Expense> = report::in_range(
+                        &tracker,
+                        report_matches.get_one::<NaiveDate>("from").copied(),
+                        report_matches.get_one::<NaiveDate>("to").copied(),
+                    )
+                    .into_iter()
+                    .filter(|e| !(mask_private && e.private))
+                    .collect();
+                    let script = report_matches
+                        .get_one::<PathBuf>("script")
+                        .expect("script should be provided");
+                    if let Err(err) = report::custom::run(script, &tracker, &expenses) {
+                        eprintln!("Error running the report: {}", err);
+                        std::process::exit(exit::FAILURE);
+                    }
+                }
+                None => {
+                    let template = sub_matches
+                        .get_one::<PathBuf>("template")
+                        .expect("report needs a subcommand or a template");
+                    let filter = filter_for(sub_matches, &tracker);
+                    let expenses = filter.apply(tracker.personal_expenses());
+                    match report::template::render(template, &expenses, &filter, mask_private) {
+                        Ok(output) => write_output(sub_matches.get_one::<PathBuf>("out"), &output),
+                        Err(err) => {
+                            eprintln!("Error rendering the template: {}", err);
+                            std::process::exit(exit::FAILURE);
+                        }
+                    }
+                }
+                _ => unreachable!("report requires a subcommand"),
+            }
+        }
+        Some(("bot", sub_matches)) => match sub_matches.subcommand() {
+            Some(("telegram", _)) => {
+                if let Err(err) = bot::run_telegram(storage.as_ref(), &config, &ledger) {
+                    eprintln!("Error: {}", err);
+                    std::process::exit(exit::FAILURE);
+                }
+            }
+            _ => unreachable!("bot requires a subcommand"),
+        },
+        Some(("serve", sub_matches)) => {
+            let token = api_token(&matches, &config).unwrap_or_else(|| {
+                let token = server::new_token();
+                println!("No token configured; using {token}");
+                token
+            });
+            let bind = sub_matches
+                .get_one::<String>("bind")
+                .expect("has a default");
+            let port = *sub_matches.get_one::<u16>("port").expect("has a default");
+            if let Err(err) = server::run(
+                filename,
+                config.clone(),
+                ledger,
+                token,
+                read_only,
+                (bind, port),
+            ) {
+                eprintln!("Error serving: {}", err);
+                std::process::exit(exit::FAILURE);
             }
         }
         _ => {
             eprintln!("Invalid command. Use 'add', 'list', 'delete', or 'total'.");
+            std::process::exit(exit::USAGE);
+        }
+    }
+    rates::print_note(&rates_used);
+}
+
+// The code of the currency the ledger is kept in: the one it records, or
+// else the config's or the default.
+fn ledger_currency(config: &config::Config, tracker: &ExpenseTracker) -> String {
+    tracker
+        .currency
+        .clone()
+        .or_else(|| config.currency.clone())
+        .unwrap_or_else(|| String::from(currency::DEFAULT_CODE))
+}
+
+// Set up formatting for the ledger's currency and the output currency,
+// which is `output` when given. Returns the output currency's code.
+fn init_currency(output: Option<&String>, ledger: &str, config: &config::Config) -> String {
+    let mut ledger = currency::Currency::for_code(ledger);
+    if let Some(symbol) = &config.symbol {
+        ledger.symbol = symbol.clone();
+    }
+    let mut output = match output {
+        Some(code) if *code != ledger.code => currency::Currency::for_code(code),
+        _ => ledger.clone(),
+    };
+    if let Some(grouping) = config.grouping {
+        ledger.grouping = grouping;
+        output.grouping = grouping;
+    }
+    let code = output.code.clone();
+    currency::init(output, ledger);
+    code
+}
+
+// The ledger with all amounts converted to `currency` for reporting,
+// adding the rates that took to `used`. Commands that save keep working on
+// the amounts as entered.
+fn converted(
+    tracker: &ExpenseTracker,
+    currency: &str,
+    used: &mut Vec<rates::Rate>,
+) -> ExpenseTracker {
+    match rates::convert(tracker, currency) {
+        Ok((view, rates)) => {
+            used.extend(rates);
+            view
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(exit::DATA);
+        }
+    }
+}
+
+// The ledger as reports see it: converted, and with only business or only
+// personal expenses when `only` says so.
+fn report_view(
+    tracker: &ExpenseTracker,
+    currency: &str,
+    only: Option<bool>,
+    used: &mut Vec<rates::Rate>,
+) -> ExpenseTracker {
+    let mut view = converted(tracker, currency, used);
+    if let Some(business) = only {
+        view.expenses.retain(|e| e.business == business);
+    }
+    view
+}
+
+// The business and personal parts of a total, when the ledger has business
+// expenses and the filter doesn't already pick one.
+fn print_business_split(tracker: &ExpenseTracker, filter: &Filter) {
+    if filter.business.is_some() || !tracker.expenses.iter().any(|e| e.business) {
+        return;
+    }
+    for (name, business) in [("Business", true), ("Personal", false)] {
+        let part = Filter {
+            business: Some(business),
+            ..filter.clone()
+        };
+        println!("  {name}: {}", currency::amount(tracker.summary(&part)));
+    }
+}
+
+// Add every valid line of standard input with a single save, reporting
+// the lines that couldn't be added.
+fn add_batch(storage: &dyn Storage, config: &config::Config, sub_matches: &ArgMatches) {
+    let mut tracker = storage.load().unwrap_or_else(|err| {
+        eprintln!("Error loading data: {}", err);
+        std::process::exit(exit::DATA);
+    });
+    let ledger = ledger_currency(config, &tracker);
+    init_currency(None, &ledger, config);
+    let currency = sub_matches
+        .get_one::<String>("currency")
+        .filter(|code| **code != ledger)
+        .cloned();
+    let today = dates::now(config.timezone).date;
+
+    let mut ids = Vec::new();
+    let mut failed = Vec::new();
+    for (i, line) in std::io::stdin().lines().enumerate() {
+        let line = line.unwrap_or_else(|err| {
+            eprintln!("Error reading input: {}", err);
+            std::process::exit(exit::DATA);
+        });
+        let line = line.trim();
+        // Blank lines, comments and a CSV header are skipped.
+        if line.is_empty() || line.starts_with('#') || line.starts_with("date,") {
+            continue;
+        }
+        let entry = entry::parse_line(line, today).and_then(|entry| {
+            if tracker.is_month_closed(entry.date) {
+                Err(format!("{} is in a closed month", entry.date))
+            } else {
+                Ok(entry)
+            }
+        });
+        match entry {
+            Ok(entry) => {
+                ids.push(tracker.next_id);
+                tracker.add_expense(Expense {
+                    date: entry.date,
+                    category: entry.category,
+                    amount: entry.amount,
+                    currency: currency.clone(),
+                    description: entry.description,
+                    payee: entry.payee,
+                    trip: sub_matches.get_one::<String>("trip").cloned(),
+                    project: sub_matches.get_one::<String>("project").cloned(),
+                    user: sub_matches
+                        .get_one::<String>("user")
+                        .cloned()
+                        .or_else(|| config.user()),
+                    private: sub_matches.get_flag("private"),
+                    deductible: sub_matches.get_flag("deductible"),
+                    business: sub_matches.get_flag("business"),
+                    tags: sub_matches
+                        .get_many::<String>("tag")
+                        .map(|tags| tags.cloned().collect())
+                        .unwrap_or_default(),
+                    meta: sub_matches
+                        .get_many::<(String, String)>("meta")
+                        .map(|fields| fields.cloned().collect())
+                        .unwrap_or_default(),
+                    reimbursement: sub_matches
+                        .get_flag("reimbursable")
+                        .then_some(reimburse::Status::Pending),
+                    ..Default::default()
+                });
+            }
+            Err(err) => failed.push((i + 1, err)),
+        }
+    }
+
+    if !ids.is_empty()
+        && let Err(err) = storage.save(&tracker)
+    {
+        eprintln!("Error saving data: {}", err);
+        std::process::exit(exit::DATA);
+    }
+    println!("Added {} expense(s).", ids.len());
+    alert::budgets(&tracker, &config.budget_alerts, &ids);
+    webhook::added(&config.webhooks, &tracker, &ids);
+    if !failed.is_empty() {
+        eprintln!("{} line(s) failed:", failed.len());
+        for (line, err) in failed.iter() {
+            eprintln!("  line {line}: {err}");
+        }
+        std::process::exit(exit::DATA);
+    }
+}
+
+// The filter given by a command's flags, on top of the `--view` named.
+fn filter_for(matches: &ArgMatches, tracker: &ExpenseTracker) -> Filter {
+    let filter = Filter::from_matches(matches);
+    match matches.try_get_one::<String>("view").ok().flatten() {
+        Some(name) => match tracker
+            .views
+            .iter()
+            .find(|v| v.name.eq_ignore_ascii_case(name))
+        {
+            Some(view) => filter.or(&view.filter),
+            None => {
+                eprintln!("No view named {name}.");
+                std::process::exit(exit::NOT_FOUND);
+            }
+        },
+        None => filter,
+    }
+}
+
+// Merge the archived years between `from` and `to` back in, for read-only
+// reports.
+fn include_archives(
+    tracker: &mut ExpenseTracker,
+    filename: &str,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) {
+    if let Err(err) = archive::include(filename, tracker, from, to) {
+        eprintln!("Error loading archives: {}", err);
+        std::process::exit(exit::DATA);
+    }
+}
+
+// Row indices of the expenses matching `filter`. Amount bounds are
+// compared in the output currency, so only then is the ledger converted.
+fn select(
+    tracker: &ExpenseTracker,
+    filter: &Filter,
+    currency: &str,
+    used: &mut Vec<rates::Rate>,
+) -> Vec<usize> {
+    let rows = |tracker: &ExpenseTracker| {
+        tracker
+            .expenses
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| filter.matches(e))
+            .map(|(row, _)| row)
+            .collect()
+    };
+    if filter.has_amount_range() {
+        rows(&converted(tracker, currency, used))
+    } else {
+        rows(tracker)
+    }
+}
+
+// The token for the HTTP API: `--token`, the environment or the config.
+fn api_token(matches: &ArgMatches, config: &config::Config) -> Option<String> {
+    matches
+        .get_one::<String>("token")
+        .cloned()
+        .or_else(|| std::env::var(server::TOKEN_VAR).ok())
+        .or_else(|| config.server_token.clone())
+}
+
+// Run `add`, `list` or `summary` against the ledger served at `url`.
+fn remote_command(matches: &ArgMatches, url: &str, config: &config::Config, mask_private: bool) {
+    let fail = |err: String| -> ! {
+        eprintln!("Error: {}", err);
+        std::process::exit(exit::FAILURE);
+    };
+    let Some(token) = api_token(matches, config) else {
+        fail(format!(
+            "--remote needs a token; pass --token or set ${}",
+            server::TOKEN_VAR
+        ))
+    };
+    let client = remote::Client::new(url, token);
+    let ledger = client.ledger().unwrap_or_else(|err| fail(err));
+    let given_currency = matches.get_one::<String>("currency");
+    init_currency(None, &ledger.currency, config);
+
+    match matches.subcommand() {
+        Some(("add", sub_matches)) => {
+            for id in ["split", "split_with", "template", "stdin", "attach"] {
+                if sub_matches.value_source(id) == Some(clap::parser::ValueSource::CommandLine) {
+                    fail(format!(
+                        "add --{} can't be used with --remote",
+                        id.replace('_', "-")
+                    ));
+                }
+            }
+            let Some(category) = sub_matches.get_one::<String>("category") else {
+                missing_add_argument("--category <category>")
+            };
+            let items: Vec<Item> = sub_matches
+                .get_many::<Item>("item")
+                .map(|items| items.cloned().collect())
+                .unwrap_or_default();
+            let Some(amount) = sub_matches
+                .get_one::<f64>("amount")
+                .copied()
+                .or((!items.is_empty()).then(|| items.iter().map(Item::amount).sum()))
+            else {
+                missing_add_argument("--amount <amount>")
+            };
+            let Some(description) = sub_matches.get_one::<String>("description") else {
+                missing_add_argument("--description <description>")
+            };
+            // Dated here rather than on the server, which may be in
+            // another timezone.
+            let zone = sub_matches
+                .get_one::<dates::Zone>("tz")
+                .copied()
+                .or(config.timezone);
+            let when = match sub_matches.get_one::<dates::When>("when") {
+                Some(when) => when.in_zone(zone),
+                None => dates::now(zone),
+            };
+            let new = server::NewExpense {
+                date: Some(when.date),
+                time: when.time,
+                offset: when.offset,
+                category: category.clone(),
+                amount,
+                description: description.clone(),
+                currency: given_currency
+                    .filter(|code| **code != ledger.currency)
+                    .cloned(),
+                payee: sub_matches.get_one::<String>("payee").cloned(),
+                trip: sub_matches.get_one::<String>("trip").cloned(),
+                project: sub_matches.get_one::<String>("project").cloned(),
+                user: sub_matches
+                    .get_one::<String>("user")
+                    .cloned()
+                    .or_else(|| config.user()),
+                tags: sub_matches
+                    .get_many::<String>("tag")
+                    .map(|tags| tags.cloned().collect())
+                    .unwrap_or_default(),
+                meta: sub_matches
+                    .get_many::<(String, String)>("meta")
+                    .map(|fields| fields.cloned().collect())
+                    .unwrap_or_default(),
+                items,
+                private: sub_matches.get_flag("private"),
+                deductible: sub_matches.get_flag("deductible"),
+                business: sub_matches.get_flag("business"),
+                reimbursable: sub_matches.get_flag("reimbursable"),
+            };
+            let expense = client.add(&new).unwrap_or_else(|err| fail(err));
+            println!("{}\n", i18n::t("expense-added"));
+            println!("{}", expense.line(mask_private));
+        }
+        Some(("list", _)) | Some(("summary", _))
+            if given_currency.is_some_and(|code| *code != ledger.currency) =>
+        {
+            fail(format!(
+                "reports over --remote are in the ledger's currency, {}",
+                ledger.currency
+            ))
+        }
+        Some(("list", sub_matches)) => {
+            if sub_matches.get_flag("cumulative") {
+                fail(String::from(
+                    "list --cumulative can't be used with --remote",
+                ));
+            }
+            let filter = Filter::from_matches(sub_matches);
+            let view = sub_matches.get_one::<String>("view").map(|s| s.as_str());
+            let expenses = client.list(&filter, view).unwrap_or_else(|err| fail(err));
+            if expenses.is_empty() {
+                println!("{}", i18n::t("no-expenses"));
+            }
+            for expense in expenses.iter() {
+                println!("{}", expense.line(mask_private));
+            }
+        }
+        Some(("summary", sub_matches)) => {
+            let mut filter = Filter::from_matches(sub_matches);
+            if let Some(date) = sub_matches.get_one::<NaiveDate>("date") {
+                filter = filter.on(*date);
+            }
+            if let Some((from, to)) = summary_period(sub_matches, config) {
+                filter.from = Some(from);
+                filter.to = Some(to);
+            }
+            let view = sub_matches.get_one::<String>("view").map(|s| s.as_str());
+            let all = sub_matches.get_flag("all");
+            if !all && filter.is_empty() && view.is_none() {
+                eprintln!(
+                    "Please provide a valid option for summary (e.g., --all, --category <name>, --date <YYYY-MM-DD>, --month <number>)."
+                );
+                std::process::exit(exit::USAGE);
+            }
+            let summary = client
+                .summary(&filter, view)
+                .unwrap_or_else(|err| fail(err));
+            let total = currency::amount(summary.total);
+            if all {
+                println!("{}", i18n::t_args("total-expenses", &[("total", &total)]));
+            } else {
+                println!("Expenses: {total}");
+            }
+        }
+        Some((name, _)) => fail(format!(
+            "{name} can't be used with --remote; only add, list and summary can"
+        )),
+        None => unreachable!("a subcommand is required"),
+    }
+}
+
+// The dates `summary --fy`, `--quarter` or `--year` cover, if given.
+fn summary_period(
+    sub_matches: &ArgMatches,
+    config: &config::Config,
+) -> Option<(NaiveDate, NaiveDate)> {
+    let fiscal_year = |year: i32, start_month: u32| {
+        dates::fiscal_year(year, start_month).unwrap_or_else(|| {
+            eprintln!("There is no year {year} starting in month {start_month}.");
+            std::process::exit(exit::USAGE);
+        })
+    };
+    if let Some(year) = sub_matches.get_one::<i32>("fy") {
+        return Some(fiscal_year(*year, config.fiscal_year_start()));
+    }
+    if let Some(quarter) = sub_matches.get_one::<NaiveDate>("quarter") {
+        let end = (*quarter + Months::new(3))
+            .pred_opt()
+            .expect("dates before the quarter end exist");
+        return Some((*quarter, end));
+    }
+    sub_matches
+        .get_one::<i32>("year")
+        .map(|year| fiscal_year(*year, 1))
+}
+
+// Exit with clap's usual error for a required `add` argument left out.
+fn missing_add_argument(arg: &str) -> ! {
+    cli::build()
+        .find_subcommand_mut("add")
+        .expect("add is a subcommand")
+        .error(
+            clap::error::ErrorKind::MissingRequiredArgument,
+            format!("the following required argument was not provided: {arg}"),
+        )
+        .exit()
+}
+
+// Write command output to a file, or standard output when none is given.
+fn write_output(path: Option<&PathBuf>, output: impl AsRef<[u8]>) {
+    let result = match path {
+        Some(path) => std::fs::write(path, output),
+        None => std::io::stdout().write_all(output.as_ref()),
+    };
+    if let Err(err) = result {
+        let target = path.map_or(String::from("output"), |p| p.display().to_string());
+        eprintln!("Error writing {}: {}", target, err);
+        std::process::exit(exit::FAILURE);
+    }
+}
+
+fn add(storage: &dyn Storage, filename: &str, config: &config::Config, sub_matches: &ArgMatches) {
+    // Without any of the main fields given, ask for them one by one.
+    let interactive = ["category", "amount", "description", "split", "template"]
+        .iter()
+        .all(|id| !sub_matches.contains_id(id));
+    let metadata = if interactive || config.sticky {
+        // Suggesting categories and remembering values need the whole history.
+        storage.load()
+    } else {
+        storage.load_metadata()
+    }
+    .unwrap_or_else(|err| {
+        eprintln!("Error loading data: {}", err);
+        std::process::exit(exit::DATA);
+    });
+    // `--currency` on `add` gives the currency the expense was paid in.
+    let ledger = ledger_currency(config, &metadata);
+    init_currency(None, &ledger, config);
+    let template = sub_matches.get_one::<String>("template").map(|name| {
+        template::find(&metadata, name).unwrap_or_else(|| {
+            eprintln!("No template named {name}.");
+            std::process::exit(exit::NOT_FOUND);
+        })
+    });
+
+    let items: Vec<Item> = sub_matches
+        .get_many::<Item>("item")
+        .map(|items| items.cloned().collect())
+        .unwrap_or_default();
+
+    let zone = sub_matches
+        .get_one::<dates::Zone>("tz")
+        .copied()
+        .or(config.timezone);
+    let now = dates::now(zone);
+    let (when, category, amount, description, payee) = if interactive {
+        let Some(entry) = wizard::run(&metadata, now.date) else {
+            println!("\nExpense not added.");
+            return;
+        };
+        // The time is only known for something added as it happens.
+        let today = entry.date == now.date;
+        (
+            dates::When {
+                date: entry.date,
+                time: now.time.filter(|_| today),
+                offset: now.offset.filter(|_| today),
+            },
+            entry.category,
+            entry.amount,
+            entry.description,
+            entry.payee,
+        )
+    } else {
+        let when = match sub_matches.get_one::<dates::When>("when") {
+            Some(when) => when.in_zone(zone),
+            None => now,
+        };
+        // Flags override the template's fields, which override remembered
+        // values and then the config's category defaults.
+        let Some(description) = sub_matches
+            .get_one::<String>("description")
+            .cloned()
+            .or(template.map(|t| t.description().to_string()))
+        else {
+            missing_add_argument("--description <description>")
+        };
+        let similar = config
+            .sticky
+            .then(|| metadata.find_similar(&description))
+            .flatten();
+        let category = match sub_matches.get_one::<String>("category") {
+            Some(category) => category.clone(),
+            None if sub_matches.contains_id("split") => String::from("Split"),
+            None => match (template, similar) {
+                (Some(template), _) => template.category.clone(),
+                (None, Some(similar)) => {
+                    println!("Using category {} from #{}.", similar.category, similar.id);
+                    similar.category.clone()
+                }
+                (None, None) => missing_add_argument("--category <category>"),
+            },
+        };
+        let defaults = config.category_defaults(&category);
+        let Some(amount) = sub_matches
+            .get_one::<f64>("amount")
+            .copied()
+            .or((!items.is_empty()).then(|| items.iter().map(Item::amount).sum()))
+            .or(template.map(|t| t.amount))
+            .or(defaults.and_then(|d| d.amount))
+        else {
+            missing_add_argument("--amount <amount>")
+        };
+        let payee = sub_matches
+            .get_one::<String>("payee")
+            .cloned()
+            .or(template.and_then(|t| t.payee.clone()))
+            .or(similar
+                .filter(|s| s.category.eq_ignore_ascii_case(&category))
+                .and_then(|s| s.payee.clone()))
+            .or(defaults.and_then(|d| d.payee.clone()));
+        (when, category, amount, description, payee)
+    };
+    let date = when.date;
+    let splits: Vec<Split> = sub_matches
+        .get_many::<Split>("split")
+        .map(|legs| legs.cloned().collect())
+        .unwrap_or_default();
+    let shared_with: Vec<String> = sub_matches
+        .get_many::<String>("split_with")
+        .map(|people| people.cloned().collect())
+        .unwrap_or_default();
+    let paid_by = sub_matches
+        .get_one::<String>("paid_by")
+        .filter(|p| !p.eq_ignore_ascii_case(shared::ME))
+        .cloned();
+    let expense_currency = sub_matches
+        .get_one::<String>("currency")
+        .filter(|code| **code != ledger)
+        .cloned();
+    if metadata.is_month_closed(date) {
+        eprintln!("{} is in a closed month.", date);
+        std::process::exit(exit::CONFLICT);
+    }
+
+    let mut expense = Expense {
+        id: metadata.next_id,
+        date,
+        time: when.time,
+        offset: when.offset,
+        amount,
+        currency: expense_currency,
+        category,
+        description,
+        payee,
+        trip: sub_matches.get_one::<String>("trip").cloned(),
+        project: sub_matches.get_one::<String>("project").cloned(),
+        user: sub_matches
+            .get_one::<String>("user")
+            .cloned()
+            .or_else(|| config.user()),
+        splits,
+        items,
+        shared_with,
+        paid_by,
+        private: sub_matches.get_flag("private"),
+        deductible: sub_matches.get_flag("deductible"),
+        business: sub_matches.get_flag("business"),
+        tags: sub_matches
+            .get_many::<String>("tag")
+            .map(|tags| tags.cloned().collect())
+            .unwrap_or_default(),
+        meta: sub_matches
+            .get_many::<(String, String)>("meta")
+            .map(|fields| fields.cloned().collect())
+            .unwrap_or_default(),
+        reimbursement: sub_matches
+            .get_flag("reimbursable")
+            .then_some(reimburse::Status::Pending),
+        ..Default::default()
+    };
+    if !expense.splits_balance() {
+        eprintln!("Split amounts should add up to the expense amount ({amount}).");
+        std::process::exit(exit::USAGE);
+    }
+    if !expense.items_balance() {
+        eprintln!("Items should add up to the expense amount ({amount}).");
+        std::process::exit(exit::USAGE);
+    }
+
+    let sources: Vec<&PathBuf> = sub_matches
+        .get_many::<PathBuf>("attach")
+        .unwrap_or_default()
+        .collect();
+    // A dry run shows the files to be attached without copying them.
+    if sub_matches.get_flag("dry_run") {
+        for source in sources.iter() {
+            expense.attachments.push(source.display().to_string());
+        }
+    }
+
+    let mask = sub_matches.get_flag("mask_private");
+    let mut summary = expense.line(mask).to_string();
+    if interactive {
+        println!("\n{summary}");
+        if !prompt::confirm(&i18n::t("confirm-save")) {
+            println!("{}", i18n::t("expense-not-added"));
+            return;
+        }
+    }
+    let id = storage.append(expense).unwrap_or_else(|err| {
+        eprintln!("Error saving data: {}", err);
+        std::process::exit(exit::DATA);
+    });
+
+    // Receipts are filed under the id the expense was saved with, so they
+    // are only copied once it is; if one can't be, the expense is taken
+    // back out.
+    if !sources.is_empty() {
+        let attached: Result<Vec<String>, _> = sources
+            .iter()
+            .map(|source| attachments::attach(filename, id, source).map_err(|err| (source, err)))
+            .collect();
+        let mut tracker = storage.load().unwrap_or_else(|err| {
+            eprintln!("Error loading data: {}", err);
+            std::process::exit(exit::DATA);
+        });
+        let failed = match attached {
+            Ok(names) => {
+                if let Some(saved) = tracker.expenses.iter_mut().find(|e| e.id == id) {
+                    saved.attachments = names;
+                    summary = saved.line(mask).to_string();
+                }
+                None
+            }
+            Err((source, err)) => {
+                eprintln!("Error attaching {}: {}", source.display(), err);
+                if let Err(err) = attachments::remove_all(filename, id) {
+                    eprintln!("Error removing the copied receipts: {}", err);
+                }
+                tracker.expenses.retain(|e| e.id != id);
+                Some(exit::FAILURE)
+            }
+        };
+        if let Err(err) = storage.save(&tracker) {
+            eprintln!("Error saving data: {}", err);
+            std::process::exit(exit::DATA);
+        }
+        if let Some(code) = failed {
+            println!("{}", i18n::t("expense-not-added"));
+            std::process::exit(code);
+        }
+    }
+
+    println!("{}\n", i18n::t("expense-added"));
+    println!("{summary}");
+    // Alerts need the month's spending, so the ledger is only loaded for
+    // them when something could be said.
+    if metadata.budgets.is_empty() && config.webhooks.is_empty() {
+        return;
+    }
+    match storage.load() {
+        Ok(tracker) => {
+            alert::budgets(&tracker, &config.budget_alerts, &[id]);
+            webhook::added(&config.webhooks, &tracker, &[id]);
         }
+        Err(err) => eprintln!("Couldn't check budgets: {}", err),
     }
 }
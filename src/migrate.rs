@@ -0,0 +1,118 @@
+use serde_json::{Map, Value};
+use std::{fs, io, path::Path};
+
+use crate::crypto;
+use crate::expense::ExpenseTracker;
+
+// The data file version this build reads and writes. Files from before
+// versioning have no `version` field and count as version 0.
+pub const CURRENT: u32 = 1;
+
+// Each step upgrades a file from the version at its index to the next one.
+const STEPS: [fn(&mut Map<String, Value>); CURRENT as usize] = [assign_ids];
+
+// 0 → 1: expenses get stable ids and the file records the next one.
+fn assign_ids(file: &mut Map<String, Value>) {
+    let mut next = file.get("next_id").and_then(Value::as_u64).unwrap_or(0);
+    if let Some(Value::Array(expenses)) = file.get_mut("expenses") {
+        let id_of = |e: &Value| e.get("id").and_then(Value::as_u64).unwrap_or(0);
+        next = next.max(expenses.iter().map(id_of).max().unwrap_or(0) + 1);
+        for expense in expenses.iter_mut() {
+            if let Value::Object(expense) = expense
+                && expense.get("id").and_then(Value::as_u64).unwrap_or(0) == 0
+            {
+                expense.insert(String::from("id"), Value::from(next));
+                next += 1;
+            }
+        }
+    }
+    file.insert(String::from("next_id"), Value::from(next.max(1)));
+}
+
+// The version recorded in a parsed data file.
+pub fn version_of(file: &Value) -> u64 {
+    file.get("version").and_then(Value::as_u64).unwrap_or(0)
+}
+
+// Bring a parsed data file up to the current version, returning the version
+// it was at.
+pub fn upgrade(file: &mut Value) -> Result<u32, String> {
+    let version = version_of(file);
+    if version > CURRENT as u64 {
+        return Err(format!(
+            "the data file is version {version}, newer than this build supports ({CURRENT})"
+        ));
+    }
+    let Value::Object(map) = file else {
+        return Err(String::from("the data file should hold a JSON object"));
+    };
+    for step in STEPS.iter().skip(version as usize) {
+        step(map);
+    }
+    map.insert(String::from("version"), Value::from(CURRENT));
+    Ok(version as u32)
+}
+
+// Upgrade `filename` in place, checking that nothing was lost and keeping
+// a copy of the original next to it.
+pub fn run(filename: &str, dry_run: bool) -> io::Result<()> {
+    let text = match crypto::read(Path::new(filename)) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            println!("No data file yet; new files start at version {CURRENT}.");
+            return Ok(());
+        }
+        Err(err) => return Err(err),
+    };
+    let original: Value = serde_json::from_slice(&text)?;
+    let mut file = original.clone();
+    let from = upgrade(&mut file).map_err(io::Error::other)?;
+    if from == CURRENT {
+        println!("{filename} is already at version {CURRENT}.");
+        return Ok(());
+    }
+    let tracker: ExpenseTracker = serde_json::from_value(file)?;
+    verify(&original, &tracker).map_err(io::Error::other)?;
+    println!(
+        "{filename} {} from version {from} to {CURRENT} ({} expense(s) checked).",
+        if dry_run {
+            "would be upgraded"
+        } else {
+            "upgraded"
+        },
+        tracker.expenses.len()
+    );
+    if dry_run {
+        return Ok(());
+    }
+    // Copied as stored, so an encrypted file stays encrypted.
+    let backup = format!("{filename}.v{from}.bak");
+    fs::copy(filename, &backup)?;
+    tracker.save_to_json(filename)?;
+    println!("The original was kept as {backup}.");
+    Ok(())
+}
+
+// Check an upgraded ledger against the file it came from.
+fn verify(original: &Value, tracker: &ExpenseTracker) -> Result<(), String> {
+    let before = original
+        .get("expenses")
+        .and_then(Value::as_array)
+        .map_or(0, Vec::len);
+    if before != tracker.expenses.len() {
+        return Err(format!(
+            "expected {before} expense(s) after upgrading but found {}",
+            tracker.expenses.len()
+        ));
+    }
+    let mut ids: Vec<u64> = tracker.expenses.iter().map(|e| e.id).collect();
+    ids.sort();
+    ids.dedup();
+    if ids.len() != tracker.expenses.len() || ids.first() == Some(&0) {
+        return Err(String::from("expense ids are missing or repeated"));
+    }
+    if ids.last().is_some_and(|last| *last >= tracker.next_id) {
+        return Err(String::from("the next id is already taken"));
+    }
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use chrono::{Datelike, NaiveDate};
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use std::error::Error;
+use std::path::Path;
+
+const SIZE: (u32, u32) = (800, 600);
+const PALETTE: [RGBColor; 10] = [
+    RGBColor(31, 119, 180),
+    RGBColor(255, 127, 14),
+    RGBColor(44, 160, 44),
+    RGBColor(214, 39, 40),
+    RGBColor(148, 103, 189),
+    RGBColor(140, 86, 75),
+    RGBColor(227, 119, 194),
+    RGBColor(127, 127, 127),
+    RGBColor(188, 189, 34),
+    RGBColor(23, 190, 207),
+];
+
+// What to draw and the data behind it
+pub enum Chart {
+    // Share of each category
+    Pie(Vec<(String, f64)>),
+    // Total per month
+    Bar(Vec<(NaiveDate, f64)>),
+    // Running total per day
+    Line(Vec<(NaiveDate, f64)>),
+}
+
+// Render a chart to `out`, as PNG when the extension is `.png` and as SVG
+// otherwise.
+pub fn render(chart: &Chart, title: &str, out: &Path) -> Result<(), Box<dyn Error>> {
+    let is_png = out
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("png"));
+    if is_png {
+        let root = BitMapBackend::new(out, SIZE).into_drawing_area();
+        draw(&root, chart, title)?;
+        root.present()?;
+    } else {
+        let root = SVGBackend::new(out, SIZE).into_drawing_area();
+        draw(&root, chart, title)?;
+        root.present()?;
+    }
+    Ok(())
+}
+
+fn draw<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    chart: &Chart,
+    title: &str,
+) -> Result<(), Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    root.fill(&WHITE)?;
+    match chart {
+        Chart::Pie(categories) => {
+            let root = root.titled(title, ("sans-serif", 28))?;
+            let (width, height) = root.dim_in_pixel();
+            let center = (width as i32 / 2, height as i32 / 2);
+            let radius = (width.min(height) as f64) * 0.35;
+            let sizes: Vec<f64> = categories.iter().map(|(_, amount)| *amount).collect();
+            let labels: Vec<&str> = categories.iter().map(|(name, _)| name.as_str()).collect();
+            let colors: Vec<RGBColor> = (0..categories.len())
+                .map(|i| PALETTE[i % PALETTE.len()])
+                .collect();
+            let mut pie = Pie::new(&center, &radius, &sizes, &colors, &labels);
+            pie.start_angle(-90.0);
+            pie.label_style(("sans-serif", 16).into_font().color(&BLACK));
+            pie.percentages(("sans-serif", 14).into_font().color(&WHITE));
+            root.draw(&pie)?;
+        }
+        Chart::Bar(months) => {
+            let max = months.iter().map(|(_, t)| *t).fold(0.0, f64::max);
+            let mut chart = ChartBuilder::on(root)
+                .caption(title, ("sans-serif", 28))
+                .margin(20)
+                .x_label_area_size(40)
+                .y_label_area_size(70)
+                .build_cartesian_2d((0..months.len()).into_segmented(), 0.0..max * 1.1 + 1.0)?;
+            chart
+                .configure_mesh()
+                .disable_x_mesh()
+                .x_labels(months.len())
+                .x_label_formatter(&|x| match x {
+                    SegmentValue::CenterOf(i) => months
+                        .get(*i)
+                        .map_or(String::new(), |(m, _)| m.format("%Y-%m").to_string()),
+                    _ => String::new(),
+                })
+                .y_desc("Spend")
+                .draw()?;
+            chart.draw_series(
+                Histogram::vertical(&chart)
+                    .style(PALETTE[0].filled())
+                    .margin(8)
+                    .data(months.iter().enumerate().map(|(i, (_, total))| (i, *total))),
+            )?;
+        }
+        Chart::Line(days) => {
+            let (Some((first, _)), Some((last, _))) = (days.first(), days.last()) else {
+                return Err("no expenses to chart".into());
+            };
+            let max = days.iter().map(|(_, t)| *t).fold(0.0, f64::max);
+            let mut chart = ChartBuilder::on(root)
+                .caption(title, ("sans-serif", 28))
+                .margin(20)
+                .x_label_area_size(40)
+                .y_label_area_size(70)
+                .build_cartesian_2d(
+                    first.num_days_from_ce()..last.num_days_from_ce() + 1,
+                    0.0..max * 1.1 + 1.0,
+                )?;
+            chart
+                .configure_mesh()
+                .x_label_formatter(&|d| {
+                    NaiveDate::from_num_days_from_ce_opt(*d)
+                        .map_or(String::new(), |d| d.to_string())
+                })
+                .y_desc("Cumulative spend")
+                .draw()?;
+            chart.draw_series(LineSeries::new(
+                days.iter().map(|(d, t)| (d.num_days_from_ce(), *t)),
+                PALETTE[0].stroke_width(2),
+            ))?;
+        }
+    }
+    Ok(())
+}
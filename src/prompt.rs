@@ -0,0 +1,36 @@
+use std::io::{self, BufRead, Write};
+
+use crate::i18n;
+
+// Ask a yes/no question on the terminal; anything but "y"/"yes" (or a yes
+// in the chosen language) is a no.
+pub fn confirm(question: &str) -> bool {
+    print!("{question} [y/N] ");
+    let _ = io::stdout().flush();
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+    i18n::is_yes(&answer)
+}
+
+// Ask for a line of input, showing `default` in brackets and returning it
+// for an empty answer. None when input has ended.
+pub fn ask(question: &str, default: Option<&str>) -> Option<String> {
+    match default {
+        Some(default) => print!("{question} [{default}]: "),
+        None => print!("{question}: "),
+    }
+    let _ = io::stdout().flush();
+    let mut answer = String::new();
+    match io::stdin().lock().read_line(&mut answer) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => {
+            let answer = answer.trim();
+            match (answer.is_empty(), default) {
+                (true, Some(default)) => Some(default.to_string()),
+                _ => Some(answer.to_string()),
+            }
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use rusqlite::{Connection, params, types::Value};
+
+use crate::expense::{Expense, MASK};
+
+// The tables a query can read, one row per expense, per category leg, per
+// tag, per item and per field of the user's own. Dates and times are text such as
+// 2025-01-31 and 22:15, so they compare and work with SQLite's date
+// functions as they are.
+const SCHEMA: &str = "
+CREATE TABLE expenses (
+    id INTEGER PRIMARY KEY,
+    date TEXT NOT NULL,
+    time TEXT,
+    utc_offset TEXT,
+    category TEXT NOT NULL,
+    description TEXT NOT NULL,
+    payee TEXT,
+    trip TEXT,
+    project TEXT,
+    user TEXT,
+    amount REAL NOT NULL,
+    currency TEXT NOT NULL,
+    tags TEXT NOT NULL,
+    private INTEGER NOT NULL,
+    deductible INTEGER NOT NULL,
+    business INTEGER NOT NULL
+);
+CREATE TABLE categories (
+    expense_id INTEGER NOT NULL,
+    category TEXT NOT NULL,
+    amount REAL NOT NULL
+);
+CREATE TABLE tags (
+    expense_id INTEGER NOT NULL,
+    tag TEXT NOT NULL
+);
+CREATE TABLE items (
+    expense_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    quantity REAL NOT NULL,
+    price REAL NOT NULL,
+    amount REAL NOT NULL
+);
+CREATE TABLE meta (
+    expense_id INTEGER NOT NULL,
+    key TEXT NOT NULL,
+    value TEXT NOT NULL
+);
+";
+
+// The result of a query
+pub struct Table {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+}
+
+// Run `sql` over the expenses, loaded into an in-memory SQLite database.
+// The descriptions and payees of private expenses are masked if asked.
+pub fn run(
+    expenses: &[&Expense],
+    currency: &str,
+    sql: &str,
+    mask_private: bool,
+) -> rusqlite::Result<Table> {
+    let mut db = Connection::open_in_memory()?;
+    db.execute_batch(SCHEMA)?;
+    let load = db.transaction()?;
+    {
+        let mut expense_row = load.prepare(
+            "INSERT INTO expenses VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+        )?;
+        let mut category_row = load.prepare("INSERT INTO categories VALUES (?1, ?2, ?3)")?;
+        let mut tag_row = load.prepare("INSERT INTO tags VALUES (?1, ?2)")?;
+        let mut item_row = load.prepare("INSERT INTO items VALUES (?1, ?2, ?3, ?4, ?5)")?;
+        let mut meta_row = load.prepare("INSERT INTO meta VALUES (?1, ?2, ?3)")?;
+        for expense in expenses {
+            let mask = mask_private && expense.private;
+            expense_row.execute(params![
+                expense.id as i64,
+                expense.date.to_string(),
+                expense.time.map(|time| time.format("%H:%M").to_string()),
+                expense.offset.map(|offset| offset.to_string()),
+                expense.category,
+                if mask { MASK } else { &expense.description },
+                if mask {
+                    expense.payee.as_ref().map(|_| MASK)
+                } else {
+                    expense.payee.as_deref()
+                },
+                expense.trip,
+                expense.project,
+                expense.user,
+                expense.amount,
+                currency,
+                expense.tags.join(","),
+                expense.private,
+                expense.deductible,
+                expense.business,
+            ])?;
+            for (category, amount) in expense.category_amounts() {
+                category_row.execute(params![expense.id as i64, category, amount])?;
+            }
+            for tag in expense.tags.iter() {
+                tag_row.execute(params![expense.id as i64, tag])?;
+            }
+            for item in expense.items.iter() {
+                item_row.execute(params![
+                    expense.id as i64,
+                    if mask { MASK } else { &item.name },
+                    item.quantity,
+                    item.price,
+                    item.amount(),
+                ])?;
+            }
+            for (key, value) in expense.meta.iter() {
+                meta_row.execute(params![expense.id as i64, key, value])?;
+            }
+        }
+    }
+    load.commit()?;
+    // Questions only; the ledger isn't changed by anything done here anyway.
+    db.pragma_update(None, "query_only", true)?;
+
+    let mut statement = db.prepare(sql)?;
+    let columns: Vec<String> = statement
+        .column_names()
+        .iter()
+        .map(|c| c.to_string())
+        .collect();
+    let rows = statement
+        .query_map([], |row| {
+            (0..columns.len()).map(|i| row.get::<_, Value>(i)).collect()
+        })?
+        .collect::<rusqlite::Result<Vec<Vec<Value>>>>()?;
+    Ok(Table { columns, rows })
+}
+
+// A value as shown in the table; real numbers are taken to be money.
+fn display(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Integer(n) => n.to_string(),
+        Value::Real(n) => format!("{n:.2}"),
+        Value::Text(s) => s.clone(),
+        Value::Blob(b) => format!("<{} bytes>", b.len()),
+    }
+}
+
+pub fn print_table(table: &Table) {
+    let cells: Vec<Vec<String>> = table
+        .rows
+        .iter()
+        .map(|row| row.iter().map(display).collect())
+        .collect();
+    let widths: Vec<usize> = (0..table.columns.len())
+        .map(|i| {
+            cells
+                .iter()
+                .map(|row| row[i].chars().count())
+                .chain([table.columns[i].chars().count()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    // Numbers are right-aligned, going by the first row that has a value.
+    let numeric: Vec<bool> = (0..table.columns.len())
+        .map(|i| {
+            table
+                .rows
+                .iter()
+                .map(|row| &row[i])
+                .find(|value| !matches!(value, Value::Null))
+                .is_some_and(|value| matches!(value, Value::Integer(_) | Value::Real(_)))
+        })
+        .collect();
+    let line = |values: &[String]| {
+        let padded: Vec<String> = values
+            .iter()
+            .zip(widths.iter().zip(numeric.iter()))
+            .map(|(value, (width, numeric))| {
+                if *numeric {
+                    format!("{value:>width$}")
+                } else {
+                    format!("{value:<width$}")
+                }
+            })
+            .collect();
+        println!("{}", padded.join("  ").trim_end());
+    };
+    line(&table.columns);
+    for row in cells.iter() {
+        line(row);
+    }
+    println!(
+        "({} row{})",
+        table.rows.len(),
+        if table.rows.len() == 1 { "" } else { "s" }
+    );
+}
+
+pub fn csv(table: &Table) -> Result<String, csv::Error> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(&table.columns)?;
+    for row in table.rows.iter() {
+        writer.write_record(row.iter().map(|value| match value {
+            Value::Real(n) => n.to_string(),
+            value => display(value),
+        }))?;
+    }
+    let bytes = writer.into_inner().map_err(|err| err.into_error())?;
+    Ok(String::from_utf8(bytes).expect("CSV of strings is valid UTF-8"))
+}
@@ -0,0 +1,239 @@
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use crate::config;
+use crate::currency;
+use crate::expense::ExpenseTracker;
+
+// The value of one unit of a foreign currency in the ledger's currency,
+// from `date` until the next rate for that currency
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Rate {
+    pub date: NaiveDate,
+    pub currency: String,
+    pub rate: f64,
+}
+
+// The rate for `code` on `date`: the latest one set on or before it, or the
+// earliest one when all were set later.
+fn lookup<'a>(rates: &'a [Rate], code: &str, date: NaiveDate) -> Option<&'a Rate> {
+    let matching = rates.iter().filter(|r| r.currency == code);
+    matching
+        .clone()
+        .filter(|r| r.date <= date)
+        .max_by_key(|r| r.date)
+        .or_else(|| matching.min_by_key(|r| r.date))
+}
+
+// Multiply amounts in `from` by this to get amounts in `to` on `date`,
+// collecting the rates that were needed into `used`.
+fn factor(
+    tracker: &ExpenseTracker,
+    known: &[Rate],
+    from: &str,
+    to: &str,
+    date: NaiveDate,
+    used: &mut Vec<Rate>,
+) -> Result<f64, String> {
+    if from == to {
+        return Ok(1.0);
+    }
+    let ledger = tracker
+        .currency
+        .as_deref()
+        .unwrap_or(currency::DEFAULT_CODE);
+    let mut in_ledger = |code: &str| -> Result<f64, String> {
+        if code == ledger {
+            return Ok(1.0);
+        }
+        let rate = lookup(known, code, date).ok_or_else(|| {
+            format!("No exchange rate for {code}; add one with `rates set {code} <rate>`.")
+        })?;
+        if !used.contains(rate) {
+            used.push(rate.clone());
+        }
+        Ok(rate.rate)
+    };
+    Ok(in_ledger(from)? / in_ledger(to)?)
+}
+
+// A copy of the ledger with every amount converted to `base`, along with
+// the rates used. Expenses convert at the rate for their date; budgets and
+// goals at today's. Rates set by hand and those cached by `rates update`
+// are both considered.
+pub fn convert(
+    tracker: &ExpenseTracker,
+    base: &str,
+) -> Result<(ExpenseTracker, Vec<Rate>), String> {
+    let mut view = tracker.clone();
+    let mut used = Vec::new();
+    let ledger = tracker
+        .currency
+        .as_deref()
+        .unwrap_or(currency::DEFAULT_CODE);
+    let foreign = |code: Option<&str>| code.is_some_and(|c| c != ledger);
+    let mut known = if ledger != base
+        || tracker
+            .expenses
+            .iter()
+            .any(|e| foreign(e.currency.as_deref()))
+    {
+        cached(ledger)
+    } else {
+        Vec::new()
+    };
+    // Rates set by hand come last so they win over cached ones on the same day.
+    known.extend(tracker.rates.iter().cloned());
+    for expense in view.expenses.iter_mut() {
+        let from = expense.currency.as_deref().unwrap_or(ledger);
+        let factor = factor(tracker, &known, from, base, expense.date, &mut used)?;
+        expense.amount *= factor;
+        for split in expense.splits.iter_mut() {
+            split.amount *= factor;
+        }
+        for item in expense.items.iter_mut() {
+            item.price *= factor;
+        }
+        expense.currency = Some(base.to_string());
+    }
+    if ledger != base {
+        for settlement in view.settlements.iter_mut() {
+            settlement.amount *= factor(tracker, &known, ledger, base, settlement.date, &mut used)?;
+        }
+        for debt in view.debts.iter_mut() {
+            debt.amount *= factor(tracker, &known, ledger, base, debt.date, &mut used)?;
+        }
+        for account in view.accounts.iter_mut() {
+            for snapshot in account.balances.iter_mut() {
+                snapshot.amount *= factor(tracker, &known, ledger, base, snapshot.date, &mut used)?;
+            }
+        }
+        let today = factor(
+            tracker,
+            &known,
+            ledger,
+            base,
+            Local::now().date_naive(),
+            &mut used,
+        )?;
+        for budget in view.budgets.iter_mut() {
+            budget.amount *= today;
+        }
+        for goal in view.goals.iter_mut() {
+            goal.target *= today;
+            goal.monthly_income = goal.monthly_income.map(|income| income * today);
+        }
+        for subscription in view.subscriptions.iter_mut() {
+            subscription.amount *= today;
+        }
+        for loan in view.loans.iter_mut() {
+            loan.principal *= today;
+            loan.emi *= today;
+        }
+    }
+    used.sort_by(|a, b| (&a.currency, a.date).cmp(&(&b.currency, b.date)));
+    Ok((view, used))
+}
+
+// Say which rates a converted report was built with.
+pub fn print_note(used: &[Rate]) {
+    if used.is_empty() {
+        return;
+    }
+    eprintln!("\nConverted to {} using:", currency::current().code);
+    for rate in used {
+        eprintln!(
+            "  1 {} = {} (rate of {})",
+            rate.currency,
+            currency::amount_in(None, rate.rate),
+            rate.date
+        );
+    }
+}
+
+pub fn print_list(tracker: &ExpenseTracker) {
+    if tracker.rates.is_empty() {
+        println!("No exchange rates set.");
+        return;
+    }
+    let mut rates: Vec<&Rate> = tracker.rates.iter().collect();
+    rates.sort_by(|a, b| (&a.currency, a.date).cmp(&(&b.currency, b.date)));
+    for rate in rates {
+        println!(
+            "{} 1 {} = {}",
+            rate.date,
+            rate.currency,
+            currency::amount_in(None, rate.rate)
+        );
+    }
+}
+
+// Frankfurter's ECB reference rates, used when the config sets no API
+pub const DEFAULT_URL: &str = "https://api.frankfurter.app/{date}?from={base}";
+
+// The part of an API response we use: units of each currency per one unit
+// of the base
+#[derive(Deserialize)]
+struct Response {
+    rates: HashMap<String, f64>,
+}
+
+// Where fetched rates into `base` are cached, one file per day.
+fn cache_dir(base: &str) -> Option<PathBuf> {
+    Some(config::cache_dir()?.join("rates").join(base))
+}
+
+// Every cached rate into `base`.
+pub fn cached(base: &str) -> Vec<Rate> {
+    let Some(entries) = cache_dir(base).and_then(|dir| fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+    let mut rates = Vec::new();
+    for path in entries.flatten().map(|entry| entry.path()) {
+        let date = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| NaiveDate::parse_from_str(stem, "%Y-%m-%d").ok());
+        let day: Option<HashMap<String, f64>> = fs::read_to_string(&path)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok());
+        if let (Some(date), Some(day)) = (date, day) {
+            rates.extend(day.into_iter().map(|(currency, rate)| Rate {
+                date,
+                currency,
+                rate,
+            }));
+        }
+    }
+    rates
+}
+
+pub fn is_cached(base: &str, date: NaiveDate) -> bool {
+    cache_dir(base).is_some_and(|dir| dir.join(format!("{date}.json")).exists())
+}
+
+// Fetch the rates into `base` for `date` from `url` and cache them,
+// returning how many currencies were stored.
+pub fn fetch(url: &str, base: &str, date: NaiveDate) -> Result<usize, String> {
+    let url = url
+        .replace("{date}", &date.to_string())
+        .replace("{base}", base);
+    let response: Response = ureq::get(&url)
+        .call()
+        .and_then(|mut response| response.body_mut().read_json())
+        .map_err(|err| format!("{url}: {err}"))?;
+    // The API quotes foreign units per base unit; we store the inverse.
+    let day: HashMap<String, f64> = response
+        .rates
+        .into_iter()
+        .filter(|(code, per_base)| code != base && *per_base > 0.0)
+        .map(|(code, per_base)| (code, 1.0 / per_base))
+        .collect();
+    let dir = cache_dir(base).ok_or("no cache directory (HOME is not set)")?;
+    fs::create_dir_all(&dir).map_err(|err| format!("{}: {}", dir.display(), err))?;
+    let path = dir.join(format!("{date}.json"));
+    let json = serde_json::to_string(&day).expect("rates serialize");
+    fs::write(&path, json).map_err(|err| format!("{}: {}", path.display(), err))?;
+    Ok(day.len())
+}
@@ -0,0 +1,38 @@
+use std::io;
+
+use crate::expense::{Expense, ExpenseTracker};
+use crate::storage::Storage;
+
+// A storage backend for --read-only: loading works, saving is refused.
+pub struct ReadOnly {
+    inner: Box<dyn Storage>,
+}
+
+pub fn wrap(storage: Box<dyn Storage>) -> Box<dyn Storage> {
+    Box::new(ReadOnly { inner: storage })
+}
+
+fn refused() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::PermissionDenied,
+        "the ledger is read-only here (--read-only or read_only in the config file)",
+    )
+}
+
+impl Storage for ReadOnly {
+    fn load(&self) -> io::Result<ExpenseTracker> {
+        self.inner.load()
+    }
+
+    fn load_metadata(&self) -> io::Result<ExpenseTracker> {
+        self.inner.load_metadata()
+    }
+
+    fn save(&self, _: &ExpenseTracker) -> io::Result<()> {
+        Err(refused())
+    }
+
+    fn append(&self, _: Expense) -> io::Result<u64> {
+        Err(refused())
+    }
+}
@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::currency;
+use crate::expense::{Expense, ExpenseTracker};
+
+// Where a reimbursable expense is in the claim process
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Pending,
+    Submitted,
+    Reimbursed,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Status::Pending => "pending",
+            Status::Submitted => "submitted",
+            Status::Reimbursed => "reimbursed",
+        };
+        write!(f, "{name}")
+    }
+}
+
+// Move the expense with `id` from `from` to `to`, returning an error message
+// when it isn't reimbursable or isn't at the expected step.
+pub fn advance(
+    tracker: &mut ExpenseTracker,
+    id: u64,
+    from: Status,
+    to: Status,
+) -> Result<(), String> {
+    let expense = tracker
+        .expenses
+        .iter_mut()
+        .find(|e| e.id == id)
+        .ok_or_else(|| format!("No expense with id {id}."))?;
+    match expense.reimbursement {
+        Some(status) if status == from => {
+            expense.reimbursement = Some(to);
+            Ok(())
+        }
+        Some(status) => Err(format!("Expense #{id} is {status}, not {from}.")),
+        None => Err(format!("Expense #{id} is not reimbursable.")),
+    }
+}
+
+pub fn print_list(tracker: &ExpenseTracker, status: Option<Status>, mask_private: bool) {
+    let claims: Vec<&Expense> = tracker
+        .expenses
+        .iter()
+        .filter(|e| e.reimbursement.is_some())
+        .filter(|e| status.is_none_or(|s| e.reimbursement == Some(s)))
+        .collect();
+    if claims.is_empty() {
+        println!("No reimbursable expenses found.");
+        return;
+    }
+    for expense in claims.iter() {
+        println!("{}", expense.line(mask_private));
+    }
+    println!();
+    for status in [Status::Pending, Status::Submitted, Status::Reimbursed] {
+        let total = claims
+            .iter()
+            .filter(|e| e.reimbursement == Some(status))
+            .fold(0.0, |sum, e| sum + e.amount);
+        println!("{:<11} {}", format!("{status}:"), currency::amount(total));
+    }
+}
@@ -0,0 +1,146 @@
+use serde::{Serialize, de::DeserializeOwned};
+use ureq::{Agent, http::Response};
+
+use crate::expense::Expense;
+use crate::filter::Filter;
+use crate::server::{Ledger, NewExpense, Summary};
+
+// A ledger served by `serve` on another machine
+pub struct Client {
+    agent: Agent,
+    base: String,
+    token: String,
+}
+
+impl Client {
+    pub fn new(base: &str, token: String) -> Client {
+        // Error statuses carry the server's message, so they're read rather
+        // than turned into errors by ureq.
+        let agent = Agent::config_builder()
+            .http_status_as_error(false)
+            .build()
+            .into();
+        Client {
+            agent,
+            base: base.trim_end_matches('/').to_string(),
+            token,
+        }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/api{}", self.base, path)
+    }
+
+    fn get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: Vec<(&str, String)>,
+    ) -> Result<T, String> {
+        let url = self.url(path);
+        let response = self
+            .agent
+            .get(&url)
+            .header("Authorization", &format!("Bearer {}", self.token))
+            .query_pairs(query)
+            .call()
+            .map_err(|err| format!("{url}: {err}"))?;
+        read(&url, response)
+    }
+
+    fn post<T: DeserializeOwned>(&self, path: &str, body: &impl Serialize) -> Result<T, String> {
+        let url = self.url(path);
+        let response = self
+            .agent
+            .post(&url)
+            .header("Authorization", &format!("Bearer {}", self.token))
+            .send_json(body)
+            .map_err(|err| format!("{url}: {err}"))?;
+        read(&url, response)
+    }
+
+    pub fn ledger(&self) -> Result<Ledger, String> {
+        self.get("/ledger", Vec::new())
+    }
+
+    pub fn add(&self, expense: &NewExpense) -> Result<Expense, String> {
+        self.post("/expenses", expense)
+    }
+
+    pub fn list(&self, filter: &Filter, view: Option<&str>) -> Result<Vec<Expense>, String> {
+        self.get("/expenses", query(filter, view))
+    }
+
+    pub fn summary(&self, filter: &Filter, view: Option<&str>) -> Result<Summary, String> {
+        self.get("/summary", query(filter, view))
+    }
+}
+
+// The body as JSON, or the server's error message.
+fn read<T: DeserializeOwned>(url: &str, mut response: Response<ureq::Body>) -> Result<T, String> {
+    let status = response.status();
+    if status.is_success() {
+        return response
+            .body_mut()
+            .read_json()
+            .map_err(|err| format!("{url}: {err}"));
+    }
+    let message = response
+        .body_mut()
+        .read_json::<serde_json::Value>()
+        .ok()
+        .and_then(|body| body["error"].as_str().map(str::to_string))
+        .unwrap_or_else(|| status.to_string());
+    Err(format!("the server refused: {message}"))
+}
+
+// `filter` as the query parameters the server reads.
+fn query<'a>(filter: &Filter, view: Option<&str>) -> Vec<(&'a str, String)> {
+    let mut query = Vec::new();
+    if let Some(view) = view {
+        query.push(("view", view.to_string()));
+    }
+    if let Some(from) = filter.from {
+        query.push(("from", from.to_string()));
+    }
+    if let Some(to) = filter.to {
+        query.push(("to", to.to_string()));
+    }
+    if let Some(month) = filter.month {
+        query.push(("month", month.to_string()));
+    }
+    if let Some(category) = &filter.category {
+        query.push(("category", category.clone()));
+    }
+    if !filter.tags.is_empty() {
+        query.push(("tag", filter.tags.join(",")));
+    }
+    if let Some(payee) = &filter.payee {
+        query.push(("payee", payee.clone()));
+    }
+    if let Some(trip) = &filter.trip {
+        query.push(("trip", trip.clone()));
+    }
+    if let Some(project) = &filter.project {
+        query.push(("project", project.clone()));
+    }
+    if let Some(user) = &filter.user {
+        query.push(("user", user.clone()));
+    }
+    if !filter.meta.is_empty() {
+        query.push(("meta", filter.meta.join(",")));
+    }
+    if let Some(business) = filter.business {
+        let class = if business { "business" } else { "personal" };
+        query.push(("only", class.to_string()));
+    }
+    if let Some(min) = filter.min_amount {
+        query.push(("min_amount", min.to_string()));
+    }
+    if let Some(max) = filter.max_amount {
+        query.push(("max_amount", max.to_string()));
+    }
+    if let Some(hours) = filter.hours {
+        query.push(("hours", format!("{}-{}", hours.start, hours.end)));
+    }
+    query
+}
@@ -0,0 +1,81 @@
+use super::stats;
+use crate::currency;
+use crate::expense::Expense;
+
+// How many other expenses a category needs before one can stand out
+pub const MIN_HISTORY: usize = 5;
+
+// An expense much larger than the rest of its category
+pub struct Anomaly<'a> {
+    pub expense: &'a Expense,
+    pub category: String,
+    pub amount: f64,
+    pub median: f64,
+    // Standard deviations above the category's mean
+    pub score: f64,
+}
+
+// The expenses in `checked` whose amount in a category lies more than
+// `sigmas` standard deviations above the mean of every other expense in
+// `history` in that category, largest first. Each expense is left out of
+// its own baseline, so a single typo can't hide itself by inflating it.
+pub fn find<'a>(checked: &[&'a Expense], history: &[&Expense], sigmas: f64) -> Vec<Anomaly<'a>> {
+    let mut found = Vec::new();
+    for expense in checked {
+        for (category, amount) in expense.category_amounts() {
+            let others: Vec<f64> = history
+                .iter()
+                .filter(|e| e.id != expense.id)
+                .flat_map(|e| e.category_amounts())
+                .filter(|(c, _)| c.eq_ignore_ascii_case(category))
+                .map(|(_, amount)| amount)
+                .collect();
+            if others.len() < MIN_HISTORY {
+                continue;
+            }
+            let Some(baseline) = stats::describe(&others) else {
+                continue;
+            };
+            // Identical amounts have no spread; anything larger stands out.
+            let spread = baseline.std_dev.max(baseline.mean.abs() * 0.01).max(0.01);
+            let score = (amount - baseline.mean) / spread;
+            if score > sigmas {
+                found.push(Anomaly {
+                    expense,
+                    category: category.to_string(),
+                    amount,
+                    median: baseline.median,
+                    score,
+                });
+            }
+        }
+    }
+    found.sort_by(|a, b| b.score.total_cmp(&a.score));
+    found
+}
+
+pub fn print(anomalies: &[Anomaly], mask_private: bool) {
+    if anomalies.is_empty() {
+        println!("No unusual expenses found.");
+        return;
+    }
+    for anomaly in anomalies {
+        println!("{}", anomaly.expense.line(mask_private));
+        if mask_private && anomaly.expense.private {
+            continue;
+        }
+        let times = if anomaly.median > 0.0 {
+            format!("{:.1}x the usual", anomaly.amount / anomaly.median)
+        } else {
+            String::from("far above the usual")
+        };
+        println!(
+            "    {} in {} is {} {} ({:.1} standard deviations above the mean)",
+            currency::amount(anomaly.amount),
+            anomaly.category,
+            times,
+            currency::amount(anomaly.median),
+            anomaly.score
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use super::chart::{bar, terminal_width};
+use super::share;
+use crate::i18n;
+
+// Spend per category (or bucket) with each one's share, optionally drawn
+// as bars scaled to the terminal width. `categories` are largest first.
+pub fn print(categories: &[(String, f64)], chart: bool) {
+    if categories.is_empty() {
+        println!("{}", i18n::t("no-expenses"));
+        return;
+    }
+    let total: f64 = categories.iter().map(|(_, amount)| amount).sum();
+    let name_width = categories
+        .iter()
+        .map(|(c, _)| c.chars().count())
+        .max()
+        .unwrap_or(0)
+        .clamp(8, 24);
+    let max = categories[0].1;
+    // name, amount and share columns plus their separating spaces
+    let bar_width = terminal_width()
+        .saturating_sub(name_width + 13 + 7 + 3)
+        .max(10);
+
+    for (category, amount) in categories.iter() {
+        let name: String = category.chars().take(name_width).collect();
+        let line = format!(
+            "{:<name_width$} {:>12.2} {:>5.1}%",
+            name,
+            amount,
+            share(*amount, total)
+        );
+        if chart {
+            println!("{line} {}", bar(*amount, max, bar_width));
+        } else {
+            println!("{line}");
+        }
+    }
+    println!("{:<name_width$} {:>12.2}", i18n::t("label-total"), total);
+}
@@ -0,0 +1,90 @@
+use chrono::{Datelike, NaiveDate};
+
+use super::in_range;
+use crate::dates::month_end;
+use crate::expense::ExpenseTracker;
+
+const CELL: usize = 8;
+// 256-colour backgrounds from light to heavy spending
+const HEAT: [u8; 5] = [229, 221, 214, 208, 196];
+
+// Print a Monday-first calendar of the month with each day's total, shaded
+// by how much was spent relative to the month's biggest day. No-spend days
+// are highlighted in green.
+pub fn print(tracker: &ExpenseTracker, month: NaiveDate, color: bool) {
+    let last = month_end(month);
+    // A calendar month, whatever the budgeting period
+    let expenses = in_range(tracker, Some(month), Some(last));
+    let totals: Vec<f64> = month
+        .iter_days()
+        .take_while(|d| *d <= last)
+        .map(|day| {
+            expenses
+                .iter()
+                .filter(|e| e.date == day)
+                .fold(0.0, |sum, e| sum + e.amount)
+        })
+        .collect();
+    let max = totals.iter().cloned().fold(0.0, f64::max);
+
+    let title = month.format("%B %Y").to_string();
+    println!("{:^width$}", title, width = CELL * 7);
+    for name in ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"] {
+        print!("{:>width$}", name, width = CELL);
+    }
+    println!();
+
+    let offset = month.weekday().num_days_from_monday() as usize;
+    let weeks = (offset + totals.len()).div_ceil(7);
+    for week in 0..weeks {
+        let cells: Vec<Option<usize>> = (0..7)
+            .map(|weekday| (week * 7 + weekday).checked_sub(offset))
+            .map(|i| i.filter(|i| *i < totals.len()))
+            .collect();
+        for cell in cells.iter() {
+            match cell {
+                Some(i) => print!("{:>width$}", i + 1, width = CELL),
+                None => print!("{:width$}", "", width = CELL),
+            }
+        }
+        println!();
+        for cell in cells.iter() {
+            match cell {
+                Some(i) => print!("{}", amount_cell(totals[*i], max, color)),
+                None => print!("{:width$}", "", width = CELL),
+            }
+        }
+        println!();
+    }
+
+    let no_spend = totals.iter().filter(|t| **t == 0.0).count();
+    println!("\nNo-spend days: {no_spend} of {}", totals.len());
+}
+
+fn amount_cell(total: f64, max: f64, color: bool) -> String {
+    if total == 0.0 {
+        let text = format!("{:>width$}", "·", width = CELL);
+        return if color {
+            format!("\x1b[32m{text}\x1b[0m")
+        } else {
+            text
+        };
+    }
+    let text = format!("{:>width$}", compact(total), width = CELL);
+    if !color {
+        return text;
+    }
+    let level = ((total / max) * (HEAT.len() - 1) as f64).round() as usize;
+    format!("\x1b[30;48;5;{}m{text}\x1b[0m", HEAT[level])
+}
+
+// Fit an amount into a calendar cell: 950, 12.5k, 1.2M.
+fn compact(amount: f64) -> String {
+    if amount >= 1_000_000.0 {
+        format!("{:.1}M", amount / 1_000_000.0)
+    } else if amount >= 10_000.0 {
+        format!("{:.1}k", amount / 1_000.0)
+    } else {
+        format!("{:.0}", amount)
+    }
+}
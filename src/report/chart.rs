@@ -0,0 +1,21 @@
+use terminal_size::{Width, terminal_size};
+
+// Width of the terminal, or 80 columns when not attached to one.
+pub fn terminal_width() -> usize {
+    terminal_size().map_or(80, |(Width(w), _)| w as usize)
+}
+
+// A horizontal bar `value / max` of `width` cells, using eighth-block
+// characters for the fractional end.
+pub fn bar(value: f64, max: f64, width: usize) -> String {
+    const PARTIAL: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+    if max <= 0.0 || value <= 0.0 {
+        return String::new();
+    }
+    let eighths = ((value / max).min(1.0) * width as f64 * 8.0).round() as usize;
+    let mut bar = "█".repeat(eighths / 8);
+    if !eighths.is_multiple_of(8) {
+        bar.push(PARTIAL[eighths % 8]);
+    }
+    bar
+}
@@ -0,0 +1,126 @@
+use chrono::{Months, NaiveDate};
+
+use super::{category_totals, in_range, percent_change};
+use crate::currency;
+use crate::expense::ExpenseTracker;
+use crate::i18n;
+
+// How many of the categories that grew are picked out
+const HIGHLIGHTED: usize = 3;
+
+// A month or a whole year to compare
+#[derive(Clone, Copy, Debug)]
+pub struct Period {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+impl Period {
+    pub fn label(&self) -> String {
+        if self.to == (self.from + Months::new(1)).pred_opt().expect("valid date") {
+            self.from.format("%Y-%m").to_string()
+        } else {
+            self.from.format("%Y").to_string()
+        }
+    }
+}
+
+// Parse `YYYY-MM` or `YYYY`, for use as a clap value parser.
+pub fn parse_period(s: &str) -> Result<Period, String> {
+    let s = s.trim();
+    let (from, months) = match NaiveDate::parse_from_str(&format!("{s}-01"), "%Y-%m-%d") {
+        Ok(from) => (from, 1),
+        Err(_) => (
+            NaiveDate::parse_from_str(&format!("{s}-01-01"), "%Y-%m-%d").map_err(|_| {
+                format!("'{s}' should be a month or a year (for example, 2025-01 or 2025)")
+            })?,
+            12,
+        ),
+    };
+    let to = (from + Months::new(months))
+        .pred_opt()
+        .expect("dates before the period end exist");
+    Ok(Period { from, to })
+}
+
+// One category's spend in both periods
+pub struct Row {
+    pub category: String,
+    pub a: f64,
+    pub b: f64,
+}
+
+impl Row {
+    pub fn change(&self) -> f64 {
+        self.b - self.a
+    }
+}
+
+// Spend per category in `a` and `b`, the one that grew most first.
+pub fn build(tracker: &ExpenseTracker, a: Period, b: Period) -> Vec<Row> {
+    let before = category_totals(&in_range(tracker, Some(a.from), Some(a.to)));
+    let after = category_totals(&in_range(tracker, Some(b.from), Some(b.to)));
+    let mut rows: Vec<Row> = before
+        .iter()
+        .map(|(category, amount)| Row {
+            category: category.clone(),
+            a: *amount,
+            b: 0.0,
+        })
+        .collect();
+    for (category, amount) in after {
+        match rows.iter_mut().find(|r| r.category == category) {
+            Some(row) => row.b = amount,
+            None => rows.push(Row {
+                category,
+                a: 0.0,
+                b: amount,
+            }),
+        }
+    }
+    rows.sort_by(|x, y| y.change().total_cmp(&x.change()));
+    rows
+}
+
+pub fn print(rows: &[Row], a: Period, b: Period, color: bool) {
+    if rows.is_empty() {
+        println!("No expenses in either period.");
+        return;
+    }
+    let (a_label, b_label) = (a.label(), b.label());
+    println!(
+        "{:<20} {:>14} {:>14} {:>14} {:>9}",
+        i18n::t("label-category"),
+        a_label,
+        b_label,
+        i18n::t("label-change"),
+        i18n::t("label-change-percent")
+    );
+    let line = |category: &str, a: f64, b: f64| {
+        format!(
+            "{:<20} {:>14} {:>14} {:>14} {:>9}",
+            category,
+            currency::amount(a),
+            currency::amount(b),
+            format!("{:+.2}", b - a),
+            percent_change(a, b).map_or(String::from("new"), |p| format!("{p:+.1}%"))
+        )
+    };
+    for (i, row) in rows.iter().enumerate() {
+        let text = line(&row.category, row.a, row.b);
+        // The categories that grew the most
+        if i < HIGHLIGHTED && row.change() > 0.0 {
+            if color {
+                println!("\x1b[31m{text}\x1b[0m");
+            } else {
+                println!("{text} ▲");
+            }
+        } else {
+            println!("{text}");
+        }
+    }
+    let (total_a, total_b) = rows
+        .iter()
+        .fold((0.0, 0.0), |(a, b), row| (a + row.a, b + row.b));
+    println!("{}", line(&i18n::t("label-total"), total_a, total_b));
+}
@@ -0,0 +1,34 @@
+use rhai::{Dynamic, Engine, Scope};
+use serde::Serialize;
+use std::{fs, path::Path};
+
+use crate::currency;
+use crate::expense::{Expense, ExpenseTracker};
+
+// Run the Rhai script at `path` over `expenses`. The script sees them as
+// `expenses`, an array of maps with the same fields as the data file, along
+// with the ledger's `budgets` and `currency`, and can format amounts with
+// `money`. Whatever it prints, and the value it ends with, is the report.
+pub fn run(path: &Path, tracker: &ExpenseTracker, expenses: &[&Expense]) -> Result<(), String> {
+    let script = fs::read_to_string(path).map_err(|err| format!("{}: {err}", path.display()))?;
+    let mut engine = Engine::new();
+    engine.register_fn("money", |amount: f64| currency::amount(amount));
+    engine.register_fn("money", |amount: i64| currency::amount(amount as f64));
+
+    let mut scope = Scope::new();
+    scope.push("expenses", to_dynamic(expenses)?);
+    scope.push("budgets", to_dynamic(&tracker.budgets)?);
+    scope.push("currency", currency::current().code.clone());
+
+    let result: Dynamic = engine
+        .eval_with_scope(&mut scope, &script)
+        .map_err(|err| format!("{}: {err}", path.display()))?;
+    if !result.is_unit() {
+        println!("{result}");
+    }
+    Ok(())
+}
+
+fn to_dynamic(value: impl Serialize) -> Result<Dynamic, String> {
+    rhai::serde::to_dynamic(value).map_err(|err| err.to_string())
+}
@@ -0,0 +1,145 @@
+use chrono::{Months, NaiveDate};
+
+use super::{category_totals, in_month, total};
+use crate::budget;
+use crate::dates::{period_end, period_of, period_start};
+use crate::expense::{Expense, ExpenseTracker};
+use crate::i18n;
+
+// Projected end-of-month spend for one category (or overall)
+pub struct Projection {
+    pub name: String,
+    pub to_date: f64,
+    pub daily_rate: f64,
+    pub projected: f64,
+    // What the budget allows this month, when one is set
+    pub budget: Option<f64>,
+}
+
+pub struct Forecast {
+    pub month: NaiveDate,
+    pub as_of: NaiveDate,
+    pub days_left: u32,
+    // Whether daily rates come from earlier months rather than this month's pace
+    pub from_history: bool,
+    pub overall: Projection,
+    pub categories: Vec<Projection>,
+}
+
+// Days in the period named by the month starting at `month`
+fn days_in(month: NaiveDate) -> u32 {
+    (period_end(month) - period_start(month)).num_days() as u32 + 1
+}
+
+// Project the month (or pay period) containing `as_of`: spend so far plus
+// the daily average of the previous `history` months for each remaining
+// day. Without any history the pace of the current month is used instead.
+pub fn build(tracker: &ExpenseTracker, as_of: NaiveDate, history: u32) -> Forecast {
+    let month = period_of(as_of);
+    let days_left = (period_end(month) - as_of).num_days() as u32;
+    let so_far: Vec<&Expense> = in_month(tracker, month)
+        .into_iter()
+        .filter(|e| e.date <= as_of)
+        .collect();
+
+    let mut past: Vec<&Expense> = Vec::new();
+    let mut past_days = 0;
+    for back in 1..=history {
+        let earlier = month - Months::new(back);
+        past.extend(in_month(tracker, earlier));
+        past_days += days_in(earlier);
+    }
+    let from_history = !past.is_empty();
+    let (basis, basis_days) = if from_history {
+        (past, past_days)
+    } else {
+        (
+            so_far.clone(),
+            (as_of - period_start(month)).num_days() as u32 + 1,
+        )
+    };
+
+    // The overall projection is measured against the sum of all budgets.
+    let budgets = budget::status(tracker, month);
+    let budget_for = |name: &str| {
+        let matching: Vec<f64> = budgets
+            .iter()
+            .filter(|b| name == "All" || b.category.eq_ignore_ascii_case(name))
+            .map(|b| b.available())
+            .collect();
+        (!matching.is_empty()).then(|| matching.iter().sum())
+    };
+    let project = |name: String, to_date: f64, basis_total: f64| {
+        let daily_rate = basis_total / basis_days as f64;
+        Projection {
+            budget: budget_for(&name),
+            name,
+            to_date,
+            daily_rate,
+            projected: to_date + daily_rate * days_left as f64,
+        }
+    };
+
+    let current = category_totals(&so_far);
+    let mut categories: Vec<Projection> = Vec::new();
+    let basis_categories = category_totals(&basis);
+    for (name, basis_total) in basis_categories.iter() {
+        let to_date = current
+            .iter()
+            .find(|(c, _)| c == name)
+            .map_or(0.0, |(_, a)| *a);
+        categories.push(project(name.clone(), to_date, *basis_total));
+    }
+    for (name, to_date) in current.iter() {
+        if !basis_categories.iter().any(|(c, _)| c == name) {
+            categories.push(project(name.clone(), *to_date, 0.0));
+        }
+    }
+    categories.sort_by(|a, b| b.projected.total_cmp(&a.projected));
+
+    Forecast {
+        month,
+        as_of,
+        days_left,
+        from_history,
+        overall: project(String::from("All"), total(&so_far), total(&basis)),
+        categories,
+    }
+}
+
+pub fn print(forecast: &Forecast) {
+    println!(
+        "Forecast for {} as of {} ({} day(s) left, daily rates from {})\n",
+        forecast.month.format("%Y-%m"),
+        forecast.as_of,
+        forecast.days_left,
+        if forecast.from_history {
+            "previous months"
+        } else {
+            "this month so far"
+        }
+    );
+    println!(
+        "{:<20} {:>12} {:>12} {:>12} {:>12}",
+        i18n::t("label-category"),
+        i18n::t("label-to-date"),
+        i18n::t("label-per-day"),
+        i18n::t("label-projected"),
+        i18n::t("label-budget")
+    );
+    for projection in std::iter::once(&forecast.overall).chain(forecast.categories.iter()) {
+        let budget = match projection.budget {
+            Some(budget) if projection.projected > budget => format!("{budget:.2} (over)"),
+            Some(budget) => format!("{budget:.2}"),
+            None => String::from("-"),
+        };
+        println!(
+            "{:<20} {:>12.2} {:>12.2} {:>12.2} {:>12}",
+            projection.name,
+            projection.to_date,
+            projection.daily_rate,
+            projection.projected,
+            budget
+        );
+    }
+}
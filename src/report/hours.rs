@@ -0,0 +1,43 @@
+use chrono::Timelike;
+
+use super::chart::{bar, terminal_width};
+use crate::expense::Expense;
+
+// Spend in each hour of the day, drawn as bars, for the expenses recorded
+// with a time. Those without one are counted but left out.
+pub fn print(expenses: &[&Expense]) {
+    let mut counts = [0usize; 24];
+    let mut totals = [0.0; 24];
+    for expense in expenses {
+        if let Some(time) = expense.time {
+            counts[time.hour() as usize] += 1;
+            totals[time.hour() as usize] += expense.amount;
+        }
+    }
+    let timed: usize = counts.iter().sum();
+    if timed == 0 {
+        println!("No expenses with a time of day found.");
+        return;
+    }
+    let max = totals.iter().cloned().fold(0.0, f64::max);
+    // hour, count and amount columns plus their separating spaces
+    let bar_width = terminal_width().saturating_sub(5 + 6 + 12 + 3).max(10);
+    for hour in 0..24 {
+        println!(
+            "{:02}:00 {:>6} {:>12.2} {}",
+            hour,
+            counts[hour],
+            totals[hour],
+            bar(totals[hour], max, bar_width)
+        );
+    }
+    println!(
+        "Total {:>6} {:>12.2}",
+        timed,
+        totals.iter().fold(0.0, |sum, t| sum + t)
+    );
+    let untimed = expenses.len() - timed;
+    if untimed > 0 {
+        println!("{untimed} expense(s) without a time of day are left out.");
+    }
+}
@@ -0,0 +1,129 @@
+use super::share;
+use crate::expense::Expense;
+use crate::i18n;
+
+// Expenses recorded without a user are shown as spent by this member
+const NO_ONE: &str = "(no one)";
+
+// Spend per category split by household member. Members are in order of
+// what they spent, most first, and categories the same.
+pub struct Household {
+    pub members: Vec<String>,
+    // A category and each member's spend in it, in `members` order
+    pub rows: Vec<(String, Vec<f64>)>,
+}
+
+impl Household {
+    // What each member spent in all, in `members` order.
+    pub fn totals(&self) -> Vec<f64> {
+        let mut totals = vec![0.0; self.members.len()];
+        for (_, amounts) in self.rows.iter() {
+            for (total, amount) in totals.iter_mut().zip(amounts) {
+                *total += amount;
+            }
+        }
+        totals
+    }
+}
+
+pub fn build(expenses: &[&Expense]) -> Household {
+    // Members are matched ignoring case, keeping the first spelling seen.
+    let mut members: Vec<String> = Vec::new();
+    let mut cells: Vec<(String, usize, f64)> = Vec::new();
+    for expense in expenses {
+        let user = expense.user.as_deref().unwrap_or(NO_ONE);
+        let member = match members.iter().position(|m| m.eq_ignore_ascii_case(user)) {
+            Some(member) => member,
+            None => {
+                members.push(user.to_string());
+                members.len() - 1
+            }
+        };
+        for (category, amount) in expense.category_amounts() {
+            match cells
+                .iter_mut()
+                .find(|(c, m, _)| c == category && *m == member)
+            {
+                Some((_, _, sum)) => *sum += amount,
+                None => cells.push((category.to_string(), member, amount)),
+            }
+        }
+    }
+
+    let mut rows: Vec<(String, Vec<f64>)> = Vec::new();
+    for (category, member, amount) in cells {
+        match rows.iter_mut().find(|(c, _)| *c == category) {
+            Some((_, amounts)) => amounts[member] += amount,
+            None => {
+                let mut amounts = vec![0.0; members.len()];
+                amounts[member] = amount;
+                rows.push((category, amounts));
+            }
+        }
+    }
+    let mut household = Household { members, rows };
+
+    let totals = household.totals();
+    let mut order: Vec<usize> = (0..household.members.len()).collect();
+    order.sort_by(|a, b| totals[*b].total_cmp(&totals[*a]));
+    household.members = order
+        .iter()
+        .map(|i| household.members[*i].clone())
+        .collect();
+    for (_, amounts) in household.rows.iter_mut() {
+        *amounts = order.iter().map(|i| amounts[*i]).collect();
+    }
+    household.rows.sort_by(|a, b| {
+        let sum = |amounts: &[f64]| amounts.iter().sum::<f64>();
+        sum(&b.1).total_cmp(&sum(&a.1))
+    });
+    household
+}
+
+// One column per member and a total, then each member's share of it.
+pub fn print(household: &Household) {
+    if household.rows.is_empty() {
+        println!("{}", i18n::t("no-expenses"));
+        return;
+    }
+    let name_width = household
+        .rows
+        .iter()
+        .map(|(c, _)| c.chars().count())
+        .max()
+        .unwrap_or(0)
+        .clamp(8, 24);
+    let widths: Vec<usize> = household
+        .members
+        .iter()
+        .map(|m| m.chars().count().max(12))
+        .collect();
+    let line = |name: &str, cells: Vec<String>| {
+        let name: String = name.chars().take(name_width).collect();
+        let mut line = format!("{name:<name_width$}");
+        for (cell, width) in cells.iter().zip(widths.iter().chain([&12])) {
+            line.push_str(&format!(" {cell:>width$}"));
+        }
+        println!("{line}");
+    };
+
+    let mut header = household.members.clone();
+    header.push(i18n::t("label-total"));
+    line(&i18n::t("label-category"), header);
+    for (category, amounts) in household.rows.iter() {
+        let mut cells: Vec<String> = amounts.iter().map(|a| format!("{a:.2}")).collect();
+        cells.push(format!("{:.2}", amounts.iter().sum::<f64>()));
+        line(category, cells);
+    }
+    let totals = household.totals();
+    let total: f64 = totals.iter().sum();
+    let mut cells: Vec<String> = totals.iter().map(|t| format!("{t:.2}")).collect();
+    cells.push(format!("{total:.2}"));
+    line(&i18n::t("label-total"), cells);
+    let mut shares: Vec<String> = totals
+        .iter()
+        .map(|t| format!("{:.1}%", share(*t, total)))
+        .collect();
+    shares.push(String::from("100.0%"));
+    line(&i18n::t("label-share"), shares);
+}
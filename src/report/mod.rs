@@ -0,0 +1,157 @@
+pub mod anomalies;
+pub mod breakdown;
+pub mod calendar;
+pub mod chart;
+pub mod compare;
+pub mod custom;
+pub mod forecast;
+pub mod hours;
+pub mod household;
+pub mod monthly;
+pub mod networth;
+pub mod pace;
+pub mod pdf;
+pub mod project;
+pub mod rolling;
+pub mod running;
+pub mod stats;
+pub mod streaks;
+pub mod tax;
+pub mod template;
+pub mod top;
+pub mod trend;
+pub mod trip;
+pub mod yearly;
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::currency;
+use crate::dates;
+use crate::expense::{Expense, ExpenseTracker, MASK};
+use crate::i18n;
+
+// Every expense counting towards personal spending.
+pub fn personal(tracker: &ExpenseTracker) -> Vec<&Expense> {
+    tracker.personal_expenses().collect()
+}
+
+// Expenses dated in the period named by the month starting at `month`;
+// see `dates::period_start`.
+pub fn in_month(tracker: &ExpenseTracker, month: NaiveDate) -> Vec<&Expense> {
+    let (from, to) = (dates::period_start(month), dates::period_end(month));
+    tracker
+        .personal_expenses()
+        .filter(|e| e.date >= from && e.date <= to)
+        .collect()
+}
+
+// Expenses dated within an optional inclusive range.
+pub fn in_range(
+    tracker: &ExpenseTracker,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> Vec<&Expense> {
+    tracker
+        .personal_expenses()
+        .filter(|e| from.is_none_or(|from| e.date >= from))
+        .filter(|e| to.is_none_or(|to| e.date <= to))
+        .collect()
+}
+
+// Running total at the end of every day from the first to the last expense.
+pub fn cumulative_by_day(expenses: &[&Expense]) -> Vec<(NaiveDate, f64)> {
+    let (Some(first), Some(last)) = (
+        expenses.iter().map(|e| e.date).min(),
+        expenses.iter().map(|e| e.date).max(),
+    ) else {
+        return Vec::new();
+    };
+    let mut running = 0.0;
+    first
+        .iter_days()
+        .take_while(|day| *day <= last)
+        .map(|day| {
+            running += expenses
+                .iter()
+                .filter(|e| e.date == day)
+                .fold(0.0, |sum, e| sum + e.amount);
+            (day, running)
+        })
+        .collect()
+}
+
+// Sum of the expenses' amounts. Folding from 0.0 avoids the -0.0 that
+// `Iterator::sum` yields for an empty list.
+pub fn total(expenses: &[&Expense]) -> f64 {
+    expenses.iter().fold(0.0, |sum, e| sum + e.amount)
+}
+
+// Spend per category, largest first, counting split legs separately.
+pub fn category_totals(expenses: &[&Expense]) -> Vec<(String, f64)> {
+    let mut totals: Vec<(String, f64)> = Vec::new();
+    for expense in expenses {
+        for (category, amount) in expense.category_amounts() {
+            match totals.iter_mut().find(|(name, _)| name == category) {
+                Some((_, sum)) => *sum += amount,
+                None => totals.push((category.to_string(), amount)),
+            }
+        }
+    }
+    totals.sort_by(|a, b| b.1.total_cmp(&a.1));
+    totals
+}
+
+// `amount` as a percentage of `total`.
+pub fn share(amount: f64, total: f64) -> f64 {
+    if total.abs() < 0.005 {
+        0.0
+    } else {
+        amount / total * 100.0
+    }
+}
+
+// Percentage change from `previous` to `current`, if there was a previous.
+pub fn percent_change(previous: f64, current: f64) -> Option<f64> {
+    (previous.abs() >= 0.005).then(|| (current - previous) / previous * 100.0)
+}
+
+// Total spend per payee, largest first. Payees are grouped case-insensitively
+// and shown with the spelling of their first occurrence. When masking,
+// private expenses are pooled under a single hidden payee.
+pub fn payee_totals(
+    tracker: &ExpenseTracker,
+    year: Option<i32>,
+    mask_private: bool,
+) -> Vec<(String, f64)> {
+    let mut totals: Vec<(String, f64)> = Vec::new();
+    for expense in tracker.personal_expenses() {
+        if year.is_some_and(|y| expense.date.year() != y) {
+            continue;
+        }
+        let payee = if mask_private && expense.private {
+            MASK
+        } else {
+            expense.payee.as_deref().unwrap_or("(no payee)")
+        };
+        match totals
+            .iter_mut()
+            .find(|(name, _)| name.eq_ignore_ascii_case(payee))
+        {
+            Some((_, sum)) => *sum += expense.amount,
+            None => totals.push((payee.to_string(), expense.amount)),
+        }
+    }
+    totals.sort_by(|a, b| b.1.total_cmp(&a.1));
+    totals
+}
+
+pub fn print_payees(tracker: &ExpenseTracker, year: Option<i32>, mask_private: bool) {
+    let totals = payee_totals(tracker, year, mask_private);
+    if totals.is_empty() {
+        println!("{}", i18n::t("no-expenses"));
+        return;
+    }
+    for (payee, sum) in totals.iter() {
+        println!("{:<30} {:>14}", payee, currency::amount(*sum));
+    }
+}
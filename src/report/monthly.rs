@@ -0,0 +1,404 @@
+use chrono::{Months, NaiveDate};
+use std::fmt::Write;
+
+use super::pdf::{self, Align, Column, Document};
+use super::{category_totals, in_month, percent_change, share, total};
+use crate::budget;
+use crate::currency::{self, Currency};
+use crate::expense::{Expense, ExpenseTracker, MASK};
+use crate::locale;
+
+// Everything shown in the monthly report document
+pub struct MonthlyReport<'a> {
+    pub month: NaiveDate,
+    pub total: f64,
+    pub count: usize,
+    pub previous_total: f64,
+    // (category, this month, previous month), largest first
+    pub categories: Vec<(String, f64, f64)>,
+    pub top: Vec<&'a Expense>,
+    pub budgets: Vec<budget::Status>,
+}
+
+pub fn build(tracker: &ExpenseTracker, month: NaiveDate) -> MonthlyReport<'_> {
+    let previous_month = month - Months::new(1);
+    let expenses = in_month(tracker, month);
+    let previous = in_month(tracker, previous_month);
+    let previous_categories = category_totals(&previous);
+
+    let mut categories: Vec<(String, f64, f64)> = category_totals(&expenses)
+        .into_iter()
+        .map(|(category, amount)| {
+            let before = previous_categories
+                .iter()
+                .find(|(c, _)| *c == category)
+                .map_or(0.0, |(_, a)| *a);
+            (category, amount, before)
+        })
+        .collect();
+    for (category, before) in previous_categories.iter() {
+        if !categories.iter().any(|(c, _, _)| c == category) {
+            categories.push((category.clone(), 0.0, *before));
+        }
+    }
+
+    let mut top = expenses.clone();
+    top.sort_by(|a, b| b.amount.total_cmp(&a.amount));
+    top.truncate(10);
+
+    MonthlyReport {
+        month,
+        total: total(&expenses),
+        count: expenses.len(),
+        previous_total: total(&previous),
+        categories,
+        top,
+        budgets: budget::status(tracker, month),
+    }
+}
+
+fn change(previous: f64, current: f64) -> String {
+    match percent_change(previous, current) {
+        Some(pct) => format!("{:+.2} ({:+.1}%)", current - previous, pct),
+        None => format!("{:+.2}", current - previous),
+    }
+}
+
+fn top_row(expense: &Expense, mask_private: bool) -> (String, String, String) {
+    if mask_private && expense.private {
+        (MASK.to_string(), expense.category.clone(), MASK.to_string())
+    } else {
+        (
+            expense.description.clone(),
+            expense.category.clone(),
+            currency::amount(expense.amount),
+        )
+    }
+}
+
+pub fn markdown(report: &MonthlyReport, mask_private: bool) -> String {
+    let mut out = String::new();
+    let month = report.month.format("%B %Y");
+    let _ = writeln!(out, "# Expenses for {month}\n");
+    let _ = writeln!(
+        out,
+        "**Total spend:** {} across {} expense(s)\n",
+        currency::amount(report.total),
+        report.count
+    );
+    let _ = writeln!(
+        out,
+        "**Previous month:** {} (change: {})\n",
+        currency::amount(report.previous_total),
+        change(report.previous_total, report.total)
+    );
+
+    let _ = writeln!(out, "## By category\n");
+    let _ = writeln!(
+        out,
+        "| Category | Amount | Share | Previous month | Change |"
+    );
+    let _ = writeln!(out, "|---|---:|---:|---:|---:|");
+    for (category, amount, before) in report.categories.iter() {
+        let _ = writeln!(
+            out,
+            "| {} | {} | {:.1}% | {} | {} |",
+            markdown_escape(category),
+            currency::amount(*amount),
+            share(*amount, report.total),
+            currency::amount(*before),
+            change(*before, *amount)
+        );
+    }
+
+    let _ = writeln!(out, "\n## Top expenses\n");
+    let _ = writeln!(out, "| Date | Description | Category | Amount |");
+    let _ = writeln!(out, "|---|---|---|---:|");
+    for expense in report.top.iter() {
+        let (description, category, amount) = top_row(expense, mask_private);
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} | {} |",
+            locale::date(expense.date),
+            markdown_escape(&description),
+            markdown_escape(&category),
+            amount
+        );
+    }
+
+    if !report.budgets.is_empty() {
+        let _ = writeln!(out, "\n## Budget status\n");
+        let _ = writeln!(out, "| Category | Budget | Spent | Remaining |");
+        let _ = writeln!(out, "|---|---:|---:|---:|");
+        for status in report.budgets.iter() {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {}{} |",
+                markdown_escape(&status.category),
+                currency::amount(status.available()),
+                currency::amount(status.spent),
+                currency::amount(status.remaining()),
+                if status.remaining() < 0.0 {
+                    " (over)"
+                } else {
+                    ""
+                }
+            );
+        }
+    }
+    out
+}
+
+pub fn html(report: &MonthlyReport, mask_private: bool) -> String {
+    let mut out = String::new();
+    let month = report.month.format("%B %Y");
+    let _ = writeln!(
+        out,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Expenses for {month}</title>"
+    );
+    let _ = writeln!(
+        out,
+        "<style>body{{font-family:sans-serif;max-width:48em;margin:2em auto}}table{{border-collapse:collapse;width:100%}}th,td{{border-bottom:1px solid #ddd;padding:.3em .6em;text-align:left}}td.num,th.num{{text-align:right}}</style>\n</head>\n<body>"
+    );
+    let _ = writeln!(out, "<h1>Expenses for {month}</h1>");
+    let _ = writeln!(
+        out,
+        "<p><strong>Total spend:</strong> {} across {} expense(s)</p>",
+        currency::amount(report.total),
+        report.count
+    );
+    let _ = writeln!(
+        out,
+        "<p><strong>Previous month:</strong> {} (change: {})</p>",
+        currency::amount(report.previous_total),
+        change(report.previous_total, report.total)
+    );
+
+    let _ = writeln!(out, "<h2>By category</h2>\n<table>");
+    let _ = writeln!(
+        out,
+        "<tr><th>Category</th><th class=\"num\">Amount</th><th class=\"num\">Share</th><th class=\"num\">Previous month</th><th class=\"num\">Change</th></tr>"
+    );
+    for (category, amount, before) in report.categories.iter() {
+        let _ = writeln!(
+            out,
+            "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{:.1}%</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>",
+            html_escape(category),
+            currency::amount(*amount),
+            share(*amount, report.total),
+            currency::amount(*before),
+            change(*before, *amount)
+        );
+    }
+    let _ = writeln!(out, "</table>");
+
+    let _ = writeln!(out, "<h2>Top expenses</h2>\n<table>");
+    let _ = writeln!(
+        out,
+        "<tr><th>Date</th><th>Description</th><th>Category</th><th class=\"num\">Amount</th></tr>"
+    );
+    for expense in report.top.iter() {
+        let (description, category, amount) = top_row(expense, mask_private);
+        let _ = writeln!(
+            out,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td class=\"num\">{}</td></tr>",
+            locale::date(expense.date),
+            html_escape(&description),
+            html_escape(&category),
+            amount
+        );
+    }
+    let _ = writeln!(out, "</table>");
+
+    if !report.budgets.is_empty() {
+        let _ = writeln!(out, "<h2>Budget status</h2>\n<table>");
+        let _ = writeln!(
+            out,
+            "<tr><th>Category</th><th class=\"num\">Budget</th><th class=\"num\">Spent</th><th class=\"num\">Remaining</th></tr>"
+        );
+        for status in report.budgets.iter() {
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}{}</td></tr>",
+                html_escape(&status.category),
+                currency::amount(status.available()),
+                currency::amount(status.spent),
+                currency::amount(status.remaining()),
+                if status.remaining() < 0.0 {
+                    " (over)"
+                } else {
+                    ""
+                }
+            );
+        }
+        let _ = writeln!(out, "</table>");
+    }
+    let _ = writeln!(out, "</body>\n</html>");
+    out
+}
+
+// The report as a printable A4 PDF, with a bar per category.
+pub fn pdf(report: &MonthlyReport, mask_private: bool) -> Vec<u8> {
+    // The standard PDF fonts lack symbols such as ₹, so those currencies
+    // are shown by their code.
+    let currency = currency::current();
+    let currency = if pdf::encodable(&currency.symbol) {
+        currency.clone()
+    } else {
+        Currency {
+            symbol: format!("{} ", currency.code),
+            ..currency.clone()
+        }
+    };
+    let money = |amount: f64| currency.format(amount);
+    let column = |x: f64, width: f64, align: Align| Column { x, width, align };
+    let mut doc = Document::new();
+
+    doc.line(
+        18.0,
+        true,
+        &format!("Expenses for {}", report.month.format("%B %Y")),
+    );
+    doc.skip(6.0);
+    doc.line(
+        11.0,
+        false,
+        &format!(
+            "Total spend: {} across {} expense(s)",
+            money(report.total),
+            report.count
+        ),
+    );
+    doc.line(
+        11.0,
+        false,
+        &format!(
+            "Previous month: {} (change: {})",
+            money(report.previous_total),
+            change(report.previous_total, report.total)
+        ),
+    );
+
+    doc.skip(12.0);
+    doc.line(13.0, true, "By category");
+    let largest = report
+        .categories
+        .iter()
+        .map(|(_, amount, _)| *amount)
+        .fold(0.0, f64::max);
+    for (category, amount, _) in report.categories.iter().filter(|(_, a, _)| *a > 0.0) {
+        doc.need(14.0);
+        doc.text(pdf::MARGIN, 9.0, false, &pdf::fit(category, 125.0, 9.0));
+        let length = if largest > 0.0 {
+            250.0 * amount / largest
+        } else {
+            0.0
+        };
+        doc.bar(pdf::MARGIN + 130.0, length, 9.0, 0.45);
+        doc.text(pdf::MARGIN + 390.0, 9.0, false, &money(*amount));
+        doc.skip(14.0);
+    }
+    doc.skip(6.0);
+    let columns = [
+        column(pdf::MARGIN, 150.0, Align::Left),
+        column(pdf::MARGIN + 150.0, 80.0, Align::Right),
+        column(pdf::MARGIN + 230.0, 55.0, Align::Right),
+        column(pdf::MARGIN + 285.0, 90.0, Align::Right),
+        column(pdf::MARGIN + 375.0, 120.0, Align::Right),
+    ];
+    doc.row(
+        &columns,
+        &["Category", "Amount", "Share", "Previous month", "Change"].map(String::from),
+        true,
+    );
+    for (category, amount, before) in report.categories.iter() {
+        doc.row(
+            &columns,
+            &[
+                category.clone(),
+                money(*amount),
+                format!("{:.1}%", share(*amount, report.total)),
+                money(*before),
+                change(*before, *amount),
+            ],
+            false,
+        );
+    }
+
+    doc.skip(12.0);
+    doc.need(40.0);
+    doc.line(13.0, true, "Top expenses");
+    let columns = [
+        column(pdf::MARGIN, 70.0, Align::Left),
+        column(pdf::MARGIN + 70.0, 225.0, Align::Left),
+        column(pdf::MARGIN + 295.0, 110.0, Align::Left),
+        column(pdf::MARGIN + 405.0, 90.0, Align::Right),
+    ];
+    doc.row(
+        &columns,
+        &["Date", "Description", "Category", "Amount"].map(String::from),
+        true,
+    );
+    for expense in report.top.iter() {
+        let (description, category, amount) = if mask_private && expense.private {
+            (MASK.to_string(), expense.category.clone(), MASK.to_string())
+        } else {
+            (
+                expense.description.clone(),
+                expense.category.clone(),
+                money(expense.amount),
+            )
+        };
+        doc.row(
+            &columns,
+            &[locale::date(expense.date), description, category, amount],
+            false,
+        );
+    }
+
+    if !report.budgets.is_empty() {
+        doc.skip(12.0);
+        doc.need(40.0);
+        doc.line(13.0, true, "Budget status");
+        let columns = [
+            column(pdf::MARGIN, 165.0, Align::Left),
+            column(pdf::MARGIN + 165.0, 110.0, Align::Right),
+            column(pdf::MARGIN + 275.0, 110.0, Align::Right),
+            column(pdf::MARGIN + 385.0, 110.0, Align::Right),
+        ];
+        doc.row(
+            &columns,
+            &["Category", "Budget", "Spent", "Remaining"].map(String::from),
+            true,
+        );
+        for status in report.budgets.iter() {
+            let over = if status.remaining() < 0.0 {
+                " (over)"
+            } else {
+                ""
+            };
+            doc.row(
+                &columns,
+                &[
+                    status.category.clone(),
+                    money(status.available()),
+                    money(status.spent),
+                    format!("{}{over}", money(status.remaining())),
+                ],
+                false,
+            );
+        }
+    }
+    doc.finish()
+}
+
+fn markdown_escape(s: &str) -> String {
+    s.replace('|', "\\|")
+}
+
+pub fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
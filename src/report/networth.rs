@@ -0,0 +1,57 @@
+use chrono::NaiveDate;
+
+use super::trend;
+use crate::asset;
+use crate::currency;
+use crate::dates;
+use crate::expense::ExpenseTracker;
+use crate::i18n;
+
+// Net worth at the end of a month, next to the spending so far
+pub struct Row {
+    pub month: NaiveDate,
+    pub net_worth: f64,
+    // Spend from the first month shown to the end of this one
+    pub spent: f64,
+}
+
+// The `months` months ending with `end`, oldest first.
+pub fn build(tracker: &ExpenseTracker, end: NaiveDate, months: u32) -> Vec<Row> {
+    let mut spent = 0.0;
+    trend::monthly_totals(tracker, None, end, months)
+        .into_iter()
+        .map(|(month, total)| {
+            spent += total;
+            Row {
+                month,
+                net_worth: asset::net_worth(tracker, dates::period_end(month)),
+                spent,
+            }
+        })
+        .collect()
+}
+
+pub fn print(rows: &[Row]) {
+    println!(
+        "{:<8} {:>16} {:>14} {:>16}",
+        i18n::t("label-month"),
+        i18n::t("label-net-worth"),
+        i18n::t("label-change"),
+        i18n::t("label-spent-to-date")
+    );
+    let mut previous: Option<f64> = None;
+    for row in rows {
+        println!(
+            "{:<8} {:>16} {:>14} {:>16}",
+            row.month.format("%Y-%m"),
+            currency::amount(row.net_worth),
+            previous.map_or(String::from("-"), |p| format!("{:+.2}", row.net_worth - p)),
+            currency::amount(row.spent)
+        );
+        previous = Some(row.net_worth);
+    }
+    let worth: Vec<f64> = rows.iter().map(|r| r.net_worth).collect();
+    let spent: Vec<f64> = rows.iter().map(|r| r.spent).collect();
+    println!("\nNet worth  {}", trend::sparkline(&worth));
+    println!("Spent      {}", trend::sparkline(&spent));
+}
@@ -0,0 +1,87 @@
+use chrono::{Datelike, NaiveDate};
+
+use crate::budget;
+use crate::currency;
+use crate::dates;
+use crate::expense::ExpenseTracker;
+use crate::filter::Filter;
+
+// The latest month numbered `month` whose period has begun by `today`.
+pub fn latest(month: u8, today: NaiveDate) -> Option<NaiveDate> {
+    let current = dates::period_of(today);
+    let year = if u32::from(month) <= current.month() {
+        current.year()
+    } else {
+        current.year() - 1
+    };
+    NaiveDate::from_ymd_opt(year, month.into(), 1)
+}
+
+// How the month starting at `month` is going as of `today`: the days gone,
+// the spend a day and, when the month is budgeted, what is left to spend a
+// day. Only the expenses `filter` matches count; its budget is the
+// category's when it names one and every budget's otherwise.
+pub fn print(tracker: &ExpenseTracker, filter: &Filter, month: NaiveDate, today: NaiveDate) {
+    let (start, end) = (dates::period_start(month), dates::period_end(month));
+    if today < start {
+        return;
+    }
+    let days = (end - start).num_days() as u32 + 1;
+    let elapsed = if today > end {
+        days
+    } else {
+        (today - start).num_days() as u32 + 1
+    };
+    let in_month = Filter {
+        from: Some(start),
+        to: Some(end),
+        month: None,
+        ..filter.clone()
+    };
+    let spent = tracker.summary(&in_month);
+    println!(
+        "  {}: {} spent over {} of {} days, {} a day",
+        month.format("%B %Y"),
+        currency::amount(spent),
+        elapsed,
+        days,
+        currency::amount(spent / f64::from(elapsed))
+    );
+
+    let statuses: Vec<budget::Status> = budget::status(tracker, month)
+        .into_iter()
+        .filter(|s| {
+            filter
+                .category
+                .as_deref()
+                .is_none_or(|c| s.category.eq_ignore_ascii_case(c))
+        })
+        .collect();
+    if statuses.is_empty() {
+        return;
+    }
+    let available: f64 = statuses.iter().map(budget::Status::available).sum();
+    let left: f64 = statuses.iter().map(budget::Status::remaining).sum();
+    let remaining_days = days - elapsed + 1;
+    if left < 0.0 {
+        println!(
+            "  Budget {}: over by {}",
+            currency::amount(available),
+            currency::amount(-left)
+        );
+    } else if today > end {
+        println!(
+            "  Budget {}: {} left unspent",
+            currency::amount(available),
+            currency::amount(left)
+        );
+    } else {
+        println!(
+            "  Budget {}: {} left, {} a day for the remaining {} day(s)",
+            currency::amount(available),
+            currency::amount(left),
+            currency::amount(left / f64::from(remaining_days)),
+            remaining_days
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use std::fmt::Write;
+
+// A4 in points, and the margin kept around the page
+pub const WIDTH: f64 = 595.0;
+pub const HEIGHT: f64 = 842.0;
+pub const MARGIN: f64 = 50.0;
+
+// A plain PDF of text, lines and filled boxes in the standard Helvetica
+// fonts, laid out top to bottom with page breaks as needed. Text outside
+// the Windows-1252 character set those fonts cover is shown as `?`.
+pub struct Document {
+    pages: Vec<String>,
+    // Distance of the next line from the bottom of the page
+    y: f64,
+}
+
+#[derive(Clone, Copy)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+// A column of a table: where it starts, how wide it is, and its alignment
+pub struct Column {
+    pub x: f64,
+    pub width: f64,
+    pub align: Align,
+}
+
+impl Document {
+    pub fn new() -> Document {
+        Document {
+            pages: vec![String::new()],
+            y: HEIGHT - MARGIN,
+        }
+    }
+
+    fn page(&mut self) -> &mut String {
+        self.pages.last_mut().expect("there is always a page")
+    }
+
+    // Start a new page unless `height` more points fit on this one.
+    pub fn need(&mut self, height: f64) {
+        if self.y - height < MARGIN {
+            self.pages.push(String::new());
+            self.y = HEIGHT - MARGIN;
+        }
+    }
+
+    pub fn skip(&mut self, height: f64) {
+        self.y -= height;
+    }
+
+    // `text` at `x` on the current line, its baseline `size` below the top.
+    pub fn text(&mut self, x: f64, size: f64, bold: bool, text: &str) {
+        let font = if bold { "F2" } else { "F1" };
+        let y = self.y - size;
+        let encoded = encode(text);
+        let _ = writeln!(
+            self.page(),
+            "BT /{font} {size} Tf {x:.2} {y:.2} Td ({encoded}) Tj ET"
+        );
+    }
+
+    // A line of text that moves the cursor down past it.
+    pub fn line(&mut self, size: f64, bold: bool, text: &str) {
+        self.need(size * 1.4);
+        self.text(MARGIN, size, bold, text);
+        self.y -= size * 1.4;
+    }
+
+    // A table row, cutting cells that don't fit their column.
+    pub fn row(&mut self, columns: &[Column], cells: &[String], bold: bool) {
+        const SIZE: f64 = 9.0;
+        self.need(SIZE * 1.6);
+        for (column, cell) in columns.iter().zip(cells) {
+            let cell = fit(cell, column.width - 4.0, SIZE);
+            let x = match column.align {
+                Align::Left => column.x,
+                Align::Right => column.x + column.width - width(&cell, SIZE),
+            };
+            self.text(x, SIZE, bold, &cell);
+        }
+        self.y -= SIZE * 1.6;
+        let y = self.y + SIZE * 0.3;
+        let end = columns.last().map_or(WIDTH - MARGIN, |c| c.x + c.width);
+        let _ = writeln!(
+            self.page(),
+            "0.85 G 0.5 w {MARGIN:.2} {y:.2} m {end:.2} {y:.2} l S 0 G"
+        );
+    }
+
+    // A filled box of grey level `gray` (0 black, 1 white) whose top is at
+    // the cursor; the cursor doesn't move.
+    pub fn bar(&mut self, x: f64, width: f64, height: f64, gray: f64) {
+        let y = self.y - height;
+        let _ = writeln!(
+            self.page(),
+            "{gray:.2} g {x:.2} {y:.2} {width:.2} {height:.2} re f 0 g"
+        );
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        let mut objects: Vec<String> = vec![
+            String::from("<< /Type /Catalog /Pages 2 0 R >>"),
+            String::new(),
+            String::from(
+                "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>",
+            ),
+            String::from(
+                "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>",
+            ),
+        ];
+        let mut kids = Vec::new();
+        for content in self.pages.iter() {
+            let page = objects.len() + 1;
+            kids.push(format!("{page} 0 R"));
+            objects.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {WIDTH} {HEIGHT}] /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                page + 1
+            ));
+            objects.push(format!(
+                "<< /Length {} >>\nstream\n{content}endstream",
+                content.len()
+            ));
+        }
+        objects[1] = format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kids.join(" "),
+            kids.len()
+        );
+
+        let mut out = String::from("%PDF-1.4\n");
+        let mut offsets = Vec::new();
+        for (number, object) in (1..).zip(objects.iter()) {
+            offsets.push(out.len());
+            let _ = write!(out, "{number} 0 obj\n{object}\nendobj\n");
+        }
+        let xref = out.len();
+        let _ = write!(out, "xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            let _ = writeln!(out, "{offset:010} 00000 n ");
+        }
+        let _ = write!(
+            out,
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+            objects.len() + 1
+        );
+        // `encode` leaves only ASCII in the content, escaping other bytes.
+        out.into_bytes()
+    }
+}
+
+// The Windows-1252 byte for `c`, which the standard fonts are set up for.
+fn win_ansi(c: char) -> Option<u8> {
+    match c {
+        ' '..='~' => Some(c as u8),
+        '\u{a0}'..='\u{ff}' => Some(c as u32 as u8),
+        '€' => Some(0x80),
+        '‘' => Some(0x91),
+        '’' => Some(0x92),
+        '“' => Some(0x93),
+        '”' => Some(0x94),
+        '•' => Some(0x95),
+        '–' => Some(0x96),
+        '—' => Some(0x97),
+        _ => None,
+    }
+}
+
+// Whether `text` can be shown as it is.
+pub fn encodable(text: &str) -> bool {
+    text.chars().all(|c| win_ansi(c).is_some())
+}
+
+// `text` as a PDF string body, with bytes beyond ASCII written as octal
+// escapes.
+fn encode(text: &str) -> String {
+    let mut out = String::new();
+    for c in text.chars() {
+        match win_ansi(c).unwrap_or(b'?') {
+            b'(' => out.push_str("\\("),
+            b')' => out.push_str("\\)"),
+            b'\\' => out.push_str("\\\\"),
+            byte if byte.is_ascii() => out.push(byte as char),
+            byte => {
+                let _ = write!(out, "\\{byte:03o}");
+            }
+        }
+    }
+    out
+}
+
+// Approximate width of `text` in Helvetica at `size`; close enough for
+// aligning numbers, whose glyphs all have the same width.
+pub fn width(text: &str, size: f64) -> f64 {
+    let units: f64 = text
+        .chars()
+        .map(|c| match c {
+            '0'..='9' => 556.0,
+            '.' | ',' | ' ' | 'i' | 'l' | 'j' | '\'' => 278.0,
+            '-' | '(' | ')' | 'r' | 't' | 'f' => 333.0,
+            '+' => 584.0,
+            '%' => 889.0,
+            'm' | 'w' | 'M' | 'W' => 833.0,
+            'A'..='Z' => 667.0,
+            _ => 556.0,
+        })
+        .sum();
+    units * size / 1000.0
+}
+
+// `text`, cut short with an ellipsis if wider than `limit` points.
+pub fn fit(text: &str, limit: f64, size: f64) -> String {
+    if width(text, size) <= limit {
+        return text.to_string();
+    }
+    let mut cut = String::new();
+    for c in text.chars() {
+        if width(&format!("{cut}{c}..."), size) > limit {
+            break;
+        }
+        cut.push(c);
+    }
+    format!("{cut}...")
+}
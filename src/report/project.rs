@@ -0,0 +1,163 @@
+use chrono::NaiveDate;
+
+use crate::config::Config;
+use crate::currency;
+use crate::expense::{Expense, MASK};
+use crate::i18n;
+use crate::locale;
+
+// The expenses billed to one client or project over a period, grouped by
+// project
+pub struct Statement<'a> {
+    pub title: String,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub projects: Vec<(String, Vec<&'a Expense>)>,
+    pub total: f64,
+}
+
+// The statement for `projects`, in the order given, leaving out those with
+// nothing billed in the period. Reimbursed expenses are still billed, so
+// every expense counts.
+pub fn build<'a>(
+    expenses: &[&'a Expense],
+    title: &str,
+    projects: &[String],
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> Statement<'a> {
+    let mut grouped = Vec::new();
+    let mut sum = 0.0;
+    for project in projects {
+        let mut billed: Vec<&Expense> = expenses
+            .iter()
+            .filter(|e| {
+                e.project
+                    .as_deref()
+                    .is_some_and(|p| p.eq_ignore_ascii_case(project))
+            })
+            .filter(|e| from.is_none_or(|from| e.date >= from))
+            .filter(|e| to.is_none_or(|to| e.date <= to))
+            .copied()
+            .collect();
+        if billed.is_empty() {
+            continue;
+        }
+        billed.sort_by_key(|e| (e.date, e.id));
+        sum += super::total(&billed);
+        grouped.push((project.clone(), billed));
+    }
+    Statement {
+        title: title.to_string(),
+        from,
+        to,
+        projects: grouped,
+        total: sum,
+    }
+}
+
+// Every project with its client, dates, number of expenses and total,
+// latest first.
+pub fn print_list(expenses: &[&Expense], config: &Config) {
+    let mut projects: Vec<(&str, NaiveDate, NaiveDate, usize, f64)> = Vec::new();
+    for expense in expenses {
+        let Some(name) = expense.project.as_deref() else {
+            continue;
+        };
+        match projects.iter_mut().find(|p| p.0.eq_ignore_ascii_case(name)) {
+            Some(project) => {
+                project.1 = project.1.min(expense.date);
+                project.2 = project.2.max(expense.date);
+                project.3 += 1;
+                project.4 += expense.amount;
+            }
+            None => projects.push((name, expense.date, expense.date, 1, expense.amount)),
+        }
+    }
+    if projects.is_empty() {
+        println!("No projects found. Bill expenses to one with add --project.");
+        return;
+    }
+    projects.sort_by_key(|p| std::cmp::Reverse(p.2));
+    for (name, first, last, count, amount) in projects {
+        println!(
+            "{:<24} {:<16} {} to {} {:>5} {:>14}",
+            name,
+            config.client_of(name).unwrap_or("-"),
+            first,
+            last,
+            count,
+            currency::amount(amount)
+        );
+    }
+}
+
+fn period(statement: &Statement) -> String {
+    match (statement.from, statement.to) {
+        (Some(from), Some(to)) => format!("{from} to {to}"),
+        (Some(from), None) => format!("from {from}"),
+        (None, Some(to)) => format!("up to {to}"),
+        (None, None) => String::from("all dates"),
+    }
+}
+
+fn description(expense: &Expense, mask_private: bool) -> &str {
+    if mask_private && expense.private {
+        MASK
+    } else {
+        &expense.description
+    }
+}
+
+pub fn print(statement: &Statement, mask_private: bool) {
+    println!(
+        "Expense statement for {}, {}\n",
+        statement.title,
+        period(statement)
+    );
+    if statement.projects.is_empty() {
+        println!("No expenses billed in this period.");
+        return;
+    }
+    for (project, expenses) in statement.projects.iter() {
+        println!("{project}");
+        for expense in expenses {
+            println!(
+                "  {} {:<16} {:<32} {:>14}",
+                locale::date(expense.date),
+                expense.category,
+                description(expense, mask_private),
+                currency::amount(expense.amount)
+            );
+        }
+        println!(
+            "  {:<60} {:>14}\n",
+            "Subtotal",
+            currency::amount(super::total(expenses))
+        );
+    }
+    println!(
+        "{:<62} {:>14}",
+        i18n::t("label-total"),
+        currency::amount(statement.total)
+    );
+}
+
+pub fn csv(statement: &Statement, mask_private: bool) -> Result<String, csv::Error> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(["project", "date", "category", "description", "amount"])?;
+    for (project, expenses) in statement.projects.iter() {
+        for expense in expenses {
+            writer.write_record([
+                project.clone(),
+                expense.date.to_string(),
+                expense.category.clone(),
+                description(expense, mask_private).to_string(),
+                format!("{:.2}", expense.amount),
+            ])?;
+        }
+    }
+    writer.write_record(["Total", "", "", "", &format!("{:.2}", statement.total)])?;
+    let bytes = writer.into_inner().map_err(|err| err.into_error())?;
+    Ok(String::from_utf8(bytes).expect("CSV of strings is valid UTF-8"))
+}
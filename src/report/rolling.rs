@@ -0,0 +1,92 @@
+use chrono::{Months, NaiveDate};
+
+use super::{category_totals, in_range, percent_change, trend};
+use crate::currency;
+use crate::dates;
+use crate::expense::ExpenseTracker;
+use crate::i18n;
+
+// The rolling averages of one category, oldest month first
+pub struct Row {
+    pub category: String,
+    pub averages: Vec<f64>,
+    // Spend in the last month and in the same month a year before
+    pub latest: f64,
+    pub year_ago: f64,
+}
+
+// For the `months` months ending with `end`, the average spend per
+// category over each month and the `window - 1` before it. Categories with
+// the most spend come first.
+pub fn build(tracker: &ExpenseTracker, end: NaiveDate, months: u32, window: u32) -> Vec<Row> {
+    let span = months + window - 1;
+    let first = end - Months::new(span - 1);
+    let categories = category_totals(&in_range(
+        tracker,
+        Some(dates::period_start(first)),
+        Some(dates::period_end(end)),
+    ));
+    categories
+        .into_iter()
+        .map(|(category, _)| {
+            let totals: Vec<f64> = trend::monthly_totals(tracker, Some(&category), end, span)
+                .into_iter()
+                .map(|(_, total)| total)
+                .collect();
+            let averages = totals
+                .windows(window as usize)
+                .map(|w| w.iter().sum::<f64>() / f64::from(window))
+                .collect();
+            let year_ago =
+                trend::monthly_totals(tracker, Some(&category), end - Months::new(12), 1)[0].1;
+            Row {
+                latest: totals.last().copied().unwrap_or(0.0),
+                year_ago,
+                category,
+                averages,
+            }
+        })
+        .collect()
+}
+
+pub fn print(rows: &[Row], end: NaiveDate, months: u32, window: u32, last_year: bool) {
+    if rows.is_empty() {
+        println!("{}", i18n::t("no-expenses"));
+        return;
+    }
+    println!("{window}-month rolling average per category\n");
+    let mut header = format!("{:<20}", i18n::t("label-category"));
+    for back in (0..months).rev() {
+        let month = end - Months::new(back);
+        header.push_str(&format!(" {:>12}", month.format("%Y-%m")));
+    }
+    println!("{header}");
+    for row in rows {
+        let mut line = format!("{:<20}", row.category);
+        for average in row.averages.iter() {
+            line.push_str(&format!(" {:>12.2}", average));
+        }
+        println!("{line}");
+    }
+
+    if last_year {
+        let year_ago = end - Months::new(12);
+        println!(
+            "\n{:<20} {:>14} {:>14} {:>9}",
+            i18n::t("label-category"),
+            end.format("%Y-%m").to_string(),
+            year_ago.format("%Y-%m").to_string(),
+            i18n::t("label-change-percent")
+        );
+        for row in rows {
+            println!(
+                "{:<20} {:>14} {:>14} {:>9}",
+                row.category,
+                currency::amount(row.latest),
+                currency::amount(row.year_ago),
+                percent_change(row.year_ago, row.latest)
+                    .map_or(String::from("-"), |p| format!("{p:+.1}%"))
+            );
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::budget;
+use crate::currency;
+use crate::dates::month_start;
+use crate::expense::ExpenseTracker;
+use crate::filter::Filter;
+
+// What the running total is measured against each month
+pub enum Limit {
+    Fixed(f64),
+    // The budgets set, for the filtered category or all of them
+    Budgets,
+}
+
+// List `rows` by date with the month-to-date total before each one. Amounts
+// come from `view`, the ledger converted for output, while lines are shown
+// as recorded in `tracker`.
+pub fn print(
+    tracker: &ExpenseTracker,
+    view: &ExpenseTracker,
+    rows: &[usize],
+    filter: &Filter,
+    limit: Option<Limit>,
+    mask_private: bool,
+) {
+    let mut rows = rows.to_vec();
+    rows.sort_by_key(|row| (tracker.expenses[*row].date, *row));
+    let mut month = None;
+    let mut running = 0.0;
+    let mut available = None;
+    for row in rows {
+        let expense = &view.expenses[row];
+        let start = month_start(expense.date);
+        if month != Some(start) {
+            if month.is_some() {
+                println!();
+            }
+            month = Some(start);
+            running = 0.0;
+            available = match &limit {
+                Some(Limit::Fixed(amount)) => Some(*amount),
+                Some(Limit::Budgets) => {
+                    let matching: Vec<f64> = budget::status(view, start)
+                        .iter()
+                        .filter(|b| {
+                            filter
+                                .category
+                                .as_ref()
+                                .is_none_or(|c| b.category.eq_ignore_ascii_case(c))
+                        })
+                        .map(|b| b.available())
+                        .collect();
+                    (!matching.is_empty()).then(|| matching.iter().fold(0.0, |sum, a| sum + a))
+                }
+                None => None,
+            };
+            match available {
+                Some(available) => println!(
+                    "{} (budget {})",
+                    start.format("%Y-%m"),
+                    currency::amount(available)
+                ),
+                None => println!("{}", start.format("%Y-%m")),
+            }
+        }
+        let before = running;
+        if expense.is_personal() {
+            running += filter.amount_of(expense);
+        }
+        let line = tracker.expenses[row].line(mask_private);
+        match available {
+            Some(available) => {
+                let used = if available > 0.0 {
+                    format!("{:.0}%", running / available * 100.0)
+                } else {
+                    String::from("-")
+                };
+                // Mark the expense that took the month over budget
+                let crossed = before <= available && running > available;
+                println!(
+                    "{:>14} {:>5}  {}. {}{}",
+                    currency::amount(running),
+                    used,
+                    row + 1,
+                    line,
+                    if crossed { "  <- over budget" } else { "" }
+                );
+            }
+            None => println!("{:>14}  {}. {}", currency::amount(running), row + 1, line),
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use crate::i18n;
+
+// Descriptive statistics of a set of amounts
+pub struct Stats {
+    pub count: usize,
+    pub total: f64,
+    pub mean: f64,
+    pub median: f64,
+    pub min: f64,
+    pub max: f64,
+    pub std_dev: f64,
+}
+
+pub fn describe(amounts: &[f64]) -> Option<Stats> {
+    if amounts.is_empty() {
+        return None;
+    }
+    let mut sorted = amounts.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let count = sorted.len();
+    let total = sorted.iter().fold(0.0, |sum, a| sum + a);
+    let mean = total / count as f64;
+    let median = if count.is_multiple_of(2) {
+        (sorted[count / 2 - 1] + sorted[count / 2]) / 2.0
+    } else {
+        sorted[count / 2]
+    };
+    let variance = sorted.iter().map(|a| (a - mean).powi(2)).sum::<f64>() / count as f64;
+    Some(Stats {
+        count,
+        total,
+        mean,
+        median,
+        min: sorted[0],
+        max: sorted[count - 1],
+        std_dev: variance.sqrt(),
+    })
+}
+
+pub fn print_table(rows: &[(String, Stats)]) {
+    println!(
+        "{:<20} {:>6} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12}",
+        i18n::t("label-category"),
+        i18n::t("label-count"),
+        i18n::t("label-total"),
+        i18n::t("label-mean"),
+        i18n::t("label-median"),
+        i18n::t("label-min"),
+        i18n::t("label-max"),
+        i18n::t("label-std-dev")
+    );
+    for (name, stats) in rows {
+        println!(
+            "{:<20} {:>6} {:>12.2} {:>12.2} {:>12.2} {:>12.2} {:>12.2} {:>12.2}",
+            name,
+            stats.count,
+            stats.total,
+            stats.mean,
+            stats.median,
+            stats.min,
+            stats.max,
+            stats.std_dev
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use chrono::NaiveDate;
+use std::collections::HashMap;
+
+use crate::currency;
+use crate::expense::Expense;
+
+// A run of consecutive days
+#[derive(Clone, Copy)]
+pub struct Streak {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+impl Streak {
+    pub fn days(&self) -> i64 {
+        (self.to - self.from).num_days() + 1
+    }
+}
+
+// How often and for how long a condition held day after day
+pub struct Habit {
+    pub days: usize,
+    // The run that reaches the last day, if the condition holds on it
+    pub current: Option<Streak>,
+    pub longest: Option<Streak>,
+}
+
+// Spend on every day from `from` to `to`.
+pub fn daily_totals(
+    expenses: &[&Expense],
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Vec<(NaiveDate, f64)> {
+    let mut spent: HashMap<NaiveDate, f64> = HashMap::new();
+    for expense in expenses {
+        *spent.entry(expense.date).or_default() += expense.amount;
+    }
+    from.iter_days()
+        .take_while(|day| *day <= to)
+        .map(|day| (day, spent.get(&day).copied().unwrap_or(0.0)))
+        .collect()
+}
+
+// The days on which `holds` is true of the day's spend, and its runs.
+pub fn habit(totals: &[(NaiveDate, f64)], holds: impl Fn(f64) -> bool) -> Habit {
+    let mut days = 0;
+    let mut run: Option<Streak> = None;
+    let mut longest: Option<Streak> = None;
+    for (day, spent) in totals {
+        if holds(*spent) {
+            days += 1;
+            let streak = match run {
+                Some(streak) => Streak { to: *day, ..streak },
+                None => Streak {
+                    from: *day,
+                    to: *day,
+                },
+            };
+            if longest.is_none_or(|l| streak.days() > l.days()) {
+                longest = Some(streak);
+            }
+            run = Some(streak);
+        } else {
+            run = None;
+        }
+    }
+    Habit {
+        days,
+        current: run,
+        longest,
+    }
+}
+
+fn print_habit(title: &str, habit: &Habit, of: usize) {
+    println!("{title}: {} of {of} days", habit.days);
+    match habit.current {
+        Some(streak) => println!(
+            "  Current streak: {} day(s), since {}",
+            streak.days(),
+            streak.from
+        ),
+        None => println!("  Current streak: none"),
+    }
+    if let Some(streak) = habit.longest {
+        println!(
+            "  Longest streak: {} day(s), {} to {}",
+            streak.days(),
+            streak.from,
+            streak.to
+        );
+    }
+}
+
+pub fn print(totals: &[(NaiveDate, f64)], limit: Option<f64>) {
+    let (Some((first, _)), Some((last, _))) = (totals.first(), totals.last()) else {
+        println!("No days to report on.");
+        return;
+    };
+    println!("From {first} to {last}\n");
+    print_habit(
+        "No-spend days",
+        &habit(totals, |spent| spent == 0.0),
+        totals.len(),
+    );
+    if let Some(limit) = limit {
+        println!();
+        print_habit(
+            &format!("Days under {}", currency::amount(limit)),
+            &habit(totals, |spent| spent < limit),
+            totals.len(),
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use chrono::NaiveDate;
+
+use super::{category_totals, in_range, total};
+use crate::currency;
+use crate::dates;
+use crate::expense::{Expense, ExpenseTracker};
+use crate::i18n;
+
+// Deductible spend per category over one fiscal year
+pub struct TaxReport {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub categories: Vec<(String, f64)>,
+    pub total: f64,
+}
+
+// The fiscal year beginning in `start_month` of `start_year`, if it has
+// dates.
+pub fn build(tracker: &ExpenseTracker, start_year: i32, start_month: u32) -> Option<TaxReport> {
+    let (from, to) = dates::fiscal_year(start_year, start_month)?;
+    let deductible: Vec<&Expense> = in_range(tracker, Some(from), Some(to))
+        .into_iter()
+        .filter(|e| e.deductible)
+        .collect();
+    Some(TaxReport {
+        from,
+        to,
+        categories: category_totals(&deductible),
+        total: total(&deductible),
+    })
+}
+
+pub fn print(report: &TaxReport) {
+    println!(
+        "Deductible expenses from {} to {}\n",
+        report.from, report.to
+    );
+    if report.categories.is_empty() {
+        println!("No deductible expenses found.");
+        return;
+    }
+    for (category, amount) in report.categories.iter() {
+        println!("{:<30} {:>14}", category, currency::amount(*amount));
+    }
+    println!(
+        "{:<30} {:>14}",
+        i18n::t("label-total"),
+        currency::amount(report.total)
+    );
+}
+
+pub fn csv(report: &TaxReport) -> Result<String, csv::Error> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(["category", "amount"])?;
+    for (category, amount) in report.categories.iter() {
+        writer.write_record([category.clone(), format!("{amount:.2}")])?;
+    }
+    writer.write_record([String::from("Total"), format!("{:.2}", report.total)])?;
+    let bytes = writer.into_inner().map_err(|err| err.into_error())?;
+    Ok(String::from_utf8(bytes).expect("CSV of strings is valid UTF-8"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add(tracker: &mut ExpenseTracker, date: (i32, u32, u32), category: &str, deductible: bool) {
+        tracker.add_expense(Expense {
+            date: NaiveDate::from_ymd_opt(date.0, date.1, date.2).unwrap(),
+            category: category.to_string(),
+            amount: 100.0,
+            description: String::from("Receipt"),
+            deductible,
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    fn only_deductible_expenses_within_the_fiscal_year_count() {
+        let mut tracker = ExpenseTracker::new();
+        add(&mut tracker, (2024, 3, 31), "Medical", true);
+        add(&mut tracker, (2024, 4, 1), "Medical", true);
+        add(&mut tracker, (2024, 9, 15), "Donations", true);
+        add(&mut tracker, (2024, 9, 15), "Food", false);
+        add(&mut tracker, (2025, 3, 31), "Medical", true);
+        add(&mut tracker, (2025, 4, 1), "Medical", true);
+
+        let report = build(&tracker, 2024, 4).unwrap();
+
+        assert_eq!(report.from, NaiveDate::from_ymd_opt(2024, 4, 1).unwrap());
+        assert_eq!(report.to, NaiveDate::from_ymd_opt(2025, 3, 31).unwrap());
+        assert_eq!(report.total, 300.0);
+        assert!(
+            report
+                .categories
+                .contains(&(String::from("Medical"), 200.0))
+        );
+        assert!(
+            report
+                .categories
+                .contains(&(String::from("Donations"), 100.0))
+        );
+        assert_eq!(report.categories.len(), 2);
+        assert!(build(&tracker, 2024, 13).is_none());
+    }
+}
@@ -0,0 +1,149 @@
+use chrono::{Datelike, Local, NaiveDate};
+use serde::Serialize;
+use serde_json::Value;
+use std::{collections::HashMap, fs, path::Path};
+use tera::{Context, Tera};
+
+use super::{category_totals, share, total};
+use crate::currency;
+use crate::expense::{Expense, MASK};
+use crate::filter::Filter;
+
+#[derive(Serialize)]
+struct Category {
+    category: String,
+    amount: f64,
+    share: f64,
+}
+
+#[derive(Serialize)]
+struct Month {
+    month: String,
+    total: f64,
+}
+
+#[derive(Serialize)]
+struct Payee {
+    payee: String,
+    amount: f64,
+}
+
+// What a template can use
+#[derive(Serialize)]
+struct Report<'a> {
+    currency: &'a str,
+    generated: NaiveDate,
+    // The filter's description, such as "from 2025-07-01, category Food"
+    filter: String,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    count: usize,
+    total: f64,
+    expenses: Vec<Expense>,
+    categories: Vec<Category>,
+    months: Vec<Month>,
+    payees: Vec<Payee>,
+}
+
+// `money` formats a number in the report's currency.
+fn money(value: &Value, _: &HashMap<String, Value>) -> tera::Result<Value> {
+    let amount = value
+        .as_f64()
+        .ok_or_else(|| tera::Error::msg("money expects a number"))?;
+    Ok(Value::String(currency::amount(amount)))
+}
+
+// Render the Tera template at `path` over `expenses`. Templates named
+// *.html or *.xml have the values they insert escaped.
+pub fn render(
+    path: &Path,
+    expenses: &[&Expense],
+    filter: &Filter,
+    mask_private: bool,
+) -> Result<String, String> {
+    let text = fs::read_to_string(path).map_err(|err| format!("{}: {err}", path.display()))?;
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().trim_end_matches(".tera").to_string())
+        .unwrap_or_default();
+    let mut tera = Tera::default();
+    tera.register_filter("money", money);
+    tera.add_raw_template(&name, &text)
+        .map_err(|err| describe(path, &err))?;
+
+    let total = total(expenses);
+    let mut months: Vec<Month> = Vec::new();
+    let mut sorted = expenses.to_vec();
+    sorted.sort_by_key(|e| (e.date, e.id));
+    for expense in sorted.iter() {
+        let month = format!("{}-{:02}", expense.date.year(), expense.date.month());
+        match months.last_mut() {
+            Some(last) if last.month == month => last.total += expense.amount,
+            _ => months.push(Month {
+                month,
+                total: expense.amount,
+            }),
+        }
+    }
+    let mut payees: Vec<Payee> = Vec::new();
+    for expense in sorted.iter() {
+        let payee = match (&expense.payee, mask_private && expense.private) {
+            (_, true) => MASK.to_string(),
+            (Some(payee), false) => payee.clone(),
+            (None, false) => continue,
+        };
+        match payees.iter_mut().find(|p| p.payee == payee) {
+            Some(p) => p.amount += expense.amount,
+            None => payees.push(Payee {
+                payee,
+                amount: expense.amount,
+            }),
+        }
+    }
+    payees.sort_by(|a, b| b.amount.total_cmp(&a.amount));
+    let report = Report {
+        currency: &currency::current().code,
+        generated: Local::now().date_naive(),
+        filter: filter.describe(),
+        from: filter.from,
+        to: filter.to,
+        count: expenses.len(),
+        total,
+        expenses: sorted
+            .iter()
+            .map(|e| {
+                let mut expense = (*e).clone();
+                if mask_private && expense.private {
+                    expense.description = MASK.to_string();
+                    expense.payee = expense.payee.map(|_| MASK.to_string());
+                }
+                expense
+            })
+            .collect(),
+        categories: category_totals(expenses)
+            .into_iter()
+            .map(|(category, amount)| Category {
+                category,
+                amount,
+                share: share(amount, total),
+            })
+            .collect(),
+        months,
+        payees,
+    };
+    let context = Context::from_serialize(&report).map_err(|err| err.to_string())?;
+    tera.render(&name, &context)
+        .map_err(|err| describe(path, &err))
+}
+
+// Tera's error with the reasons it wraps, which say where the template went
+// wrong.
+fn describe(path: &Path, err: &tera::Error) -> String {
+    let mut message = format!("{}: {err}", path.display());
+    let mut source = std::error::Error::source(err);
+    while let Some(err) = source {
+        message.push_str(&format!(": {err}"));
+        source = err.source();
+    }
+    message
+}
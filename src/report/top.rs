@@ -0,0 +1,58 @@
+use super::{category_totals, share, total};
+use crate::currency;
+use crate::expense::{Expense, MASK};
+use crate::i18n;
+use crate::locale;
+
+// The `n` largest expenses and categories of `expenses`, with each one's
+// share of their total.
+pub fn print(expenses: &[&Expense], n: usize, mask_private: bool) {
+    if expenses.is_empty() {
+        println!("{}", i18n::t("no-expenses"));
+        return;
+    }
+    let total = total(expenses);
+    let mut largest = expenses.to_vec();
+    largest.sort_by(|a, b| b.amount.total_cmp(&a.amount));
+
+    println!(
+        "Top {} expenses (of {} total)",
+        n.min(largest.len()),
+        currency::amount(total)
+    );
+    for (rank, expense) in largest.iter().take(n).enumerate() {
+        if mask_private && expense.private {
+            println!(
+                "{:>3}. {} {:<30} {:<15} {:>12} {:>6}",
+                rank + 1,
+                locale::date(expense.date),
+                MASK,
+                expense.category,
+                MASK,
+                MASK
+            );
+        } else {
+            println!(
+                "{:>3}. {} {:<30} {:<15} {:>12.2} {:>5.1}%",
+                rank + 1,
+                locale::date(expense.date),
+                expense.description,
+                expense.category,
+                expense.amount,
+                share(expense.amount, total)
+            );
+        }
+    }
+
+    let categories = category_totals(expenses);
+    println!("\nTop {} categories", n.min(categories.len()));
+    for (rank, (category, amount)) in categories.iter().take(n).enumerate() {
+        println!(
+            "{:>3}. {:<46} {:>12.2} {:>5.1}%",
+            rank + 1,
+            category,
+            amount,
+            share(*amount, total)
+        );
+    }
+}
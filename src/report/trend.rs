@@ -0,0 +1,97 @@
+use chrono::{Months, NaiveDate};
+use std::collections::BTreeMap;
+
+use super::{in_month, percent_change};
+use crate::dates;
+use crate::expense::ExpenseTracker;
+use crate::i18n;
+
+// Total spend in each of the `months` months ending with `end`, oldest
+// first, optionally restricted to one category.
+pub fn monthly_totals(
+    tracker: &ExpenseTracker,
+    category: Option<&str>,
+    end: NaiveDate,
+    months: u32,
+) -> Vec<(NaiveDate, f64)> {
+    (0..months)
+        .rev()
+        .map(|back| {
+            let month = end - Months::new(back);
+            let total = in_month(tracker, month)
+                .iter()
+                .flat_map(|e| e.category_amounts())
+                .filter(|(c, _)| category.is_none_or(|category| *c == category))
+                .fold(0.0, |sum, (_, amount)| sum + amount);
+            (month, total)
+        })
+        .collect()
+}
+
+// The totals in the money of the latest month in `cpi`, scaling each by
+// the latest index over the index in force in its month. Months before
+// the first index use the first.
+pub fn real(rows: &[(NaiveDate, f64)], cpi: &BTreeMap<String, f64>) -> Vec<(NaiveDate, f64)> {
+    let (Some((_, first)), Some((_, latest))) = (cpi.first_key_value(), cpi.last_key_value())
+    else {
+        return rows.to_vec();
+    };
+    rows.iter()
+        .map(|(month, total)| {
+            let key = dates::month_key(*month);
+            let index = cpi
+                .range(..=key)
+                .next_back()
+                .map_or(*first, |(_, index)| *index);
+            (*month, total * latest / index)
+        })
+        .collect()
+}
+
+// One block character per value, scaled between zero and the largest value.
+pub fn sparkline(values: &[f64]) -> String {
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let max = values.iter().cloned().fold(0.0, f64::max);
+    values
+        .iter()
+        .map(|v| {
+            if max <= 0.0 {
+                BLOCKS[0]
+            } else {
+                BLOCKS[((v / max) * (BLOCKS.len() - 1) as f64).round() as usize]
+            }
+        })
+        .collect()
+}
+
+pub fn print(rows: &[(NaiveDate, f64)], show_sparkline: bool) {
+    println!(
+        "{:<8} {:>12} {:>12} {:>9}",
+        i18n::t("label-month"),
+        i18n::t("label-total"),
+        i18n::t("label-change"),
+        i18n::t("label-change-percent")
+    );
+    let mut previous: Option<f64> = None;
+    for (month, total) in rows {
+        let (change, pct) = match previous {
+            Some(previous) => (
+                format!("{:+.2}", total - previous),
+                percent_change(previous, *total).map_or(String::from("-"), |p| format!("{p:+.1}%")),
+            ),
+            None => (String::from("-"), String::from("-")),
+        };
+        println!(
+            "{:<8} {:>12.2} {:>12} {:>9}",
+            month.format("%Y-%m"),
+            total,
+            change,
+            pct
+        );
+        previous = Some(*total);
+    }
+    if show_sparkline {
+        let values: Vec<f64> = rows.iter().map(|(_, total)| *total).collect();
+        println!("\n{}", sparkline(&values));
+    }
+}
@@ -0,0 +1,78 @@
+use chrono::NaiveDate;
+
+use super::{breakdown, category_totals, total};
+use crate::currency;
+use crate::expense::Expense;
+use crate::locale;
+
+// Every trip with its dates, number of expenses and total, latest first.
+pub fn print_list(expenses: &[&Expense]) {
+    let mut trips: Vec<(&str, NaiveDate, NaiveDate, usize, f64)> = Vec::new();
+    for expense in expenses {
+        let Some(name) = expense.trip.as_deref() else {
+            continue;
+        };
+        match trips.iter_mut().find(|t| t.0.eq_ignore_ascii_case(name)) {
+            Some(trip) => {
+                trip.1 = trip.1.min(expense.date);
+                trip.2 = trip.2.max(expense.date);
+                trip.3 += 1;
+                trip.4 += expense.amount;
+            }
+            None => trips.push((name, expense.date, expense.date, 1, expense.amount)),
+        }
+    }
+    if trips.is_empty() {
+        println!("No trips found. Log expenses under one with add --trip.");
+        return;
+    }
+    trips.sort_by_key(|t| std::cmp::Reverse(t.2));
+    for (name, first, last, count, amount) in trips {
+        println!(
+            "{:<24} {} to {} {:>5} {:>14}",
+            name,
+            first,
+            last,
+            count,
+            currency::amount(amount)
+        );
+    }
+}
+
+// The expenses logged under a trip: its dates, total and daily average,
+// then spend per category and per day.
+pub fn print(expenses: &[&Expense], name: &str) {
+    let (Some(first), Some(last)) = (
+        expenses.iter().map(|e| e.date).min(),
+        expenses.iter().map(|e| e.date).max(),
+    ) else {
+        println!("No expenses logged under {name}.");
+        return;
+    };
+    let days = (last - first).num_days() + 1;
+    let total = total(expenses);
+    println!("{name}: {first} to {last}, {days} day(s)");
+    println!(
+        "{} expense(s), {} in total, {} a day\n",
+        expenses.len(),
+        currency::amount(total),
+        currency::amount(total / days as f64)
+    );
+    breakdown::print(&category_totals(expenses), false);
+
+    println!();
+    for day in first.iter_days().take_while(|day| *day <= last) {
+        let spent = expenses
+            .iter()
+            .filter(|e| e.date == day)
+            .fold(0.0, |sum, e| sum + e.amount);
+        if spent > 0.0 {
+            println!(
+                "{} {} {:>14}",
+                day.format("%a"),
+                locale::date(day),
+                currency::amount(spent)
+            );
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use chrono::{Months, NaiveDate};
+
+use super::{category_totals, in_month};
+use crate::currency;
+use crate::expense::ExpenseTracker;
+use crate::i18n;
+
+// Spend per category in each month of a year
+pub struct YearlyReport {
+    pub year: i32,
+    // (category, spend in January to December), largest yearly total first
+    pub categories: Vec<(String, [f64; 12])>,
+    pub totals: [f64; 12],
+}
+
+pub fn build(tracker: &ExpenseTracker, year: i32) -> YearlyReport {
+    let january = NaiveDate::from_ymd_opt(year, 1, 1).expect("every year has a January");
+    let months: Vec<Vec<(String, f64)>> = (0..12)
+        .map(|i| category_totals(&in_month(tracker, january + Months::new(i))))
+        .collect();
+    let mut categories: Vec<(String, [f64; 12])> = Vec::new();
+    for (i, month) in months.iter().enumerate() {
+        for (category, amount) in month {
+            match categories.iter_mut().find(|(c, _)| c == category) {
+                Some((_, amounts)) => amounts[i] = *amount,
+                None => {
+                    let mut amounts = [0.0; 12];
+                    amounts[i] = *amount;
+                    categories.push((category.clone(), amounts));
+                }
+            }
+        }
+    }
+    categories.sort_by(|a, b| {
+        let sum = |amounts: &[f64; 12]| amounts.iter().sum::<f64>();
+        sum(&b.1).total_cmp(&sum(&a.1))
+    });
+    let mut totals = [0.0; 12];
+    for (_, amounts) in categories.iter() {
+        for (total, amount) in totals.iter_mut().zip(amounts) {
+            *total += amount;
+        }
+    }
+    YearlyReport {
+        year,
+        categories,
+        totals,
+    }
+}
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+pub fn print(report: &YearlyReport) {
+    if report.categories.is_empty() {
+        println!("No expenses in {}.", report.year);
+        return;
+    }
+    println!("Expenses in {}\n", report.year);
+    let mut header = format!("{:<16}", i18n::t("label-category"));
+    for month in MONTHS {
+        header.push_str(&format!(" {:>9}", month));
+    }
+    header.push_str(&format!(" {:>14}", i18n::t("label-total")));
+    println!("{header}");
+    let line = |name: &str, amounts: &[f64; 12]| {
+        let mut line = format!("{:<16}", name);
+        for amount in amounts {
+            line.push_str(&format!(" {:>9.0}", amount));
+        }
+        line.push_str(&format!(
+            " {:>14}",
+            currency::amount(amounts.iter().sum::<f64>())
+        ));
+        line
+    };
+    for (category, amounts) in report.categories.iter() {
+        println!("{}", line(category, amounts));
+    }
+    println!("{}", line(&i18n::t("label-total"), &report.totals));
+}
+
+pub fn csv(report: &YearlyReport) -> Result<String, csv::Error> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    let mut header = vec![String::from("category")];
+    header.extend(MONTHS.iter().map(|m| m.to_lowercase()));
+    header.push(String::from("total"));
+    writer.write_record(&header)?;
+    let record = |name: &str, amounts: &[f64; 12]| {
+        let mut record = vec![name.to_string()];
+        record.extend(amounts.iter().map(|a| format!("{a:.2}")));
+        record.push(format!("{:.2}", amounts.iter().sum::<f64>()));
+        record
+    };
+    for (category, amounts) in report.categories.iter() {
+        writer.write_record(record(category, amounts))?;
+    }
+    writer.write_record(record("Total", &report.totals))?;
+    let bytes = writer.into_inner().map_err(|err| err.into_error())?;
+    Ok(String::from_utf8(bytes).expect("CSV of strings is valid UTF-8"))
+}
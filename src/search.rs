@@ -0,0 +1,39 @@
+use regex::{Regex, RegexBuilder};
+
+use crate::expense::Expense;
+
+// How a search query is matched against an expense's text fields
+pub enum Matcher {
+    // Case-insensitive substring, stored lowercased
+    Substring(String),
+    Regex(Regex),
+}
+
+impl Matcher {
+    pub fn new(query: &str, regex: bool) -> Result<Matcher, regex::Error> {
+        if regex {
+            Ok(Matcher::Regex(
+                RegexBuilder::new(query).case_insensitive(true).build()?,
+            ))
+        } else {
+            Ok(Matcher::Substring(query.to_lowercase()))
+        }
+    }
+
+    fn is_match(&self, text: &str) -> bool {
+        match self {
+            Matcher::Substring(query) => text.to_lowercase().contains(query),
+            Matcher::Regex(regex) => regex.is_match(text),
+        }
+    }
+
+    // Whether the description, payee or any category of `expense` matches.
+    pub fn matches(&self, expense: &Expense) -> bool {
+        self.is_match(&expense.description)
+            || expense.payee.as_deref().is_some_and(|p| self.is_match(p))
+            || expense
+                .category_amounts()
+                .iter()
+                .any(|(category, _)| self.is_match(category))
+    }
+}
@@ -0,0 +1,773 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, Request, State},
+    http::{StatusCode, header},
+    middleware::{self, Next},
+    response::{Html, IntoResponse, Response},
+    routing::get,
+};
+use chrono::{FixedOffset, NaiveDate, NaiveTime};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{
+    collections::BTreeMap,
+    io,
+    sync::{Arc, Mutex},
+};
+
+use crate::archive;
+use crate::config::Config;
+use crate::dates;
+use crate::expense::{self, Expense, ExpenseTracker, Item};
+use crate::filter::{self, Filter};
+use crate::hooks;
+use crate::rates;
+use crate::read_only;
+use crate::reimburse;
+use crate::report;
+use crate::storage::{self, Storage};
+use crate::webhook;
+
+// Read when `serve` isn't given `--token`
+pub const TOKEN_VAR: &str = "EXPENSE_TRACKER_TOKEN";
+
+struct Server {
+    filename: String,
+    config: Config,
+    // The ledger's currency, recorded on every save
+    currency: String,
+    token: String,
+    // Every change is refused, as with --read-only
+    read_only: bool,
+    // Requests read and write the data file one at a time
+    lock: Mutex<()>,
+}
+
+type Shared = Arc<Server>;
+
+impl Server {
+    fn storage(&self) -> Box<dyn Storage> {
+        let storage = hooks::wrap(
+            storage::open(&self.filename, &self.config),
+            &self.config.hooks,
+            "serve",
+        );
+        if self.read_only {
+            read_only::wrap(storage)
+        } else {
+            storage
+        }
+    }
+
+    fn load(&self) -> Result<ExpenseTracker, ApiError> {
+        let mut tracker = self.storage().load()?;
+        tracker.currency = Some(self.currency.clone());
+        Ok(tracker)
+    }
+
+    // The ledger with every amount in the ledger's currency.
+    fn convert(&self, tracker: &ExpenseTracker) -> Result<ExpenseTracker, ApiError> {
+        rates::convert(tracker, &self.currency)
+            .map(|(view, _)| view)
+            .map_err(|err| ApiError(StatusCode::UNPROCESSABLE_ENTITY, err))
+    }
+
+    // The ledger converted for reports, with the archived years overlapping
+    // `from`..`to` merged back in.
+    fn report_view(
+        &self,
+        mut tracker: ExpenseTracker,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    ) -> Result<ExpenseTracker, ApiError> {
+        archive::include(&self.filename, &mut tracker, from, to)?;
+        self.convert(&tracker)
+    }
+}
+
+// An error sent back as `{"error": message}`
+struct ApiError(StatusCode, String);
+
+impl From<io::Error> for ApiError {
+    fn from(err: io::Error) -> ApiError {
+        let status = match err.kind() {
+            io::ErrorKind::PermissionDenied => StatusCode::FORBIDDEN,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        ApiError(status, err.to_string())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(json!({ "error": self.1 }))).into_response()
+    }
+}
+
+fn bad_request(message: impl Into<String>) -> ApiError {
+    ApiError(StatusCode::BAD_REQUEST, message.into())
+}
+
+fn not_found(id: u64) -> ApiError {
+    ApiError(StatusCode::NOT_FOUND, format!("no expense with id {id}"))
+}
+
+fn closed(date: NaiveDate) -> ApiError {
+    ApiError(StatusCode::CONFLICT, format!("{date} is in a closed month"))
+}
+
+// Run `work` with the data file to itself on the blocking pool, so a slow
+// hook, git commit or disk doesn't hold up the other requests.
+async fn locked<T, F>(server: Shared, work: F) -> Result<T, ApiError>
+where
+    T: Send + 'static,
+    F: FnOnce(&Server) -> Result<T, ApiError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || {
+        let _guard = server.lock.lock().expect("server lock");
+        work(&server)
+    })
+    .await
+    .map_err(|err| ApiError(StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?
+}
+
+// The filter flags as query parameters, with tags separated by commas.
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct FilterQuery {
+    view: Option<String>,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    after: Option<NaiveDate>,
+    before: Option<NaiveDate>,
+    month: Option<u8>,
+    category: Option<String>,
+    tag: Option<String>,
+    payee: Option<String>,
+    trip: Option<String>,
+    project: Option<String>,
+    user: Option<String>,
+    meta: Option<String>,
+    // business or personal
+    only: Option<String>,
+    min_amount: Option<f64>,
+    max_amount: Option<f64>,
+    // An hour range such as 22-4
+    hours: Option<String>,
+}
+
+impl FilterQuery {
+    fn filter(self, tracker: &ExpenseTracker) -> Result<Filter, ApiError> {
+        let filter = Filter {
+            from: self.from.or(self.after.and_then(|d| d.succ_opt())),
+            to: self.to.or(self.before.and_then(|d| d.pred_opt())),
+            month: self.month,
+            category: self.category,
+            tags: self
+                .tag
+                .iter()
+                .flat_map(|tags| tags.split(','))
+                .map(|tag| tag.trim().to_string())
+                .filter(|tag| !tag.is_empty())
+                .collect(),
+            payee: self.payee,
+            trip: self.trip,
+            project: self.project,
+            user: self.user,
+            meta: self
+                .meta
+                .iter()
+                .flat_map(|fields| fields.split(','))
+                .map(|field| field.trim().to_string())
+                .filter(|field| !field.is_empty())
+                .collect(),
+            business: self
+                .only
+                .as_deref()
+                .map(filter::parse_class)
+                .transpose()
+                .map_err(|err| ApiError(StatusCode::BAD_REQUEST, err))?,
+            min_amount: self.min_amount,
+            max_amount: self.max_amount,
+            hours: self
+                .hours
+                .as_deref()
+                .map(filter::parse_hours)
+                .transpose()
+                .map_err(|err| ApiError(StatusCode::BAD_REQUEST, err))?,
+        };
+        match self.view {
+            Some(name) => tracker
+                .views
+                .iter()
+                .find(|v| v.name.eq_ignore_ascii_case(&name))
+                .map(|view| filter.or(&view.filter))
+                .ok_or_else(|| bad_request(format!("no view named {name}"))),
+            None => Ok(filter),
+        }
+    }
+}
+
+// The body of `POST /api/expenses`, also sent by `--remote` clients
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NewExpense {
+    // Today when left out
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<NaiveDate>,
+    // Only read along with the date; both are now when it is left out
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time: Option<NaiveTime>,
+    // Where the time was, when not in the server's timezone
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "dates::offset"
+    )]
+    pub offset: Option<FixedOffset>,
+    pub category: String,
+    pub amount: f64,
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payee: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trip: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub meta: BTreeMap<String, String>,
+    // They must add up to the amount
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<Item>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub private: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deductible: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub business: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub reimbursable: bool,
+}
+
+// The body of `PATCH /api/expenses/{id}`; only the fields given change and
+// an empty payee, trip, project or user clears it.
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct ExpenseUpdate {
+    date: Option<NaiveDate>,
+    category: Option<String>,
+    amount: Option<f64>,
+    description: Option<String>,
+    payee: Option<String>,
+    trip: Option<String>,
+    project: Option<String>,
+    user: Option<String>,
+    tags: Option<Vec<String>>,
+    meta: Option<BTreeMap<String, String>>,
+    private: Option<bool>,
+    deductible: Option<bool>,
+    business: Option<bool>,
+}
+
+fn check_amount(amount: f64) -> Result<(), ApiError> {
+    if expense::valid_amount(amount) {
+        Ok(())
+    } else {
+        Err(bad_request(format!("{amount} is not a valid amount")))
+    }
+}
+
+fn check_text(field: &str, value: &str) -> Result<(), ApiError> {
+    if value.trim().is_empty() {
+        Err(bad_request(format!("{field} can't be empty")))
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CategoryTotal {
+    pub category: String,
+    pub amount: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Summary {
+    pub currency: String,
+    pub count: usize,
+    pub total: f64,
+    // Largest first
+    pub categories: Vec<CategoryTotal>,
+}
+
+// What clients need to know about the ledger itself
+#[derive(Serialize, Deserialize)]
+pub struct Ledger {
+    pub currency: String,
+    pub closed_months: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct MonthlyCategory {
+    category: String,
+    amount: f64,
+    previous: f64,
+}
+
+#[derive(Serialize)]
+struct Monthly {
+    currency: String,
+    month: String,
+    count: usize,
+    total: f64,
+    previous_total: f64,
+    categories: Vec<MonthlyCategory>,
+    top: Vec<Expense>,
+}
+
+#[derive(Serialize)]
+struct TrendMonth {
+    month: String,
+    total: f64,
+}
+
+async fn list_expenses(
+    State(server): State<Shared>,
+    Query(query): Query<FilterQuery>,
+) -> Result<Json<Vec<Expense>>, ApiError> {
+    locked(server, move |server| {
+        let tracker = server.load()?;
+        let filter = query.filter(&tracker)?;
+        // Amount bounds are compared after converting to the ledger's currency.
+        let ids: Vec<u64> = if filter.has_amount_range() {
+            let view = server.convert(&tracker)?;
+            filter.apply(&view.expenses).iter().map(|e| e.id).collect()
+        } else {
+            filter
+                .apply(&tracker.expenses)
+                .iter()
+                .map(|e| e.id)
+                .collect()
+        };
+        Ok(Json(
+            tracker
+                .expenses
+                .into_iter()
+                .filter(|e| ids.contains(&e.id))
+                .collect(),
+        ))
+    })
+    .await
+}
+
+async fn get_expense(
+    State(server): State<Shared>,
+    Path(id): Path<u64>,
+) -> Result<Json<Expense>, ApiError> {
+    locked(server, move |server| {
+        let tracker = server.load()?;
+        tracker
+            .find_by_id(id)
+            .cloned()
+            .map(Json)
+            .ok_or_else(|| not_found(id))
+    })
+    .await
+}
+
+async fn create_expense(
+    State(server): State<Shared>,
+    Json(new): Json<NewExpense>,
+) -> Result<(StatusCode, Json<Expense>), ApiError> {
+    check_amount(new.amount)?;
+    check_text("category", &new.category)?;
+    check_text("description", &new.description)?;
+    locked(server, move |server| {
+        let storage = server.storage();
+        let metadata = storage.load_metadata()?;
+        let zone = server.config.timezone;
+        let when = match new.date {
+            Some(date) => dates::When {
+                date,
+                time: new.time,
+                offset: new.offset,
+            }
+            .in_zone(zone),
+            None => dates::now(zone),
+        };
+        if metadata.is_month_closed(when.date) {
+            return Err(closed(when.date));
+        }
+        let mut expense = Expense {
+            date: when.date,
+            time: when.time,
+            offset: when.offset,
+            category: new.category,
+            amount: new.amount,
+            currency: new.currency.filter(|code| *code != server.currency),
+            description: new.description,
+            payee: new.payee.filter(|p| !p.is_empty()),
+            trip: new.trip.filter(|t| !t.is_empty()),
+            project: new.project.filter(|p| !p.is_empty()),
+            user: new.user.filter(|u| !u.is_empty()),
+            tags: new.tags,
+            meta: new.meta,
+            items: new.items,
+            private: new.private,
+            deductible: new.deductible,
+            business: new.business,
+            reimbursement: new.reimbursable.then_some(reimburse::Status::Pending),
+            ..Default::default()
+        };
+        if !expense.items_balance() {
+            return Err(ApiError(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                String::from("the items don't add up to the amount"),
+            ));
+        }
+        expense.id = storage.append(expense.clone())?;
+        if !server.config.webhooks.is_empty() {
+            // Retries mustn't hold up the response.
+            let tracker = storage.load()?;
+            let webhooks = server.config.webhooks.clone();
+            let id = expense.id;
+            std::thread::spawn(move || webhook::added(&webhooks, &tracker, &[id]));
+        }
+        Ok((StatusCode::CREATED, Json(expense)))
+    })
+    .await
+}
+
+async fn update_expense(
+    State(server): State<Shared>,
+    Path(id): Path<u64>,
+    Json(update): Json<ExpenseUpdate>,
+) -> Result<Json<Expense>, ApiError> {
+    if let Some(amount) = update.amount {
+        check_amount(amount)?;
+    }
+    if let Some(category) = &update.category {
+        check_text("category", category)?;
+    }
+    if let Some(description) = &update.description {
+        check_text("description", description)?;
+    }
+    locked(server, move |server| {
+        let mut tracker = server.load()?;
+        let closed_months = tracker.clone();
+        let expense = tracker
+            .expenses
+            .iter_mut()
+            .find(|e| e.id == id)
+            .ok_or_else(|| not_found(id))?;
+        for date in [Some(expense.date), update.date].into_iter().flatten() {
+            if closed_months.is_month_closed(date) {
+                return Err(closed(date));
+            }
+        }
+        if let Some(date) = update.date {
+            expense.date = date;
+        }
+        if let Some(category) = update.category {
+            expense.category = category;
+        }
+        if let Some(amount) = update.amount {
+            expense.amount = amount;
+        }
+        if let Some(description) = update.description {
+            expense.description = description;
+        }
+        if let Some(payee) = update.payee {
+            expense.payee = (!payee.is_empty()).then_some(payee);
+        }
+        if let Some(trip) = update.trip {
+            expense.trip = (!trip.is_empty()).then_some(trip);
+        }
+        if let Some(project) = update.project {
+            expense.project = (!project.is_empty()).then_some(project);
+        }
+        if let Some(user) = update.user {
+            expense.user = (!user.is_empty()).then_some(user);
+        }
+        if let Some(tags) = update.tags {
+            expense.tags = tags;
+        }
+        if let Some(meta) = update.meta {
+            expense.meta = meta;
+        }
+        if let Some(private) = update.private {
+            expense.private = private;
+        }
+        if let Some(deductible) = update.deductible {
+            expense.deductible = deductible;
+        }
+        if let Some(business) = update.business {
+            expense.business = business;
+        }
+        if !expense.splits_balance() {
+            return Err(ApiError(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                String::from("the splits no longer add up to the amount"),
+            ));
+        }
+        if !expense.items_balance() {
+            return Err(ApiError(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                String::from("the items no longer add up to the amount"),
+            ));
+        }
+        let expense = expense.clone();
+        server.storage().save(&tracker)?;
+        Ok(Json(expense))
+    })
+    .await
+}
+
+async fn delete_expense(
+    State(server): State<Shared>,
+    Path(id): Path<u64>,
+) -> Result<StatusCode, ApiError> {
+    locked(server, move |server| {
+        let mut tracker = server.load()?;
+        let expense = tracker.find_by_id(id).ok_or_else(|| not_found(id))?;
+        if tracker.is_month_closed(expense.date) {
+            return Err(closed(expense.date));
+        }
+        tracker.expenses.retain(|e| e.id != id);
+        server.storage().save(&tracker)?;
+        Ok(StatusCode::NO_CONTENT)
+    })
+    .await
+}
+
+async fn ledger(State(server): State<Shared>) -> Result<Json<Ledger>, ApiError> {
+    locked(server, move |server| {
+        let metadata = server.storage().load_metadata()?;
+        Ok(Json(Ledger {
+            currency: server.currency.clone(),
+            closed_months: metadata.closed_months,
+        }))
+    })
+    .await
+}
+
+async fn summary(
+    State(server): State<Shared>,
+    Query(query): Query<FilterQuery>,
+) -> Result<Json<Summary>, ApiError> {
+    locked(server, move |server| {
+        let tracker = server.load()?;
+        let filter = query.filter(&tracker)?;
+        let tracker = server.report_view(tracker, filter.from, filter.to)?;
+        let expenses = filter.apply(tracker.personal_expenses());
+        let categories = report::category_totals(&expenses)
+            .into_iter()
+            .filter(|(category, _)| {
+                filter
+                    .category
+                    .as_deref()
+                    .is_none_or(|c| c.eq_ignore_ascii_case(category))
+            })
+            .map(|(category, amount)| CategoryTotal { category, amount })
+            .collect();
+        Ok(Json(Summary {
+            currency: server.currency.clone(),
+            count: expenses.len(),
+            total: tracker.summary(&filter),
+            categories,
+        }))
+    })
+    .await
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MonthQuery {
+    // This month when left out, as `YYYY-MM`
+    month: Option<String>,
+}
+
+async fn monthly(
+    State(server): State<Shared>,
+    Query(query): Query<MonthQuery>,
+) -> Result<Json<Monthly>, ApiError> {
+    let month = match query.month {
+        Some(month) => dates::parse_month(&month).map_err(bad_request)?,
+        None => dates::period_of(dates::now(server.config.timezone).date),
+    };
+    locked(server, move |server| {
+        let tracker = server.load()?;
+        // The previous month is compared against, so it's read too.
+        let previous = dates::period_start(month - chrono::Months::new(1));
+        let tracker =
+            server.report_view(tracker, Some(previous), Some(dates::period_end(month)))?;
+        let report = report::monthly::build(&tracker, month);
+        Ok(Json(Monthly {
+            currency: server.currency.clone(),
+            month: dates::month_key(month),
+            count: report.count,
+            total: report.total,
+            previous_total: report.previous_total,
+            categories: report
+                .categories
+                .into_iter()
+                .map(|(category, amount, previous)| MonthlyCategory {
+                    category,
+                    amount,
+                    previous,
+                })
+                .collect(),
+            // Converted amounts are all in the ledger's currency.
+            top: report
+                .top
+                .into_iter()
+                .map(|e| Expense {
+                    currency: None,
+                    ..e.clone()
+                })
+                .collect(),
+        }))
+    })
+    .await
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TrendQuery {
+    #[serde(default = "default_months")]
+    months: u32,
+    category: Option<String>,
+}
+
+fn default_months() -> u32 {
+    12
+}
+
+async fn trend(
+    State(server): State<Shared>,
+    Query(query): Query<TrendQuery>,
+) -> Result<Json<Vec<TrendMonth>>, ApiError> {
+    if !(1..=120).contains(&query.months) {
+        return Err(bad_request("months should be between 1 and 120"));
+    }
+    let end = dates::period_of(dates::now(server.config.timezone).date);
+    let start = end - chrono::Months::new(query.months - 1);
+    locked(server, move |server| {
+        let tracker = server.load()?;
+        let tracker = server.report_view(
+            tracker,
+            Some(dates::period_start(start)),
+            Some(dates::period_end(end)),
+        )?;
+        let totals =
+            report::trend::monthly_totals(&tracker, query.category.as_deref(), end, query.months);
+        Ok(Json(
+            totals
+                .into_iter()
+                .map(|(month, total)| TrendMonth {
+                    month: dates::month_key(month),
+                    total,
+                })
+                .collect(),
+        ))
+    })
+    .await
+}
+
+// Let through only requests carrying `Authorization: Bearer <token>`.
+async fn authorize(
+    State(server): State<Shared>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let given = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    match given {
+        Some(token) if same(token.as_bytes(), server.token.as_bytes()) => {
+            Ok(next.run(request).await)
+        }
+        _ => Err(ApiError(
+            StatusCode::UNAUTHORIZED,
+            String::from("missing or wrong bearer token"),
+        )),
+    }
+}
+
+// Compare without returning early, so timing doesn't give the token away.
+fn same(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+// A random token for when none was configured.
+pub fn new_token() -> String {
+    use aes_gcm::aead::{OsRng, rand_core::RngCore};
+    let mut bytes = [0u8; 16];
+    OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+// The dashboard page, which asks for the token and then uses the API
+const DASHBOARD: &str = include_str!("dashboard.html");
+
+async fn dashboard() -> Html<&'static str> {
+    Html(DASHBOARD)
+}
+
+fn routes(server: Shared) -> Router {
+    let api = Router::new()
+        .route("/expenses", get(list_expenses).post(create_expense))
+        .route(
+            "/expenses/{id}",
+            get(get_expense)
+                .patch(update_expense)
+                .delete(delete_expense),
+        )
+        .route("/ledger", get(ledger))
+        .route("/summary", get(summary))
+        .route("/reports/monthly", get(monthly))
+        .route("/reports/trend", get(trend))
+        .route_layer(middleware::from_fn_with_state(server.clone(), authorize));
+    Router::new()
+        .route("/", get(dashboard))
+        .nest("/api", api)
+        .with_state(server)
+}
+
+// Serve the ledger over HTTP on `address` until interrupted.
+pub fn run(
+    filename: &str,
+    config: Config,
+    currency: String,
+    token: String,
+    read_only: bool,
+    address: (&str, u16),
+) -> io::Result<()> {
+    let server = Arc::new(Server {
+        filename: filename.to_string(),
+        config,
+        currency,
+        token,
+        read_only,
+        lock: Mutex::new(()),
+    });
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    runtime.block_on(async {
+        let listener = tokio::net::TcpListener::bind(address).await?;
+        println!(
+            "Serving the ledger on http://{}; the dashboard is at /",
+            listener.local_addr()?
+        );
+        axum::serve(listener, routes(server)).await
+    })
+}
@@ -0,0 +1,97 @@
+use crate::currency;
+use crate::expense::ExpenseTracker;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+// How the ledger owner is referred to in shared expenses and settlements
+pub const ME: &str = "me";
+
+// A payment made between two people to settle shared expenses
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Settlement {
+    pub date: NaiveDate,
+    pub from: String,
+    pub to: String,
+    pub amount: f64,
+}
+
+// Net position of every person: positive means they are owed money,
+// negative means they owe. Shared expenses are split equally between the
+// payer's group (the owner plus everyone in `shared_with`).
+pub fn net_balances(tracker: &ExpenseTracker) -> Vec<(String, f64)> {
+    let mut balances: Vec<(String, f64)> = Vec::new();
+    let mut adjust = |name: &str, amount: f64| match balances
+        .iter_mut()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+    {
+        Some((_, balance)) => *balance += amount,
+        None => balances.push((name.to_string(), amount)),
+    };
+
+    for expense in tracker.expenses.iter() {
+        if expense.shared_with.is_empty() {
+            continue;
+        }
+        let share = expense.amount / (expense.shared_with.len() + 1) as f64;
+        adjust(expense.paid_by.as_deref().unwrap_or(ME), expense.amount);
+        adjust(ME, -share);
+        for person in expense.shared_with.iter() {
+            adjust(person, -share);
+        }
+    }
+    for settlement in tracker.settlements.iter() {
+        adjust(&settlement.from, settlement.amount);
+        adjust(&settlement.to, -settlement.amount);
+    }
+
+    balances.retain(|(_, balance)| balance.abs() >= 0.005);
+    balances.sort_by(|a, b| b.1.total_cmp(&a.1));
+    balances
+}
+
+// The payments that would settle every balance, largest debts first.
+pub fn settle_up(balances: &[(String, f64)]) -> Vec<(String, String, f64)> {
+    let mut creditors: Vec<(String, f64)> =
+        balances.iter().filter(|(_, b)| *b > 0.0).cloned().collect();
+    let mut debtors: Vec<(String, f64)> = balances
+        .iter()
+        .filter(|(_, b)| *b < 0.0)
+        .map(|(n, b)| (n.clone(), -b))
+        .collect();
+    debtors.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let mut payments = Vec::new();
+    let (mut c, mut d) = (0, 0);
+    while c < creditors.len() && d < debtors.len() {
+        let amount = creditors[c].1.min(debtors[d].1);
+        payments.push((debtors[d].0.clone(), creditors[c].0.clone(), amount));
+        creditors[c].1 -= amount;
+        debtors[d].1 -= amount;
+        if creditors[c].1 < 0.005 {
+            c += 1;
+        }
+        if debtors[d].1 < 0.005 {
+            d += 1;
+        }
+    }
+    payments
+}
+
+pub fn print_balances(tracker: &ExpenseTracker) {
+    let balances = net_balances(tracker);
+    if balances.is_empty() {
+        println!("All settled up.");
+        return;
+    }
+    for (person, balance) in balances.iter() {
+        if *balance > 0.0 {
+            println!("{:<20} is owed {}", person, currency::amount(*balance));
+        } else {
+            println!("{:<20} owes    {}", person, currency::amount(-balance));
+        }
+    }
+    println!("\nTo settle up:");
+    for (from, to, amount) in settle_up(&balances) {
+        println!("  {from} pays {to} {}", currency::amount(amount));
+    }
+}
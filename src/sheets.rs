@@ -0,0 +1,316 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{Days, NaiveDate, Utc};
+use rsa::{
+    RsaPrivateKey,
+    pkcs1v15::SigningKey,
+    pkcs8::DecodePrivateKey,
+    signature::{SignatureEncoding, Signer},
+};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use sha2::Sha256;
+use std::fs;
+use ureq::{Agent, http::Response};
+
+use crate::config::SheetsConfig;
+use crate::expense::Expense;
+use crate::storage::Storage;
+
+const API: &str = "https://sheets.googleapis.com/v4/spreadsheets";
+const SCOPE: &str = "https://www.googleapis.com/auth/spreadsheets";
+const HEADER: [&str; 7] = [
+    "Id",
+    "Date",
+    "Category",
+    "Amount",
+    "Currency",
+    "Description",
+    "Payee",
+];
+
+// The parts of a service account's key file that are used
+#[derive(Deserialize)]
+struct ServiceAccount {
+    client_email: String,
+    private_key: String,
+    token_uri: String,
+}
+
+#[derive(Deserialize)]
+struct Token {
+    access_token: String,
+}
+
+#[derive(Deserialize)]
+struct Values {
+    #[serde(default)]
+    values: Vec<Vec<Value>>,
+}
+
+// What a sync did
+pub struct Outcome {
+    pub pulled: Vec<u64>,
+    pub pushed: usize,
+    // Rows that couldn't be read, left in the sheet under the ledger's
+    pub skipped: Vec<(usize, String)>,
+}
+
+struct Client {
+    agent: Agent,
+    token: String,
+    spreadsheet: String,
+}
+
+// An access token for the service account in the key file at `path`.
+fn access_token(agent: &Agent, path: &str) -> Result<String, String> {
+    let key = fs::read_to_string(path).map_err(|err| format!("{path}: {err}"))?;
+    let account: ServiceAccount =
+        serde_json::from_str(&key).map_err(|err| format!("{path}: {err}"))?;
+    let private_key = RsaPrivateKey::from_pkcs8_pem(&account.private_key)
+        .map_err(|err| format!("{path}: the private key can't be read: {err}"))?;
+
+    let now = Utc::now().timestamp();
+    let header = URL_SAFE_NO_PAD.encode(json!({ "alg": "RS256", "typ": "JWT" }).to_string());
+    let claims = URL_SAFE_NO_PAD.encode(
+        json!({
+            "iss": account.client_email,
+            "scope": SCOPE,
+            "aud": account.token_uri,
+            "iat": now,
+            "exp": now + 3600,
+        })
+        .to_string(),
+    );
+    let unsigned = format!("{header}.{claims}");
+    let signature = SigningKey::<Sha256>::new(private_key).sign(unsigned.as_bytes());
+    let assertion = format!(
+        "{unsigned}.{}",
+        URL_SAFE_NO_PAD.encode(signature.to_bytes())
+    );
+
+    let response = agent
+        .post(&account.token_uri)
+        .send_form([
+            ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+            ("assertion", assertion.as_str()),
+        ])
+        .map_err(|err| format!("{}: {err}", account.token_uri))?;
+    let token: Token = read(response)?;
+    Ok(token.access_token)
+}
+
+// The body as JSON, or Google's error message.
+fn read<T: for<'de> Deserialize<'de>>(mut response: Response<ureq::Body>) -> Result<T, String> {
+    let status = response.status();
+    if status.is_success() {
+        return response
+            .body_mut()
+            .read_json()
+            .map_err(|err| err.to_string());
+    }
+    let body: Value = response.body_mut().read_json().unwrap_or_default();
+    let message = body["error"]["message"]
+        .as_str()
+        .or(body["error_description"].as_str())
+        .map_or_else(|| status.to_string(), str::to_string);
+    Err(format!("Google refused: {message}"))
+}
+
+// `text` safe to put in a URL path.
+fn escape(text: &str) -> String {
+    text.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+impl Client {
+    fn url(&self, range: &str, action: &str) -> String {
+        format!(
+            "{API}/{}/values/{}{action}",
+            self.spreadsheet,
+            escape(range)
+        )
+    }
+
+    fn get(&self, range: &str) -> Result<Vec<Vec<Value>>, String> {
+        let response = self
+            .agent
+            .get(&self.url(range, ""))
+            .header("Authorization", &format!("Bearer {}", self.token))
+            .query("valueRenderOption", "UNFORMATTED_VALUE")
+            .query("dateTimeRenderOption", "SERIAL_NUMBER")
+            .call()
+            .map_err(|err| err.to_string())?;
+        Ok(read::<Values>(response)?.values)
+    }
+
+    fn clear(&self, range: &str) -> Result<(), String> {
+        let response = self
+            .agent
+            .post(&self.url(range, ":clear"))
+            .header("Authorization", &format!("Bearer {}", self.token))
+            .send_json(json!({}))
+            .map_err(|err| err.to_string())?;
+        read::<Value>(response).map(|_| ())
+    }
+
+    fn put(&self, range: &str, values: Vec<Vec<Value>>) -> Result<(), String> {
+        let response = self
+            .agent
+            .put(&self.url(range, ""))
+            .header("Authorization", &format!("Bearer {}", self.token))
+            .query("valueInputOption", "USER_ENTERED")
+            .send_json(json!({ "range": range, "majorDimension": "ROWS", "values": values }))
+            .map_err(|err| err.to_string())?;
+        read::<Value>(response).map(|_| ())
+    }
+}
+
+fn text(cell: Option<&Value>) -> String {
+    match cell {
+        Some(Value::String(s)) => s.trim().to_string(),
+        Some(Value::Number(n)) => n.to_string(),
+        _ => String::new(),
+    }
+}
+
+// A date cell: a serial number counting days from 1899-12-30, or text such
+// as 2025-07-01.
+fn date(cell: Option<&Value>) -> Result<NaiveDate, String> {
+    match cell {
+        Some(Value::Number(n)) => {
+            let epoch = NaiveDate::from_ymd_opt(1899, 12, 30).expect("valid date");
+            n.as_f64()
+                .filter(|days| *days >= 0.0)
+                .and_then(|days| epoch.checked_add_days(Days::new(days as u64)))
+                .ok_or_else(|| format!("{n} is not a date"))
+        }
+        _ => {
+            let text = text(cell);
+            NaiveDate::parse_from_str(&text, "%Y-%m-%d")
+                .map_err(|_| format!("'{text}' should be a date such as 2025-07-01"))
+        }
+    }
+}
+
+fn amount(cell: Option<&Value>) -> Result<f64, String> {
+    let amount = match cell {
+        Some(Value::Number(n)) => n.as_f64(),
+        _ => text(cell).replace(',', "").parse().ok(),
+    };
+    amount
+        .filter(|a| *a > 0.0 && a.is_finite())
+        .ok_or_else(|| format!("'{}' is not an amount", text(cell)))
+}
+
+// An expense from a row added in the sheet.
+fn parse_row(row: &[Value], ledger: &str) -> Result<Expense, String> {
+    let category = text(row.get(2));
+    let description = text(row.get(5));
+    if category.is_empty() || description.is_empty() {
+        return Err(String::from("a category and a description are needed"));
+    }
+    let currency = text(row.get(4)).to_ascii_uppercase();
+    let payee = text(row.get(6));
+    Ok(Expense {
+        date: date(row.get(1))?,
+        category,
+        amount: amount(row.get(3))?,
+        currency: (!currency.is_empty() && currency != ledger).then_some(currency),
+        description,
+        payee: (!payee.is_empty()).then_some(payee),
+        ..Default::default()
+    })
+}
+
+fn row(expense: &Expense) -> Vec<Value> {
+    vec![
+        json!(expense.id),
+        json!(expense.date.to_string()),
+        json!(expense.category),
+        json!(expense.amount),
+        json!(expense.currency.clone().unwrap_or_default()),
+        json!(expense.description),
+        json!(expense.payee.clone().unwrap_or_default()),
+    ]
+}
+
+// Save the rows added in the sheet to the ledger, then rewrite the sheet
+// with every expense, each row carrying its expense's id. Rows with an id
+// belong to the ledger, so edits made to them in the sheet are overwritten.
+pub fn sync(
+    storage: &dyn Storage,
+    config: &SheetsConfig,
+    spreadsheet: &str,
+    ledger: &str,
+) -> Result<Outcome, String> {
+    let credentials = config
+        .credentials
+        .as_deref()
+        .ok_or("set credentials under [sheets] in the config file")?;
+    let agent: Agent = Agent::config_builder()
+        .http_status_as_error(false)
+        .build()
+        .into();
+    let client = Client {
+        token: access_token(&agent, credentials)?,
+        agent,
+        spreadsheet: spreadsheet.to_string(),
+    };
+    let sheet = format!("'{}'", config.sheet.replace('\'', "''"));
+    let mut tracker = storage.load().map_err(|err| err.to_string())?;
+
+    let mut pulled = Vec::new();
+    let mut skipped = Vec::new();
+    let mut unreadable = Vec::new();
+    for (number, row) in (2..).zip(client.get(&format!("{sheet}!A2:G"))?) {
+        if row.iter().all(|cell| text(Some(cell)).is_empty()) {
+            continue;
+        }
+        // Rows with an id came from the ledger.
+        if !text(row.first()).is_empty() {
+            continue;
+        }
+        let expense = parse_row(&row, ledger).and_then(|expense| {
+            if tracker.is_month_closed(expense.date) {
+                Err(format!("{} is in a closed month", expense.date))
+            } else {
+                Ok(expense)
+            }
+        });
+        match expense {
+            Ok(expense) => {
+                pulled.push(tracker.next_id);
+                tracker.add_expense(expense);
+            }
+            Err(err) => {
+                skipped.push((number, err));
+                unreadable.push(row);
+            }
+        }
+    }
+
+    // Saved first, so a failure can't leave ids in the sheet for expenses
+    // the ledger doesn't have.
+    if !pulled.is_empty() {
+        storage.save(&tracker).map_err(|err| err.to_string())?;
+    }
+
+    let mut expenses: Vec<&Expense> = tracker.expenses.iter().collect();
+    expenses.sort_by_key(|e| (e.date, e.id));
+    let mut values = vec![HEADER.iter().map(|h| json!(h)).collect::<Vec<Value>>()];
+    values.extend(expenses.iter().map(|e| row(e)));
+    values.extend(unreadable);
+    client.clear(&format!("{sheet}!A:G"))?;
+    client.put(&format!("{sheet}!A1"), values)?;
+    Ok(Outcome {
+        pulled,
+        pushed: expenses.len(),
+        skipped,
+    })
+}
@@ -0,0 +1,126 @@
+use crate::attachments;
+use crate::currency;
+use crate::expense::{Expense, MASK};
+use crate::history;
+use crate::locale;
+
+// Every stored field of `expense`, one to a line, then its attachments and
+// the changes made to it. Attachments are opened too when `open` is set.
+pub fn print(expense: &Expense, data_file: &str, mask_private: bool, open: bool) {
+    let mask = mask_private && expense.private;
+    let amount = |amount: f64| {
+        if mask {
+            MASK.to_string()
+        } else {
+            currency::amount_in(expense.currency.as_deref(), amount)
+        }
+    };
+    let hidden = |text: &str| {
+        if mask {
+            MASK.to_string()
+        } else {
+            text.to_string()
+        }
+    };
+    let field = |name: &str, value: &str| println!("  {:<14} {}", name, value);
+
+    println!("Expense #{}", expense.id);
+    let mut date = locale::date(expense.date);
+    if let Some(time) = expense.time {
+        date.push_str(&time.format(" %H:%M").to_string());
+    }
+    if let Some(offset) = expense.offset {
+        date.push_str(&format!(" {offset}"));
+    }
+    field("Date", &date);
+    field("Category", &expense.category);
+    field("Amount", &amount(expense.amount));
+    if let Some(code) = &expense.currency {
+        field("Currency", code);
+    }
+    field("Description", &hidden(&expense.description));
+    if let Some(payee) = &expense.payee {
+        field("Payee", &hidden(payee));
+    }
+    if let Some(trip) = &expense.trip {
+        field("Trip", trip);
+    }
+    if let Some(project) = &expense.project {
+        field("Project", project);
+    }
+    if let Some(user) = &expense.user {
+        field("User", user);
+    }
+    if !expense.tags.is_empty() {
+        field("Tags", &expense.tags.join(", "));
+    }
+    for (key, value) in expense.meta.iter() {
+        field(key, value);
+    }
+    if !expense.shared_with.is_empty() {
+        field("Shared with", &expense.shared_with.join(", "));
+    }
+    if let Some(paid_by) = &expense.paid_by {
+        field("Paid by", paid_by);
+    }
+    if let Some(status) = expense.reimbursement {
+        field("Reimbursement", &status.to_string());
+    }
+    let flags: Vec<&str> = [
+        (expense.business, "business"),
+        (expense.deductible, "deductible"),
+        (expense.private, "private"),
+    ]
+    .into_iter()
+    .filter_map(|(set, name)| set.then_some(name))
+    .collect();
+    if !flags.is_empty() {
+        field("Marked", &flags.join(", "));
+    }
+    if let Some(modified) = expense.modified {
+        field("Modified", &modified.format("%Y-%m-%d %H:%M").to_string());
+    }
+
+    if !expense.splits.is_empty() {
+        println!("\nSplit");
+        for split in expense.splits.iter() {
+            println!("  {:<28} {:>14}", split.category, amount(split.amount));
+        }
+    }
+    if !expense.items.is_empty() {
+        println!("\nItems");
+        for item in expense.items.iter() {
+            println!(
+                "  {:<28} {:>8} x {:>12} {:>14}",
+                hidden(&item.name),
+                item.quantity,
+                amount(item.price),
+                amount(item.amount())
+            );
+        }
+    }
+
+    println!("\nAttachments");
+    if expense.attachments.is_empty() {
+        println!("  None");
+    }
+    for name in expense.attachments.iter() {
+        let path = attachments::dir(data_file, expense.id).join(name);
+        println!("  {}", path.display());
+        if open && let Err(err) = attachments::open(&path) {
+            eprintln!("Error opening {}: {}", path.display(), err);
+        }
+    }
+
+    println!("\nHistory");
+    match history::load(data_file) {
+        Ok(events) => {
+            let changes: Vec<&history::Event> = events
+                .iter()
+                .filter(|e| e.change.id() == expense.id)
+                .collect();
+            history::print(&changes, mask_private);
+        }
+        Err(err) => eprintln!("Error reading history: {}", err),
+    }
+}
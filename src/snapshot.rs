@@ -0,0 +1,129 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::currency;
+use crate::expense::ExpenseTracker;
+use crate::history::{self, Change};
+use crate::report;
+
+// Directory holding named snapshots, next to the data file.
+pub fn root(data_file: &str) -> PathBuf {
+    Path::new(data_file).with_file_name("snapshots")
+}
+
+pub fn path(data_file: &str, name: &str) -> PathBuf {
+    root(data_file).join(format!("{name}.json"))
+}
+
+// Every snapshot file.
+pub fn list(data_file: &str) -> io::Result<Vec<PathBuf>> {
+    let dir = root(data_file);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut snapshots: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    snapshots.sort();
+    Ok(snapshots)
+}
+
+// Snapshot names become file names, so keep them simple.
+pub fn parse_name(s: &str) -> Result<String, String> {
+    if !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        && !s.starts_with('.')
+    {
+        Ok(s.to_string())
+    } else {
+        Err(format!(
+            "'{s}' should only use letters, digits, '-', '_' and '.'"
+        ))
+    }
+}
+
+// Save the ledger as it is now under `name`, replacing any snapshot with the
+// same name.
+pub fn create(data_file: &str, name: &str, tracker: &ExpenseTracker) -> io::Result<()> {
+    fs::create_dir_all(root(data_file))?;
+    tracker.save_to_json(&path(data_file, name).to_string_lossy())
+}
+
+pub fn load(data_file: &str, name: &str) -> io::Result<ExpenseTracker> {
+    let path = path(data_file, name);
+    if !path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no snapshot named {name}; see `snapshot list`"),
+        ));
+    }
+    Ok(ExpenseTracker::load_from_json(&path.to_string_lossy())?)
+}
+
+pub fn print_list(data_file: &str) -> io::Result<()> {
+    let dir = root(data_file);
+    let mut names: Vec<String> = if dir.exists() {
+        fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                name.strip_suffix(".json").map(str::to_string)
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+    if names.is_empty() {
+        println!("No snapshots yet.");
+        return Ok(());
+    }
+    names.sort();
+    for name in names {
+        match load(data_file, &name) {
+            Ok(tracker) => println!("{:<24} {} expense(s)", name, tracker.expenses.len()),
+            Err(_) => println!("{:<24} unreadable", name),
+        }
+    }
+    Ok(())
+}
+
+// The expenses added, removed and modified going from `before` to `after`.
+pub fn print_diff(before: &ExpenseTracker, after: &ExpenseTracker, mask_private: bool) {
+    let changes = history::changes(before, after);
+    if changes.is_empty() {
+        println!("No differences.");
+        return;
+    }
+    let (mut added, mut removed, mut modified) = (0, 0, 0);
+    for change in changes.iter() {
+        match change {
+            Change::Added { expense } => {
+                added += 1;
+                println!("+ {}", expense.line(mask_private));
+            }
+            Change::Deleted { expense } => {
+                removed += 1;
+                println!("- {}", expense.line(mask_private));
+            }
+            Change::Edited { before, after } => {
+                modified += 1;
+                println!("~ {}", after.line(mask_private));
+                if mask_private && (before.private || after.private) {
+                    continue;
+                }
+                for difference in history::differences(before, after) {
+                    println!("    {difference}");
+                }
+            }
+        }
+    }
+    println!(
+        "\n{added} added, {removed} removed, {modified} modified; total {} → {}",
+        currency::amount(report::total(&report::personal(before))),
+        currency::amount(report::total(&report::personal(after)))
+    );
+}
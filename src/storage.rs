@@ -0,0 +1,515 @@
+use crate::backup;
+use crate::config::{Config, StorageKind};
+use crate::crypto;
+use crate::expense::{Expense, ExpenseTracker};
+use crate::history;
+use crate::sync;
+use crate::webdav::WebDav;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+use tracing::{debug, info};
+
+// A place the ledger is persisted to.
+pub trait Storage {
+    // Load the full ledger.
+    fn load(&self) -> io::Result<ExpenseTracker>;
+
+    // Load the ledger's metadata (closed months and the like) and its next
+    // id without its expenses. Backends that cannot separate the two load
+    // everything.
+    fn load_metadata(&self) -> io::Result<ExpenseTracker> {
+        self.load()
+    }
+
+    // Replace the stored ledger with `tracker`.
+    fn save(&self, tracker: &ExpenseTracker) -> io::Result<()>;
+
+    // Record a single new expense and return its id. Append-capable
+    // backends override this to avoid reading and rewriting the whole ledger.
+    fn append(&self, expense: Expense) -> io::Result<u64> {
+        let mut tracker = self.load()?;
+        tracker.add_expense(expense);
+        self.save(&tracker)?;
+        Ok(tracker.next_id - 1)
+    }
+}
+
+// `tracker` with the time noted on the expenses that are new or changed
+// since `before`.
+pub fn stamped(before: &ExpenseTracker, tracker: &ExpenseTracker) -> ExpenseTracker {
+    let now = Local::now();
+    let mut tracker = tracker.clone();
+    for expense in tracker.expenses.iter_mut() {
+        if before.find_by_id(expense.id) != Some(expense) {
+            expense.modified = Some(now);
+        }
+    }
+    tracker
+}
+
+// Note the changes from `before` to `after` in the history, and commit
+// them when the data directory is synced.
+pub fn record(data_file: &str, before: &ExpenseTracker, after: &ExpenseTracker) -> io::Result<()> {
+    let changes = history::changes(before, after);
+    let message = sync::describe(&changes);
+    history::record(data_file, before, changes)?;
+    sync::commit(data_file, &message);
+    Ok(())
+}
+
+// The original single-document JSON data file.
+pub struct JsonFile {
+    filename: String,
+}
+
+impl JsonFile {
+    // Replace the ledger as it stood, `before`, with `tracker`. The previous
+    // version is kept in the backups directory first, and the changes to
+    // expenses are added to the history.
+    fn write(&self, before: &ExpenseTracker, tracker: &ExpenseTracker) -> io::Result<()> {
+        let tracker = stamped(before, tracker);
+        backup::create(&self.filename)?;
+        tracker.save_to_json(&self.filename)?;
+        record(&self.filename, before, &tracker)
+    }
+}
+
+impl Storage for JsonFile {
+    fn load(&self) -> io::Result<ExpenseTracker> {
+        Ok(ExpenseTracker::load_from_json(&self.filename)?)
+    }
+
+    fn load_metadata(&self) -> io::Result<ExpenseTracker> {
+        Ok(ExpenseTracker::load_metadata_from_json(&self.filename)?)
+    }
+
+    fn save(&self, tracker: &ExpenseTracker) -> io::Result<()> {
+        let before = ExpenseTracker::load_from_json(&self.filename)?;
+        self.write(&before, tracker)
+    }
+
+    // The whole file is rewritten, but loaded only once.
+    fn append(&self, expense: Expense) -> io::Result<u64> {
+        let before = ExpenseTracker::load_from_json(&self.filename)?;
+        let mut tracker = before.clone();
+        tracker.add_expense(expense);
+        self.write(&before, &tracker)?;
+        Ok(tracker.next_id - 1)
+    }
+}
+
+// The data file plus an append-only journal of expenses added since it was
+// last written, one JSON object per line. `add` only appends a line; any
+// other change rewrites the data file with the journal folded in.
+pub struct Journal {
+    filename: String,
+}
+
+// Fold the journal into the data file once it has this many lines
+const COMPACT_AFTER: usize = 200;
+
+// Kept next to the journal so `append` needn't read the ledger: the next
+// id and how many lines are journaled. It only counts while the data file
+// and the journal are as it notes them, so anything else that writes them
+// leaves it stale rather than wrong.
+#[derive(Serialize, Deserialize)]
+struct Index {
+    next_id: u64,
+    journaled: usize,
+    data: Stamp,
+    journal: Stamp,
+}
+
+// A file's size and modification time, or zeros when it doesn't exist
+#[derive(Serialize, Deserialize, PartialEq, Default)]
+struct Stamp {
+    len: u64,
+    modified: u64,
+}
+
+fn stamp(path: &Path) -> Stamp {
+    fs::metadata(path)
+        .map(|metadata| Stamp {
+            len: metadata.len(),
+            modified: metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |since| since.as_nanos() as u64),
+        })
+        .unwrap_or_default()
+}
+
+impl Journal {
+    fn journal_path(&self) -> PathBuf {
+        Path::new(&self.filename).with_extension("journal")
+    }
+
+    fn index_path(&self) -> PathBuf {
+        Path::new(&self.filename).with_extension("index")
+    }
+
+    // The index, if the data file and journal haven't changed since.
+    fn read_index(&self) -> Option<Index> {
+        let index: Index = serde_json::from_slice(&fs::read(self.index_path()).ok()?).ok()?;
+        (index.data == stamp(Path::new(&self.filename))
+            && index.journal == stamp(&self.journal_path()))
+        .then_some(index)
+    }
+
+    // An index that can't be written only makes the next append slower.
+    fn write_index(&self, next_id: u64, journaled: usize) {
+        let index = Index {
+            next_id,
+            journaled,
+            data: stamp(Path::new(&self.filename)),
+            journal: stamp(&self.journal_path()),
+        };
+        let temporary = self.index_path().with_extension("index.tmp");
+        let written = serde_json::to_vec(&index)
+            .map_err(io::Error::from)
+            .and_then(|bytes| fs::write(&temporary, bytes))
+            .and_then(|()| fs::rename(&temporary, self.index_path()));
+        if let Err(err) = written {
+            debug!(%err, "couldn't write the journal index");
+        }
+    }
+
+    fn read_journal(&self) -> io::Result<Vec<Expense>> {
+        let text = match fs::read_to_string(self.journal_path()) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+        let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+        let mut expenses = Vec::new();
+        for (i, line) in lines.iter().enumerate() {
+            match serde_json::from_str(line) {
+                Ok(expense) => expenses.push(expense),
+                // A crash mid-append can only leave the last line incomplete.
+                Err(_) if i + 1 == lines.len() && !text.ends_with('\n') => {
+                    eprintln!("Ignoring an incomplete last line in the journal.");
+                }
+                Err(err) => {
+                    return Err(io::Error::other(format!("journal line {}: {err}", i + 1)));
+                }
+            }
+        }
+        Ok(expenses)
+    }
+
+    // The ledger with the journal replayed, and the journal's length.
+    fn replay(&self) -> io::Result<(ExpenseTracker, usize)> {
+        let mut tracker = ExpenseTracker::load_from_json(&self.filename)?;
+        let journal = self.read_journal()?;
+        let length = journal.len();
+        if length > 0 {
+            info!(entries = length, "replaying journal");
+        }
+        // Entries already in the data file were folded in by a compaction
+        // that stopped before clearing the journal.
+        for expense in journal {
+            if tracker.find_by_id(expense.id).is_none() {
+                tracker.add_expense(expense);
+            }
+        }
+        Ok((tracker, length))
+    }
+}
+
+impl Storage for Journal {
+    fn load(&self) -> io::Result<ExpenseTracker> {
+        Ok(self.replay()?.0)
+    }
+
+    // The data file's settings, with the next id past anything journaled;
+    // the journal is never longer than `COMPACT_AFTER` lines.
+    fn load_metadata(&self) -> io::Result<ExpenseTracker> {
+        let mut tracker = ExpenseTracker::load_metadata_from_json(&self.filename)?;
+        if let Some(last) = self.read_journal()?.iter().map(|e| e.id).max() {
+            tracker.next_id = tracker.next_id.max(last + 1);
+        }
+        Ok(tracker)
+    }
+
+    // The data file is replaced atomically, then the journal is cleared.
+    fn save(&self, tracker: &ExpenseTracker) -> io::Result<()> {
+        let before = self.load()?;
+        let tracker = stamped(&before, tracker);
+        backup::create(&self.filename)?;
+        let temporary = Path::new(&self.filename).with_extension("json.tmp");
+        tracker.save_to_json(&temporary.to_string_lossy())?;
+        fs::rename(&temporary, &self.filename)?;
+        match fs::remove_file(self.journal_path()) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+            _ => {}
+        }
+        self.write_index(tracker.next_id, 0);
+        record(&self.filename, &before, &tracker)
+    }
+
+    // With an up-to-date index only the new line is written; otherwise the
+    // ledger is replayed to find the next id. It is also needed to start
+    // the history, whose first entries are the ledger as it stood.
+    fn append(&self, mut expense: Expense) -> io::Result<u64> {
+        let index = self
+            .read_index()
+            .filter(|_| history::path(&self.filename).exists());
+        let (replayed, next_id, journaled) = match index {
+            Some(index) => (None, index.next_id, index.journaled),
+            None => {
+                let (tracker, journaled) = self.replay()?;
+                let next_id = tracker.next_id;
+                (Some(tracker), next_id, journaled)
+            }
+        };
+        // Journal lines are plain text, so encrypted ledgers are rewritten.
+        // Synced ledgers are too, since journals from two machines can't be
+        // merged.
+        if crypto::is_active()
+            || crypto::is_encrypted_file(Path::new(&self.filename))
+            || sync::enabled(&self.filename)
+            || journaled + 1 >= COMPACT_AFTER
+        {
+            let mut tracker = match replayed {
+                Some(tracker) => tracker,
+                None => self.load()?,
+            };
+            tracker.add_expense(expense);
+            self.save(&tracker)?;
+            return Ok(tracker.next_id - 1);
+        }
+        expense.id = next_id;
+        expense.modified = Some(Local::now());
+        // Drop an incomplete last line so the new one starts cleanly. Only
+        // an append that stopped part way leaves one, and that leaves the
+        // index stale too.
+        if replayed.is_some()
+            && let Ok(text) = fs::read_to_string(self.journal_path())
+            && !text.is_empty()
+            && !text.ends_with('\n')
+        {
+            let complete = text.rfind('\n').map_or(0, |end| end + 1);
+            fs::write(self.journal_path(), &text[..complete])?;
+        }
+        let mut line = serde_json::to_string(&expense)?;
+        line.push('\n');
+        let mut journal = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.journal_path())?;
+        journal.write_all(line.as_bytes())?;
+        journal.sync_data()?;
+        drop(journal);
+        self.write_index(next_id + 1, journaled + 1);
+        info!(
+            id = expense.id,
+            journaled = journaled + 1,
+            "appended to journal"
+        );
+        let id = expense.id;
+        history::record(
+            &self.filename,
+            &replayed.unwrap_or_else(ExpenseTracker::new),
+            vec![history::Change::Added { expense }],
+        )?;
+        Ok(id)
+    }
+}
+
+// Whether expenses are waiting in a journal next to the data file.
+pub fn journal_exists(filename: &str) -> bool {
+    Journal {
+        filename: filename.to_string(),
+    }
+    .journal_path()
+    .exists()
+}
+
+// For a data file being replaced outright: drop its journal index, and move
+// any journal aside to `<journal>.bak` so it isn't replayed on top of the
+// new file. Returns where a journal was moved to.
+pub fn set_journal_aside(filename: &str) -> io::Result<Option<PathBuf>> {
+    let journal = Journal {
+        filename: filename.to_string(),
+    };
+    match fs::remove_file(journal.index_path()) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+        _ => {}
+    }
+    let path = journal.journal_path();
+    if !path.exists() {
+        return Ok(None);
+    }
+    let aside = path.with_extension("journal.bak");
+    fs::rename(&path, &aside)?;
+    Ok(Some(aside))
+}
+
+// Pick the storage backend for a data file.
+pub fn open(filename: &str, config: &Config) -> Box<dyn Storage> {
+    let journal = Journal {
+        filename: filename.to_string(),
+    };
+    // A journal left from before switching back to plain JSON is still
+    // replayed, and folded in on the next save, so its ids aren't reused.
+    match config.storage {
+        StorageKind::WebDav => Box::new(WebDav::new(filename, &config.webdav)),
+        StorageKind::Json if !journal.journal_path().exists() => Box::new(JsonFile {
+            filename: journal.filename,
+        }),
+        _ => Box::new(journal),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use std::env;
+
+    fn expense(description: &str) -> Expense {
+        Expense {
+            date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            category: String::from("Food"),
+            amount: 100.0,
+            description: description.to_string(),
+            ..Default::default()
+        }
+    }
+
+    // A journaled ledger in a fresh directory holding one expense.
+    fn journal(name: &str) -> (PathBuf, Journal) {
+        let dir = env::temp_dir().join(format!(
+            "expense-tracker-journal-{name}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let filename = dir.join("expenses.json").to_string_lossy().into_owned();
+        let mut tracker = ExpenseTracker::new();
+        tracker.add_expense(expense("rent"));
+        tracker.save_to_json(&filename).unwrap();
+        (dir, Journal { filename })
+    }
+
+    #[test]
+    fn appends_are_journaled_and_replayed_until_a_save_folds_them_in() {
+        let (dir, journal) = journal("replay");
+
+        let ids = (
+            journal.append(expense("lunch")).unwrap(),
+            journal.append(expense("dinner")).unwrap(),
+        );
+        let lines = fs::read_to_string(journal.journal_path())
+            .unwrap()
+            .lines()
+            .count();
+        let in_data_file = ExpenseTracker::load_from_json(&journal.filename)
+            .unwrap()
+            .expenses
+            .len();
+        let loaded = journal.load().unwrap();
+        let next_id = journal.load_metadata().unwrap().next_id;
+        journal.save(&loaded).unwrap();
+        let saved = ExpenseTracker::load_from_json(&journal.filename).unwrap();
+        let journal_left = journal.journal_path().exists();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(ids, (2, 3));
+        assert_eq!((lines, in_data_file), (2, 1));
+        assert_eq!(loaded.expenses.len(), 3);
+        assert_eq!(loaded.expenses[2].description, "dinner");
+        assert_eq!(next_id, 4);
+        assert_eq!(saved.expenses.len(), 3);
+        assert!(!journal_left);
+    }
+
+    #[test]
+    fn the_journal_is_compacted_once_it_is_long_enough() {
+        let (dir, journal) = journal("compact");
+
+        for i in 0..COMPACT_AFTER {
+            journal.append(expense(&format!("coffee {i}"))).unwrap();
+        }
+        let journal_left = journal.journal_path().exists();
+        let saved = ExpenseTracker::load_from_json(&journal.filename).unwrap();
+        let next = journal.append(expense("tea")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(!journal_left);
+        assert_eq!(saved.expenses.len(), COMPACT_AFTER + 1);
+        assert_eq!(next, COMPACT_AFTER as u64 + 2);
+    }
+
+    #[test]
+    fn an_incomplete_last_line_is_ignored_and_then_dropped() {
+        let (dir, journal) = journal("torn");
+        let lunch = Expense {
+            id: 2,
+            ..expense("lunch")
+        };
+        let line = serde_json::to_string(&lunch).unwrap();
+        fs::write(
+            journal.journal_path(),
+            format!("{line}\n{}", &line[..line.len() / 2]),
+        )
+        .unwrap();
+
+        let loaded = journal.load().unwrap();
+        let id = journal.append(expense("dinner")).unwrap();
+        let reloaded = journal.load().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(loaded.expenses.len(), 2);
+        assert_eq!(id, 3);
+        let descriptions: Vec<&str> = reloaded
+            .expenses
+            .iter()
+            .map(|e| e.description.as_str())
+            .collect();
+        assert_eq!(descriptions, ["rent", "lunch", "dinner"]);
+    }
+
+    #[test]
+    fn entries_already_in_the_data_file_are_not_replayed_twice() {
+        let (dir, journal) = journal("folded");
+        let mut tracker = ExpenseTracker::load_from_json(&journal.filename).unwrap();
+        tracker.add_expense(expense("lunch"));
+        tracker.save_to_json(&journal.filename).unwrap();
+        // As left by a compaction that stopped before clearing the journal
+        let line = serde_json::to_string(&tracker.expenses[1]).unwrap();
+        fs::write(journal.journal_path(), format!("{line}\n")).unwrap();
+
+        let loaded = journal.load().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(loaded.expenses.len(), 2);
+        assert_eq!(loaded.next_id, 3);
+    }
+
+    #[test]
+    fn a_replaced_data_file_sets_the_journal_aside() {
+        let (dir, journal) = journal("aside");
+        journal.append(expense("lunch")).unwrap();
+
+        let aside = set_journal_aside(&journal.filename).unwrap();
+        let left = (
+            journal.journal_path().exists(),
+            journal.index_path().exists(),
+        );
+        let kept = aside.as_ref().map(fs::read_to_string);
+        let none_left = set_journal_aside(&journal.filename).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(left, (false, false));
+        assert!(kept.unwrap().unwrap().contains("lunch"));
+        assert_eq!(none_left, None);
+    }
+}
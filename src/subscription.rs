@@ -0,0 +1,128 @@
+use chrono::{Datelike, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::currency;
+use crate::dates::{month_end, month_start};
+use crate::expense::ExpenseTracker;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Cycle {
+    Monthly,
+    Yearly,
+}
+
+// A service billed again and again until cancelled
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Subscription {
+    pub name: String,
+    pub amount: f64,
+    pub cycle: Cycle,
+    // Day of the month it renews on, the month's last day when shorter
+    pub day: u32,
+    // Month of the year yearly subscriptions renew in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub month: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    // Where to cancel it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+impl Subscription {
+    // The renewal date in the month starting at `month`, if it renews then.
+    fn renewal_in(&self, month: NaiveDate) -> Option<NaiveDate> {
+        if self.cycle == Cycle::Yearly && self.month != Some(month.month()) {
+            return None;
+        }
+        let last = month_end(month).day();
+        month.with_day(self.day.min(last))
+    }
+
+    // The first renewal on or after `today`.
+    pub fn next_renewal(&self, today: NaiveDate) -> NaiveDate {
+        let mut month = month_start(today);
+        loop {
+            if let Some(date) = self.renewal_in(month)
+                && date >= today
+            {
+                return date;
+            }
+            month = month + Months::new(1);
+        }
+    }
+
+    // What it costs a month on average
+    pub fn monthly(&self) -> f64 {
+        match self.cycle {
+            Cycle::Monthly => self.amount,
+            Cycle::Yearly => self.amount / 12.0,
+        }
+    }
+}
+
+fn print_one(subscription: &Subscription, today: NaiveDate) {
+    let per = match subscription.cycle {
+        Cycle::Monthly => "month",
+        Cycle::Yearly => "year",
+    };
+    let next = subscription.next_renewal(today);
+    println!(
+        "{}: {}/{}, renews {} (in {} day(s))",
+        subscription.name,
+        currency::amount(subscription.amount),
+        per,
+        next,
+        (next - today).num_days()
+    );
+    if let Some(category) = &subscription.category {
+        println!("  Category: {category}");
+    }
+    if let Some(url) = &subscription.url {
+        println!("  Cancel at: {url}");
+    }
+    if let Some(notes) = &subscription.notes {
+        println!("  Notes: {notes}");
+    }
+}
+
+// Every subscription, soonest renewal first, and what they cost together.
+pub fn print_list(tracker: &ExpenseTracker, today: NaiveDate) {
+    if tracker.subscriptions.is_empty() {
+        println!("No subscriptions.");
+        return;
+    }
+    let mut subscriptions: Vec<&Subscription> = tracker.subscriptions.iter().collect();
+    subscriptions.sort_by_key(|s| s.next_renewal(today));
+    for subscription in subscriptions.iter() {
+        print_one(subscription, today);
+    }
+    let monthly: f64 = subscriptions.iter().map(|s| s.monthly()).sum();
+    println!(
+        "\nTotal: {}/month, {}/year",
+        currency::amount(monthly),
+        currency::amount(monthly * 12.0)
+    );
+}
+
+// The subscriptions renewing within `days` days of `today`.
+pub fn print_upcoming(tracker: &ExpenseTracker, today: NaiveDate, days: u32) {
+    let mut upcoming: Vec<&Subscription> = tracker
+        .subscriptions
+        .iter()
+        .filter(|s| (s.next_renewal(today) - today).num_days() <= i64::from(days))
+        .collect();
+    if upcoming.is_empty() {
+        println!("No renewals in the next {days} day(s).");
+        return;
+    }
+    upcoming.sort_by_key(|s| s.next_renewal(today));
+    for subscription in upcoming.iter() {
+        print_one(subscription, today);
+    }
+    let due: f64 = upcoming.iter().map(|s| s.amount).sum();
+    println!("\n{} due in the next {days} day(s)", currency::amount(due));
+}
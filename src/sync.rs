@@ -0,0 +1,782 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+use crate::attachments;
+use crate::crypto;
+use crate::expense::{Expense, ExpenseTracker};
+use crate::history::{self, Change};
+use crate::migrate;
+use crate::prompt;
+
+// Set in the data repository's git config by `sync init`; saves are only
+// committed when it is.
+const AUTOCOMMIT_KEY: &str = "expense-tracker.autocommit";
+// The merge driver `sync init` registers for data files
+const DRIVER: &str = "expense-tracker";
+
+const ATTRIBUTES: &str = "*.json merge=expense-tracker\n*.history merge=union\n";
+const IGNORE: &str = "backups/\n*.tmp\n*.bak\n*.index\n";
+// Kept in the git directory by the merge driver for `pull`: the expenses
+// it renumbered, whose attachments have to move with them
+const RENUMBERED: &str = "expense-tracker-renumbered.json";
+
+fn error(message: impl Into<String>) -> io::Error {
+    io::Error::other(message.into())
+}
+
+// The directory holding the data file, which is the repository.
+fn dir(data_file: &str) -> PathBuf {
+    match Path::new(data_file).parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    }
+}
+
+fn git(dir: &Path, args: &[&str]) -> io::Result<Output> {
+    Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|err| error(format!("couldn't run git: {err}")))
+}
+
+// Run git, returning its output or its complaint.
+fn run(dir: &Path, args: &[&str]) -> io::Result<String> {
+    let output = git(dir, args)?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(error(match stderr.trim() {
+            "" => format!("git {} failed", args[0]),
+            message => message.to_string(),
+        }))
+    }
+}
+
+// Run git with its output going to the terminal, for pushes and pulls that
+// may report progress or ask for credentials.
+fn run_shown(dir: &Path, args: &[&str]) -> io::Result<()> {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .status()
+        .map_err(|err| error(format!("couldn't run git: {err}")))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(error(format!("git {} failed", args[0])))
+    }
+}
+
+// Whether `sync init` set up the data directory for syncing.
+pub fn enabled(data_file: &str) -> bool {
+    git(
+        &dir(data_file),
+        &["config", "--get", "--bool", AUTOCOMMIT_KEY],
+    )
+    .is_ok_and(|output| output.status.success() && output.stdout.starts_with(b"true"))
+}
+
+// A commit message for `changes`.
+pub fn describe(changes: &[Change]) -> String {
+    let summary = |expense: &Expense| format!("#{} {}", expense.id, expense.description);
+    match changes {
+        [] => String::from("Update ledger settings"),
+        [Change::Added { expense }] => format!(
+            "Add {} ({}, {:.2})",
+            summary(expense),
+            expense.category,
+            expense.amount
+        ),
+        [Change::Edited { after, .. }] => format!("Edit {}", summary(after)),
+        [Change::Deleted { expense }] => format!("Delete {}", summary(expense)),
+        _ => {
+            let count = |f: fn(&Change) -> bool| changes.iter().filter(|c| f(c)).count();
+            let parts: Vec<String> = [
+                ("add", count(|c| matches!(c, Change::Added { .. }))),
+                ("edit", count(|c| matches!(c, Change::Edited { .. }))),
+                ("delete", count(|c| matches!(c, Change::Deleted { .. }))),
+            ]
+            .iter()
+            .filter(|(_, n)| *n > 0)
+            .map(|(verb, n)| format!("{verb} {n}"))
+            .collect();
+            let mut message = format!("{} expenses", parts.join(", "));
+            message[..1].make_ascii_uppercase();
+            message
+        }
+    }
+}
+
+// Stage everything in the repository and commit it, returning whether
+// there was anything to commit.
+fn commit_all(dir: &Path, message: &str) -> io::Result<bool> {
+    run(dir, &["add", "-A"])?;
+    if git(dir, &["diff", "--cached", "--quiet"])?.status.success() {
+        return Ok(false);
+    }
+    run(dir, &["commit", "-q", "-m", message])?;
+    Ok(true)
+}
+
+// Commit the data directory after a save, when syncing is set up. A failed
+// commit is reported but doesn't undo the save.
+pub fn commit(data_file: &str, message: &str) {
+    if !enabled(data_file) {
+        return;
+    }
+    if let Err(err) = commit_all(&dir(data_file), message) {
+        eprintln!("Couldn't commit the change: {err}");
+    }
+}
+
+// Add `lines` missing from the file at `path`.
+fn add_lines(path: &Path, lines: &str) -> io::Result<()> {
+    let mut text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err),
+    };
+    let existing: Vec<String> = text.lines().map(str::to_string).collect();
+    for line in lines.lines().filter(|l| !existing.iter().any(|e| e == l)) {
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+        text.push_str(line);
+        text.push('\n');
+    }
+    fs::write(path, text)
+}
+
+// Make the data directory a git repository that commits every save and
+// merges data files expense by expense, pushing to and pulling from
+// `origin` when given.
+pub fn init(data_file: &str, origin: Option<&str>) -> io::Result<()> {
+    let dir = dir(data_file);
+    match run(&dir, &["rev-parse", "--show-toplevel"]) {
+        Ok(top) => {
+            if Path::new(&top).canonicalize()? != dir.canonicalize()? {
+                return Err(error(format!(
+                    "{} is inside the git repository {top}; keep the data file in a directory of its own to sync it",
+                    dir.display()
+                )));
+            }
+        }
+        Err(_) => {
+            run(&dir, &["init", "-q"])?;
+        }
+    }
+    add_lines(&dir.join(".gitattributes"), ATTRIBUTES)?;
+    add_lines(&dir.join(".gitignore"), IGNORE)?;
+
+    // Git runs the driver through the shell.
+    let exe = std::env::current_exe()?;
+    let exe = exe.to_string_lossy().replace('\'', r"'\''");
+    let driver = format!("'{exe}' sync merge %O %A %B %P");
+    run(
+        &dir,
+        &[
+            "config",
+            &format!("merge.{DRIVER}.name"),
+            "expense-tracker ledger merge",
+        ],
+    )?;
+    run(
+        &dir,
+        &["config", &format!("merge.{DRIVER}.driver"), &driver],
+    )?;
+    run(&dir, &["config", AUTOCOMMIT_KEY, "true"])?;
+
+    if let Some(url) = origin {
+        if run(&dir, &["remote", "get-url", "origin"]).is_ok() {
+            run(&dir, &["remote", "set-url", "origin", url])?;
+        } else {
+            run(&dir, &["remote", "add", "origin", url])?;
+        }
+    }
+    commit_all(&dir, "Start syncing the ledger")?;
+    Ok(())
+}
+
+fn branch(dir: &Path) -> io::Result<String> {
+    run(dir, &["rev-parse", "--abbrev-ref", "HEAD"])
+}
+
+pub fn push(data_file: &str) -> io::Result<()> {
+    let dir = dir(data_file);
+    commit_all(&dir, "Update ledger")?;
+    run_shown(&dir, &["push", "-u", "origin", &branch(&dir)?])
+}
+
+// Fetch and merge the other machine's changes. The first pull may join
+// two repositories started separately.
+pub fn pull(data_file: &str) -> io::Result<()> {
+    let dir = dir(data_file);
+    commit_all(&dir, "Update ledger")?;
+    let record = dir.join(run(&dir, &["rev-parse", "--git-path", RENUMBERED])?);
+    match fs::remove_file(&record) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+        _ => {}
+    }
+    // Merges stop before committing so attachments can be moved first.
+    let pulled = run_shown(
+        &dir,
+        &[
+            "pull",
+            "--no-rebase",
+            "--no-commit",
+            "--no-edit",
+            "--allow-unrelated-histories",
+            "origin",
+            &branch(&dir)?,
+        ],
+    );
+    if record.exists() {
+        let renumbered: Vec<Renumbered> = serde_json::from_slice(&fs::read(&record)?)?;
+        move_attachments(data_file, &dir, &renumbered)?;
+        fs::remove_file(&record)?;
+    }
+    let merging = git(&dir, &["rev-parse", "-q", "--verify", "MERGE_HEAD"])?
+        .status
+        .success();
+    let unmerged = run(&dir, &["diff", "--name-only", "--diff-filter=U"])?;
+    if merging && unmerged.is_empty() {
+        run(&dir, &["commit", "-q", "--no-edit"])?;
+        return Ok(());
+    }
+    pulled
+}
+
+// Move the attachments of expenses the merge renumbered from their old
+// directory to their new one. The other machine's files are taken from the
+// commit being merged; this machine's, which may share the old directory,
+// are put back as they were.
+fn move_attachments(data_file: &str, dir: &Path, renumbered: &[Renumbered]) -> io::Result<()> {
+    let root = attachments::root(data_file);
+    let folder = root.file_name().unwrap_or_default().to_string_lossy();
+    for moved in renumbered {
+        for name in moved.attachments.iter() {
+            let old = format!("{folder}/{}/{name}", moved.from);
+            let incoming = git(dir, &["show", &format!("MERGE_HEAD:{old}")])?;
+            if !incoming.status.success() {
+                continue;
+            }
+            let target = attachments::dir(data_file, moved.to);
+            fs::create_dir_all(&target)?;
+            fs::write(target.join(name), &incoming.stdout)?;
+            if git(dir, &["cat-file", "-e", &format!("HEAD:{old}")])?
+                .status
+                .success()
+            {
+                run(dir, &["checkout", "HEAD", "--", &old])?;
+            } else {
+                run(dir, &["rm", "-q", "-f", "--ignore-unmatch", "--", &old])?;
+            }
+        }
+        run(dir, &["add", "-A", "--", &format!("{folder}/{}", moved.to)])?;
+    }
+    Ok(())
+}
+
+// Put `conflict`'s incoming version into `tracker`.
+fn take_incoming(tracker: &mut ExpenseTracker, conflict: &Conflict) {
+    let position = tracker.expenses.iter().position(|e| e.id == conflict.id);
+    match (&conflict.incoming, position) {
+        (Some(expense), Some(i)) => tracker.expenses[i] = expense.clone(),
+        (Some(expense), None) => {
+            tracker.expenses.push(expense.clone());
+            tracker.expenses.sort_by_key(|e| (e.date, e.id));
+        }
+        (None, Some(i)) => {
+            tracker.expenses.remove(i);
+        }
+        (None, None) => {}
+    }
+}
+
+// Whether the incoming version of `conflict` was saved after the local one.
+// A deletion counts as older than any edit.
+fn incoming_is_newer(conflict: &Conflict) -> bool {
+    let when = |expense: &Option<Expense>| expense.as_ref().map(|e| e.modified);
+    when(&conflict.incoming) > when(&conflict.local)
+}
+
+fn show_conflict(conflict: &Conflict) {
+    println!("#{} was changed on both machines.", conflict.id);
+    match (&conflict.local, &conflict.incoming) {
+        (Some(local), Some(incoming)) => {
+            println!("  this machine → other:");
+            for line in history::differences(local, incoming) {
+                println!("    {line}");
+            }
+        }
+        (Some(local), None) => {
+            println!("  this machine: {local}");
+            println!("  other: deleted it");
+        }
+        (None, Some(incoming)) => {
+            println!("  this machine: deleted it");
+            println!("  other: {incoming}");
+        }
+        (None, None) => {}
+    }
+}
+
+// Settle the conflicts left by merges, asking which version to keep unless
+// `keep` is "local", "other" or "newest". Returns how many were settled.
+pub fn resolve(tracker: &mut ExpenseTracker, keep: Option<&str>) -> usize {
+    let mut settled = 0;
+    let mut waiting = Vec::new();
+    for conflict in std::mem::take(&mut tracker.conflicts) {
+        let choice = match keep {
+            Some("newest") if incoming_is_newer(&conflict) => "other",
+            Some("newest") => "local",
+            Some(keep) => keep,
+            None => {
+                show_conflict(&conflict);
+                match prompt::ask("Keep [t]his machine's, the [o]ther, or [s]kip?", Some("s"))
+                    .as_deref()
+                {
+                    Some("t") => "local",
+                    Some("o") => "other",
+                    _ => "skip",
+                }
+            }
+        };
+        match choice {
+            "local" => settled += 1,
+            "other" => {
+                take_incoming(tracker, &conflict);
+                settled += 1;
+            }
+            _ => waiting.push(conflict),
+        }
+    }
+    tracker.conflicts = waiting;
+    settled
+}
+
+// A data file as JSON at the current version; an empty file, as git gives
+// for a file added on both sides, is an empty ledger.
+fn read_value(path: &Path) -> io::Result<Value> {
+    let bytes = crypto::read(path)?;
+    if bytes.iter().all(u8::is_ascii_whitespace) {
+        return Ok(serde_json::to_value(ExpenseTracker::new())?);
+    }
+    let mut value: Value = serde_json::from_slice(&bytes)?;
+    migrate::upgrade(&mut value).map_err(error)?;
+    Ok(value)
+}
+
+fn tracker(value: &Value) -> io::Result<ExpenseTracker> {
+    Ok(serde_json::from_value(value.clone())?)
+}
+
+// An expense both machines changed differently. `None` is a side that
+// deleted it. The ledger keeps this machine's version until `sync resolve`
+// picks one.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Conflict {
+    pub id: u64,
+    pub local: Option<Expense>,
+    pub incoming: Option<Expense>,
+}
+
+// An expense the other machine added under an id this machine also used,
+// and the id it was given instead.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Renumbered {
+    from: u64,
+    to: u64,
+    attachments: Vec<String>,
+}
+
+// Whether `a` and `b` are the same expense, whenever they were saved.
+fn same(a: &Expense, b: &Expense) -> bool {
+    Expense {
+        modified: None,
+        ..a.clone()
+    } == Expense {
+        modified: None,
+        ..b.clone()
+    }
+}
+
+// Merge the expenses of `ours` and `theirs` by id against their common
+// `base`. Additions from both sides are kept, giving theirs a new id when
+// both used the same one; an edit or deletion on one side is applied when
+// the other side left the expense alone. Expenses both sides changed keep
+// our version and are returned as conflicts.
+fn merge_expenses(
+    base: &ExpenseTracker,
+    ours: &ExpenseTracker,
+    theirs: &ExpenseTracker,
+) -> (Vec<Expense>, Vec<Conflict>, Vec<Renumbered>) {
+    let mut merged = ours.expenses.clone();
+    let mut renumbered = Vec::new();
+    let mut conflicts = Vec::new();
+    for expense in theirs.expenses.iter() {
+        let original = base.find_by_id(expense.id);
+        let position = merged.iter().position(|e| e.id == expense.id);
+        match (original, position) {
+            (None, None) => merged.push(expense.clone()),
+            (None, Some(i)) => {
+                if !same(&merged[i], expense) {
+                    renumbered.push(expense.clone());
+                }
+            }
+            (Some(original), Some(i)) => {
+                if same(expense, original) || same(&merged[i], expense) {
+                    continue;
+                }
+                if same(&merged[i], original) {
+                    merged[i] = expense.clone();
+                } else {
+                    conflicts.push(Conflict {
+                        id: expense.id,
+                        local: Some(merged[i].clone()),
+                        incoming: Some(expense.clone()),
+                    });
+                }
+            }
+            (Some(original), None) => {
+                if !same(expense, original) {
+                    conflicts.push(Conflict {
+                        id: expense.id,
+                        local: None,
+                        incoming: Some(expense.clone()),
+                    });
+                }
+            }
+        }
+    }
+    for original in base.expenses.iter() {
+        if theirs.find_by_id(original.id).is_some() {
+            continue;
+        }
+        match merged.iter().position(|e| e.id == original.id) {
+            Some(i) if same(&merged[i], original) => {
+                merged.remove(i);
+            }
+            Some(i) => conflicts.push(Conflict {
+                id: original.id,
+                local: Some(merged[i].clone()),
+                incoming: None,
+            }),
+            None => {}
+        }
+    }
+
+    let next_id = [base.next_id, ours.next_id, theirs.next_id]
+        .into_iter()
+        .chain(merged.iter().map(|e| e.id + 1))
+        .max()
+        .unwrap_or(1);
+    // Our expenses keep their order, and so their row numbers; theirs are
+    // added after them.
+    let mut moved = Vec::new();
+    for (id, mut expense) in (next_id..).zip(renumbered) {
+        moved.push(Renumbered {
+            from: expense.id,
+            to: id,
+            attachments: expense.attachments.clone(),
+        });
+        expense.id = id;
+        merged.push(expense);
+    }
+    (merged, conflicts, moved)
+}
+
+// The git merge driver for data files: merge `theirs` into `ours` in place.
+// Settings such as budgets are taken from whichever side changed them,
+// preferring ours.
+//
+// `path` is where the file is in the repository, when git says. Expenses
+// renumbered outside snapshots are noted for `pull`, which moves their
+// attachments once the other machine's files are there.
+pub fn merge(base: &Path, ours: &Path, theirs: &Path, path: Option<&Path>) -> io::Result<()> {
+    let (base_value, ours_value, theirs_value) =
+        (read_value(base)?, read_value(ours)?, read_value(theirs)?);
+    let (base_tracker, ours_tracker, theirs_tracker) = (
+        tracker(&base_value)?,
+        tracker(&ours_value)?,
+        tracker(&theirs_value)?,
+    );
+    let (expenses, mut conflicts, renumbered) =
+        merge_expenses(&base_tracker, &ours_tracker, &theirs_tracker);
+    let snapshot = path.is_some_and(|path| path.starts_with("snapshots"));
+    if !snapshot && renumbered.iter().any(|r| !r.attachments.is_empty()) {
+        note_renumbered(renumbered)?;
+    }
+    // Conflicts still waiting on either side are carried over.
+    let waiting = ours_tracker
+        .conflicts
+        .iter()
+        .chain(&theirs_tracker.conflicts);
+    for conflict in waiting {
+        if !conflicts.iter().any(|c| c.id == conflict.id) {
+            conflicts.push(conflict.clone());
+        }
+    }
+    conflicts.sort_by_key(|c| c.id);
+
+    let empty = Map::new();
+    let object = |value: &Value| value.as_object().cloned().unwrap_or_default();
+    let (base_map, ours_map, theirs_map) = (
+        base_value.as_object().unwrap_or(&empty),
+        object(&ours_value),
+        object(&theirs_value),
+    );
+    let mut merged = ours_map.clone();
+    for (key, value) in theirs_map.iter() {
+        if ours_map.get(key) == base_map.get(key) {
+            merged.insert(key.clone(), value.clone());
+        }
+    }
+    for key in base_map.keys() {
+        if !theirs_map.contains_key(key) && ours_map.get(key) == base_map.get(key) {
+            merged.remove(key);
+        }
+    }
+    let next_id = expenses
+        .iter()
+        .map(|e| e.id + 1)
+        .chain([ours_tracker.next_id, theirs_tracker.next_id])
+        .max()
+        .unwrap_or(1);
+    merged.insert(String::from("expenses"), serde_json::to_value(&expenses)?);
+    merged.insert(String::from("next_id"), Value::from(next_id));
+    if conflicts.is_empty() {
+        merged.remove("conflicts");
+    } else {
+        merged.insert(String::from("conflicts"), serde_json::to_value(&conflicts)?);
+    }
+
+    let result = tracker(&Value::Object(merged))?;
+    for conflict in conflicts.iter() {
+        eprintln!(
+            "#{} was changed on both machines; run `sync resolve` to choose a version.",
+            conflict.id
+        );
+    }
+    crypto::write(ours, &serde_json::to_vec(&result)?)
+}
+
+// Add to the expenses renumbered by this merge, kept in the git directory
+// of the repository git runs the driver in.
+fn note_renumbered(renumbered: Vec<Renumbered>) -> io::Result<()> {
+    let record = PathBuf::from(run(
+        Path::new("."),
+        &["rev-parse", "--git-path", RENUMBERED],
+    )?);
+    let mut noted: Vec<Renumbered> = match fs::read(&record) {
+        Ok(bytes) => serde_json::from_slice(&bytes)?,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+        Err(err) => return Err(err),
+    };
+    noted.extend(renumbered);
+    fs::write(record, serde_json::to_vec(&noted)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use std::env;
+
+    fn expense(id: u64, description: &str) -> Expense {
+        Expense {
+            id,
+            date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            category: String::from("Food"),
+            amount: 100.0,
+            description: description.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn ledger(expenses: &[Expense]) -> ExpenseTracker {
+        let mut tracker = ExpenseTracker::new();
+        for expense in expenses {
+            tracker.add_expense(expense.clone());
+        }
+        tracker
+    }
+
+    fn descriptions(expenses: &[Expense]) -> Vec<(u64, &str)> {
+        expenses
+            .iter()
+            .map(|e| (e.id, e.description.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn both_added_same_id_keeps_both() {
+        let base = ledger(&[expense(1, "rent")]);
+        let ours = ledger(&[expense(1, "rent"), expense(2, "lunch")]);
+        let theirs = ledger(&[expense(1, "rent"), expense(2, "taxi")]);
+        let (merged, conflicts, _) = merge_expenses(&base, &ours, &theirs);
+        assert!(conflicts.is_empty());
+        assert_eq!(
+            descriptions(&merged),
+            [(1, "rent"), (2, "lunch"), (3, "taxi")]
+        );
+    }
+
+    #[test]
+    fn renumbered_expenses_bring_their_attachments() {
+        let base = ledger(&[expense(1, "rent")]);
+        let ours = ledger(&[expense(1, "rent"), expense(2, "lunch")]);
+        let mut taxi = expense(2, "taxi");
+        taxi.attachments = vec![String::from("receipt.jpg")];
+        let theirs = ledger(&[expense(1, "rent"), taxi]);
+        let (_, _, renumbered) = merge_expenses(&base, &ours, &theirs);
+        assert_eq!(
+            renumbered,
+            [Renumbered {
+                from: 2,
+                to: 3,
+                attachments: vec![String::from("receipt.jpg")],
+            }]
+        );
+    }
+
+    #[test]
+    fn our_order_is_kept_and_theirs_added_after() {
+        let mut late = expense(1, "late");
+        late.date = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let base = ledger(&[late.clone()]);
+        let ours = ledger(&[late.clone(), expense(2, "early")]);
+        let theirs = ledger(&[late, expense(3, "also early")]);
+        let (merged, _, _) = merge_expenses(&base, &ours, &theirs);
+        assert_eq!(
+            descriptions(&merged),
+            [(1, "late"), (2, "early"), (3, "also early")]
+        );
+    }
+
+    #[test]
+    fn both_added_same_expense_keeps_one() {
+        let base = ledger(&[]);
+        let ours = ledger(&[expense(1, "lunch")]);
+        let theirs = ledger(&[expense(1, "lunch")]);
+        let (merged, conflicts, _) = merge_expenses(&base, &ours, &theirs);
+        assert!(conflicts.is_empty());
+        assert_eq!(descriptions(&merged), [(1, "lunch")]);
+    }
+
+    #[test]
+    fn edit_on_one_side_is_applied() {
+        let base = ledger(&[expense(1, "lunch")]);
+        let ours = ledger(&[expense(1, "lunch")]);
+        let theirs = ledger(&[expense(1, "team lunch")]);
+        let (merged, conflicts, _) = merge_expenses(&base, &ours, &theirs);
+        assert!(conflicts.is_empty());
+        assert_eq!(descriptions(&merged), [(1, "team lunch")]);
+    }
+
+    #[test]
+    fn edits_on_both_sides_conflict() {
+        let base = ledger(&[expense(1, "lunch")]);
+        let ours = ledger(&[expense(1, "team lunch")]);
+        let theirs = ledger(&[expense(1, "client lunch")]);
+        let (merged, conflicts, _) = merge_expenses(&base, &ours, &theirs);
+        assert_eq!(descriptions(&merged), [(1, "team lunch")]);
+        assert_eq!(conflicts.len(), 1);
+        let conflict = &conflicts[0];
+        assert_eq!(conflict.id, 1);
+        assert_eq!(conflict.local.as_ref().unwrap().description, "team lunch");
+        assert_eq!(
+            conflict.incoming.as_ref().unwrap().description,
+            "client lunch"
+        );
+    }
+
+    #[test]
+    fn our_edit_against_their_delete_conflicts() {
+        let base = ledger(&[expense(1, "lunch")]);
+        let ours = ledger(&[expense(1, "team lunch")]);
+        let theirs = ledger(&[]);
+        let (merged, conflicts, _) = merge_expenses(&base, &ours, &theirs);
+        assert_eq!(descriptions(&merged), [(1, "team lunch")]);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(
+            conflicts[0].local.as_ref().unwrap().description,
+            "team lunch"
+        );
+        assert!(conflicts[0].incoming.is_none());
+    }
+
+    #[test]
+    fn our_delete_against_their_edit_conflicts() {
+        let base = ledger(&[expense(1, "lunch")]);
+        let ours = ledger(&[]);
+        let theirs = ledger(&[expense(1, "team lunch")]);
+        let (merged, conflicts, _) = merge_expenses(&base, &ours, &theirs);
+        assert!(merged.is_empty());
+        assert_eq!(conflicts.len(), 1);
+        assert!(conflicts[0].local.is_none());
+        assert_eq!(
+            conflicts[0].incoming.as_ref().unwrap().description,
+            "team lunch"
+        );
+    }
+
+    #[test]
+    fn their_delete_of_an_unchanged_expense_is_applied() {
+        let base = ledger(&[expense(1, "lunch"), expense(2, "taxi")]);
+        let ours = ledger(&[expense(1, "lunch"), expense(2, "taxi")]);
+        let theirs = ledger(&[expense(2, "taxi")]);
+        let (merged, conflicts, _) = merge_expenses(&base, &ours, &theirs);
+        assert!(conflicts.is_empty());
+        assert_eq!(descriptions(&merged), [(2, "taxi")]);
+    }
+
+    #[test]
+    fn our_delete_of_an_unchanged_expense_is_kept() {
+        let base = ledger(&[expense(1, "lunch"), expense(2, "taxi")]);
+        let ours = ledger(&[expense(2, "taxi")]);
+        let theirs = ledger(&[expense(1, "lunch"), expense(2, "taxi")]);
+        let (merged, conflicts, _) = merge_expenses(&base, &ours, &theirs);
+        assert!(conflicts.is_empty());
+        assert_eq!(descriptions(&merged), [(2, "taxi")]);
+    }
+
+    #[test]
+    fn next_id_follows_renumbered_expenses() {
+        let dir = env::temp_dir().join(format!("expense-tracker-merge-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let write = |name: &str, tracker: &ExpenseTracker| {
+            let path = dir.join(name);
+            fs::write(&path, serde_json::to_vec(tracker).unwrap()).unwrap();
+            path
+        };
+        let base = write("base.json", &ledger(&[expense(1, "rent")]));
+        let ours = write(
+            "ours.json",
+            &ledger(&[expense(1, "rent"), expense(2, "lunch")]),
+        );
+        let theirs = write(
+            "theirs.json",
+            &ledger(&[expense(1, "rent"), expense(2, "taxi"), expense(3, "tea")]),
+        );
+        merge(&base, &ours, &theirs, None).unwrap();
+        let merged: ExpenseTracker = serde_json::from_slice(&fs::read(&ours).unwrap()).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(merged.conflicts.is_empty());
+        let mut ids: Vec<u64> = merged.expenses.iter().map(|e| e.id).collect();
+        ids.sort();
+        assert_eq!(ids, [1, 2, 3, 4]);
+        assert_eq!(merged.find_by_id(4).unwrap().description, "taxi");
+        assert_eq!(merged.next_id, 5);
+    }
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+use crate::currency;
+use crate::expense::ExpenseTracker;
+
+// A named expense that's logged often at the same price
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Template {
+    pub name: String,
+    pub category: String,
+    pub amount: f64,
+    // Defaults to the template's name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payee: Option<String>,
+}
+
+impl Template {
+    pub fn description(&self) -> &str {
+        self.description.as_deref().unwrap_or(&self.name)
+    }
+}
+
+pub fn find<'a>(tracker: &'a ExpenseTracker, name: &str) -> Option<&'a Template> {
+    tracker
+        .templates
+        .iter()
+        .find(|t| t.name.eq_ignore_ascii_case(name))
+}
+
+pub fn print_list(tracker: &ExpenseTracker) {
+    if tracker.templates.is_empty() {
+        println!("No templates defined.");
+        return;
+    }
+    for template in tracker.templates.iter() {
+        print!(
+            "{:<15} {:<15} {:>12} {}",
+            template.name,
+            template.category,
+            currency::amount_in(None, template.amount),
+            template.description()
+        );
+        match &template.payee {
+            Some(payee) => println!(", Payee: {payee}"),
+            None => println!(),
+        }
+    }
+}
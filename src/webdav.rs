@@ -0,0 +1,180 @@
+use base64::{Engine, engine::general_purpose::STANDARD};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+use ureq::{Agent, http::StatusCode};
+
+use crate::backup;
+use crate::config::WebDavConfig;
+use crate::expense::ExpenseTracker;
+use crate::storage::{self, Storage};
+
+// Read when the config file doesn't hold the password
+pub const PASSWORD_VAR: &str = "EXPENSE_TRACKER_WEBDAV_PASSWORD";
+
+// The ledger kept as one file on a WebDAV server, such as Nextcloud. The
+// local data file is a cache of the last version seen, so reports keep
+// working offline. Saves only replace the version that was loaded, going
+// by its ETag, so two devices can't overwrite each other's changes.
+pub struct WebDav {
+    filename: String,
+    url: Option<String>,
+    authorization: Option<String>,
+    agent: Agent,
+}
+
+fn error(message: impl Into<String>) -> io::Error {
+    io::Error::other(message.into())
+}
+
+impl WebDav {
+    pub fn new(filename: &str, config: &WebDavConfig) -> WebDav {
+        let password = config
+            .password
+            .clone()
+            .or_else(|| std::env::var(PASSWORD_VAR).ok());
+        let authorization = config.username.as_ref().map(|user| {
+            let credentials = format!("{user}:{}", password.unwrap_or_default());
+            format!("Basic {}", STANDARD.encode(credentials))
+        });
+        // Statuses such as 304 and 412 are part of the protocol here.
+        let agent = Agent::config_builder()
+            .http_status_as_error(false)
+            .build()
+            .into();
+        WebDav {
+            filename: filename.to_string(),
+            url: config.url.clone(),
+            authorization,
+            agent,
+        }
+    }
+
+    fn url(&self) -> io::Result<&str> {
+        self.url
+            .as_deref()
+            .ok_or_else(|| error("set url under [webdav] in the config file"))
+    }
+
+    // Where the ETag of the cached copy is kept.
+    fn etag_path(&self) -> PathBuf {
+        Path::new(&self.filename).with_extension("etag")
+    }
+
+    fn cached_etag(&self) -> Option<String> {
+        fs::read_to_string(self.etag_path())
+            .ok()
+            .map(|etag| etag.trim().to_string())
+            .filter(|etag| !etag.is_empty())
+    }
+
+    fn set_cached_etag(&self, etag: Option<&str>) -> io::Result<()> {
+        match etag {
+            Some(etag) => fs::write(self.etag_path(), etag),
+            None => match fs::remove_file(self.etag_path()) {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+                _ => Ok(()),
+            },
+        }
+    }
+
+    // Bring the cache up to date with the server, unless it already is.
+    fn refresh(&self) -> io::Result<()> {
+        let url = self.url()?;
+        let mut request = self.agent.get(url);
+        if let Some(authorization) = &self.authorization {
+            request = request.header("Authorization", authorization);
+        }
+        // Without a cached copy the ETag can't be relied on.
+        if Path::new(&self.filename).exists()
+            && let Some(etag) = self.cached_etag()
+        {
+            request = request.header("If-None-Match", &etag);
+        }
+        let mut response = request
+            .call()
+            .map_err(|err| error(format!("{url}: {err}")))?;
+        match response.status() {
+            StatusCode::NOT_MODIFIED => Ok(()),
+            // Nothing uploaded yet: the first save uploads the local file.
+            StatusCode::NOT_FOUND => self.set_cached_etag(None),
+            status if status.is_success() => {
+                let etag = response
+                    .headers()
+                    .get("ETag")
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string);
+                let bytes = response
+                    .body_mut()
+                    .with_config()
+                    .limit(u64::MAX)
+                    .read_to_vec()
+                    .map_err(|err| error(format!("{url}: {err}")))?;
+                fs::write(&self.filename, bytes)?;
+                self.set_cached_etag(etag.as_deref())
+            }
+            status => Err(error(format!("{url}: the server answered {status}"))),
+        }
+    }
+
+    fn upload(&self, bytes: Vec<u8>) -> io::Result<Option<String>> {
+        let url = self.url()?;
+        let mut request = self.agent.put(url);
+        if let Some(authorization) = &self.authorization {
+            request = request.header("Authorization", authorization);
+        }
+        request = match self.cached_etag() {
+            Some(etag) => request.header("If-Match", &etag),
+            None => request.header("If-None-Match", "*"),
+        };
+        let response = request
+            .send(&bytes[..])
+            .map_err(|err| error(format!("{url}: {err}")))?;
+        match response.status() {
+            StatusCode::PRECONDITION_FAILED => Err(error(
+                "the ledger was changed from another device since it was loaded; run the command again",
+            )),
+            status if status.is_success() => Ok(response
+                .headers()
+                .get("ETag")
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)),
+            status => Err(error(format!("{url}: the server answered {status}"))),
+        }
+    }
+}
+
+impl Storage for WebDav {
+    // Offline, the cached copy is used.
+    fn load(&self) -> io::Result<ExpenseTracker> {
+        if let Err(err) = self.refresh() {
+            if !Path::new(&self.filename).exists() {
+                return Err(err);
+            }
+            eprintln!("Using the cached ledger: {err}");
+        }
+        Ok(ExpenseTracker::load_from_json(&self.filename)?)
+    }
+
+    fn save(&self, tracker: &ExpenseTracker) -> io::Result<()> {
+        let before = ExpenseTracker::load_from_json(&self.filename)?;
+        let tracker = &storage::stamped(&before, tracker);
+        // Written locally first, encrypted if need be, then uploaded.
+        let temporary = Path::new(&self.filename).with_extension("json.tmp");
+        tracker.save_to_json(&temporary.to_string_lossy())?;
+        let etag = match self.upload(fs::read(&temporary)?) {
+            Ok(etag) => etag,
+            Err(err) => {
+                let _ = fs::remove_file(&temporary);
+                return Err(err);
+            }
+        };
+        backup::create(&self.filename)?;
+        fs::rename(&temporary, &self.filename)?;
+        // A server that doesn't return the new ETag gets asked for it
+        // on the next load.
+        self.set_cached_etag(etag.as_deref())?;
+        storage::record(&self.filename, &before, tracker)
+    }
+}
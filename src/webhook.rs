@@ -0,0 +1,181 @@
+use chrono::Local;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use sha2::Sha256;
+use std::{thread, time::Duration};
+use ureq::Agent;
+
+use crate::budget;
+use crate::config::Webhook;
+use crate::dates;
+use crate::expense::{Expense, ExpenseTracker, MASK};
+use crate::storage::Storage;
+
+// Tries per delivery, waiting twice as long after each failure
+const ATTEMPTS: u32 = 3;
+const FIRST_RETRY: Duration = Duration::from_secs(1);
+const TIMEOUT: Duration = Duration::from_secs(10);
+// Hex HMAC-SHA256 of the body, keyed with the webhook's secret
+const SIGNATURE_HEADER: &str = "X-Expense-Tracker-Signature";
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Event {
+    ExpenseAdded,
+    BudgetExceeded,
+}
+
+impl Event {
+    fn name(self) -> &'static str {
+        match self {
+            Event::ExpenseAdded => "expense_added",
+            Event::BudgetExceeded => "budget_exceeded",
+        }
+    }
+}
+
+fn signature(secret: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("any key length");
+    mac.update(body);
+    let digest = mac.finalize().into_bytes();
+    let hex: String = digest.iter().map(|b| format!("{b:02x}")).collect();
+    format!("sha256={hex}")
+}
+
+// POST `body` to the webhook, retrying network errors and server errors.
+fn deliver(agent: &Agent, webhook: &Webhook, body: &[u8]) -> Result<(), String> {
+    let mut wait = FIRST_RETRY;
+    let mut attempt = 1;
+    loop {
+        let mut request = agent
+            .post(&webhook.url)
+            .header("Content-Type", "application/json");
+        if let Some(secret) = &webhook.secret {
+            request = request.header(SIGNATURE_HEADER, &signature(secret, body));
+        }
+        let error = match request.send(body) {
+            Ok(response) if response.status().is_success() => return Ok(()),
+            // The receiver turned the payload down; sending it again won't help.
+            Ok(response) if response.status().is_client_error() && response.status() != 429 => {
+                return Err(format!("the receiver answered {}", response.status()));
+            }
+            Ok(response) => format!("the receiver answered {}", response.status()),
+            Err(err) => err.to_string(),
+        };
+        if attempt == ATTEMPTS {
+            return Err(error);
+        }
+        thread::sleep(wait);
+        wait *= 2;
+        attempt += 1;
+    }
+}
+
+// Send `payload` for `event` to every webhook that listens for it. Failed
+// deliveries are reported but don't fail the command.
+fn send(webhooks: &[Webhook], event: Event, text: String, mut payload: Value) {
+    let webhooks: Vec<&Webhook> = webhooks
+        .iter()
+        .filter(|w| w.events.is_empty() || w.events.contains(&event))
+        .collect();
+    if webhooks.is_empty() {
+        return;
+    }
+    payload["event"] = json!(event.name());
+    payload["at"] = json!(Local::now().to_rfc3339());
+    // Slack reads `text` and Discord `content`, so either can take the
+    // payload as it is.
+    payload["text"] = json!(text);
+    payload["content"] = json!(text);
+    let body = payload.to_string().into_bytes();
+    let agent: Agent = Agent::config_builder()
+        .http_status_as_error(false)
+        .timeout_global(Some(TIMEOUT))
+        .build()
+        .into();
+    for webhook in webhooks {
+        if let Err(err) = deliver(&agent, webhook, &body) {
+            eprintln!("Webhook {} failed: {err}", webhook.url);
+        }
+    }
+}
+
+// An expense as sent in a payload. Private expenses leave out what
+// `Expense::line(true)` hides: the amounts, description, payee and items.
+fn payload(expense: &Expense) -> Value {
+    let mut value = json!(expense);
+    if !expense.private {
+        return value;
+    }
+    if let Some(fields) = value.as_object_mut() {
+        fields.insert("description".into(), json!(MASK));
+        fields.insert("amount".into(), json!(MASK));
+        if fields.contains_key("payee") {
+            fields.insert("payee".into(), json!(MASK));
+        }
+        if let Some(Value::Array(splits)) = fields.get_mut("splits") {
+            for split in splits.iter_mut().filter_map(Value::as_object_mut) {
+                split.insert("amount".into(), json!(MASK));
+            }
+        }
+        if let Some(Value::Array(items)) = fields.get_mut("items") {
+            for item in items.iter_mut().filter_map(Value::as_object_mut) {
+                item.insert("name".into(), json!(MASK));
+                item.insert("price".into(), json!(MASK));
+            }
+        }
+    }
+    value
+}
+
+// Announce the expenses with `ids`, now in `tracker`, and any budgets they
+// took over the limit.
+pub fn added(webhooks: &[Webhook], tracker: &ExpenseTracker, ids: &[u64]) {
+    if webhooks.is_empty() {
+        return;
+    }
+    for expense in ids.iter().filter_map(|id| tracker.find_by_id(*id)) {
+        send(
+            webhooks,
+            Event::ExpenseAdded,
+            format!("Added {}", expense.line(true)),
+            json!({ "expense": payload(expense) }),
+        );
+        let month = dates::period_of(expense.date);
+        for (status, _) in budget::crossed(tracker, expense, |_| vec![100.0]) {
+            send(
+                webhooks,
+                Event::BudgetExceeded,
+                format!(
+                    "{} is over budget for {}: {:.2} spent of {:.2}",
+                    status.category,
+                    month.format("%Y-%m"),
+                    status.spent,
+                    status.available()
+                ),
+                json!({
+                    "expense": payload(expense),
+                    "budget": {
+                        "category": status.category,
+                        "month": month.format("%Y-%m").to_string(),
+                        "available": status.available(),
+                        "spent": status.spent,
+                    },
+                }),
+            );
+        }
+    }
+}
+
+// `added` for a ledger that has to be loaded first, which only happens
+// when there are webhooks to tell.
+pub fn added_to(storage: &dyn Storage, webhooks: &[Webhook], ids: &[u64]) {
+    if webhooks.is_empty() {
+        return;
+    }
+    match storage.load() {
+        Ok(tracker) => added(webhooks, &tracker, ids),
+        Err(err) => eprintln!("Couldn't send webhooks: {err}"),
+    }
+}
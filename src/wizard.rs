@@ -0,0 +1,134 @@
+use chrono::NaiveDate;
+
+use crate::dates;
+use crate::entry::Entry;
+use crate::expense::ExpenseTracker;
+use crate::i18n;
+use crate::prompt;
+
+// How well `query` matches `candidate`, higher being better: a prefix
+// beats a substring, which beats the query's letters appearing in order.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<usize> {
+    let query = query.to_lowercase();
+    let candidate = candidate.to_lowercase();
+    if candidate.starts_with(&query) {
+        return Some(3000 - candidate.len());
+    }
+    if candidate.contains(&query) {
+        return Some(2000 - candidate.len());
+    }
+    let mut letters = candidate.chars();
+    query
+        .chars()
+        .all(|q| letters.any(|c| c == q))
+        .then(|| 1000 - candidate.len())
+}
+
+// Categories used so far, most used first, then those set up but not
+// used yet.
+fn known_categories(tracker: &ExpenseTracker) -> Vec<String> {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for expense in tracker.expenses.iter() {
+        for (category, _) in expense.category_amounts() {
+            match counts
+                .iter_mut()
+                .find(|(name, _)| name.eq_ignore_ascii_case(category))
+            {
+                Some((_, count)) => *count += 1,
+                None => counts.push((category.to_string(), 1)),
+            }
+        }
+    }
+    counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    for category in tracker.categories.iter() {
+        if !counts
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case(category))
+        {
+            counts.push((category.clone(), 0));
+        }
+    }
+    counts.into_iter().map(|(name, _)| name).collect()
+}
+
+fn ask_amount() -> Option<f64> {
+    loop {
+        let answer = prompt::ask(&i18n::t("ask-amount"), None)?;
+        match answer.parse::<f64>() {
+            Ok(amount) if amount > 0.0 && amount.is_finite() => return Some(amount),
+            _ => println!("{}", i18n::t_args("invalid-amount", &[("answer", &answer)])),
+        }
+    }
+}
+
+// Ask for a category, offering the closest ones already in use.
+fn ask_category(known: &[String]) -> Option<String> {
+    let hint: Vec<&str> = known.iter().take(5).map(String::as_str).collect();
+    loop {
+        let question = if hint.is_empty() {
+            i18n::t("ask-category")
+        } else {
+            i18n::t_args("ask-category-hint", &[("examples", &hint.join(", "))])
+        };
+        let answer = prompt::ask(&question, None)?;
+        if answer.is_empty() {
+            continue;
+        }
+        if let Some(exact) = known.iter().find(|c| c.eq_ignore_ascii_case(&answer)) {
+            return Some(exact.clone());
+        }
+        let mut matches: Vec<(usize, &String)> = known
+            .iter()
+            .filter_map(|c| Some((fuzzy_score(&answer, c)?, c)))
+            .collect();
+        if matches.is_empty() {
+            return Some(answer);
+        }
+        matches.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        for (i, (_, category)) in matches.iter().take(5).enumerate() {
+            println!("  {}. {}", i + 1, category);
+        }
+        let pick = prompt::ask(&i18n::t_args("ask-pick", &[("answer", &answer)]), None)?;
+        if pick.is_empty() {
+            return Some(answer);
+        }
+        match pick.parse::<usize>() {
+            Ok(n) if (1..=matches.len().min(5)).contains(&n) => {
+                return Some(matches[n - 1].1.clone());
+            }
+            _ => println!("{}", i18n::t_args("invalid-pick", &[("pick", &pick)])),
+        }
+    }
+}
+
+fn ask_date(today: NaiveDate) -> Option<NaiveDate> {
+    let today = today.to_string();
+    loop {
+        let answer = prompt::ask(&i18n::t("ask-date"), Some(&today))?;
+        match dates::parse_date(&answer) {
+            Ok(date) => return Some(date),
+            Err(err) => println!("{err}"),
+        }
+    }
+}
+
+// Ask for each field of a new expense in turn. None when input ends early.
+pub fn run(tracker: &ExpenseTracker, today: NaiveDate) -> Option<Entry> {
+    let amount = ask_amount()?;
+    let category = ask_category(&known_categories(tracker))?;
+    let description = loop {
+        let answer = prompt::ask(&i18n::t("ask-description"), None)?;
+        if !answer.is_empty() {
+            break answer;
+        }
+    };
+    let date = ask_date(today)?;
+    let payee = prompt::ask(&i18n::t("ask-payee"), None)?;
+    Some(Entry {
+        date,
+        category,
+        amount,
+        description,
+        payee: (!payee.is_empty()).then_some(payee),
+    })
+}